@@ -0,0 +1,63 @@
+//! Codec encode/decode benchmarks
+//!
+//! Run with `cargo bench --bench codec`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use tower_a2a::{
+    codec::{Codec, JsonCodec, JsonRpcCodec},
+    protocol::{message::Message, operation::A2AOperation},
+};
+
+const TASK_RESPONSE: &str = r#"{
+    "jsonrpc": "2.0",
+    "result": {
+        "id": "task-123",
+        "status": "completed",
+        "input": {
+            "role": "user",
+            "parts": [{"text": "Summarize the quarterly report"}]
+        },
+        "artifacts": [],
+        "createdAt": "2024-01-01T00:00:00Z"
+    },
+    "id": "req-123"
+}"#;
+
+fn send_message() -> A2AOperation {
+    A2AOperation::SendMessage {
+        message: Message::user("Summarize the quarterly report ".repeat(32)),
+        stream: false,
+        context_id: Some("ctx-456".to_string()),
+        task_id: None,
+    }
+}
+
+fn encode(c: &mut Criterion) {
+    let operation = send_message();
+    c.bench_function("json/encode_send_message", |b| {
+        b.iter(|| JsonCodec.encode_request(black_box(&operation)).unwrap())
+    });
+    c.bench_function("jsonrpc/encode_send_message", |b| {
+        b.iter(|| {
+            JsonRpcCodec::new()
+                .encode_request(black_box(&operation))
+                .unwrap()
+        })
+    });
+}
+
+fn decode(c: &mut Criterion) {
+    let operation = A2AOperation::GetTask {
+        task_id: "task-123".to_string(),
+    };
+    c.bench_function("jsonrpc/decode_task", |b| {
+        b.iter(|| {
+            JsonRpcCodec::new()
+                .decode_response(black_box(TASK_RESPONSE.as_bytes()), &operation)
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, encode, decode);
+criterion_main!(benches);
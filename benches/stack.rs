@@ -0,0 +1,93 @@
+//! Layer stack and client round-trip benchmarks
+//!
+//! Requests go through [`StaticTransport`], so only encoding, layers, and decoding are
+//! measured. Run with `cargo bench --features bench-util --bench stack`.
+
+use std::{sync::Arc, time::Duration};
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use tokio::runtime::Runtime;
+use tower::{ServiceBuilder, ServiceExt};
+use tower_a2a::{
+    bench::{self, StaticTransport},
+    client::A2AClientBuilder,
+    codec::{JsonCodec, JsonRpcCodec},
+    layer::{A2ARetryLayer, A2ATimeoutLayer, A2AValidationLayer, AuthLayer, RetryPolicy},
+    service::A2AProtocolService,
+};
+
+fn runtime() -> Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap()
+}
+
+fn protocol_service(c: &mut Criterion) {
+    let rt = runtime();
+
+    let service = A2AProtocolService::new(
+        StaticTransport::json(&bench::task(4)).unwrap(),
+        Arc::new(JsonCodec),
+    );
+    c.bench_function("service/json_get_task", |b| {
+        b.iter_batched(
+            || bench::request(bench::get_task()),
+            |request| rt.block_on(service.clone().oneshot(request)).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    let service = A2AProtocolService::new(
+        StaticTransport::jsonrpc(&bench::task(4)).unwrap(),
+        Arc::new(JsonRpcCodec::new()),
+    );
+    c.bench_function("service/jsonrpc_send_message", |b| {
+        b.iter_batched(
+            || bench::request(bench::send_message(1024)),
+            |request| rt.block_on(service.clone().oneshot(request)).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn layer_stack(c: &mut Criterion) {
+    let rt = runtime();
+
+    let service = ServiceBuilder::new()
+        .layer(AuthLayer::bearer("bench-token"))
+        .layer(A2AValidationLayer::new())
+        .layer(A2ATimeoutLayer::new())
+        .layer(A2ARetryLayer::new(RetryPolicy::new(3)))
+        .service(A2AProtocolService::new(
+            StaticTransport::json(&bench::task(4)).unwrap(),
+            Arc::new(JsonCodec),
+        ));
+    c.bench_function("stack/json_get_task", |b| {
+        b.iter_batched(
+            || bench::request(bench::get_task()),
+            |request| rt.block_on(service.clone().oneshot(request)).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn client(c: &mut Criterion) {
+    let rt = runtime();
+
+    let mut client = A2AClientBuilder::new(bench::AGENT_URL.parse().unwrap())
+        .with_transport(StaticTransport::json(&bench::task(4)).unwrap())
+        .with_timeout(Duration::from_secs(5))
+        .build()
+        .unwrap();
+    c.bench_function("client/send_message", |b| {
+        b.iter_batched(
+            || bench::message(1024),
+            |message| rt.block_on(client.send_message(message)).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, protocol_service, layer_stack, client);
+criterion_main!(benches);
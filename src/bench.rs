@@ -0,0 +1,151 @@
+//! Helpers for benchmarking client stacks
+//!
+//! Only available with the `bench-util` feature. [`StaticTransport`] answers every
+//! request with a fixed response and no I/O, so a benchmark measures encoding, layers,
+//! and decoding only. The fixture functions build representative payloads, letting
+//! downstream stacks be compared against the crate's own `benches/` suite:
+//!
+//! ```rust
+//! use tower_a2a::{bench, codec::JsonCodec, service::A2AProtocolService};
+//! use std::sync::Arc;
+//!
+//! let transport = bench::StaticTransport::json(&bench::task(4)).unwrap();
+//! let service = A2AProtocolService::new(transport, Arc::new(JsonCodec));
+//! let request = bench::request(bench::get_task());
+//! ```
+
+use std::{
+    collections::HashMap,
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use serde::Serialize;
+use url::Url;
+
+use crate::{
+    protocol::{
+        error::A2AError, message::MessagePart, operation::A2AOperation, Artifact, Message, Task,
+        TaskStatus,
+    },
+    service::{A2ARequest, RequestContext},
+    transport::{Transport, TransportRequest, TransportResponse},
+};
+
+/// Base URL used by the helpers
+pub const AGENT_URL: &str = "https://agent.example.com";
+
+/// Transport answering every request with the same response, without I/O
+#[derive(Debug, Clone)]
+pub struct StaticTransport {
+    status: u16,
+    headers: HashMap<String, String>,
+    body: Bytes,
+    base_url: Url,
+}
+
+impl StaticTransport {
+    /// Create a transport responding `200 OK` with a body
+    pub fn new(body: impl Into<Bytes>) -> Self {
+        Self {
+            status: 200,
+            headers: HashMap::new(),
+            body: body.into(),
+            base_url: Url::parse(AGENT_URL).expect("valid URL"),
+        }
+    }
+
+    /// Create a transport responding with a value serialized as JSON
+    pub fn json<T: Serialize + ?Sized>(value: &T) -> Result<Self, A2AError> {
+        Ok(Self::new(serde_json::to_vec(value)?))
+    }
+
+    /// Create a transport responding with a value wrapped in a JSON-RPC 2.0 response
+    pub fn jsonrpc<T: Serialize + ?Sized>(value: &T) -> Result<Self, A2AError> {
+        Self::json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": value,
+            "id": "bench",
+        }))
+    }
+
+    /// Set the response status (default: 200)
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Add a response header
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(key.into(), value.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Transport for StaticTransport {
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), A2AError>> {
+        Poll::Ready(Ok(()))
+    }
+
+    async fn execute(&self, _request: TransportRequest) -> Result<TransportResponse, A2AError> {
+        Ok(TransportResponse {
+            status: self.status,
+            headers: self.headers.clone(),
+            body: self.body.clone(),
+        })
+    }
+
+    fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+}
+
+/// Create a user message with a text part of roughly `text_len` bytes
+pub fn message(text_len: usize) -> Message {
+    Message::user("lorem ipsum ".repeat(text_len.div_ceil(12)))
+}
+
+/// Create a completed task with a number of text artifacts
+pub fn task(artifacts: usize) -> Task {
+    (0..artifacts).fold(
+        Task::new("task-bench", message(256)).with_status(TaskStatus::Completed),
+        |task, i| {
+            task.with_artifact(Artifact {
+                artifact_id: format!("artifact-{}", i),
+                name: Some(format!("Result {}", i)),
+                description: None,
+                parts: vec![MessagePart::text("dolor sit amet ".repeat(64))],
+                metadata: None,
+                extensions: Vec::new(),
+                extra: Default::default(),
+            })
+        },
+    )
+}
+
+/// Create a non-streaming send of a message with roughly `text_len` bytes of text
+pub fn send_message(text_len: usize) -> A2AOperation {
+    A2AOperation::SendMessage {
+        message: message(text_len),
+        stream: false,
+        context_id: Some("ctx-bench".to_string()),
+        task_id: None,
+    }
+}
+
+/// Create a lookup of the task returned by [`task`]
+pub fn get_task() -> A2AOperation {
+    A2AOperation::GetTask {
+        task_id: "task-bench".to_string(),
+    }
+}
+
+/// Wrap an operation in a request to [`AGENT_URL`]
+pub fn request(operation: A2AOperation) -> A2ARequest {
+    A2ARequest::new(
+        operation,
+        RequestContext::new(Url::parse(AGENT_URL).expect("valid URL")),
+    )
+}
@@ -1,12 +1,37 @@
 //! High-level A2A agent client
 
+use std::time::{Duration, Instant};
+
+use futures::{
+    future::{self, Either},
+    stream, Stream, StreamExt, TryStreamExt,
+};
+use tokio::sync::broadcast;
+use tower_service::Service;
+
 use crate::{
-    client::config::ClientConfig,
+    client::{
+        balance::AgentService,
+        card_cache::AgentCardCache,
+        config::{ClientConfig, RequestOptions, StreamDropPolicy, CORRELATION_ID_HEADER},
+        events::{ClientEvent, ClientEvents, ConnectionState},
+        proxy::ProxyService,
+        stream::{AbandonGuard, EventStreamExt},
+    },
+    codec::{JsonRpcNotification, SseEvent, TaskListItem},
+    layer::validation::{validate_response, Validated},
     prelude::A2AError,
-    protocol::{A2AOperation, AgentCard, Message, Task, TaskStatus},
-    service::{A2ARequest, A2AResponse, RequestContext},
+    protocol::{
+        consts::extensions, task::TaskListResponse, A2AOperation, AgentCard, CancelReason, Message,
+        PushNotificationConfig, StreamEvent, Task, TaskDelta, TaskPushNotificationConfig,
+        TaskStatus,
+    },
+    service::{
+        A2ARequest, A2AResponse, BackgroundHealth, MetaRecorder, ProtocolStack, RequestContext,
+        ResponseMeta, TaskListStream,
+    },
+    transport::{DryRunTransport, EventStream, Transport, TransportRequest, WebSocketTransport},
 };
-use tower_service::Service;
 
 /// High-level A2A client for interacting with agents
 ///
@@ -32,6 +57,8 @@ use tower_service::Service;
 pub struct AgentClient<S> {
     service: S,
     config: ClientConfig,
+    /// The agent card fetched last, shared with background prefetches
+    card: AgentCardCache,
 }
 
 impl<S> AgentClient<S>
@@ -45,7 +72,11 @@ where
     /// * `service` - The Tower service that handles requests
     /// * `config` - Client configuration
     pub fn new(service: S, config: ClientConfig) -> Self {
-        Self { service, config }
+        Self {
+            card: AgentCardCache::new(config.card_ttl),
+            service,
+            config,
+        }
     }
 
     /// Get the client configuration
@@ -53,14 +84,246 @@ where
         &self.config
     }
 
-    /// Build a request context from the client configuration
-    fn build_context(&self) -> RequestContext {
-        RequestContext {
-            agent_url: self.config.agent_url.clone(),
+    /// Get a reference to the underlying service
+    pub fn service(&self) -> &S {
+        &self.service
+    }
+
+    /// Turn the client into a service of operations, e.g. to balance over replicas
+    pub fn into_service(self) -> AgentService<S> {
+        AgentService::new(self.service, self.config)
+    }
+
+    /// Turn the client into a Tower service of HTTP requests, e.g. to mount it as a
+    /// reverse proxy in an existing server
+    pub fn into_proxy(self) -> ProxyService<S> {
+        ProxyService::new(self.service, self.config)
+    }
+
+    /// Get the agent card fetched last by [`discover`](Self::discover) or an eager
+    /// connect, if any, even if it expired
+    pub fn agent_card(&self) -> Option<AgentCard> {
+        self.card.last()
+    }
+
+    /// Get the cache of the agent card, e.g. to force a refresh
+    pub fn card_cache(&self) -> &AgentCardCache {
+        &self.card
+    }
+
+    /// Get the health of the client's background tasks, such as the WebSocket reader
+    pub fn background_health(&self) -> BackgroundHealth {
+        self.config.executor.health()
+    }
+
+    /// Subscribe to retry, stream resumption, agent card, and connection change events
+    ///
+    /// Layers publish to the same hub when built with the configuration's
+    /// [`ClientConfig::events`], e.g. `A2ARetryLayer::with_events(config.events.clone())`.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<ClientEvent> {
+        self.config.events.subscribe()
+    }
+
+    /// Build a request for an operation from the client configuration
+    ///
+    /// New tasks get a client-generated ID if the cached agent card advertises the
+    /// [`CLIENT_TASK_IDS`](extensions::CLIENT_TASK_IDS) extension.
+    async fn build_request(&self, operation: A2AOperation) -> Result<A2ARequest, A2AError> {
+        Self::request_with_task_id(&self.config, operation, self.client_task_ids()).await
+    }
+
+    /// Check if the cached agent card accepts client-generated task IDs
+    fn client_task_ids(&self) -> bool {
+        self.agent_card().is_some_and(|card| {
+            card.capabilities
+                .supports_extension(extensions::CLIENT_TASK_IDS)
+        })
+    }
+
+    /// Build a request for an operation from a client configuration, generating the ID
+    /// of a new task if the agent accepts client-generated task IDs
+    async fn request_with_task_id(
+        config: &ClientConfig,
+        mut operation: A2AOperation,
+        supported: bool,
+    ) -> Result<A2ARequest, A2AError> {
+        let assigned = supported && config.ids.assign_task_id(&mut operation);
+
+        let mut request = Self::request_from(config, operation).await?;
+        if assigned {
+            request.context.add_extension(extensions::CLIENT_TASK_IDS);
+        }
+        Ok(request)
+    }
+
+    /// Build a request for an operation from a client configuration
+    ///
+    /// Missing IDs are generated (or rejected) according to [`ClientConfig::ids`], and
+    /// sent messages list the accepted languages.
+    pub(crate) async fn request_from(
+        config: &ClientConfig,
+        mut operation: A2AOperation,
+    ) -> Result<A2ARequest, A2AError> {
+        config.ids.apply(&mut operation)?;
+        config.apply_accept_languages(&mut operation);
+
+        let mut context = RequestContext {
+            agent_url: config.agent_url.clone(),
             auth: None, // Set by AuthLayer
-            timeout: Some(self.config.timeout),
-            metadata: Default::default(),
+            timeout: Some(config.timeout_for(operation.kind())),
+            deadline: config
+                .operation_deadline
+                .map(|budget| Instant::now() + budget),
+            metadata: config.headers().await,
+            meta: None,
+            context_namespace: config.context_namespace.clone(),
+        };
+        if config.ids.correlation_ids {
+            context.metadata.insert(
+                CORRELATION_ID_HEADER.to_string(),
+                uuid::Uuid::now_v7().to_string(),
+            );
         }
+
+        Ok(A2ARequest::new(operation, context))
+    }
+
+    /// Execute an operation with per-request overrides
+    ///
+    /// This is the low-level entry point behind the convenience methods, for callers that
+    /// need to adjust the timeout or headers of an individual request.
+    ///
+    /// # Arguments
+    ///
+    /// * `operation` - The operation to execute
+    /// * `options` - Overrides applied on top of the client configuration
+    pub async fn execute(
+        &mut self,
+        operation: A2AOperation,
+        options: RequestOptions,
+    ) -> Result<A2AResponse, A2AError> {
+        let mut request = self.build_request(operation).await?;
+        if let Some(timeout) = options.timeout {
+            request.context.timeout = Some(timeout);
+        }
+        if let Some(budget) = options.deadline {
+            request.context.deadline = Some(Instant::now() + budget);
+        }
+        request.context.metadata.extend(options.headers);
+        request.context.meta = options.meta;
+        if let Some(namespace) = options.context_namespace {
+            request.context.context_namespace = Some(namespace);
+        }
+
+        self.call(request).await
+    }
+
+    /// Execute an operation, returning the response with details of how it was obtained
+    ///
+    /// The [`ResponseMeta`] reports the number of attempts made by retry layers in the
+    /// client's stack, the total latency, and the endpoint and transport of the last
+    /// attempt.
+    pub async fn execute_with_meta(
+        &mut self,
+        operation: A2AOperation,
+        options: RequestOptions,
+    ) -> Result<(A2AResponse, ResponseMeta), A2AError> {
+        let started = Instant::now();
+        let recorder = MetaRecorder::new();
+        let options = RequestOptions {
+            meta: Some(recorder.clone()),
+            ..options
+        };
+
+        let response = self.execute(operation, options).await?;
+        recorder.record(|meta| meta.latency = started.elapsed());
+        Ok((response, recorder.snapshot()))
+    }
+
+    /// Send a message to the agent, returning the task with details of how it was
+    /// obtained (see [`AgentClient::execute_with_meta`])
+    pub async fn send_message_with_meta(
+        &mut self,
+        message: Message,
+    ) -> Result<(Task, ResponseMeta), A2AError> {
+        let operation = A2AOperation::SendMessage {
+            message,
+            stream: false,
+            context_id: None,
+            task_id: None,
+        };
+
+        match self
+            .execute_with_meta(operation, RequestOptions::new())
+            .await?
+        {
+            (A2AResponse::Task(task), meta) => Ok((*task, meta)),
+            _ => Err(A2AError::Protocol(
+                "Expected task response from send_message_with_meta".into(),
+            )),
+        }
+    }
+
+    /// Execute an operation, returning the response with the validation rules it violates
+    ///
+    /// Imperfect responses, e.g. a completed task without artifacts, are returned rather
+    /// than discarded; check [`Validated::is_valid`] or use [`Validated::into_strict`] to
+    /// reject them.
+    pub async fn execute_validated(
+        &mut self,
+        operation: A2AOperation,
+        options: RequestOptions,
+    ) -> Result<Validated<A2AResponse>, A2AError> {
+        self.execute(operation, options)
+            .await
+            .map(validate_response)
+    }
+
+    /// Call the service, failing with [`A2AError::Timeout`] once the operation deadline
+    /// passes
+    async fn call(&mut self, request: A2ARequest) -> Result<A2AResponse, A2AError> {
+        Self::call_service(&mut self.service, &self.config, request).await
+    }
+
+    /// Call a service, failing with [`A2AError::Timeout`] once the request's deadline
+    /// passes, and record the usage reported in the response
+    ///
+    /// Context IDs are namespaced and stripped if the request has a namespace.
+    pub(crate) async fn call_service(
+        service: &mut S,
+        config: &ClientConfig,
+        mut request: A2ARequest,
+    ) -> Result<A2AResponse, A2AError> {
+        let namespace = request.context.context_namespace.clone();
+        if let Some(namespace) = &namespace {
+            namespace.apply(&mut request.operation);
+        }
+
+        let account = config
+            .usage
+            .as_ref()
+            .map(|usage| usage.account_for(&request.context));
+
+        let mut response = match request.context.remaining() {
+            None => service.call(request).await?,
+            Some(remaining) => {
+                let kind = request.kind();
+                tokio::time::timeout(remaining, service.call(request))
+                    .await
+                    .unwrap_or_else(|_| {
+                        tracing::warn!(?kind, "Operation deadline exceeded");
+                        Err(A2AError::Timeout)
+                    })?
+            }
+        };
+
+        if let (Some(usage), Some(account)) = (&config.usage, account) {
+            usage.observe(&account, &config.usage_keys, &response);
+        }
+        if let Some(namespace) = &namespace {
+            namespace.strip_response(&mut response)?;
+        }
+        Ok(response)
     }
 
     /// Send a message to the agent and get a task
@@ -84,8 +347,8 @@ where
             task_id: None,
         };
 
-        let request = A2ARequest::new(operation, self.build_context());
-        let response = self.service.call(request).await?;
+        let request = self.build_request(operation).await?;
+        let response = self.call(request).await?;
 
         match response {
             A2AResponse::Task(task) => Ok(*task),
@@ -106,8 +369,8 @@ where
             task_id: None,
         };
 
-        let request = A2ARequest::new(operation, self.build_context());
-        let response = self.service.call(request).await?;
+        let request = self.build_request(operation).await?;
+        let response = self.call(request).await?;
 
         match response {
             A2AResponse::Task(task) => Ok(*task),
@@ -135,8 +398,8 @@ where
             task_id: None,
         };
 
-        let request = A2ARequest::new(operation, self.build_context());
-        let response = self.service.call(request).await?;
+        let request = self.build_request(operation).await?;
+        let response = self.call(request).await?;
 
         match response {
             A2AResponse::Task(task) => Ok(*task),
@@ -162,8 +425,8 @@ where
     pub async fn get_task(&mut self, task_id: String) -> Result<Task, A2AError> {
         let operation = A2AOperation::GetTask { task_id };
 
-        let request = A2ARequest::new(operation, self.build_context());
-        let response = self.service.call(request).await?;
+        let request = self.build_request(operation).await?;
+        let response = self.call(request).await?;
 
         match response {
             A2AResponse::Task(task) => Ok(*task),
@@ -195,8 +458,8 @@ where
             next_token: None,
         };
 
-        let request = A2ARequest::new(operation, self.build_context());
-        let response = self.service.call(request).await?;
+        let request = self.build_request(operation).await?;
+        let response = self.call(request).await?;
 
         match response {
             A2AResponse::TaskList { tasks, .. } => Ok(tasks),
@@ -206,6 +469,43 @@ where
         }
     }
 
+    /// List one page of tasks, continuing from a previous page's token
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - Optional filter by task status
+    /// * `page_size` - Maximum number of tasks to return, the agent's default if `None`
+    /// * `page_token` - The `next_token` of the previous page, `None` for the first
+    pub async fn list_tasks_page(
+        &mut self,
+        status: Option<TaskStatus>,
+        page_size: Option<u32>,
+        page_token: Option<String>,
+    ) -> Result<TaskListResponse, A2AError> {
+        let operation = A2AOperation::ListTasks {
+            status,
+            limit: page_size,
+            offset: None,
+            next_token: page_token,
+        };
+
+        let request = self.build_request(operation).await?;
+        match self.call(request).await? {
+            A2AResponse::TaskList {
+                tasks,
+                total,
+                next_token,
+            } => Ok(TaskListResponse {
+                tasks,
+                total,
+                next_token: next_token.filter(|token| !token.is_empty()),
+            }),
+            _ => Err(A2AError::Protocol(
+                "Expected task list response from list_tasks_page".into(),
+            )),
+        }
+    }
+
     /// List all tasks without filtering
     pub async fn list_all_tasks(&mut self) -> Result<Vec<Task>, A2AError> {
         self.list_tasks(None, None).await
@@ -229,10 +529,37 @@ where
     ///
     /// The updated task with cancelled status
     pub async fn cancel_task(&mut self, task_id: String) -> Result<Task, A2AError> {
-        let operation = A2AOperation::CancelTask { task_id };
+        self.cancel(task_id, None).await
+    }
+
+    /// Cancel a task by ID, telling the agent why
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The unique identifier of the task to cancel
+    /// * `reason` - Why the task is cancelled, sent in the request body
+    ///
+    /// # Returns
+    ///
+    /// The updated task with cancelled status
+    pub async fn cancel_task_with_reason(
+        &mut self,
+        task_id: String,
+        reason: CancelReason,
+    ) -> Result<Task, A2AError> {
+        self.cancel(task_id, Some(reason)).await
+    }
 
-        let request = A2ARequest::new(operation, self.build_context());
-        let response = self.service.call(request).await?;
+    /// Send a cancel request
+    async fn cancel(
+        &mut self,
+        task_id: String,
+        reason: Option<CancelReason>,
+    ) -> Result<Task, A2AError> {
+        let operation = A2AOperation::CancelTask { task_id, reason };
+
+        let request = self.build_request(operation).await?;
+        let response = self.call(request).await?;
 
         match response {
             A2AResponse::Task(task) => Ok(*task),
@@ -242,19 +569,115 @@ where
         }
     }
 
+    /// Create or replace a push notification configuration of a task
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task whose updates are pushed
+    /// * `config` - The webhook to push to; the agent assigns an ID if it has none
+    ///
+    /// # Returns
+    ///
+    /// The configuration as stored by the agent
+    pub async fn set_push_notification_config(
+        &mut self,
+        task_id: String,
+        config: PushNotificationConfig,
+    ) -> Result<TaskPushNotificationConfig, A2AError> {
+        let operation = A2AOperation::SetPushNotificationConfig { task_id, config };
+        self.push_notification_config(operation).await
+    }
+
+    /// Get a push notification configuration of a task
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task the configuration belongs to
+    /// * `config_id` - ID of the configuration
+    pub async fn get_push_notification_config(
+        &mut self,
+        task_id: String,
+        config_id: String,
+    ) -> Result<TaskPushNotificationConfig, A2AError> {
+        let operation = A2AOperation::GetPushNotificationConfig { task_id, config_id };
+        self.push_notification_config(operation).await
+    }
+
+    /// List the push notification configurations of a task
+    pub async fn list_push_notification_configs(
+        &mut self,
+        task_id: String,
+    ) -> Result<Vec<TaskPushNotificationConfig>, A2AError> {
+        let operation = A2AOperation::ListPushNotificationConfigs { task_id };
+
+        let request = self.build_request(operation).await?;
+        match self.call(request).await? {
+            A2AResponse::PushNotificationConfigs(configs) => Ok(configs),
+            A2AResponse::Empty => Ok(Vec::new()),
+            _ => Err(A2AError::Protocol(
+                "Expected push notification configs from list_push_notification_configs".into(),
+            )),
+        }
+    }
+
+    /// Delete a push notification configuration of a task
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task the configuration belongs to
+    /// * `config_id` - ID of the configuration
+    pub async fn delete_push_notification_config(
+        &mut self,
+        task_id: String,
+        config_id: String,
+    ) -> Result<(), A2AError> {
+        let operation = A2AOperation::DeletePushNotificationConfig { task_id, config_id };
+
+        let request = self.build_request(operation).await?;
+        self.call(request).await?;
+        Ok(())
+    }
+
+    /// Send a request answered with a single push notification configuration
+    async fn push_notification_config(
+        &mut self,
+        operation: A2AOperation,
+    ) -> Result<TaskPushNotificationConfig, A2AError> {
+        let request = self.build_request(operation).await?;
+        match self.call(request).await? {
+            A2AResponse::PushNotificationConfig(config) => Ok(*config),
+            _ => Err(A2AError::Protocol(
+                "Expected push notification config response".into(),
+            )),
+        }
+    }
+
     /// Discover agent capabilities by fetching the Agent Card
     ///
-    /// This retrieves the agent's metadata from `/.well-known/agent-card.json`
+    /// This retrieves the agent's metadata from `/.well-known/agent-card.json`, unless
+    /// the card fetched last is still within its time to live (see
+    /// [`ClientConfig::card_ttl`] and [`AgentCardCache::force_refresh`]).
     ///
     /// # Returns
     ///
     /// The agent's capability card
     pub async fn discover(&mut self) -> Result<AgentCard, A2AError> {
+        if let Some(card) = self.card.get() {
+            return Ok(card);
+        }
+
         let operation = A2AOperation::DiscoverAgent;
 
-        let request = A2ARequest::new(operation, self.build_context());
-        let response = self.service.call(request).await?;
+        let request = self.build_request(operation).await?;
+        let response = self.call(request).await?;
+
+        let card = Self::card_from(response)?;
+        Self::cache_card(&self.card, &self.config.events, &card);
+        Ok(card)
+    }
 
+    /// Extract the agent card from a discovery response
+    fn card_from(response: A2AResponse) -> Result<AgentCard, A2AError> {
         match response {
             A2AResponse::AgentCard(card) => Ok(*card),
             _ => Err(A2AError::Protocol(
@@ -263,6 +686,19 @@ where
         }
     }
 
+    /// Cache a fetched card, publishing its changes from the previously cached one
+    fn cache_card(cache: &AgentCardCache, events: &ClientEvents, card: &AgentCard) {
+        if let Some(previous) = cache.insert(card.clone()) {
+            let delta = previous.diff(card);
+            if !delta.is_empty() {
+                events.emit(ClientEvent::AgentCardChanged {
+                    agent: card.name.clone(),
+                    delta,
+                });
+            }
+        }
+    }
+
     /// Poll a task until it reaches a terminal state
     ///
     /// This is a convenience method that repeatedly calls get_task until
@@ -300,6 +736,686 @@ where
             tokio::time::sleep(tokio::time::Duration::from_millis(poll_interval_ms)).await;
         }
     }
+
+    /// Watch a task by polling, yielding only the changes between snapshots
+    ///
+    /// The first item describes the initial snapshot (see [`TaskDelta::snapshot`]).
+    /// Subsequent polls that change nothing are skipped. The stream ends after the task
+    /// reaches a terminal state or a poll fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task ID to watch
+    /// * `poll_interval` - How often to poll
+    pub fn watch_task(
+        &mut self,
+        task_id: String,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = Result<(Task, TaskDelta), A2AError>> + '_ {
+        let state = (self, None::<Task>, false);
+
+        stream::unfold(state, move |(client, previous, done)| {
+            let task_id = task_id.clone();
+            async move {
+                if done {
+                    return None;
+                }
+
+                loop {
+                    if previous.is_some() {
+                        tokio::time::sleep(poll_interval).await;
+                    }
+
+                    let task = match client.get_task(task_id.clone()).await {
+                        Ok(task) => task,
+                        Err(e) => return Some((Err(e), (client, previous, true))),
+                    };
+
+                    let delta = match &previous {
+                        Some(previous) => previous.diff(&task),
+                        None => TaskDelta::snapshot(&task),
+                    };
+
+                    if delta.is_empty() {
+                        continue;
+                    }
+
+                    let done = task.is_terminal();
+                    return Some((Ok((task.clone(), delta)), (client, Some(task), done)));
+                }
+            }
+        })
+    }
+}
+
+impl<S> AgentClient<S>
+where
+    S: ProtocolStack + Service<A2ARequest, Response = A2AResponse, Error = A2AError>,
+    S::Future: Send,
+{
+    /// Send a message and stream the agent's events as they are produced
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transport does not support streaming or the stream
+    /// cannot be opened
+    pub async fn send_message_stream(&self, message: Message) -> Result<EventStream, A2AError> {
+        let operation = A2AOperation::SendMessage {
+            message,
+            stream: true,
+            context_id: None,
+            task_id: None,
+        };
+
+        let request = self.build_request(operation).await?;
+        let events = Self::call_streaming(&self.service, request).await?;
+        Ok(self.guard_stream(events).boxed())
+    }
+
+    /// Subscribe to the events of an existing task
+    ///
+    /// The events are streamed over SSE on HTTP and over the connection on WebSocket,
+    /// without heartbeats and retry hints. If the transport cannot stream or the cached
+    /// agent card says the agent does not, the task is polled every
+    /// [`poll_interval`](ClientConfig::poll_interval) instead, yielding a
+    /// [`StreamEvent::Task`] snapshot whenever it changes until it reaches a terminal
+    /// state.
+    ///
+    /// Dropping a streamed subscription before the task finished cancels the task if
+    /// the client's [`StreamDropPolicy`] says so.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task to subscribe to
+    pub fn subscribe_task(
+        &self,
+        task_id: String,
+    ) -> impl Stream<Item = Result<StreamEvent, A2AError>> {
+        let streaming = self.service.protocol().transport().supports_streaming()
+            && self
+                .agent_card()
+                .is_none_or(|card| card.capabilities.streaming);
+        if !streaming {
+            return Either::Right(self.poll_task(task_id));
+        }
+
+        let (service, config) = (self.service.clone(), self.config.clone());
+        let events = stream::once(async move {
+            let request =
+                Self::request_from(&config, A2AOperation::SubscribeTask { task_id }).await?;
+            Self::call_streaming(&service, request).await
+        })
+        .try_flatten();
+        let events = self.guard_stream(events).filter_map(|event| {
+            future::ready(match event {
+                Ok(event) if event.is_control() => None,
+                Ok(event) => Some(StreamEvent::try_from(event)),
+                Err(e) => Some(Err(e)),
+            })
+        });
+        Either::Left(events)
+    }
+
+    /// Poll a task, yielding a snapshot whenever it changes until it is terminal
+    fn poll_task(&self, task_id: String) -> impl Stream<Item = Result<StreamEvent, A2AError>> {
+        let state = (
+            self.service.clone(),
+            self.config.clone(),
+            None::<Task>,
+            false,
+        );
+
+        stream::unfold(state, move |(mut service, config, previous, done)| {
+            let task_id = task_id.clone();
+            async move {
+                if done || previous.as_ref().is_some_and(Task::is_terminal) {
+                    return None;
+                }
+
+                loop {
+                    if previous.is_some() {
+                        tokio::time::sleep(config.poll_interval).await;
+                    }
+
+                    let operation = A2AOperation::GetTask {
+                        task_id: task_id.clone(),
+                    };
+                    let result = match Self::request_from(&config, operation).await {
+                        Ok(request) => Self::call_service(&mut service, &config, request).await,
+                        Err(e) => Err(e),
+                    };
+                    let task = match result {
+                        Ok(A2AResponse::Task(task)) => Ok(*task),
+                        Ok(_) => Err(A2AError::Protocol(
+                            "Expected task response from get_task".into(),
+                        )),
+                        Err(e) => Err(e),
+                    };
+                    let task = match task {
+                        Ok(task) => task,
+                        Err(e) => return Some((Err(e), (service, config, previous, true))),
+                    };
+
+                    if previous
+                        .as_ref()
+                        .is_some_and(|previous| previous.diff(&task).is_empty())
+                    {
+                        continue;
+                    }
+                    let event = StreamEvent::Task(task.clone());
+                    return Some((Ok(event), (service, config, Some(task), false)));
+                }
+            }
+        })
+    }
+
+    /// List tasks as a stream, fetching pages as they are consumed
+    ///
+    /// Tasks are decoded while each page arrives (see
+    /// [`A2AProtocolService::call_task_list`](crate::service::A2AProtocolService::call_task_list)), so neither a page nor the whole list is
+    /// held in memory. The next page is requested with the continuation token once the
+    /// previous one is consumed; the stream ends after a page without one, or after
+    /// the first error.
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - Optional filter by task status
+    /// * `page_size` - Maximum number of tasks per page, the agent's default if `None`
+    pub fn list_tasks_stream(
+        &self,
+        status: Option<TaskStatus>,
+        page_size: Option<u32>,
+    ) -> impl Stream<Item = Result<Task, A2AError>> {
+        let state = (
+            self.service.clone(),
+            self.config.clone(),
+            None::<TaskListStream>,
+            Some(None::<String>),
+        );
+
+        stream::unfold(
+            state,
+            move |(service, config, mut page, mut next)| async move {
+                loop {
+                    if let Some(items) = page.as_mut() {
+                        match items.next().await {
+                            Some(Ok(TaskListItem::Task(mut task))) => {
+                                // Tasks of other tenants are left out
+                                if let Some(namespace) = &config.context_namespace {
+                                    if namespace.strip_listed(&mut task).is_err() {
+                                        continue;
+                                    }
+                                }
+                                return Some((Ok(*task), (service, config, page, next)));
+                            }
+                            Some(Ok(TaskListItem::End { next_token, .. })) => {
+                                next = next_token.filter(|token| !token.is_empty()).map(Some);
+                            }
+                            Some(Err(e)) => return Some((Err(e), (service, config, None, None))),
+                            None => next = None,
+                        }
+                        page = None;
+                        continue;
+                    }
+
+                    let operation = A2AOperation::ListTasks {
+                        status,
+                        limit: page_size,
+                        offset: None,
+                        next_token: next.take()?,
+                    };
+                    let result = match Self::request_from(&config, operation).await {
+                        Ok(mut request) => match service.prepare(&mut request).await {
+                            Ok(()) => service.protocol().call_task_list(request).await,
+                            Err(e) => Err(e),
+                        },
+                        Err(e) => Err(e),
+                    };
+                    match result {
+                        Ok(items) => page = Some(items),
+                        Err(e) => return Some((Err(e), (service, config, None, None))),
+                    }
+                }
+            },
+        )
+    }
+
+    /// Open a stream past the service stack, after applying the layers' changes
+    ///
+    /// Context IDs are namespaced and stripped if the request has a namespace.
+    pub(crate) async fn call_streaming(
+        service: &S,
+        mut request: A2ARequest,
+    ) -> Result<EventStream, A2AError> {
+        let namespace = request.context.context_namespace.clone();
+        if let Some(namespace) = &namespace {
+            namespace.apply(&mut request.operation);
+        }
+        service.prepare(&mut request).await?;
+        let events = service.protocol().call_streaming(request).await?;
+
+        Ok(match namespace {
+            Some(namespace) => events
+                .map(move |event| {
+                    let mut event = event?;
+                    namespace.strip_event(&mut event)?;
+                    Ok(event)
+                })
+                .boxed(),
+            None => events,
+        })
+    }
+
+    /// Apply the configured [`StreamDropPolicy`] to a stream of events
+    fn guard_stream<E>(&self, events: E) -> AbandonGuard<E>
+    where
+        E: Stream<Item = Result<SseEvent, A2AError>>,
+    {
+        let cancel = (self.config.stream_drop == StreamDropPolicy::Cancel)
+            .then(|| (self.service.clone(), self.config.clone()));
+        events.on_abandon(move |task_id| {
+            let Some((mut service, config)) = cancel else {
+                return;
+            };
+            let executor = config.executor.clone();
+            executor.spawn("a2a-stream-cancel", async move {
+                let operation = A2AOperation::CancelTask {
+                    task_id: task_id.clone(),
+                    reason: Some(
+                        CancelReason::new("Client stopped listening").with_code("stream-dropped"),
+                    ),
+                };
+                let result = match Self::request_from(&config, operation).await {
+                    Ok(request) => Self::call_service(&mut service, &config, request).await,
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = result {
+                    tracing::warn!(%task_id, error = %e, "Failed to cancel abandoned task");
+                }
+            });
+        })
+    }
+
+    /// Send a message and stream its events, resuming after transient failures
+    ///
+    /// The ID of the created task is captured from the first event that carries it. If
+    /// the stream then fails with a retryable error or closes before a terminal event,
+    /// the client resubscribes to the task instead of sending the message again, which
+    /// would create a duplicate task. Failures before the task ID is known are returned
+    /// as-is.
+    ///
+    /// If the agent sent an SSE `retry:` hint, the client waits that long before
+    /// resubscribing (bounded by the operation deadline). Hints are still delivered
+    /// to the caller, along with heartbeats; see [`SseEvent::is_control`].
+    ///
+    /// Resubscribing may replay events that were already delivered; combine with
+    /// [`EventStreamExt::sequenced`] to drop them.
+    /// To fetch the task's last known status before resubscribing, resume the stream
+    /// with [`resume_task_stream`](Self::resume_task_stream) instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to send
+    /// * `max_reconnects` - Maximum number of times to resubscribe
+    pub fn send_message_stream_resilient(
+        &self,
+        message: Message,
+        max_reconnects: u32,
+    ) -> impl Stream<Item = Result<SseEvent, A2AError>> {
+        self.resumable_stream(Some(message), None, false, max_reconnects)
+    }
+
+    /// Resume the event stream of a task, e.g. after a disconnect dropped the stream of
+    /// [`send_message_stream`](Self::send_message_stream)
+    ///
+    /// The task is fetched first and yielded as a `task` snapshot carrying its last
+    /// known status, then its updates are streamed through `tasks/resubscribe`. The
+    /// stream ends after the snapshot if the task already finished. Later failures are
+    /// resumed the same way, fetching a fresh snapshot before each resubscription.
+    /// Timeouts configured for
+    /// [`OperationKind::ResubscribeTask`](crate::protocol::OperationKind::ResubscribeTask) apply to the
+    /// resubscriptions.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task whose stream dropped
+    /// * `max_reconnects` - Maximum number of times to resubscribe after the first time
+    pub fn resume_task_stream(
+        &self,
+        task_id: String,
+        max_reconnects: u32,
+    ) -> impl Stream<Item = Result<SseEvent, A2AError>> {
+        self.resumable_stream(None, Some(task_id), true, max_reconnects)
+    }
+
+    /// Stream the events of a new message or an existing task, subscribing to the task
+    /// again after transient failures
+    ///
+    /// With `replay`, each subscription is preceded by a task snapshot and made through
+    /// `tasks/resubscribe`.
+    fn resumable_stream(
+        &self,
+        message: Option<Message>,
+        task_id: Option<String>,
+        replay: bool,
+        max_reconnects: u32,
+    ) -> impl Stream<Item = Result<SseEvent, A2AError>> {
+        let state = ResumableStream {
+            service: self.service.clone(),
+            deadline: self
+                .config
+                .operation_deadline
+                .map(|budget| Instant::now() + budget),
+            config: self.config.clone(),
+            message,
+            task_id,
+            client_task_ids: self.client_task_ids(),
+            events: None,
+            retry: None,
+            reconnects: 0,
+            max_reconnects,
+            replay,
+            replayed: false,
+            done: false,
+        };
+
+        let events = stream::unfold(state, |mut state| async move {
+            loop {
+                if state.done {
+                    return None;
+                }
+
+                let events = match state.events.as_mut() {
+                    Some(events) => events,
+                    None => {
+                        if state.reconnects > 0 {
+                            state.wait_for_retry().await;
+                        }
+                        if let (Some(task_id), true, false) =
+                            (&state.task_id, state.replay, state.replayed)
+                        {
+                            state.replayed = true;
+                            match Self::task_snapshot(&mut state.service, &state.config, task_id)
+                                .await
+                            {
+                                Ok(event) => {
+                                    state.done = event.is_terminal();
+                                    return Some((Ok(event), state));
+                                }
+                                Err(e) => {
+                                    tracing::warn!(%task_id, error = %e, "Failed to fetch task before resubscribing");
+                                }
+                            }
+                        }
+                        let operation = match (&state.task_id, state.message.take()) {
+                            (Some(task_id), _) if state.replay => A2AOperation::ResubscribeTask {
+                                task_id: task_id.clone(),
+                            },
+                            (Some(task_id), _) => A2AOperation::SubscribeTask {
+                                task_id: task_id.clone(),
+                            },
+                            (None, Some(message)) => A2AOperation::SendMessage {
+                                message,
+                                stream: true,
+                                context_id: None,
+                                task_id: None,
+                            },
+                            (None, None) => return None,
+                        };
+
+                        let request = match Self::request_with_task_id(
+                            &state.config,
+                            operation,
+                            state.client_task_ids,
+                        )
+                        .await
+                        {
+                            Ok(request) => request,
+                            Err(e) => {
+                                state.done = true;
+                                return Some((Err(e), state));
+                            }
+                        };
+                        // A client-generated ID lets the stream resume before the agent
+                        // reported the task
+                        if let A2AOperation::SendMessage {
+                            task_id: Some(task_id),
+                            ..
+                        } = &request.operation
+                        {
+                            state.task_id.get_or_insert_with(|| task_id.clone());
+                        }
+                        match Self::call_streaming(&state.service, request).await {
+                            Ok(events) => state.events.insert(events),
+                            Err(e) if state.can_resume(&e) => {
+                                state.resubscribe(Some(&e));
+                                continue;
+                            }
+                            Err(e) => {
+                                state.done = true;
+                                return Some((Err(e), state));
+                            }
+                        }
+                    }
+                };
+
+                match events.next().await {
+                    Some(Ok(event)) => {
+                        if let Some(delay) = event.retry_hint() {
+                            state.retry = Some(delay);
+                        }
+                        if state.task_id.is_none() {
+                            state.task_id = event.task_id().map(String::from);
+                        }
+                        state.done = event.is_terminal();
+                        return Some((Ok(event), state));
+                    }
+                    Some(Err(e)) if state.can_resume(&e) => state.resubscribe(Some(&e)),
+                    Some(Err(e)) => {
+                        state.done = true;
+                        return Some((Err(e), state));
+                    }
+                    None if state.task_id.is_some() && state.can_reconnect() => {
+                        state.resubscribe(None)
+                    }
+                    None => return None,
+                }
+            }
+        });
+        self.guard_stream(events)
+    }
+
+    /// Fetch a task as a `task` event, final if the task is terminal
+    async fn task_snapshot(
+        service: &mut S,
+        config: &ClientConfig,
+        task_id: &str,
+    ) -> Result<SseEvent, A2AError> {
+        let operation = A2AOperation::GetTask {
+            task_id: task_id.to_string(),
+        };
+        let request = Self::request_from(config, operation).await?;
+        let A2AResponse::Task(task) = Self::call_service(service, config, request).await? else {
+            return Err(A2AError::Protocol(
+                "Expected task response from get_task".into(),
+            ));
+        };
+
+        Ok(SseEvent {
+            kind: "task".to_string(),
+            final_event: task.is_terminal(),
+            payload: serde_json::to_value(&task)?,
+            id: None,
+        })
+    }
+}
+
+/// State of a stream returned by `send_message_stream_resilient` or
+/// `resume_task_stream`
+struct ResumableStream<S> {
+    service: S,
+    /// Deadline after which the stream is no longer resumed
+    deadline: Option<Instant>,
+    config: ClientConfig,
+    message: Option<Message>,
+    task_id: Option<String>,
+    /// Whether the agent accepts client-generated task IDs
+    client_task_ids: bool,
+    events: Option<EventStream>,
+    /// Reconnection delay requested by the agent
+    retry: Option<Duration>,
+    reconnects: u32,
+    max_reconnects: u32,
+    /// Whether to fetch the task before each resubscription
+    replay: bool,
+    /// Whether the task was fetched since the last resubscription
+    replayed: bool,
+    done: bool,
+}
+
+impl<S> ResumableStream<S> {
+    /// Check whether the stream can be resumed after an error
+    fn can_resume(&self, error: &A2AError) -> bool {
+        self.task_id.is_some() && error.is_retryable() && self.can_reconnect()
+    }
+
+    /// Check whether reconnects are left and the operation deadline has not passed
+    fn can_reconnect(&self) -> bool {
+        self.reconnects < self.max_reconnects
+            && self
+                .deadline
+                .is_none_or(|deadline| Instant::now() < deadline)
+    }
+
+    /// Wait for the reconnection delay requested by the agent, if any
+    async fn wait_for_retry(&self) {
+        let Some(mut delay) = self.retry else {
+            return;
+        };
+        if let Some(deadline) = self.deadline {
+            delay = delay.min(deadline.saturating_duration_since(Instant::now()));
+        }
+        tracing::debug!(?delay, "Waiting before resubscribing");
+        tokio::time::sleep(delay).await;
+    }
+
+    /// Drop the current connection so the next poll resubscribes to the task
+    fn resubscribe(&mut self, error: Option<&A2AError>) {
+        self.events = None;
+        self.reconnects += 1;
+        self.replayed = false;
+
+        self.config.events.emit(ClientEvent::StreamResumed {
+            task_id: self.task_id.clone().unwrap_or_default(),
+            attempt: self.reconnects,
+            error: error.map(ToString::to_string),
+        });
+    }
+}
+
+impl<S> AgentClient<S>
+where
+    S: ProtocolStack + Service<A2ARequest, Response = A2AResponse, Error = A2AError>,
+    S::Future: Send,
+{
+    /// Establish the transport connection ahead of the first request
+    ///
+    /// Pays the TCP, TLS, or WebSocket handshake up front so the first operation does
+    /// not. Requests connect on demand, so calling this is optional.
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::BindingMismatch` if the agent card fetched earlier declares
+    /// no endpoint for the client's binding
+    pub async fn connect(&self) -> Result<(), A2AError> {
+        if let Some(card) = self.agent_card() {
+            card.check_bindings(self.service.protocol().codec().bindings())?;
+        }
+        self.service.protocol().transport().connect().await
+    }
+
+    /// Get the connection state the transport observed last, or `None` before the
+    /// first request
+    ///
+    /// Changes are also published as [`ClientEvent::ConnectionChanged`] to
+    /// [`subscribe_events`](Self::subscribe_events).
+    pub fn connection_state(&self) -> Option<ConnectionState> {
+        self.service.protocol().transport().connection_state()
+    }
+
+    /// Connect (and optionally fetch the agent card) in the background
+    ///
+    /// Runs on the configured executor. Failures are logged, since the next request
+    /// retries the connection anyway.
+    pub(crate) fn spawn_connect(&self, prefetch_card: bool) {
+        let mut service = self.service.clone();
+        let config = self.config.clone();
+        let slot = self.card.clone();
+        self.config.executor.spawn("a2a-eager-connect", async move {
+            if let Err(e) = service.protocol().transport().connect().await {
+                tracing::warn!(error = %e, "Eager connect failed");
+                return;
+            }
+            if !prefetch_card {
+                return;
+            }
+
+            let request = match Self::request_from(&config, A2AOperation::DiscoverAgent).await {
+                Ok(request) => request,
+                Err(e) => return tracing::warn!(error = %e, "Agent card prefetch failed"),
+            };
+            match service.call(request).await.and_then(Self::card_from) {
+                Ok(card) => {
+                    if let Err(e) = card.check_bindings(service.protocol().codec().bindings()) {
+                        tracing::warn!(error = %e, "Agent card does not list the client's binding");
+                    }
+                    Self::cache_card(&slot, &config.events, &card);
+                }
+                Err(e) => tracing::warn!(error = %e, "Agent card prefetch failed"),
+            }
+        });
+    }
+}
+
+impl<S> AgentClient<S>
+where
+    S: ProtocolStack<Transport = DryRunTransport>
+        + Service<A2ARequest, Response = A2AResponse, Error = A2AError>,
+{
+    /// Render the wire request for an operation without sending it
+    ///
+    /// The operation goes through the same steps as [`AgentClient::execute`], so the
+    /// returned request carries the generated IDs, configured headers, and encoded body.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request could not be composed, e.g. because validation
+    /// or encoding failed
+    pub async fn dry_run(
+        &mut self,
+        operation: A2AOperation,
+        options: RequestOptions,
+    ) -> Result<TransportRequest, A2AError> {
+        let transport = self.service.protocol().transport().clone();
+        transport.take_requests();
+
+        match self.execute(operation, options).await {
+            Err(err) => transport.last_request().ok_or(err),
+            Ok(_) => Err(A2AError::Protocol(
+                "Dry-run transport unexpectedly produced a response".into(),
+            )),
+        }
+    }
+}
+
+impl<S: ProtocolStack<Transport = WebSocketTransport>> AgentClient<S> {
+    /// Subscribe to JSON-RPC notifications pushed by the agent
+    ///
+    /// Agents may push task updates and custom notifications over the WebSocket without
+    /// a preceding request. Only notifications received after subscribing are delivered.
+    pub fn notifications(&self) -> impl Stream<Item = JsonRpcNotification> {
+        self.service.protocol().transport().notifications()
+    }
 }
 
 #[cfg(test)]
@@ -308,7 +1424,7 @@ mod tests {
 
     use crate::{
         codec::JsonCodec,
-        protocol::message::Message,
+        protocol::{consts::headers, message::Message},
         service::A2AProtocolService,
         transport::{mock::MockTransport, TransportResponse},
     };
@@ -357,14 +1473,575 @@ mod tests {
         assert_eq!(task.id, "task-456");
     }
 
+    #[tokio::test]
+    async fn test_push_notification_configs() {
+        let transport = MockTransport::new(|req| {
+            let config = TaskPushNotificationConfig {
+                task_id: "task-1".into(),
+                push_notification_config: PushNotificationConfig::new(
+                    "https://app.example.com/hook",
+                )
+                .with_id("hook-1"),
+            };
+            let body = match (req.method.as_str(), req.endpoint.as_str()) {
+                ("POST", "/v1/tasks/task-1/pushNotificationConfigs") => {
+                    serde_json::to_vec(&config).unwrap()
+                }
+                ("GET", "/v1/tasks/task-1/pushNotificationConfigs") => {
+                    serde_json::to_vec(&[config]).unwrap()
+                }
+                ("DELETE", "/v1/tasks/task-1/pushNotificationConfigs/hook-1") => Vec::new(),
+                other => panic!("unexpected request {:?}", other),
+            };
+            TransportResponse::new(200).body(Bytes::from(body))
+        });
+
+        let service = A2AProtocolService::new(transport, Arc::new(JsonCodec::new()));
+        let mut client = AgentClient::new(service, ClientConfig::new(agent_url()));
+
+        let config = client
+            .set_push_notification_config(
+                "task-1".into(),
+                PushNotificationConfig::new("https://app.example.com/hook"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            config.push_notification_config.id.as_deref(),
+            Some("hook-1")
+        );
+
+        let configs = client
+            .list_push_notification_configs("task-1".into())
+            .await
+            .unwrap();
+        assert_eq!(configs, [config]);
+
+        client
+            .delete_push_notification_config("task-1".into(), "hook-1".into())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_meta() {
+        use std::{
+            sync::atomic::{AtomicUsize, Ordering},
+            time::Duration,
+        };
+
+        use tower::Layer;
+
+        use crate::layer::{A2ARetryLayer, RetryPolicy};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let transport = MockTransport::new(move |_req| {
+            if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                return TransportResponse::new(429);
+            }
+            let task = Task::new("task-456", Message::user("Test"));
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+
+        let service = A2AProtocolService::new(transport, Arc::new(JsonCodec::new()));
+        let retry = RetryPolicy::new(3).with_backoff(Duration::ZERO, Duration::ZERO);
+        let service = A2ARetryLayer::new(retry).layer(service);
+        let mut client = AgentClient::new(service, ClientConfig::new(agent_url()));
+
+        let operation = A2AOperation::GetTask {
+            task_id: "task-456".to_string(),
+        };
+        let (response, meta) = client
+            .execute_with_meta(operation, RequestOptions::new())
+            .await
+            .unwrap();
+
+        assert_eq!(response.into_task().unwrap().id, "task-456");
+        assert_eq!(meta.attempts, 3);
+        assert_eq!(meta.endpoint.as_deref(), Some("/v1/tasks/task-456"));
+        assert_eq!(meta.transport.as_deref(), Some("mock"));
+        assert!(meta.latency > Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_send_message_stream_resilient() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Mutex,
+        };
+
+        use serde_json::json;
+
+        let endpoints = Arc::new(Mutex::new(Vec::new()));
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let recorded = endpoints.clone();
+        let counter = calls.clone();
+        let fetched = recorded.clone();
+        let transport = MockTransport::new(move |req| {
+            fetched.lock().unwrap().push(req.endpoint);
+            TransportResponse::new(200)
+        })
+        .with_stream_handler(move |req| {
+            recorded.lock().unwrap().push(req.endpoint);
+            let event = |kind: &str, payload| SseEvent {
+                kind: kind.to_string(),
+                payload,
+                final_event: false,
+                id: None,
+            };
+
+            Ok(match counter.fetch_add(1, Ordering::SeqCst) {
+                0 => vec![
+                    Ok(event("task", json!({"kind": "task", "id": "task-1"}))),
+                    Ok(SseEvent::retry(Duration::from_millis(50))),
+                    Err(A2AError::Transport("connection reset".into())),
+                ],
+                _ => vec![Ok(event(
+                    "status-update",
+                    json!({"taskId": "task-1", "state": "completed"}),
+                ))],
+            })
+        });
+
+        let service = A2AProtocolService::new(transport, Arc::new(JsonCodec));
+        let client = AgentClient::new(service, ClientConfig::new(agent_url()));
+        let mut client_events = client.subscribe_events();
+
+        let started = Instant::now();
+        let events: Vec<_> = client
+            .send_message_stream_resilient(Message::user("Hello"), 3)
+            .collect()
+            .await;
+
+        // The retry hint is delivered and delays the resubscription
+        assert!(started.elapsed() >= Duration::from_millis(50));
+
+        assert!(matches!(
+            client_events.try_recv().unwrap(),
+            ClientEvent::StreamResumed { attempt: 1, .. }
+        ));
+
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(Result::is_ok));
+        assert!(events[1].as_ref().unwrap().is_control());
+
+        // The stream subscribes to the task again without fetching it first
+        assert_eq!(
+            *endpoints.lock().unwrap(),
+            vec!["/v1/tasks", "/v1/tasks/task-1:stream"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_message_stream_unsupported() {
+        let service = A2AProtocolService::new(MockTransport::ok(), Arc::new(JsonCodec));
+        let client = AgentClient::new(service, ClientConfig::new(agent_url()));
+
+        let result = client.send_message_stream(Message::user("Hello")).await;
+        assert!(matches!(result, Err(A2AError::Transport(_))));
+    }
+
+    #[tokio::test]
+    async fn test_context_namespace() {
+        use crate::service::ContextNamespace;
+
+        let sent = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = sent.clone();
+        let transport = MockTransport::new(move |req| {
+            let body: serde_json::Value = serde_json::from_slice(&req.body).unwrap();
+            let context_id = body["message"]["contextId"].as_str().unwrap().to_string();
+            recorded.lock().unwrap().push(context_id.clone());
+
+            let mut task = Task::new("task-1", Message::user("Hello"));
+            task.context_id = Some(context_id);
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+        let service = A2AProtocolService::new(transport, Arc::new(JsonCodec::new()));
+        let mut config = ClientConfig::new(agent_url());
+        config.context_namespace = Some(ContextNamespace::prefix("acme"));
+        let mut client = AgentClient::new(service, config);
+        let in_context = |mut message: Message| {
+            message.context_id = Some("ctx-1".into());
+            message
+        };
+
+        let task = client
+            .send_message(in_context(Message::user("Hello")))
+            .await
+            .unwrap();
+        assert_eq!(task.context_id.as_deref(), Some("ctx-1"));
+
+        let operation = A2AOperation::SendMessage {
+            message: in_context(Message::user("Hello")),
+            stream: false,
+            context_id: None,
+            task_id: None,
+        };
+        let options =
+            RequestOptions::new().with_context_namespace(ContextNamespace::prefix("globex"));
+        let task = client.execute(operation, options).await.unwrap();
+        assert_eq!(
+            task.into_task().unwrap().context_id.as_deref(),
+            Some("ctx-1")
+        );
+
+        // Messages without a context get a fresh one in the namespace
+        client.send_message(Message::user("Hello")).await.unwrap();
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent[..2], ["4:acme:ctx-1", "6:globex:ctx-1"]);
+        assert!(sent[2].starts_with("4:acme:"));
+    }
+
+    #[tokio::test]
+    async fn test_operation_timeouts() {
+        use std::time::Duration;
+
+        use crate::protocol::OperationKind;
+
+        #[derive(Clone)]
+        struct Timeouts;
+
+        impl Service<A2ARequest> for Timeouts {
+            type Response = A2AResponse;
+            type Error = A2AError;
+            type Future = std::future::Ready<Result<A2AResponse, A2AError>>;
+
+            fn poll_ready(
+                &mut self,
+                _cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<Result<(), A2AError>> {
+                std::task::Poll::Ready(Ok(()))
+            }
+
+            fn call(&mut self, req: A2ARequest) -> Self::Future {
+                let secs = req.context.timeout.unwrap().as_secs();
+                let task = Task::new(secs.to_string(), Message::user("Test"));
+                std::future::ready(Ok(A2AResponse::Task(Box::new(task))))
+            }
+        }
+
+        let config = ClientConfig::new(agent_url())
+            .with_timeout(Duration::from_secs(120))
+            .with_operation_timeout(OperationKind::GetTask, Duration::from_secs(5));
+        let mut client = AgentClient::new(Timeouts, config);
+
+        let task = client.get_task("task-1".to_string()).await.unwrap();
+        assert_eq!(task.id, "5");
+
+        let task = client.send_message(Message::user("Hello")).await.unwrap();
+        assert_eq!(task.id, "120");
+
+        let options = RequestOptions::new().with_timeout(Duration::from_secs(1));
+        let operation = A2AOperation::GetTask {
+            task_id: "task-1".to_string(),
+        };
+        match client.execute(operation, options).await.unwrap() {
+            A2AResponse::Task(task) => assert_eq!(task.id, "1"),
+            _ => panic!("Expected task response"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_task() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let polls = AtomicUsize::new(0);
+        let transport = MockTransport::new(move |_req| {
+            let task = Task::new("task-123", Message::user("Test"));
+            let task = match polls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => task.with_status(TaskStatus::Working),
+                _ => task.with_status(TaskStatus::Completed),
+            };
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+
+        let service = A2AProtocolService::new(transport, Arc::new(JsonCodec));
+        let mut client = AgentClient::new(service, ClientConfig::new(agent_url()));
+
+        let deltas: Vec<_> = client
+            .watch_task("task-123".to_string(), Duration::from_millis(1))
+            .map(|item| item.unwrap().1)
+            .collect()
+            .await;
+
+        // The unchanged second poll is skipped
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[0].status, Some(TaskStatus::Working));
+        assert_eq!(deltas[1].status, Some(TaskStatus::Completed));
+    }
+
+    #[tokio::test]
+    async fn test_send_message_generates_ids() {
+        use crate::client::config::{IdMode, IdOptions};
+
+        let transport = MockTransport::new(|req| {
+            let body: serde_json::Value = serde_json::from_slice(&req.body).unwrap();
+            assert!(body["message"]["messageId"].is_string());
+            assert!(req.headers.contains_key(CORRELATION_ID_HEADER));
+
+            let task = Task::new("task-123", Message::user("Test"));
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+
+        let service = A2AProtocolService::new(transport, Arc::new(JsonCodec));
+        let config = ClientConfig::new(agent_url()).with_ids(IdOptions {
+            message_ids: IdMode::Generate,
+            context_ids: IdMode::Off,
+            correlation_ids: true,
+            client_task_ids: true,
+        });
+        let mut client = AgentClient::new(service, config);
+
+        client.send_message(Message::user("Hello")).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_client_task_ids() {
+        use crate::protocol::{
+            agent::{AgentCapabilities, AgentCard, AgentExtension},
+            consts::extensions::CLIENT_TASK_IDS,
+        };
+
+        let transport = MockTransport::new(|req| {
+            if req.method == "GET" {
+                let capabilities =
+                    AgentCapabilities::new().with_extension(AgentExtension::new(CLIENT_TASK_IDS));
+                let card = AgentCard::new("Test Agent", "A test agent", capabilities);
+                return TransportResponse::new(200)
+                    .body(Bytes::from(serde_json::to_vec(&card).unwrap()));
+            }
+
+            let body: serde_json::Value = serde_json::from_slice(&req.body).unwrap();
+            let task_id = body["taskId"].as_str().unwrap();
+            assert_eq!(req.headers[headers::EXTENSIONS], CLIENT_TASK_IDS);
+            let task = Task::new(task_id, Message::user("Test"));
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+
+        let service = A2AProtocolService::new(transport, Arc::new(JsonCodec::new()));
+        let mut client = AgentClient::new(service, ClientConfig::new(agent_url()));
+        client.discover().await.unwrap();
+
+        let first = client.send_message(Message::user("Hello")).await.unwrap();
+        let second = client.send_message(Message::user("Hello")).await.unwrap();
+        assert_ne!(first.id, second.id);
+    }
+
+    #[tokio::test]
+    async fn test_client_task_ids_are_retried() {
+        use std::{
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Mutex,
+            },
+            time::Duration,
+        };
+
+        use tower::Layer;
+
+        use crate::{
+            layer::{A2ARetryLayer, RetryPolicy},
+            protocol::{
+                agent::{AgentCapabilities, AgentCard, AgentExtension},
+                consts::extensions::CLIENT_TASK_IDS,
+            },
+        };
+
+        let sends = Arc::new(AtomicUsize::new(0));
+        let task_ids = Arc::new(Mutex::new(Vec::new()));
+        let (counter, seen) = (sends.clone(), task_ids.clone());
+        let transport = MockTransport::new(move |req| {
+            if req.method == "GET" {
+                let capabilities =
+                    AgentCapabilities::new().with_extension(AgentExtension::new(CLIENT_TASK_IDS));
+                let card = AgentCard::new("Test Agent", "A test agent", capabilities);
+                return TransportResponse::new(200)
+                    .body(Bytes::from(serde_json::to_vec(&card).unwrap()));
+            }
+
+            assert_eq!(
+                req.headers[headers::EXTENSIONS],
+                format!("urn:example:other, {}", CLIENT_TASK_IDS)
+            );
+            let body: serde_json::Value = serde_json::from_slice(&req.body).unwrap();
+            let task_id = body["taskId"].as_str().unwrap().to_string();
+            seen.lock().unwrap().push(task_id.clone());
+            if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                return TransportResponse::new(503);
+            }
+            let task = Task::new(task_id, Message::user("Test"));
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+
+        let service = A2AProtocolService::new(transport, Arc::new(JsonCodec::new()));
+        let retry = RetryPolicy::new(2).with_backoff(Duration::ZERO, Duration::ZERO);
+        let service = A2ARetryLayer::new(retry).layer(service);
+        let config = ClientConfig::new(agent_url())
+            .with_default_header(headers::EXTENSIONS, "urn:example:other");
+        let mut client = AgentClient::new(service, config);
+        client.discover().await.unwrap();
+
+        let task = client.send_message(Message::user("Hello")).await.unwrap();
+
+        assert_eq!(sends.load(Ordering::SeqCst), 2);
+        let task_ids = task_ids.lock().unwrap();
+        assert_eq!(task_ids[0], task_ids[1]);
+        assert_eq!(task.id, task_ids[0]);
+    }
+
+    #[tokio::test]
+    async fn test_list_tasks_stream() {
+        use crate::protocol::endpoint::EndpointMap;
+
+        let transport = MockTransport::new(|req| {
+            let (ids, next_token) = match req.endpoint.contains("page_token=page-2") {
+                false => (["task-1", "task-2"], Some("page-2")),
+                true => (["task-3", "task-4"], None),
+            };
+            let tasks: Vec<_> = ids
+                .iter()
+                .map(|id| Task::new(*id, Message::user("Test")))
+                .collect();
+            let body = serde_json::json!({"tasks": tasks, "total": 4, "nextToken": next_token});
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&body).unwrap()))
+        });
+
+        let service = A2AProtocolService::new(transport, Arc::new(JsonCodec::new()))
+            .with_endpoints(EndpointMap::rest());
+        let client = AgentClient::new(service, ClientConfig::new(agent_url()));
+
+        let ids: Vec<_> = client
+            .list_tasks_stream(None, Some(2))
+            .map(|task| task.unwrap().id)
+            .collect()
+            .await;
+        assert_eq!(ids, ["task-1", "task-2", "task-3", "task-4"]);
+
+        let service = A2AProtocolService::new(
+            MockTransport::new(|_| TransportResponse::new(500)),
+            Arc::new(JsonCodec::new()),
+        );
+        let client = AgentClient::new(service, ClientConfig::new(agent_url()));
+        let results: Vec<_> = client.list_tasks_stream(None, None).collect().await;
+        assert!(matches!(results.as_slice(), [Err(_)]));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_task() {
+        use crate::protocol::TaskStatus;
+
+        let transport = MockTransport::ok().with_stream_handler(|req| {
+            assert!(req.endpoint.ends_with("task-1:stream"));
+            let event = |kind: &str, payload| SseEvent {
+                kind: kind.to_string(),
+                payload,
+                final_event: false,
+                id: None,
+            };
+            Ok(vec![
+                Ok(SseEvent::heartbeat("keep-alive")),
+                Ok(event(
+                    "status-update",
+                    serde_json::json!({"taskId": "task-1", "status": {"state": "completed"}}),
+                )),
+            ])
+        });
+        let service = A2AProtocolService::new(transport, Arc::new(JsonCodec::new()));
+        let client = AgentClient::new(service, ClientConfig::new(agent_url()));
+
+        let events: Vec<_> = client.subscribe_task("task-1".into()).collect().await;
+        assert!(matches!(
+            events.as_slice(),
+            [Ok(StreamEvent::StatusUpdate(update))] if update.status == TaskStatus::Completed
+        ));
+
+        let polls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = polls.clone();
+        let transport = MockTransport::new(move |_| {
+            let mut task = Task::new("task-1", Message::user("Test"));
+            task.status = match counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) {
+                0 | 1 => TaskStatus::Working,
+                _ => TaskStatus::Completed,
+            };
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+        let service = A2AProtocolService::new(transport, Arc::new(JsonCodec::new()));
+        let config = ClientConfig::new(agent_url()).with_poll_interval(Duration::from_millis(1));
+        let client = AgentClient::new(service, config);
+
+        let statuses: Vec<_> = client
+            .subscribe_task("task-1".into())
+            .map(|event| match event.unwrap() {
+                StreamEvent::Task(task) => task.status,
+                other => panic!("unexpected event: {:?}", other),
+            })
+            .collect()
+            .await;
+        assert_eq!(statuses, [TaskStatus::Working, TaskStatus::Completed]);
+        assert_eq!(polls.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_resume_task_stream() {
+        use crate::protocol::TaskStatus;
+
+        let status = Arc::new(std::sync::Mutex::new(TaskStatus::Working));
+        let transport = MockTransport::new({
+            let status = status.clone();
+            move |req| {
+                assert_eq!(req.endpoint, "/v1/tasks/task-1");
+                let mut task = Task::new("task-1", Message::user("Test"));
+                task.status = *status.lock().unwrap();
+                TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+            }
+        })
+        .with_stream_handler(|req| {
+            assert_eq!(req.endpoint, "/v1/tasks/task-1:stream");
+            Ok(vec![Ok(SseEvent {
+                kind: "status-update".to_string(),
+                payload: serde_json::json!({"taskId": "task-1", "state": "completed"}),
+                final_event: true,
+                id: None,
+            })])
+        });
+        let service = A2AProtocolService::new(transport, Arc::new(JsonCodec::new()));
+        let client = AgentClient::new(service, ClientConfig::new(agent_url()));
+
+        // The last known status is replayed before the stream resumes
+        let events: Vec<_> = client
+            .resume_task_stream("task-1".into(), 3)
+            .map(|event| event.unwrap().kind)
+            .collect()
+            .await;
+        assert_eq!(events, ["task", "status-update"]);
+
+        // Tasks that finished while disconnected are not resubscribed to
+        *status.lock().unwrap() = TaskStatus::Completed;
+        let events: Vec<_> = client
+            .resume_task_stream("task-1".into(), 3)
+            .collect()
+            .await;
+        assert_eq!(events.len(), 1);
+        assert!(events[0].as_ref().unwrap().is_terminal());
+    }
+
     #[tokio::test]
     async fn test_discover() {
         use crate::protocol::agent::{AgentCapabilities, AgentCard};
 
-        let transport = MockTransport::new(|_req| {
-            let card = AgentCard::new("Test Agent", "A test agent", AgentCapabilities::default());
-            let json = serde_json::to_vec(&card).unwrap();
-            TransportResponse::new(200).body(Bytes::from(json))
+        let fetches = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let transport = MockTransport::new({
+            let fetches = fetches.clone();
+            move |_req| {
+                fetches.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let card =
+                    AgentCard::new("Test Agent", "A test agent", AgentCapabilities::default());
+                let json = serde_json::to_vec(&card).unwrap();
+                TransportResponse::new(200).body(Bytes::from(json))
+            }
         });
 
         let codec = Arc::new(JsonCodec);
@@ -373,7 +2050,38 @@ mod tests {
         let mut client = AgentClient::new(service, config);
 
         let card = client.discover().await.unwrap();
-
         assert_eq!(card.name, "Test Agent");
+
+        // Repeated discovery is answered from the cache until a refresh is forced
+        client.discover().await.unwrap();
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::SeqCst), 1);
+        client.card_cache().force_refresh();
+        client.discover().await.unwrap();
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run() {
+        let transport = DryRunTransport::new(agent_url());
+        let service = A2AProtocolService::new(transport, Arc::new(JsonCodec));
+        let config = ClientConfig::new(agent_url()).with_default_header("X-Tenant", "acme");
+        let mut client = AgentClient::new(service, config);
+
+        let operation = A2AOperation::SendMessage {
+            message: Message::user("Hello"),
+            stream: false,
+            context_id: None,
+            task_id: None,
+        };
+        let request = client
+            .dry_run(operation, RequestOptions::new())
+            .await
+            .unwrap();
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.headers.get("X-Tenant").unwrap(), "acme");
+        let rendered = request.to_string();
+        assert!(rendered.starts_with("POST "));
+        assert!(rendered.contains("\"messageId\""));
     }
 }
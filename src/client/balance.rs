@@ -0,0 +1,161 @@
+//! Load balancing over replicas of an agent
+//!
+//! An [`AgentService`] is a client turned into a Tower service of operations: each
+//! call builds its request from the client's own configuration, so the same
+//! operation can go to any replica. Replicas become a `tower::discover::Discover`
+//! through a stream of `Change`s, e.g. from [`AgentRegistry::watch`], and compose with
+//! `tower::balance`:
+//!
+//! ```rust,no_run
+//! use futures::stream;
+//! use tower::{
+//!     balance::p2c::Balance,
+//!     load::{CompleteOnResponse, PendingRequestsDiscover},
+//! };
+//! use tower_a2a::client::AgentRegistry;
+//!
+//! # fn example(updates: impl futures::Stream<Item = AgentRegistry> + Send + 'static) {
+//! let changes = AgentRegistry::watch(updates);
+//! let balance = Balance::new(PendingRequestsDiscover::new(
+//!     changes,
+//!     CompleteOnResponse::default(),
+//! ));
+//! # }
+//! ```
+//!
+//! [`AgentRegistry::watch`]: crate::client::AgentRegistry::watch
+
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use tower_service::Service;
+
+use crate::{
+    client::{AgentClient, ClientConfig},
+    protocol::{error::A2AError, operation::A2AOperation},
+    service::{A2ARequest, A2AResponse},
+};
+
+/// Service executing operations against one agent with a client's configuration
+///
+/// Created with [`AgentClient::into_service`]. Requests get the headers, timeouts,
+/// and IDs the client would give them; per-request overrides are not available.
+#[derive(Clone)]
+pub struct AgentService<S> {
+    service: S,
+    config: Arc<ClientConfig>,
+}
+
+impl<S> AgentService<S> {
+    /// Create a service from a request service and a client configuration
+    pub fn new(service: S, config: ClientConfig) -> Self {
+        Self {
+            service,
+            config: Arc::new(config),
+        }
+    }
+
+    /// Get the client configuration
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+}
+
+impl<S> Service<A2AOperation> for AgentService<S>
+where
+    S: Service<A2ARequest, Response = A2AResponse, Error = A2AError> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = A2AResponse;
+    type Error = A2AError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, operation: A2AOperation) -> Self::Future {
+        // Take the service that was polled ready, leaving a clone in its place
+        let clone = self.service.clone();
+        let mut service = std::mem::replace(&mut self.service, clone);
+        let config = self.config.clone();
+
+        Box::pin(async move {
+            let request = AgentClient::<S>::request_from(&config, operation).await?;
+            AgentClient::call_service(&mut service, &config, request).await
+        })
+    }
+}
+
+impl<S> fmt::Debug for AgentService<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AgentService")
+            .field("agent_url", &self.config.agent_url.as_str())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use bytes::Bytes;
+    use futures::stream;
+    use tower::{
+        balance::p2c::Balance,
+        discover::Change,
+        load::{CompleteOnResponse, PendingRequestsDiscover},
+        ServiceExt,
+    };
+
+    use super::*;
+    use crate::{
+        client::A2AClientBuilder,
+        protocol::{Message, Task},
+        transport::{mock::MockTransport, TransportResponse},
+    };
+
+    #[tokio::test]
+    async fn test_balance_over_replicas() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let replicas = ["replica-a", "replica-b"].map(|name| {
+            let counter = calls.clone();
+            let transport = MockTransport::new(move |req| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                assert_eq!(req.headers["X-Replica"], name);
+                let task = Task::new(name, Message::user("Hello"));
+                TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+            });
+            let service = A2AClientBuilder::new("mock://agent".parse().unwrap())
+                .with_transport(transport)
+                .with_default_header("X-Replica", name)
+                .build()
+                .unwrap()
+                .into_service();
+            Ok::<_, A2AError>(Change::Insert(name.to_string(), service))
+        });
+
+        let mut balance = Balance::new(PendingRequestsDiscover::new(
+            stream::iter(replicas),
+            CompleteOnResponse::default(),
+        ));
+        for _ in 0..4 {
+            let operation = A2AOperation::SendMessage {
+                message: Message::user("Hello"),
+                stream: false,
+                context_id: None,
+                task_id: None,
+            };
+            let response = balance.ready().await.unwrap().call(operation).await;
+            assert!(
+                matches!(response, Ok(A2AResponse::Task(task)) if task.id.starts_with("replica-"))
+            );
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+}
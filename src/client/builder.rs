@@ -1,18 +1,46 @@
 //! Client builder for constructing A2A clients with composable layers
 
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, future::Future, sync::Arc, time::Duration};
 
+use tower::{Layer, ServiceBuilder};
 use url::Url;
 
 use crate::{
-    client::{AgentClient, ClientConfig},
-    codec::{Codec, JsonCodec},
-    layer::AuthCredentials,
+    client::{
+        events::ClientEvents, AgentClient, ClientConfig, CompatProfile, EndpointSelector,
+        HeaderProvider, IdOptions, PreferredEndpointSelector, Profile, StreamDropPolicy,
+        UsageTracker,
+    },
+    codec::{Codec, JsonCodec, JsonRpcCodec},
+    layer::{
+        A2ARetryLayer, A2ARetryService, A2ATimeoutLayer, A2ATimeoutService, A2AValidationLayer,
+        A2AValidationService, AuthCredentials, AuthLayer, AuthService, CredentialProvider,
+        ValidationMode,
+    },
     prelude::A2AError,
-    service::A2AProtocolService,
-    transport::{HttpTransport, Transport},
+    protocol::{
+        language::validate_language_tag, usage::UsageKeys, AgentCard, EndpointMap, OperationKind,
+    },
+    service::{
+        A2AProtocolService, ContextNamespace, ErrorShape, Executor, ExecutorHandle, ProtocolStack,
+        ResponseTransform, ResponseTransforms, SunsetPolicy,
+    },
+    transport::{HttpTransport, NegotiatedTransport, Transport, WebSocketTransport},
 };
 
+/// Bindings [`A2AClientBuilder::discover_and_build`] can build a client for, most
+/// desirable first
+const NEGOTIABLE_BINDINGS: &[&str] = &["http+json", "http+rest", "rest", "json-rpc", "jsonrpc"];
+
+/// Tower stack of a built client
+///
+/// Requests pass through validation, retries, authentication, and the per-attempt
+/// timeout before reaching the protocol service. Retries sit outside authentication,
+/// so that each attempt asks the [`CredentialProvider`] for fresh credentials, and
+/// outside the timeout, so that each attempt gets the full timeout.
+pub type ClientStack<T> =
+    A2AValidationService<A2ARetryService<AuthService<A2ATimeoutService<A2AProtocolService<T>>>>>;
+
 /// Builder for constructing A2A clients
 ///
 /// This builder provides a fluent API for configuring and building an A2A client
@@ -48,10 +76,30 @@ pub struct A2AClientBuilder<T: Transport> {
     agent_url: Url,
     transport: Option<T>,
     codec: Option<Arc<dyn Codec>>,
-    auth: Option<AuthCredentials>,
+    endpoints: Option<EndpointMap>,
+    auth: Option<Arc<dyn CredentialProvider>>,
     timeout: Option<Duration>,
+    operation_timeouts: HashMap<OperationKind, Duration>,
+    operation_deadline: Option<Duration>,
     max_retries: u32,
+    backoff: Option<(Duration, Duration)>,
     validate_responses: bool,
+    default_headers: HashMap<String, String>,
+    header_providers: Vec<HeaderProvider>,
+    accept_languages: Vec<String>,
+    usage_keys: Option<UsageKeys>,
+    usage: Option<UsageTracker>,
+    ids: IdOptions,
+    eager_connect: Option<bool>,
+    transforms: ResponseTransforms,
+    error_shape: Option<ErrorShape>,
+    executor: Option<ExecutorHandle>,
+    sunset_policy: SunsetPolicy,
+    stream_drop: StreamDropPolicy,
+    poll_interval: Option<Duration>,
+    card_ttl: Option<Duration>,
+    context_namespace: Option<ContextNamespace>,
+    endpoint_selector: Option<Arc<dyn EndpointSelector>>,
 }
 
 impl<T: Transport> A2AClientBuilder<T> {
@@ -65,10 +113,30 @@ impl<T: Transport> A2AClientBuilder<T> {
             agent_url,
             transport: None,
             codec: None,
+            endpoints: None,
             auth: None,
             timeout: Some(Duration::from_secs(30)),
+            operation_timeouts: HashMap::new(),
+            operation_deadline: None,
             max_retries: 3,
+            backoff: None,
             validate_responses: true,
+            default_headers: HashMap::new(),
+            header_providers: Vec::new(),
+            accept_languages: Vec::new(),
+            usage_keys: None,
+            usage: None,
+            ids: IdOptions::default(),
+            eager_connect: None,
+            transforms: ResponseTransforms::new(),
+            error_shape: None,
+            executor: None,
+            sunset_policy: SunsetPolicy::default(),
+            stream_drop: StreamDropPolicy::default(),
+            poll_interval: None,
+            card_ttl: None,
+            context_namespace: None,
+            endpoint_selector: None,
         }
     }
 
@@ -92,13 +160,45 @@ impl<T: Transport> A2AClientBuilder<T> {
         self
     }
 
+    /// Use custom endpoint path templates
+    ///
+    /// Useful for agents that expose A2A under a prefix (e.g., `/api/a2a/v1/tasks`) or
+    /// with non-default paths. See [`EndpointMap::from_agent_card`] to derive the map
+    /// from a discovered Agent Card.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoints` - The path templates used to resolve operation endpoints
+    pub fn with_endpoints(mut self, endpoints: EndpointMap) -> Self {
+        self.endpoints = Some(endpoints);
+        self
+    }
+
+    /// Set the policy choosing the endpoint [`discover_and_build`](Self::discover_and_build)
+    /// connects to (default: [`PreferredEndpointSelector`])
+    ///
+    /// The selector only sees the endpoints of bindings this client can build.
+    pub fn with_endpoint_selector(mut self, selector: impl EndpointSelector + 'static) -> Self {
+        self.endpoint_selector = Some(Arc::new(selector));
+        self
+    }
+
+    /// Use the official A2A REST binding routes (`/v1/message:send`, ...)
+    ///
+    /// Any prefix configured through [`with_endpoints`](Self::with_endpoints) is kept.
+    pub fn with_rest_binding(mut self) -> Self {
+        let prefix = self.endpoints.take().map(|e| e.prefix).unwrap_or_default();
+        self.endpoints = Some(EndpointMap::rest().with_prefix(prefix));
+        self
+    }
+
     /// Enable bearer token authentication
     ///
     /// # Arguments
     ///
     /// * `token` - The bearer token for authentication
     pub fn with_bearer_auth(mut self, token: impl Into<String>) -> Self {
-        self.auth = Some(AuthCredentials::bearer(token));
+        self.auth = Some(Arc::new(AuthCredentials::bearer(token)));
         self
     }
 
@@ -109,7 +209,7 @@ impl<T: Transport> A2AClientBuilder<T> {
     /// * `key` - The API key
     /// * `header` - The header name for the API key (e.g., "X-API-Key")
     pub fn with_api_key_auth(mut self, key: impl Into<String>, header: impl Into<String>) -> Self {
-        self.auth = Some(AuthCredentials::api_key(key, header));
+        self.auth = Some(Arc::new(AuthCredentials::api_key(key, header)));
         self
     }
 
@@ -124,13 +224,20 @@ impl<T: Transport> A2AClientBuilder<T> {
         username: impl Into<String>,
         password: impl Into<String>,
     ) -> Self {
-        self.auth = Some(AuthCredentials::basic(username, password));
+        self.auth = Some(Arc::new(AuthCredentials::basic(username, password)));
         self
     }
 
     /// Set custom authentication credentials
     pub fn with_auth(mut self, credentials: AuthCredentials) -> Self {
-        self.auth = Some(credentials);
+        self.auth = Some(Arc::new(credentials));
+        self
+    }
+
+    /// Fetch the credentials of each request from a provider, e.g. a secret store or
+    /// a token refreshed in the background
+    pub fn with_credential_provider(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.auth = Some(Arc::new(provider));
         self
     }
 
@@ -144,6 +251,36 @@ impl<T: Transport> A2AClientBuilder<T> {
         self
     }
 
+    /// Set the timeout for a specific kind of operation
+    ///
+    /// Overrides the default timeout, e.g. to keep discovery short while allowing
+    /// message sends to run for minutes.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of operation
+    /// * `timeout` - The timeout duration for operations of this kind
+    pub fn with_operation_timeout(mut self, kind: OperationKind, timeout: Duration) -> Self {
+        self.operation_timeouts.insert(kind, timeout);
+        self
+    }
+
+    /// Set the total time budget of each operation
+    ///
+    /// The request timeout applies to every attempt, so a retried request can take
+    /// several times longer. The operation deadline bounds the whole operation,
+    /// including retries and stream reconnects, and fails it with
+    /// [`A2AError::Timeout`] once exceeded. Can be overridden per call with
+    /// [`RequestOptions::with_deadline`](crate::client::RequestOptions::with_deadline).
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - The maximum total duration of an operation
+    pub fn with_operation_deadline(mut self, budget: Duration) -> Self {
+        self.operation_deadline = Some(budget);
+        self
+    }
+
     /// Set the maximum number of retry attempts
     ///
     /// # Arguments
@@ -154,6 +291,34 @@ impl<T: Transport> A2AClientBuilder<T> {
         self
     }
 
+    /// Set the initial and maximum delay between retries
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - Delay before the first retry (default: 200ms)
+    /// * `max` - Upper bound for the delay between retries (default: 10s)
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.backoff = Some((initial, max));
+        self
+    }
+
+    /// Apply a preset tuned for a kind of workload
+    ///
+    /// Replaces the timeout, per-operation timeouts, retries, and backoff. Settings made
+    /// after applying the profile override it, e.g.
+    /// `.with_profile(Profile::Batch).with_max_retries(10)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The workload profile
+    pub fn with_profile(mut self, profile: Profile) -> Self {
+        self.timeout = Some(profile.timeout());
+        self.operation_timeouts = profile.operation_timeouts();
+        self.max_retries = profile.max_retries();
+        self.backoff = Some(profile.backoff());
+        self
+    }
+
     /// Enable or disable response validation
     ///
     /// # Arguments
@@ -164,9 +329,218 @@ impl<T: Transport> A2AClientBuilder<T> {
         self
     }
 
+    /// Attach a header to every request
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The header name (e.g., "X-Tenant-Id")
+    /// * `value` - The header value
+    pub fn with_default_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.insert(key.into(), value.into());
+        self
+    }
+
+    /// Attach dynamic headers produced by an async function to every request
+    ///
+    /// The provider is called once per request and its headers are merged over the
+    /// default headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - Async function returning the headers to attach
+    pub fn with_header_provider<F, Fut>(mut self, provider: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = HashMap<String, String>> + Send + 'static,
+    {
+        self.header_providers.push(HeaderProvider::new(provider));
+        self
+    }
+
+    /// Advertise the languages the client prefers for text, in order of preference
+    ///
+    /// Every request carries them in the `Accept-Language` header, with decreasing
+    /// quality values after the first. An explicit `Accept-Language` default header
+    /// takes precedence. See [`crate::protocol::language`] for tagging the language of
+    /// sent text.
+    ///
+    /// # Arguments
+    ///
+    /// * `languages` - BCP 47 language tags, e.g. `["en-GB", "en"]`
+    pub fn with_accept_languages<I>(mut self, languages: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.accept_languages = languages.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Configure automatic generation of message, context, and correlation IDs
+    ///
+    /// Message IDs are generated by default. Use [`IdOptions::require_explicit`] to
+    /// reject messages without explicit IDs instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The ID generation options
+    pub fn with_ids(mut self, ids: IdOptions) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Connect to the agent in the background as soon as the client is built
+    ///
+    /// Takes the connection handshake off the first request's latency. With
+    /// `prefetch_card`, the agent card is fetched too and available from
+    /// [`AgentClient::agent_card`]. Runs on the [executor](Self::with_executor), by
+    /// default the Tokio runtime current at build time.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefetch_card` - Whether to fetch the agent card after connecting
+    pub fn with_eager_connect(mut self, prefetch_card: bool) -> Self {
+        self.eager_connect = Some(prefetch_card);
+        self
+    }
+
+    /// Spawn background tasks on an executor instead of the ambient Tokio runtime
+    ///
+    /// Applies to the eager connect and to the transport's own tasks, such as the
+    /// WebSocket message reader, unless the transport was given an executor with
+    /// [`WebSocketTransport::with_executor`]. See [`crate::service::executor`].
+    ///
+    /// # Arguments
+    ///
+    /// * `executor` - The executor, e.g. a [`TokioExecutor`] for a dedicated runtime
+    ///
+    /// [`TokioExecutor`]: crate::service::TokioExecutor
+    pub fn with_executor(mut self, executor: impl Executor + 'static) -> Self {
+        self.executor = Some(ExecutorHandle::new(executor));
+        self
+    }
+
+    /// Decide what happens to requests once the agent's announced sunset date has
+    /// passed (default: [`SunsetPolicy::Warn`])
+    ///
+    /// See [`crate::service::deprecation`].
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The sunset policy
+    pub fn with_sunset_policy(mut self, policy: SunsetPolicy) -> Self {
+        self.sunset_policy = policy;
+        self
+    }
+
+    /// Decide what to tell the agent when an event stream is dropped before its task
+    /// finished (default: [`StreamDropPolicy::Disconnect`])
+    ///
+    /// Applies to the streams of `send_message_stream`, `subscribe_task`, and
+    /// `send_message_stream_resilient`.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The stream drop policy
+    pub fn with_stream_drop_policy(mut self, policy: StreamDropPolicy) -> Self {
+        self.stream_drop = policy;
+        self
+    }
+
+    /// Set how often `subscribe_task` polls the task if the transport cannot stream
+    /// (default: 1 second)
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - Time between polls
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = Some(interval);
+        self
+    }
+
+    /// Set how long a discovered agent card is reused before `discover` fetches it
+    /// again (default: 5 minutes)
+    ///
+    /// # Arguments
+    ///
+    /// * `ttl` - Time to live of the cached card
+    pub fn with_card_ttl(mut self, ttl: Duration) -> Self {
+        self.card_ttl = Some(ttl);
+        self
+    }
+
+    /// Namespace the context IDs of every request for a tenant
+    ///
+    /// Context IDs are namespaced on send and stripped from responses, so tenants
+    /// sharing an agent cannot collide on a context ID; see [`ContextNamespace`].
+    /// [`RequestOptions::with_context_namespace`](crate::client::RequestOptions::with_context_namespace)
+    /// overrides it for a single request.
+    pub fn with_context_namespace(mut self, namespace: ContextNamespace) -> Self {
+        self.context_namespace = Some(namespace);
+        self
+    }
+
+    /// Rewrite the agent's responses before and after decoding
+    ///
+    /// Useful to normalize quirks of a specific agent without replacing the codec. See
+    /// [`ResponseTransform`]; transforms run in the order they are added.
+    pub fn with_response_transform(mut self, transform: impl ResponseTransform + 'static) -> Self {
+        self.transforms = self
+            .transforms
+            .with_transform(self.agent_url.clone(), transform);
+        self
+    }
+
+    /// Add up the token usage reported in returned tasks
+    ///
+    /// Share one tracker between clients to account usage across agents. See
+    /// [`crate::client::usage`].
+    ///
+    /// # Arguments
+    ///
+    /// * `tracker` - The tracker to record usage with
+    pub fn with_usage_tracker(mut self, tracker: UsageTracker) -> Self {
+        self.usage = Some(tracker);
+        self
+    }
+
+    /// Read token usage from different keys (default: [`UsageKeys::default`])
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - Where the agent reports usage, e.g. [`UsageKeys::gemini`]
+    pub fn with_usage_keys(mut self, keys: UsageKeys) -> Self {
+        self.usage_keys = Some(keys);
+        self
+    }
+
+    /// Apply a compatibility profile for the agent's implementation
+    ///
+    /// Replaces the endpoint paths and error shape if the profile sets them, adds its
+    /// headers as default headers, and renames aliased response fields. Settings
+    /// applied afterwards take precedence.
+    pub fn with_compat_profile(mut self, profile: CompatProfile) -> Self {
+        if let Some(transform) = profile.field_alias_transform() {
+            self = self.with_response_transform(transform);
+        }
+        if let Some(endpoints) = profile.endpoints {
+            self.endpoints = Some(endpoints);
+        }
+        if let Some(shape) = profile.error_shape {
+            self.error_shape = Some(shape);
+        }
+        if let Some(keys) = profile.usage_keys {
+            self.usage_keys = Some(keys);
+        }
+        self.default_headers.extend(profile.headers);
+        self
+    }
+
     /// Build the A2A client
     ///
-    /// This assembles all the Tower layers and returns a configured client.
+    /// This assembles the Tower layers of a [`ClientStack`] and returns a configured
+    /// client. With validation disabled, invalid responses are logged and returned
+    /// rather than rejected.
     ///
     /// # Returns
     ///
@@ -177,30 +551,130 @@ impl<T: Transport> A2AClientBuilder<T> {
     /// Returns an error if:
     /// - No transport has been configured
     /// - No codec has been configured (usually set automatically with transport)
-    pub fn build(self) -> Result<AgentClient<A2AProtocolService<T>>, A2AError> {
+    /// - An accepted language is not a well-formed language tag
+    pub fn build(self) -> Result<AgentClient<ClientStack<T>>, A2AError> {
+        for language in &self.accept_languages {
+            validate_language_tag(language)?;
+        }
+
         // Ensure transport is configured
-        let transport = self.transport.ok_or_else(|| {
+        let mut transport = self.transport.ok_or_else(|| {
             A2AError::Protocol(
                 "Transport not configured. Call with_http() or with_transport()".into(),
             )
         })?;
 
+        // Share the executor, so the client reports the transport's tasks too. A
+        // transport given an executor of its own keeps it.
+        let executor = self
+            .executor
+            .or_else(|| transport.executor())
+            .unwrap_or_default();
+        transport.set_executor(executor.clone());
+
+        // Share the event hub, so subscribers see the transport's connection changes
+        let events = ClientEvents::default();
+        transport.set_events(events.clone());
+
         // Ensure codec is configured (should be set with transport)
         let codec = self.codec.unwrap_or_else(|| Arc::new(JsonCodec));
 
         // Create the core protocol service
-        let service = A2AProtocolService::new(transport, codec);
+        let mut service =
+            A2AProtocolService::new(transport, codec).with_sunset_policy(self.sunset_policy);
+        if let Some(endpoints) = self.endpoints {
+            service = service.with_endpoints(endpoints);
+        }
+        if !self.transforms.is_empty() {
+            service = service.with_transforms(self.transforms);
+        }
+        if let Some(shape) = self.error_shape {
+            service = service.with_error_shape(shape);
+        }
 
         // Create client configuration
-        // Note: auth, timeout, and validation would be better handled as Tower layers
-        // but for simplicity we're storing them in the config for now
-        let config = ClientConfig::new(self.agent_url)
-            .with_timeout(self.timeout.unwrap_or(Duration::from_secs(30)))
+        let timeout = self.timeout.unwrap_or(Duration::from_secs(30));
+        let mut config = ClientConfig::new(self.agent_url)
+            .with_timeout(timeout)
             .with_max_retries(self.max_retries)
             .with_validation(self.validate_responses);
+        if let Some((initial, max)) = self.backoff {
+            config = config.with_backoff(initial, max);
+        }
+        config.operation_timeouts = self.operation_timeouts;
+        config.operation_deadline = self.operation_deadline;
+        config.default_headers = self.default_headers;
+        config.header_providers = self.header_providers;
+        config.accept_languages = self.accept_languages;
+        config.usage_keys = self.usage_keys.unwrap_or_default();
+        config.usage = self.usage;
+        config.ids = self.ids;
+        config.executor = executor;
+        config.events = events;
+        config.stream_drop = self.stream_drop;
+        if let Some(interval) = self.poll_interval {
+            config.poll_interval = interval;
+        }
+        if let Some(ttl) = self.card_ttl {
+            config.card_ttl = ttl;
+        }
+        config.context_namespace = self.context_namespace;
+
+        // Assemble the stack, outermost layer first
+        let validation = if config.validate_responses {
+            ValidationMode::Strict
+        } else {
+            ValidationMode::Lenient
+        };
+        let stack = ServiceBuilder::new()
+            .layer(A2AValidationLayer::new().with_mode(validation))
+            .layer(A2ARetryLayer::new(config.retry_policy()).with_events(config.events.clone()))
+            .layer(
+                self.auth
+                    .map_or(AuthLayer::optional(None), AuthLayer::from_provider),
+            )
+            .layer(A2ATimeoutLayer::new().with_default(timeout))
+            .service(service);
 
         // Create and return the agent client
-        Ok(AgentClient::new(service, config))
+        let client = AgentClient::new(stack, config);
+        if let Some(prefetch_card) = self.eager_connect {
+            client.spawn_connect(prefetch_card);
+        }
+        Ok(client)
+    }
+
+    /// Move the settings to a builder of another transport type
+    fn into_transport<U: Transport>(self, transport: U) -> A2AClientBuilder<U> {
+        A2AClientBuilder {
+            agent_url: self.agent_url,
+            transport: Some(transport),
+            codec: self.codec,
+            endpoints: self.endpoints,
+            auth: self.auth,
+            timeout: self.timeout,
+            operation_timeouts: self.operation_timeouts,
+            operation_deadline: self.operation_deadline,
+            max_retries: self.max_retries,
+            backoff: self.backoff,
+            validate_responses: self.validate_responses,
+            default_headers: self.default_headers,
+            header_providers: self.header_providers,
+            accept_languages: self.accept_languages,
+            usage_keys: self.usage_keys,
+            usage: self.usage,
+            ids: self.ids,
+            eager_connect: self.eager_connect,
+            transforms: self.transforms,
+            error_shape: self.error_shape,
+            executor: self.executor,
+            sunset_policy: self.sunset_policy,
+            stream_drop: self.stream_drop,
+            poll_interval: self.poll_interval,
+            card_ttl: self.card_ttl,
+            context_namespace: self.context_namespace,
+            endpoint_selector: self.endpoint_selector,
+        }
     }
 }
 
@@ -216,17 +690,203 @@ impl A2AClientBuilder<HttpTransport> {
             agent_url,
             transport: Some(transport),
             codec: Some(Arc::new(JsonCodec)),
+            endpoints: None,
             auth: None,
             timeout: Some(Duration::from_secs(30)),
+            operation_timeouts: HashMap::new(),
+            operation_deadline: None,
             max_retries: 3,
+            backoff: None,
             validate_responses: true,
+            default_headers: HashMap::new(),
+            header_providers: Vec::new(),
+            accept_languages: Vec::new(),
+            usage_keys: None,
+            usage: None,
+            ids: IdOptions::default(),
+            eager_connect: None,
+            transforms: ResponseTransforms::new(),
+            error_shape: None,
+            executor: None,
+            sunset_policy: SunsetPolicy::default(),
+            stream_drop: StreamDropPolicy::default(),
+            poll_interval: None,
+            card_ttl: None,
+            context_namespace: None,
+            endpoint_selector: None,
+        }
+    }
+
+    /// Fetch the agent card, then build a client for the binding it advertises
+    ///
+    /// The endpoint is chosen by the [endpoint selector](Self::with_endpoint_selector),
+    /// [`PreferredEndpointSelector`] by default, among the bindings this client can build: HTTP+JSON and REST over HTTP, and JSON-RPC over
+    /// WebSocket (`ws://` and `wss://` endpoints). Agents whose card lists no endpoints
+    /// are reached at the builder's URL. The card is fetched with the builder's
+    /// credentials and default headers, and fills the client's card cache.
+    ///
+    /// Endpoint paths set with [`with_endpoints`](Self::with_endpoints) are kept, and
+    /// so is the codec if it speaks the selected binding.
+    ///
+    /// ```rust,no_run
+    /// use tower_a2a::prelude::*;
+    ///
+    /// # async fn example() -> Result<(), A2AError> {
+    /// let url = "https://agent.example.com".parse().unwrap();
+    /// let mut client = A2AClientBuilder::new_http(url)
+    ///     .with_bearer_auth("token123")
+    ///     .discover_and_build()
+    ///     .await?;
+    /// let task = client.send_message(Message::user("Hello")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::BindingMismatch` if the card lists only bindings this client
+    /// cannot build, such as gRPC, and the errors of fetching the card and of
+    /// [`build`](Self::build) otherwise
+    pub async fn discover_and_build(
+        mut self,
+    ) -> Result<AgentClient<ClientStack<NegotiatedTransport>>, A2AError> {
+        let http = match self.transport.take() {
+            Some(transport) => transport,
+            None => HttpTransport::try_new(self.agent_url.clone())?,
+        };
+        let card = self.fetch_card(&http).await?;
+
+        let builder = match negotiate(&card, &self.agent_url, self.endpoint_selector.as_deref())? {
+            None => self.into_transport(NegotiatedTransport::Http(http)),
+            Some((binding, url)) if matches!(url.scheme(), "ws" | "wss") => {
+                if !self.speaks(&binding) {
+                    self.codec = Some(Arc::new(JsonRpcCodec::new()));
+                }
+                self.agent_url = url.clone();
+                self.into_transport(WebSocketTransport::new(url).into())
+            }
+            Some((binding, url)) => {
+                if !self.speaks(&binding) {
+                    self.codec = Some(Arc::new(JsonCodec::new()));
+                }
+                if self.endpoints.is_none() {
+                    let endpoints = match binding.as_str() {
+                        "rest" | "http+rest" => EndpointMap::rest(),
+                        _ => EndpointMap::new(),
+                    };
+                    self.endpoints = Some(endpoints.with_prefix(url.path()));
+                }
+
+                // Paths go to the endpoint map, so the transport only needs the origin
+                let mut origin = url;
+                origin.set_path("/");
+                origin.set_query(None);
+                let transport = if http.base_url().origin() == origin.origin() {
+                    http
+                } else {
+                    HttpTransport::try_new(origin.clone())?
+                };
+                self.agent_url = origin;
+                self.into_transport(transport.into())
+            }
+        };
+
+        let client = builder.build()?;
+        card.check_bindings(client.service().protocol().codec().bindings())?;
+        client.card_cache().insert(card);
+        Ok(client)
+    }
+
+    /// Fetch the agent card over HTTP with the builder's credentials and headers
+    async fn fetch_card(&self, http: &HttpTransport) -> Result<AgentCard, A2AError> {
+        let mut service = A2AProtocolService::new(http.clone(), Arc::new(JsonCodec::new()));
+        if let Some(endpoints) = &self.endpoints {
+            service = service.with_endpoints(endpoints.clone());
+        }
+        let auth = self
+            .auth
+            .clone()
+            .map_or(AuthLayer::optional(None), AuthLayer::from_provider);
+
+        let mut config = ClientConfig::new(self.agent_url.clone());
+        if let Some(timeout) = self.timeout {
+            config = config.with_timeout(timeout);
         }
+        config.default_headers = self.default_headers.clone();
+        AgentClient::new(auth.layer(service), config)
+            .discover()
+            .await
+    }
+
+    /// Check whether the configured codec speaks a binding
+    fn speaks(&self, binding: &str) -> bool {
+        self.codec.as_ref().is_some_and(|codec| {
+            codec
+                .bindings()
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(binding))
+        })
     }
 }
 
+/// Choose the endpoint of a card to build a client for, returning its binding and URL
+///
+/// The selector chooses among the endpoints of negotiable bindings, defaulting to the
+/// [`PreferredEndpointSelector`]. Returns `None` if the card lists no endpoints.
+fn negotiate(
+    card: &AgentCard,
+    agent_url: &Url,
+    selector: Option<&dyn EndpointSelector>,
+) -> Result<Option<(String, Url)>, A2AError> {
+    if card.endpoints.is_empty() {
+        return Ok(None);
+    }
+
+    // JSON-RPC is only available over WebSocket
+    let mut usable = card.clone();
+    usable.endpoints.retain(|_, endpoint| {
+        let is = |bindings: &[&str]| {
+            bindings
+                .iter()
+                .any(|binding| binding.eq_ignore_ascii_case(&endpoint.endpoint_type))
+        };
+        is(NEGOTIABLE_BINDINGS)
+            && (!is(&["json-rpc", "jsonrpc"])
+                || agent_url
+                    .join(&endpoint.url)
+                    .is_ok_and(|url| matches!(url.scheme(), "ws" | "wss")))
+    });
+
+    let preferred = PreferredEndpointSelector::with_bindings(NEGOTIABLE_BINDINGS.iter().copied());
+    let Some((_, endpoint)) = selector.unwrap_or(&preferred).select(&usable) else {
+        let mut supported: Vec<_> = card
+            .endpoints
+            .values()
+            .map(|endpoint| endpoint.endpoint_type.clone())
+            .collect();
+        supported.sort();
+        supported.dedup();
+        return Err(A2AError::BindingMismatch {
+            client: NEGOTIABLE_BINDINGS.iter().map(|b| b.to_string()).collect(),
+            supported,
+        });
+    };
+
+    let url = agent_url.join(&endpoint.url).map_err(|e| {
+        A2AError::Validation(format!("Invalid endpoint URL {}: {}", endpoint.url, e))
+    })?;
+    Ok(Some((endpoint.endpoint_type.to_ascii_lowercase(), url)))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::transport::mock::MockTransport;
+    use bytes::Bytes;
+
+    use crate::{
+        protocol::{agent::EndpointConfig, AgentCapabilities, AgentCard, Message, Task},
+        service::ProtocolStack,
+        transport::{mock::MockTransport, TransportResponse},
+    };
 
     use super::*;
 
@@ -271,6 +931,117 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_builder_with_endpoints() {
+        let client = A2AClientBuilder::new_http(agent_url())
+            .with_endpoints(EndpointMap::new().with_prefix("/api/a2a"))
+            .build();
+
+        assert!(client.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_builder_with_headers() {
+        let transport = MockTransport::new(|req| {
+            assert_eq!(req.headers.get("X-Tenant-Id").unwrap(), "tenant-1");
+            assert_eq!(req.headers.get("X-Session").unwrap(), "session-2");
+            let task = Task::new("task-123", Message::user("Test"));
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+
+        let mut client = A2AClientBuilder::new(agent_url())
+            .with_transport(transport)
+            .with_default_header("X-Tenant-Id", "tenant-1")
+            .with_default_header("X-Session", "stale")
+            .with_header_provider(|| async {
+                HashMap::from([("X-Session".to_string(), "session-2".to_string())])
+            })
+            .build()
+            .unwrap();
+
+        client.get_task("task-123".to_string()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_builder_installs_layers() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = calls.clone();
+        let transport = MockTransport::new(move |req| {
+            assert_eq!(req.headers["Authorization"], "Bearer token123");
+            if counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                return TransportResponse::new(503);
+            }
+            let task = Task::new("task-123", Message::user("Test"));
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+
+        let mut client = A2AClientBuilder::new(agent_url())
+            .with_transport(transport)
+            .with_bearer_auth("token123")
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(1))
+            .build()
+            .unwrap();
+
+        let task = client.get_task("task-123".to_string()).await.unwrap();
+        assert_eq!(task.id, "task-123");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_credentials_per_attempt() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Issues a new token on every call
+        struct Rotating(AtomicUsize);
+
+        #[async_trait::async_trait]
+        impl CredentialProvider for Rotating {
+            async fn credentials(&self) -> Result<AuthCredentials, A2AError> {
+                let n = self.0.fetch_add(1, Ordering::SeqCst);
+                Ok(AuthCredentials::bearer(format!("token-{}", n)))
+            }
+        }
+
+        // The retried attempt is sent with fresh credentials
+        let transport = MockTransport::new(|req| {
+            if req.headers["Authorization"] == "Bearer token-0" {
+                return TransportResponse::new(503);
+            }
+            assert_eq!(req.headers["Authorization"], "Bearer token-1");
+            let task = Task::new("task-123", Message::user("Test"));
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+
+        let mut client = A2AClientBuilder::new(agent_url())
+            .with_transport(transport)
+            .with_credential_provider(Rotating(AtomicUsize::new(0)))
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(1))
+            .build()
+            .unwrap();
+        client.get_task("task-123".to_string()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_builder_with_accept_languages() {
+        let transport = MockTransport::new(|req| {
+            assert_eq!(req.headers["Accept-Language"], "de-CH, de;q=0.9, en;q=0.8");
+            let task = Task::new("task-123", Message::user("Test"));
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+
+        let mut client = A2AClientBuilder::new(agent_url())
+            .with_transport(transport)
+            .with_accept_languages(["de-CH", "de", "en"])
+            .build()
+            .unwrap();
+        client.get_task("task-123".to_string()).await.unwrap();
+
+        let result = A2AClientBuilder::new_http(agent_url())
+            .with_accept_languages(["en_GB"])
+            .build();
+        assert!(matches!(result, Err(A2AError::Validation(_))));
+    }
+
     #[test]
     fn test_builder_all_options() {
         let client = A2AClientBuilder::new_http(agent_url())
@@ -282,4 +1053,283 @@ mod tests {
 
         assert!(client.is_ok());
     }
+
+    #[test]
+    fn test_builder_with_profile() {
+        let client = A2AClientBuilder::new_http(agent_url())
+            .with_profile(Profile::Interactive)
+            .with_timeout(Duration::from_secs(15))
+            .build()
+            .unwrap();
+
+        let config = client.config();
+        assert_eq!(config.timeout, Duration::from_secs(15));
+        assert_eq!(config.max_retries, 2);
+        assert_eq!(config.max_backoff, Duration::from_secs(2));
+        assert_eq!(
+            config.timeout_for(OperationKind::DiscoverAgent),
+            Duration::from_secs(10)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_builder_keeps_transport_executor() {
+        let executor = ExecutorHandle::default();
+        let transport = WebSocketTransport::new("ws://localhost:1".parse::<Url>().unwrap())
+            .with_executor(executor.clone());
+        let client = A2AClientBuilder::new("ws://localhost:1".parse().unwrap())
+            .with_transport(transport)
+            .with_codec(Arc::new(JsonRpcCodec::new()))
+            .build()
+            .unwrap();
+
+        // The client reports the tasks of the transport's executor
+        executor.spawn("a2a-probe", async {});
+        assert!(client.background_health().task("a2a-probe").is_some());
+        let transport = client.service().protocol().transport();
+        assert!(transport.executor().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_builder_with_eager_connect() {
+        let transport = MockTransport::new(|_req| {
+            let card = AgentCard::new("Test Agent", "A test agent", AgentCapabilities::default())
+                .with_endpoint("http", EndpointConfig::new("mock://agent", "http+json"));
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&card).unwrap()))
+        });
+
+        let client = A2AClientBuilder::new(agent_url())
+            .with_transport(transport)
+            .with_eager_connect(true)
+            .build()
+            .unwrap();
+        client.connect().await.unwrap();
+
+        for _ in 0..50 {
+            if client.agent_card().is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(client.agent_card().unwrap().name, "Test Agent");
+    }
+
+    /// Serve an agent card at every path, recording the requested paths
+    async fn serve_card(card: AgentCard) -> (Url, Arc<std::sync::Mutex<Vec<String>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let paths = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let recorded = paths.clone();
+        let body = serde_json::to_string(&card).unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0; 8192];
+                let n = stream.read(&mut buf).await.unwrap();
+                let head = String::from_utf8_lossy(&buf[..n]).to_string();
+                let path = head.split_whitespace().nth(1).unwrap_or_default();
+                recorded.lock().unwrap().push(path.to_string());
+
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (url, paths)
+    }
+
+    #[tokio::test]
+    async fn test_discover_and_build() {
+        let card = AgentCard::new("Test Agent", "A test agent", AgentCapabilities::default())
+            .with_endpoint(
+                "grpc",
+                EndpointConfig::new("https://grpc.example.com", "grpc"),
+            )
+            .with_endpoint(
+                "rpc",
+                EndpointConfig::new("wss://ws.example.com/a2a", "json-rpc"),
+            )
+            .with_endpoint("rest", EndpointConfig::new("/api/a2a", "http+rest"));
+        let (url, paths) = serve_card(card.clone()).await;
+
+        // HTTP bindings are preferred, and relative endpoints resolve against the agent
+        let client = A2AClientBuilder::new_http(url.clone())
+            .discover_and_build()
+            .await
+            .unwrap();
+        let protocol = client.service().protocol();
+        assert_eq!(protocol.transport().base_url(), &url);
+        assert_eq!(protocol.codec().bindings(), JsonCodec::new().bindings());
+        assert_eq!(client.card_cache().get().unwrap().name, "Test Agent");
+        assert_eq!(paths.lock().unwrap().len(), 1);
+
+        let card = card.with_endpoint(
+            "rpc",
+            EndpointConfig::new("wss://ws.example.com/a2a", "json-rpc").preferred(),
+        );
+        let (url, _) = serve_card(card).await;
+        let client = A2AClientBuilder::new_http(url)
+            .discover_and_build()
+            .await
+            .unwrap();
+        let transport = client.service().protocol().transport();
+        assert_eq!(
+            transport.as_websocket().unwrap().base_url().as_str(),
+            "wss://ws.example.com/a2a"
+        );
+
+        let grpc_only = AgentCard::new("gRPC Agent", "A test agent", AgentCapabilities::default())
+            .with_endpoint(
+                "grpc",
+                EndpointConfig::new("https://grpc.example.com", "grpc"),
+            );
+        // Cards listing only JSON-RPC over WebSocket are discovered over HTTP
+        let ws_only = AgentCard::new("WS Agent", "A test agent", AgentCapabilities::default())
+            .with_endpoint(
+                "rpc",
+                EndpointConfig::new("ws://ws.example.com/a2a", "json-rpc"),
+            );
+        let (url, _) = serve_card(ws_only).await;
+        let client = A2AClientBuilder::new_http(url.clone())
+            .discover_and_build()
+            .await
+            .unwrap();
+        let protocol = client.service().protocol();
+        assert!(protocol.transport().as_websocket().is_some());
+        assert_eq!(protocol.codec().bindings(), JsonRpcCodec::new().bindings());
+
+        // An HTTP client still discovers the card, but refuses to connect
+        let mut client = A2AClientBuilder::new_http(url).build().unwrap();
+        assert_eq!(client.discover().await.unwrap().name, "WS Agent");
+        assert!(matches!(
+            client.connect().await,
+            Err(A2AError::BindingMismatch { supported, .. }) if supported == ["json-rpc"]
+        ));
+
+        let (url, _) = serve_card(grpc_only).await;
+        let result = A2AClientBuilder::new_http(url).discover_and_build().await;
+        assert!(matches!(
+            result,
+            Err(A2AError::BindingMismatch { supported, .. }) if supported == ["grpc"]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_discover_and_build_websocket() {
+        use futures::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::{
+            handshake::server::{Callback, ErrorResponse, Request, Response},
+            Message as Frame,
+        };
+
+        /// Handshake callback agreeing to the requested subprotocol
+        struct Accept;
+
+        impl Callback for Accept {
+            fn on_request(
+                self,
+                request: &Request,
+                mut response: Response,
+            ) -> Result<Response, ErrorResponse> {
+                if let Some(protocol) = request.headers().get("Sec-WebSocket-Protocol") {
+                    response
+                        .headers_mut()
+                        .insert("Sec-WebSocket-Protocol", protocol.clone());
+                }
+                Ok(response)
+            }
+        }
+
+        // Agent answering JSON-RPC requests over WebSocket with the task they name
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}/a2a", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_hdr_async(stream, Accept)
+                .await
+                .unwrap();
+            while let Some(Ok(Frame::Text(text))) = ws.next().await {
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                let task_id = request["params"]["id"].as_str().unwrap_or("task-1");
+                let response = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "result": Task::new(task_id, Message::user("Hello")),
+                });
+                ws.send(Frame::Text(response.to_string())).await.unwrap();
+            }
+        });
+
+        let card = AgentCard::new("WS Agent", "A test agent", AgentCapabilities::default())
+            .with_endpoint("rpc", EndpointConfig::new(&ws_url, "json-rpc"));
+        let (url, _) = serve_card(card).await;
+        let mut client = A2AClientBuilder::new_http(url)
+            .discover_and_build()
+            .await
+            .unwrap();
+
+        let task = client.send_message(Message::user("Hello")).await.unwrap();
+        assert_eq!(task.id, "task-1");
+        let task = client.get_task("task-2".into()).await.unwrap();
+        assert_eq!(task.id, "task-2");
+    }
+
+    #[tokio::test]
+    async fn test_discover_and_build_with_selector() {
+        /// Prefer the endpoint with the longest name
+        #[derive(Debug)]
+        struct LongestName;
+
+        impl EndpointSelector for LongestName {
+            fn select<'a>(&self, card: &'a AgentCard) -> Option<(&'a str, &'a EndpointConfig)> {
+                card.endpoints
+                    .iter()
+                    .max_by_key(|(name, _)| name.len())
+                    .map(|(name, endpoint)| (name.as_str(), endpoint))
+            }
+        }
+
+        let card = AgentCard::new("Test Agent", "A test agent", AgentCapabilities::default())
+            .with_endpoint("rest", EndpointConfig::new("/api/a2a", "http+rest"))
+            .with_endpoint(
+                "websocket",
+                EndpointConfig::new("wss://ws.example.com/a2a", "json-rpc"),
+            );
+        let (url, _) = serve_card(card.clone()).await;
+        let client = A2AClientBuilder::new_http(url)
+            .with_endpoint_selector(LongestName)
+            .discover_and_build()
+            .await
+            .unwrap();
+        assert!(client
+            .service()
+            .protocol()
+            .transport()
+            .as_websocket()
+            .is_some());
+
+        // Endpoints of bindings the client cannot build are never offered
+        let card = card.with_endpoint(
+            "grpc-endpoint",
+            EndpointConfig::new("https://grpc.example.com", "grpc"),
+        );
+        let (url, _) = serve_card(card).await;
+        let client = A2AClientBuilder::new_http(url)
+            .with_endpoint_selector(LongestName)
+            .discover_and_build()
+            .await
+            .unwrap();
+        assert!(client
+            .service()
+            .protocol()
+            .transport()
+            .as_websocket()
+            .is_some());
+    }
 }
@@ -0,0 +1,159 @@
+//! Caching of agent cards
+//!
+//! Agent cards change rarely, but capability checks need one at hand. An
+//! [`AgentCardCache`] keeps the card fetched last for a time to live, within which
+//! [`AgentClient::discover`](crate::client::AgentClient::discover) answers from the cache
+//! instead of asking the agent again:
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use tower_a2a::prelude::*;
+//!
+//! # async fn example() -> Result<(), A2AError> {
+//! let url = "https://agent.example.com".parse().unwrap();
+//! let mut client = A2AClientBuilder::new_http(url)
+//!     .with_card_ttl(Duration::from_secs(60))
+//!     .build()?;
+//!
+//! let card = client.discover().await?; // Fetched
+//! let card = client.discover().await?; // Cached
+//!
+//! client.card_cache().force_refresh();
+//! let card = client.discover().await?; // Fetched again
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use crate::protocol::agent::AgentCard;
+
+/// Default time to live of a cached agent card
+pub const DEFAULT_CARD_TTL: Duration = Duration::from_secs(300);
+
+/// A card and when it was fetched
+#[derive(Debug)]
+struct Entry {
+    card: AgentCard,
+    fetched_at: Instant,
+    stale: bool,
+}
+
+/// Cache of an agent's card with a time to live
+///
+/// Clones share the cached card, so a client and its background prefetch see the same
+/// card. A card past its time to live is no longer returned by [`get`](Self::get), but
+/// is kept for [`last`](Self::last) until replaced.
+#[derive(Debug, Clone)]
+pub struct AgentCardCache {
+    ttl: Duration,
+    entry: Arc<RwLock<Option<Entry>>>,
+}
+
+impl AgentCardCache {
+    /// Create an empty cache keeping cards for `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: Arc::default(),
+        }
+    }
+
+    /// Get the time to live of cached cards
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Get the cached card, unless it expired or a refresh was forced
+    pub fn get(&self) -> Option<AgentCard> {
+        let entry = self.entry.read().ok()?;
+        entry
+            .as_ref()
+            .filter(|entry| !entry.stale && entry.fetched_at.elapsed() < self.ttl)
+            .map(|entry| entry.card.clone())
+    }
+
+    /// Get the card fetched last, however old
+    ///
+    /// Capability checks use it, since a stale card is a better guess than none.
+    pub fn last(&self) -> Option<AgentCard> {
+        let entry = self.entry.read().ok()?;
+        entry.as_ref().map(|entry| entry.card.clone())
+    }
+
+    /// Check whether the cache holds a card that has not expired
+    pub fn is_fresh(&self) -> bool {
+        self.get().is_some()
+    }
+
+    /// Cache a freshly fetched card, returning the one it replaces
+    pub fn insert(&self, card: AgentCard) -> Option<AgentCard> {
+        let mut entry = self.entry.write().ok()?;
+        let previous = entry.replace(Entry {
+            card,
+            fetched_at: Instant::now(),
+            stale: false,
+        });
+        previous.map(|previous| previous.card)
+    }
+
+    /// Make the next discovery fetch the card from the agent again
+    ///
+    /// The current card stays available through [`last`](Self::last) meanwhile.
+    pub fn force_refresh(&self) {
+        if let Ok(mut entry) = self.entry.write() {
+            if let Some(entry) = entry.as_mut() {
+                entry.stale = true;
+            }
+        }
+    }
+
+    /// Forget the cached card
+    pub fn clear(&self) {
+        if let Ok(mut entry) = self.entry.write() {
+            *entry = None;
+        }
+    }
+}
+
+impl Default for AgentCardCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CARD_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::agent::AgentCapabilities;
+
+    fn card(name: &str) -> AgentCard {
+        AgentCard::new(name, "A test agent", AgentCapabilities::default())
+    }
+
+    #[test]
+    fn test_card_cache_expiry() {
+        let cache = AgentCardCache::new(Duration::from_millis(20));
+        assert!(cache.get().is_none());
+
+        assert!(cache.insert(card("v1")).is_none());
+        assert_eq!(cache.get().unwrap().name, "v1");
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(!cache.is_fresh());
+        assert_eq!(cache.last().unwrap().name, "v1");
+
+        assert_eq!(cache.clone().insert(card("v2")).unwrap().name, "v1");
+        assert_eq!(cache.get().unwrap().name, "v2");
+
+        cache.force_refresh();
+        assert!(cache.get().is_none());
+        assert_eq!(cache.last().unwrap().name, "v2");
+
+        cache.clear();
+        assert!(cache.last().is_none());
+    }
+}
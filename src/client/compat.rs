@@ -0,0 +1,205 @@
+//! Compatibility profiles bundling the deviations of agent implementations
+//!
+//! Agents built on some frameworks differ from the protocol in known ways: routes
+//! under another prefix, renamed response fields, errors nested in an envelope, or
+//! headers they insist on. A [`CompatProfile`] describes these deviations in one
+//! place. Apply it to a client with
+//! [`A2AClientBuilder::with_compat_profile`](crate::client::A2AClientBuilder::with_compat_profile),
+//! or name it from a registry entry so every client of the agent gets it:
+//!
+//! ```json
+//! {
+//!   "compat_profiles": [{
+//!     "name": "acme-gateway",
+//!     "endpoints": { "prefix": "/gateway/a2a" },
+//!     "field_aliases": { "task_id": "id", "state": "status" },
+//!     "error_shape": { "message": ["/error/message", "/message"] },
+//!     "headers": { "X-Acme-Client": "a2a" }
+//!   }],
+//!   "agents": [
+//!     { "name": "billing", "url": "https://billing.example.com", "compat": "acme-gateway" }
+//!   ]
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    protocol::{endpoint::EndpointMap, error::A2AError, operation::A2AOperation, usage::UsageKeys},
+    service::{ErrorShape, ResponseTransform},
+};
+
+/// Known deviations of an agent implementation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompatProfile {
+    /// Name used to select the profile, e.g. from a registry entry
+    pub name: String,
+
+    /// Endpoint paths replacing the defaults
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoints: Option<EndpointMap>,
+
+    /// Response fields renamed by the agent, mapping its name to the protocol's
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub field_aliases: HashMap<String, String>,
+
+    /// Where the agent puts error details
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_shape: Option<ErrorShape>,
+
+    /// Headers the agent requires on every request
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+
+    /// Where the agent reports token usage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage_keys: Option<UsageKeys>,
+}
+
+impl CompatProfile {
+    /// Create an empty profile
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Use different endpoint paths
+    pub fn with_endpoints(mut self, endpoints: EndpointMap) -> Self {
+        self.endpoints = Some(endpoints);
+        self
+    }
+
+    /// Rename a response field from the agent's name to the protocol's
+    pub fn with_field_alias(mut self, alias: impl Into<String>, field: impl Into<String>) -> Self {
+        self.field_aliases.insert(alias.into(), field.into());
+        self
+    }
+
+    /// Read error details from a different shape
+    pub fn with_error_shape(mut self, shape: ErrorShape) -> Self {
+        self.error_shape = Some(shape);
+        self
+    }
+
+    /// Add a header sent on every request
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(key.into(), value.into());
+        self
+    }
+
+    /// Read token usage from different keys
+    pub fn with_usage_keys(mut self, keys: UsageKeys) -> Self {
+        self.usage_keys = Some(keys);
+        self
+    }
+
+    /// Get a transform applying the field aliases, if there are any
+    pub fn field_alias_transform(&self) -> Option<FieldAliases> {
+        (!self.field_aliases.is_empty()).then(|| FieldAliases(self.field_aliases.clone()))
+    }
+}
+
+/// Protocol fields holding objects whose fields may be renamed too
+///
+/// Metadata, data parts, and other fields holding user data are left alone, so a key
+/// that happens to match an alias is not renamed there.
+const PROTOCOL_CONTAINERS: &[&str] = &[
+    "result",
+    "task",
+    "tasks",
+    "message",
+    "status",
+    "history",
+    "artifact",
+    "artifacts",
+    "parts",
+    "file",
+    "statusUpdate",
+    "artifactUpdate",
+];
+
+/// Transform renaming the fields of protocol objects in a response
+///
+/// A field is renamed only if the object does not already have the protocol's name.
+/// Renaming descends into the protocol's own containers, such as the tasks of a list or
+/// the parts of a message, but never into metadata or data parts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldAliases(HashMap<String, String>);
+
+impl FieldAliases {
+    fn rename(&self, value: &mut Value) {
+        match value {
+            Value::Object(object) => {
+                for (alias, field) in &self.0 {
+                    if object.contains_key(field) {
+                        continue;
+                    }
+                    if let Some(renamed) = object.remove(alias) {
+                        object.insert(field.clone(), renamed);
+                    }
+                }
+                object
+                    .iter_mut()
+                    .filter(|(key, _)| PROTOCOL_CONTAINERS.contains(&key.as_str()))
+                    .for_each(|(_, value)| self.rename(value));
+            }
+            Value::Array(items) => items.iter_mut().for_each(|value| self.rename(value)),
+            _ => {}
+        }
+    }
+}
+
+impl ResponseTransform for FieldAliases {
+    fn transform_value(&self, body: &mut Value, _operation: &A2AOperation) -> Result<(), A2AError> {
+        self.rename(body);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_field_aliases() {
+        let profile = CompatProfile::new("legacy")
+            .with_field_alias("task_id", "id")
+            .with_field_alias("state", "status");
+        let aliases = profile.field_alias_transform().unwrap();
+
+        let mut body = json!({
+            "tasks": [{
+                "task_id": "task-1",
+                "state": "working",
+                "metadata": {"state": "custom"},
+                "history": [{"parts": [{"kind": "data", "data": {"state": "user"}}]}]
+            }],
+            "id": "page-1",
+            "task_id": "ignored"
+        });
+        aliases
+            .transform_value(&mut body, &A2AOperation::DiscoverAgent)
+            .unwrap();
+
+        assert_eq!(body["tasks"][0]["id"], "task-1");
+        assert_eq!(body["tasks"][0]["status"], "working");
+        assert_eq!(body["tasks"][0]["metadata"], json!({"state": "custom"}));
+        assert_eq!(
+            body["tasks"][0]["history"][0]["parts"][0]["data"],
+            json!({"state": "user"})
+        );
+        assert_eq!(body["id"], "page-1");
+        assert_eq!(body["task_id"], "ignored");
+        assert!(CompatProfile::new("empty")
+            .field_alias_transform()
+            .is_none());
+    }
+}
@@ -1,9 +1,191 @@
 //! Client configuration
 
-use std::time::Duration;
+use std::{collections::HashMap, fmt, future::Future, sync::Arc, time::Duration};
 
+use futures::future::BoxFuture;
 use url::Url;
 
+use crate::{
+    client::{card_cache::DEFAULT_CARD_TTL, events::ClientEvents, usage::UsageTracker},
+    layer::RetryPolicy,
+    protocol::{
+        consts::headers,
+        error::A2AError,
+        language::{accept_language, ACCEPT_LANGUAGES_METADATA_KEY},
+        usage::UsageKeys,
+        A2AOperation, OperationKind,
+    },
+    service::{ContextNamespace, ExecutorHandle, MetaRecorder},
+};
+
+/// Header carrying the per-request correlation ID
+pub const CORRELATION_ID_HEADER: &str = headers::CORRELATION_ID;
+
+/// What the client tells the agent when an event stream is dropped before its task
+/// reached a terminal state
+///
+/// The protocol has no operation to unsubscribe from a task, so stopping to listen
+/// only closes the stream: HTTP connections are closed, while events of a shared
+/// WebSocket connection are discarded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StreamDropPolicy {
+    /// Close the stream and let the task run to completion
+    #[default]
+    Disconnect,
+
+    /// Close the stream and cancel the task in the background
+    Cancel,
+}
+
+/// How the client treats an identifier that the caller did not set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdMode {
+    /// Generate a UUIDv7 when absent
+    #[default]
+    Generate,
+
+    /// Fail the request with a validation error when absent
+    Require,
+
+    /// Leave the identifier unset
+    Off,
+}
+
+/// Automatic generation of message, context, and correlation IDs
+///
+/// By default message IDs are generated as recommended by the spec, while context IDs
+/// are left to the agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdOptions {
+    /// Handling of `messageId` on outgoing messages
+    pub message_ids: IdMode,
+
+    /// Handling of `contextId` on outgoing messages
+    pub context_ids: IdMode,
+
+    /// Whether to attach a fresh correlation ID header to every request
+    pub correlation_ids: bool,
+
+    /// Whether to generate the task ID of new tasks when the agent supports the
+    /// [`CLIENT_TASK_IDS`](crate::protocol::consts::extensions::CLIENT_TASK_IDS) extension
+    ///
+    /// Retries then resend the same ID, so the agent creates the task at most once
+    /// even if a response is lost.
+    pub client_task_ids: bool,
+}
+
+impl Default for IdOptions {
+    fn default() -> Self {
+        Self {
+            message_ids: IdMode::Generate,
+            context_ids: IdMode::Off,
+            correlation_ids: false,
+            client_task_ids: true,
+        }
+    }
+}
+
+impl IdOptions {
+    /// Require callers to set message and context IDs explicitly
+    pub fn require_explicit() -> Self {
+        Self {
+            message_ids: IdMode::Require,
+            context_ids: IdMode::Require,
+            correlation_ids: false,
+            client_task_ids: true,
+        }
+    }
+
+    /// Fill in or check the IDs of an outgoing operation
+    pub fn apply(&self, operation: &mut A2AOperation) -> Result<(), A2AError> {
+        let A2AOperation::SendMessage {
+            message,
+            context_id,
+            ..
+        } = operation
+        else {
+            return Ok(());
+        };
+
+        match (self.message_ids, &message.message_id) {
+            (IdMode::Generate, None) => message.message_id = Some(Self::generate()),
+            (IdMode::Require, None) => {
+                return Err(A2AError::Validation("Message ID is required".into()))
+            }
+            _ => {}
+        }
+
+        if context_id.is_none() && message.context_id.is_none() {
+            match self.context_ids {
+                IdMode::Generate => {
+                    let id = Self::generate();
+                    message.context_id = Some(id.clone());
+                    *context_id = Some(id);
+                }
+                IdMode::Require => {
+                    return Err(A2AError::Validation("Context ID is required".into()))
+                }
+                IdMode::Off => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Generate the task ID of a message that starts a new task
+    ///
+    /// Returns whether an ID was generated. Messages continuing a task are left alone.
+    pub(crate) fn assign_task_id(&self, operation: &mut A2AOperation) -> bool {
+        match operation {
+            A2AOperation::SendMessage {
+                message,
+                task_id: task_id @ None,
+                ..
+            } if self.client_task_ids && message.task_id.is_none() => {
+                *task_id = Some(Self::generate());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Generate a new identifier
+    fn generate() -> String {
+        uuid::Uuid::now_v7().to_string()
+    }
+}
+
+/// Asynchronous source of headers attached to every request
+///
+/// Providers are invoked once per request, so they can supply values that change
+/// over time such as session tokens.
+#[derive(Clone)]
+pub struct HeaderProvider(
+    Arc<dyn Fn() -> BoxFuture<'static, HashMap<String, String>> + Send + Sync>,
+);
+
+impl HeaderProvider {
+    /// Create a header provider from an async function
+    pub fn new<F, Fut>(provider: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = HashMap<String, String>> + Send + 'static,
+    {
+        Self(Arc::new(move || Box::pin(provider())))
+    }
+
+    /// Produce the headers for a request
+    pub async fn headers(&self) -> HashMap<String, String> {
+        (self.0)().await
+    }
+}
+
+impl fmt::Debug for HeaderProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HeaderProvider").finish_non_exhaustive()
+    }
+}
+
 /// Configuration for an A2A client
 #[derive(Debug, Clone)]
 pub struct ClientConfig {
@@ -13,11 +195,59 @@ pub struct ClientConfig {
     /// Default request timeout
     pub timeout: Duration,
 
+    /// Timeouts overriding the default for specific kinds of operations
+    pub operation_timeouts: HashMap<OperationKind, Duration>,
+
+    /// Total time budget of an operation across retries and reconnects, if any
+    pub operation_deadline: Option<Duration>,
+
     /// Maximum number of retry attempts
     pub max_retries: u32,
 
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+
+    /// Upper bound for the delay between retries
+    pub max_backoff: Duration,
+
     /// Enable response validation
     pub validate_responses: bool,
+
+    /// Headers attached to every request
+    pub default_headers: HashMap<String, String>,
+
+    /// Providers of dynamic headers, applied after the default headers
+    pub header_providers: Vec<HeaderProvider>,
+
+    /// Languages the client prefers for text, in order of preference
+    pub accept_languages: Vec<String>,
+
+    /// Where the agent reports token usage in its tasks
+    pub usage_keys: UsageKeys,
+
+    /// Tracker adding up the usage reported by the agent, if any
+    pub usage: Option<UsageTracker>,
+
+    /// Hub publishing retry, stream resumption, agent card, and connection change events
+    pub events: ClientEvents,
+
+    /// Automatic ID generation for outgoing requests
+    pub ids: IdOptions,
+
+    /// Executor running background tasks such as eager connects
+    pub executor: ExecutorHandle,
+
+    /// What to tell the agent when an event stream is dropped early
+    pub stream_drop: StreamDropPolicy,
+
+    /// How often task subscriptions poll agents that cannot stream
+    pub poll_interval: Duration,
+
+    /// How long a discovered agent card is reused before it is fetched again
+    pub card_ttl: Duration,
+
+    /// Namespace of the tenant's context IDs, if the client serves a single tenant
+    pub context_namespace: Option<ContextNamespace>,
 }
 
 impl ClientConfig {
@@ -26,8 +256,24 @@ impl ClientConfig {
         Self {
             agent_url,
             timeout: Duration::from_secs(30),
+            operation_timeouts: HashMap::new(),
+            operation_deadline: None,
             max_retries: 3,
+            initial_backoff: RetryPolicy::default().initial_backoff,
+            max_backoff: RetryPolicy::default().max_backoff,
             validate_responses: true,
+            default_headers: HashMap::new(),
+            header_providers: Vec::new(),
+            accept_languages: Vec::new(),
+            usage_keys: UsageKeys::default(),
+            usage: None,
+            events: ClientEvents::default(),
+            ids: IdOptions::default(),
+            executor: ExecutorHandle::default(),
+            stream_drop: StreamDropPolicy::default(),
+            poll_interval: Duration::from_secs(1),
+            card_ttl: DEFAULT_CARD_TTL,
+            context_namespace: None,
         }
     }
 
@@ -37,17 +283,266 @@ impl ClientConfig {
         self
     }
 
+    /// Set the timeout for a specific kind of operation
+    pub fn with_operation_timeout(mut self, kind: OperationKind, timeout: Duration) -> Self {
+        self.operation_timeouts.insert(kind, timeout);
+        self
+    }
+
+    /// Get the timeout for a kind of operation, falling back to the default timeout
+    pub fn timeout_for(&self, kind: OperationKind) -> Duration {
+        self.operation_timeouts
+            .get(&kind)
+            .copied()
+            .unwrap_or(self.timeout)
+    }
+
+    /// Set the total time budget of an operation across retries and reconnects
+    ///
+    /// Unlike the timeout, which applies to each attempt, the deadline bounds the whole
+    /// operation: retries and stream reconnects are not started once it has passed.
+    pub fn with_operation_deadline(mut self, budget: Duration) -> Self {
+        self.operation_deadline = Some(budget);
+        self
+    }
+
     /// Set the maximum number of retries
     pub fn with_max_retries(mut self, max_retries: u32) -> Self {
         self.max_retries = max_retries;
         self
     }
 
+    /// Set the initial and maximum delay between retries
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Apply the settings of a profile, replacing timeouts, retries, and backoff
+    pub fn with_profile(mut self, profile: Profile) -> Self {
+        let (initial, max) = profile.backoff();
+        self.timeout = profile.timeout();
+        self.operation_timeouts = profile.operation_timeouts();
+        self.max_retries = profile.max_retries();
+        self.with_backoff(initial, max)
+    }
+
+    /// Get the retry policy described by this configuration
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::new(self.max_retries).with_backoff(self.initial_backoff, self.max_backoff)
+    }
+
     /// Enable or disable response validation
     pub fn with_validation(mut self, enabled: bool) -> Self {
         self.validate_responses = enabled;
         self
     }
+
+    /// Set what to tell the agent when an event stream is dropped early
+    pub fn with_stream_drop_policy(mut self, policy: StreamDropPolicy) -> Self {
+        self.stream_drop = policy;
+        self
+    }
+
+    /// Set how often task subscriptions poll agents that cannot stream
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Set how long a discovered agent card is reused before it is fetched again
+    pub fn with_card_ttl(mut self, ttl: Duration) -> Self {
+        self.card_ttl = ttl;
+        self
+    }
+
+    /// Set the ID generation options
+    pub fn with_ids(mut self, ids: IdOptions) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Add a header attached to every request
+    pub fn with_default_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.insert(key.into(), value.into());
+        self
+    }
+
+    /// Add a provider of dynamic headers
+    pub fn with_header_provider(mut self, provider: HeaderProvider) -> Self {
+        self.header_providers.push(provider);
+        self
+    }
+
+    /// Set the languages the client prefers for text, in order of preference
+    ///
+    /// The languages are advertised to agents with the `Accept-Language` header and,
+    /// since WebSocket requests carry no headers, in the metadata of sent messages.
+    pub fn with_accept_languages<I>(mut self, languages: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.accept_languages = languages.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Resolve the headers for a request
+    ///
+    /// The `Accept-Language` header built from the accepted languages is applied first,
+    /// then the default headers, followed by each provider in the order they were
+    /// added, so later providers override earlier values. Header names are compared
+    /// ignoring case, so `x-tenant-id` overrides `X-Tenant-Id`.
+    pub async fn headers(&self) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        if !self.accept_languages.is_empty() {
+            headers.insert(
+                headers::ACCEPT_LANGUAGE.to_string(),
+                accept_language(&self.accept_languages),
+            );
+        }
+        merge_headers(&mut headers, self.default_headers.clone());
+        for provider in &self.header_providers {
+            merge_headers(&mut headers, provider.headers().await);
+        }
+        headers
+    }
+
+    /// List the accepted languages in the metadata of a sent message, unless the
+    /// caller already did
+    pub(crate) fn apply_accept_languages(&self, operation: &mut A2AOperation) {
+        let A2AOperation::SendMessage { message, .. } = operation else {
+            return;
+        };
+        if self.accept_languages.is_empty() {
+            return;
+        }
+        message
+            .metadata
+            .get_or_insert_with(Default::default)
+            .entry(ACCEPT_LANGUAGES_METADATA_KEY.to_string())
+            .or_insert_with(|| self.accept_languages.clone().into());
+    }
+}
+
+/// Add headers, replacing existing values of the same name in any case
+fn merge_headers(headers: &mut HashMap<String, String>, overrides: HashMap<String, String>) {
+    for (key, value) in overrides {
+        headers.retain(|existing, _| !existing.eq_ignore_ascii_case(&key));
+        headers.insert(key, value);
+    }
+}
+
+/// Preset tuning for a kind of workload
+///
+/// A profile sets the default timeout, per-operation timeouts, retries, and backoff.
+/// Each setting can still be overridden individually after applying the profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// A human is waiting: moderate timeouts and a couple of quick retries
+    Interactive,
+
+    /// Background processing: long timeouts and patient retries
+    Batch,
+
+    /// Latency-sensitive calls: short timeouts and at most one fast retry
+    LowLatency,
+}
+
+impl Profile {
+    /// Default request timeout
+    pub fn timeout(&self) -> Duration {
+        match self {
+            Profile::Interactive => Duration::from_secs(30),
+            Profile::Batch => Duration::from_secs(300),
+            Profile::LowLatency => Duration::from_secs(5),
+        }
+    }
+
+    /// Timeouts overriding the default for specific kinds of operations
+    pub fn operation_timeouts(&self) -> HashMap<OperationKind, Duration> {
+        let discovery = match self {
+            Profile::Interactive => Duration::from_secs(10),
+            Profile::Batch => Duration::from_secs(30),
+            Profile::LowLatency => Duration::from_secs(2),
+        };
+        HashMap::from([(OperationKind::DiscoverAgent, discovery)])
+    }
+
+    /// Maximum number of retries
+    pub fn max_retries(&self) -> u32 {
+        match self {
+            Profile::Interactive => 2,
+            Profile::Batch => 5,
+            Profile::LowLatency => 1,
+        }
+    }
+
+    /// Initial and maximum delay between retries
+    pub fn backoff(&self) -> (Duration, Duration) {
+        match self {
+            Profile::Interactive => (Duration::from_millis(200), Duration::from_secs(2)),
+            Profile::Batch => (Duration::from_secs(1), Duration::from_secs(60)),
+            Profile::LowLatency => (Duration::from_millis(50), Duration::from_millis(200)),
+        }
+    }
+}
+
+/// Per-request overrides of the client configuration
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Timeout for this request, overriding the configured timeouts
+    pub timeout: Option<Duration>,
+
+    /// Total time budget for this request, overriding the configured operation deadline
+    pub deadline: Option<Duration>,
+
+    /// Extra headers for this request, overriding configured headers
+    pub headers: HashMap<String, String>,
+
+    /// Recorder of response details for this request
+    pub meta: Option<MetaRecorder>,
+
+    /// Namespace of the tenant's context IDs for this request, overriding the client's
+    pub context_namespace: Option<ContextNamespace>,
+}
+
+impl RequestOptions {
+    /// Create empty request options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the timeout for this request
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the total time budget for this request across retries
+    pub fn with_deadline(mut self, budget: Duration) -> Self {
+        self.deadline = Some(budget);
+        self
+    }
+
+    /// Add a header to this request
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(key.into(), value.into());
+        self
+    }
+
+    /// Record response details for this request
+    pub fn with_meta_recorder(mut self, recorder: MetaRecorder) -> Self {
+        self.meta = Some(recorder);
+        self
+    }
+
+    /// Namespace the context IDs of this request for a tenant
+    pub fn with_context_namespace(mut self, namespace: ContextNamespace) -> Self {
+        self.context_namespace = Some(namespace);
+        self
+    }
 }
 
 impl Default for ClientConfig {
@@ -55,3 +550,136 @@ impl Default for ClientConfig {
         Self::new("http://.".parse().unwrap())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::protocol::Message;
+
+    use super::*;
+
+    fn send(message: Message) -> A2AOperation {
+        A2AOperation::SendMessage {
+            message,
+            stream: false,
+            context_id: None,
+            task_id: None,
+        }
+    }
+
+    #[test]
+    fn test_id_generation() {
+        let mut operation = send(Message::user("Hello"));
+        IdOptions::default().apply(&mut operation).unwrap();
+
+        let A2AOperation::SendMessage {
+            message,
+            context_id,
+            ..
+        } = &operation
+        else {
+            unreachable!()
+        };
+        assert!(message.message_id.is_some());
+        assert!(context_id.is_none());
+
+        let ids = IdOptions {
+            context_ids: IdMode::Generate,
+            ..IdOptions::default()
+        };
+        let mut operation = send(Message::user("Hello"));
+        ids.apply(&mut operation).unwrap();
+
+        let A2AOperation::SendMessage {
+            message,
+            context_id,
+            ..
+        } = &operation
+        else {
+            unreachable!()
+        };
+        assert!(context_id.is_some());
+        assert_eq!(&message.context_id, context_id);
+    }
+
+    #[test]
+    fn test_id_required() {
+        let ids = IdOptions::require_explicit();
+
+        let mut operation = send(Message::user("Hello"));
+        assert!(matches!(
+            ids.apply(&mut operation),
+            Err(A2AError::Validation(_))
+        ));
+
+        let message = Message::builder()
+            .role(crate::protocol::Role::User)
+            .parts(vec![crate::protocol::MessagePart::text("Hello")])
+            .message_id("msg-1")
+            .context_id("ctx-1")
+            .build();
+        let mut operation = send(message);
+        assert!(ids.apply(&mut operation).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_headers_ignore_case() {
+        let config = ClientConfig::default()
+            .with_accept_languages(["de"])
+            .with_default_header("accept-language", "fr")
+            .with_default_header("X-Tenant-Id", "acme")
+            .with_header_provider(HeaderProvider::new(|| async {
+                HashMap::from([("x-tenant-id".to_string(), "globex".to_string())])
+            }));
+
+        let headers = config.headers().await;
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers["accept-language"], "fr");
+        assert_eq!(headers["x-tenant-id"], "globex");
+    }
+
+    #[test]
+    fn test_accept_languages_metadata() {
+        let config = ClientConfig::default().with_accept_languages(["de-CH", "en"]);
+        let mut operation = send(Message::user("Hello"));
+        config.apply_accept_languages(&mut operation);
+
+        let A2AOperation::SendMessage { message, .. } = operation else {
+            unreachable!()
+        };
+        assert_eq!(message.accept_languages(), ["de-CH", "en"]);
+    }
+
+    #[test]
+    fn test_operation_timeouts() {
+        let config = ClientConfig::default()
+            .with_timeout(Duration::from_secs(120))
+            .with_operation_timeout(OperationKind::DiscoverAgent, Duration::from_secs(5));
+
+        assert_eq!(
+            config.timeout_for(OperationKind::DiscoverAgent),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            config.timeout_for(OperationKind::SendMessage),
+            Duration::from_secs(120)
+        );
+    }
+
+    #[test]
+    fn test_profiles() {
+        let config = ClientConfig::default().with_profile(Profile::LowLatency);
+        assert_eq!(config.timeout, Duration::from_secs(5));
+        assert_eq!(config.retry_policy().max_retries, 1);
+        assert_eq!(
+            config.timeout_for(OperationKind::DiscoverAgent),
+            Duration::from_secs(2)
+        );
+
+        let config = ClientConfig::default()
+            .with_profile(Profile::Batch)
+            .with_max_retries(0);
+        assert_eq!(config.timeout, Duration::from_secs(300));
+        assert_eq!(config.retry_policy().max_retries, 0);
+        assert_eq!(config.retry_policy().max_backoff, Duration::from_secs(60));
+    }
+}
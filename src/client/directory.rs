@@ -0,0 +1,324 @@
+//! Capability index over agent cards for dynamic routing
+//!
+//! A [`Directory`] indexes agent cards by skill, media types, and authentication
+//! scheme so callers can pick an agent at runtime:
+//!
+//! ```rust,no_run
+//! use tower_a2a::client::{directory::{AgentQuery, Directory}, AgentDiscovery};
+//!
+//! # async fn example() {
+//! let urls = vec!["https://translate.example.com/.well-known/agent-card.json"
+//!     .parse()
+//!     .unwrap()];
+//! let discovery = AgentDiscovery::new(reqwest::Client::new()).with_source(urls);
+//! let directory = Directory::crawl(&discovery).await;
+//!
+//! let query = AgentQuery::new().skill("translate").input("text/plain");
+//! for agent in directory.find_agents(&query) {
+//!     println!("{} at {}", agent.card.name, agent.agent_url());
+//! }
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{
+    client::discovery::{AgentDiscovery, DiscoveredAgent},
+    protocol::agent::{AgentCard, AgentSkill, SecurityScheme},
+};
+
+/// Criteria for finding agents; unset criteria match every agent
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentQuery {
+    skill: Option<String>,
+    input: Option<String>,
+    output: Option<String>,
+    auth: Option<String>,
+}
+
+impl AgentQuery {
+    /// Create a query matching every agent
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require a skill whose ID, name, or tags match (case-insensitively)
+    pub fn skill(mut self, skill: impl Into<String>) -> Self {
+        self.skill = Some(skill.into().to_lowercase());
+        self
+    }
+
+    /// Require the matching skill (or agent) to accept a media type
+    pub fn input(mut self, media_type: impl Into<String>) -> Self {
+        self.input = Some(media_type.into());
+        self
+    }
+
+    /// Require the matching skill (or agent) to produce a media type
+    pub fn output(mut self, media_type: impl Into<String>) -> Self {
+        self.output = Some(media_type.into());
+        self
+    }
+
+    /// Require support for an authentication scheme
+    ///
+    /// Schemes are named `bearer`, `basic` (or any other HTTP auth scheme), `apiKey`,
+    /// `oauth2`, `openIdConnect`, or `none` for agents without authentication.
+    pub fn auth(mut self, scheme: impl Into<String>) -> Self {
+        self.auth = Some(scheme.into().to_lowercase());
+        self
+    }
+}
+
+/// Index of agent cards by capabilities
+#[derive(Debug, Clone, Default)]
+pub struct Directory {
+    agents: Vec<DiscoveredAgent>,
+    /// Lowercased skill IDs, names, and tags to agent indices
+    skills: HashMap<String, Vec<usize>>,
+    /// Lowercased authentication scheme names to agent indices
+    auth_schemes: HashMap<String, Vec<usize>>,
+}
+
+impl Directory {
+    /// Create an empty directory
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fetch the cards of all agents known to a discovery and index them
+    pub async fn crawl(discovery: &AgentDiscovery) -> Self {
+        let mut directory = Self::new();
+        for agent in discovery.discover().await {
+            directory.insert(agent);
+        }
+        directory
+    }
+
+    /// Index an agent, replacing any agent fetched from the same card URL
+    pub fn insert(&mut self, agent: DiscoveredAgent) {
+        if let Some(index) = self
+            .agents
+            .iter()
+            .position(|known| known.card_url == agent.card_url)
+        {
+            self.agents[index] = agent;
+            self.reindex();
+            return;
+        }
+
+        self.agents.push(agent);
+        self.index(self.agents.len() - 1);
+    }
+
+    /// Get all indexed agents
+    pub fn agents(&self) -> &[DiscoveredAgent] {
+        &self.agents
+    }
+
+    /// Find the agents matching a query, in insertion order
+    pub fn find_agents(&self, query: &AgentQuery) -> Vec<&DiscoveredAgent> {
+        let mut candidates: Vec<usize> = match &query.skill {
+            Some(skill) => self.skills.get(skill).cloned().unwrap_or_default(),
+            None => (0..self.agents.len()).collect(),
+        };
+        if let Some(auth) = &query.auth {
+            let supported = self.auth_schemes.get(auth).map(Vec::as_slice);
+            candidates.retain(|index| supported.is_some_and(|s| s.contains(index)));
+        }
+
+        candidates
+            .into_iter()
+            .map(|index| &self.agents[index])
+            .filter(|agent| Self::matches_modes(&agent.card, query))
+            .collect()
+    }
+
+    /// Check whether a skill (or the agent itself) satisfies the query's media types
+    fn matches_modes(card: &AgentCard, query: &AgentQuery) -> bool {
+        let fits = |input: &[String], output: &[String]| {
+            query.input.as_ref().is_none_or(|mode| accepts(input, mode))
+                && query
+                    .output
+                    .as_ref()
+                    .is_none_or(|mode| accepts(output, mode))
+        };
+
+        // Skills matching the query, or all skills when no skill is requested
+        let mut skills = card
+            .skills
+            .iter()
+            .filter(|skill| {
+                query
+                    .skill
+                    .as_ref()
+                    .is_none_or(|wanted| skill_keys(skill).any(|key| key == *wanted))
+            })
+            .peekable();
+
+        if skills.peek().is_none() {
+            return query.skill.is_none()
+                && fits(&card.default_input_modes, &card.default_output_modes);
+        }
+
+        skills.any(|skill| {
+            fits(
+                skill
+                    .input_modes
+                    .as_deref()
+                    .unwrap_or(&card.default_input_modes),
+                skill
+                    .output_modes
+                    .as_deref()
+                    .unwrap_or(&card.default_output_modes),
+            )
+        })
+    }
+
+    fn reindex(&mut self) {
+        self.skills.clear();
+        self.auth_schemes.clear();
+        for index in 0..self.agents.len() {
+            self.index(index);
+        }
+    }
+
+    fn index(&mut self, index: usize) {
+        let card = &self.agents[index].card;
+
+        for skill in &card.skills {
+            for key in skill_keys(skill) {
+                let entry = self.skills.entry(key).or_default();
+                if !entry.contains(&index) {
+                    entry.push(index);
+                }
+            }
+        }
+
+        let schemes: Vec<String> = match card.authentication.as_deref() {
+            None | Some([]) => vec!["none".to_string()],
+            Some(schemes) => schemes.iter().map(scheme_name).collect(),
+        };
+        for scheme in schemes {
+            let entry = self.auth_schemes.entry(scheme).or_default();
+            if !entry.contains(&index) {
+                entry.push(index);
+            }
+        }
+    }
+}
+
+/// Lowercased keys a skill can be found by
+fn skill_keys(skill: &AgentSkill) -> impl Iterator<Item = String> + '_ {
+    [&skill.id, &skill.name]
+        .into_iter()
+        .chain(&skill.tags)
+        .map(|key| key.to_lowercase())
+}
+
+/// Lowercased name of a security scheme
+fn scheme_name(scheme: &SecurityScheme) -> String {
+    match scheme {
+        SecurityScheme::ApiKey(_) => "apikey".to_string(),
+        SecurityScheme::HttpAuth(http) => http.scheme.to_lowercase(),
+        SecurityScheme::OAuth2(_) => "oauth2".to_string(),
+        SecurityScheme::OpenIdConnect(_) => "openidconnect".to_string(),
+    }
+}
+
+/// Check whether a list of supported media types accepts a media type
+///
+/// Supports `type/*` and `*/*` wildcards on either side. An empty list accepts
+/// everything, since the agent did not restrict its modes.
+fn accepts(supported: &[String], media_type: &str) -> bool {
+    fn matches(pattern: &str, media_type: &str) -> bool {
+        let (pattern_type, pattern_sub) = pattern.split_once('/').unwrap_or((pattern, "*"));
+        let (media, sub) = media_type.split_once('/').unwrap_or((media_type, "*"));
+        (pattern_type == "*" || media == "*" || pattern_type.eq_ignore_ascii_case(media))
+            && (pattern_sub == "*" || sub == "*" || pattern_sub.eq_ignore_ascii_case(sub))
+    }
+
+    supported.is_empty() || supported.iter().any(|mode| matches(mode, media_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::protocol::{
+        agent::{EndpointConfig, HttpAuthSecurityScheme},
+        AgentCapabilities,
+    };
+
+    use super::*;
+
+    fn agent(name: &str, card: AgentCard) -> DiscoveredAgent {
+        DiscoveredAgent {
+            card_url: format!("https://{}.example.com/.well-known/agent-card.json", name)
+                .parse()
+                .unwrap(),
+            card: card.with_endpoint(
+                "http",
+                EndpointConfig::new(format!("https://{}.example.com", name), "http+json"),
+            ),
+        }
+    }
+
+    fn directory() -> Directory {
+        let translator = AgentCard::new("translator", "Translates text", AgentCapabilities::new())
+            .with_default_modes(["text/plain"], ["text/plain"])
+            .with_skill(AgentSkill::new("translate", "Translate").with_tags(["language"]))
+            .with_skill(
+                AgentSkill::new("ocr-translate", "Translate images")
+                    .with_tags(["translate"])
+                    .with_modes(["image/*"], ["text/plain"]),
+            )
+            .with_authentication(vec![SecurityScheme::HttpAuth(HttpAuthSecurityScheme {
+                description: None,
+                scheme: "Bearer".to_string(),
+                bearer_format: None,
+            })]);
+        let summarizer = AgentCard::new("summarizer", "Summarizes", AgentCapabilities::new())
+            .with_skill(AgentSkill::new("summarize", "Summarize"));
+
+        let mut directory = Directory::new();
+        directory.insert(agent("translator", translator));
+        directory.insert(agent("summarizer", summarizer));
+        directory
+    }
+
+    fn names(agents: Vec<&DiscoveredAgent>) -> Vec<&str> {
+        agents
+            .iter()
+            .map(|agent| agent.card.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_find_by_skill_and_modes() {
+        let directory = directory();
+
+        let query = AgentQuery::new().skill("Translate").input("text/plain");
+        assert_eq!(names(directory.find_agents(&query)), ["translator"]);
+
+        let query = AgentQuery::new().skill("translate").input("image/png");
+        assert_eq!(names(directory.find_agents(&query)), ["translator"]);
+
+        let query = AgentQuery::new().skill("summarize").input("audio/wav");
+        assert_eq!(names(directory.find_agents(&query)), ["summarizer"]);
+
+        let query = AgentQuery::new().skill("language").input("audio/wav");
+        assert!(directory.find_agents(&query).is_empty());
+    }
+
+    #[test]
+    fn test_find_by_auth() {
+        let directory = directory();
+
+        let query = AgentQuery::new().auth("bearer");
+        assert_eq!(names(directory.find_agents(&query)), ["translator"]);
+
+        let query = AgentQuery::new().auth("none");
+        assert_eq!(names(directory.find_agents(&query)), ["summarizer"]);
+
+        assert_eq!(directory.find_agents(&AgentQuery::new()).len(), 2);
+    }
+}
@@ -0,0 +1,709 @@
+//! Discovery of agents from index endpoints and DNS
+//!
+//! An [`AgentDiscovery`] collects agent card URLs from one or more
+//! [`DiscoverySource`]s, fetches the cards, and keeps an [`AgentRegistry`] up to date.
+//!
+//! Two kinds of sources are provided:
+//!
+//! - [`IndexSource`] reads a well-known index document listing card URLs, either as a
+//!   JSON array or as `{"agents": [...]}`. Relative URLs are resolved against the index.
+//! - [`DnsSource`] looks up `_a2a.<domain>` TXT records holding card URLs (optionally
+//!   prefixed with `card=`) and `_a2a._tcp.<domain>` SRV records, which point at
+//!   `https://<target>:<port>/.well-known/agent-card.json`. DNS resolution is supplied
+//!   by the application through [`DnsResolver`].
+//!
+//! When a refresh fetches a card that differs from the one registered before, the
+//! discovery publishes a [`ClientEvent::AgentCardChanged`] describing the changes.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::future::AbortHandle;
+use serde::Deserialize;
+use tokio::{
+    sync::{broadcast, oneshot, Mutex, RwLock},
+    task::JoinHandle,
+};
+use url::Url;
+
+use crate::{
+    client::{
+        events::{ClientEvent, ClientEvents},
+        registry::{AgentEntry, AgentRegistry, TransportKind},
+        selector::{EndpointSelector, PreferredEndpointSelector},
+    },
+    protocol::{
+        consts::{headers, media_types, AGENT_CARD_PATH},
+        error::A2AError,
+        AgentCard,
+    },
+    service::{BackgroundHealth, Executor, ExecutorHandle},
+};
+
+/// Default interval between discovery refreshes
+const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Fetches documents (indexes and agent cards) by URL
+#[async_trait]
+pub trait Fetcher: Send + Sync {
+    /// Fetch the body of the document at `url`
+    async fn fetch(&self, url: &Url) -> Result<Bytes, A2AError>;
+}
+
+#[async_trait]
+impl Fetcher for reqwest::Client {
+    async fn fetch(&self, url: &Url) -> Result<Bytes, A2AError> {
+        let response = self
+            .get(url.clone())
+            .header(headers::ACCEPT, media_types::JSON)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(A2AError::Transport(format!(
+                "Fetching {} failed with status {}",
+                url,
+                response.status()
+            )));
+        }
+        Ok(response.bytes().await?)
+    }
+}
+
+/// Source of agent card URLs
+#[async_trait]
+pub trait DiscoverySource: Send + Sync {
+    /// List the card URLs currently advertised by this source
+    async fn card_urls(&self, fetcher: &dyn Fetcher) -> Result<Vec<Url>, A2AError>;
+}
+
+/// A fixed list of card URLs
+#[async_trait]
+impl DiscoverySource for Vec<Url> {
+    async fn card_urls(&self, _fetcher: &dyn Fetcher) -> Result<Vec<Url>, A2AError> {
+        Ok(self.clone())
+    }
+}
+
+/// Index document listing agent card URLs
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AgentIndex {
+    List(Vec<String>),
+    Object { agents: Vec<String> },
+}
+
+/// Discovery source reading an index endpoint
+#[derive(Debug, Clone)]
+pub struct IndexSource {
+    index_url: Url,
+}
+
+impl IndexSource {
+    /// Create a source reading the index at the given URL
+    pub fn new(index_url: Url) -> Self {
+        Self { index_url }
+    }
+}
+
+#[async_trait]
+impl DiscoverySource for IndexSource {
+    async fn card_urls(&self, fetcher: &dyn Fetcher) -> Result<Vec<Url>, A2AError> {
+        let body = fetcher.fetch(&self.index_url).await?;
+        let urls = match serde_json::from_slice(&body)? {
+            AgentIndex::List(urls) | AgentIndex::Object { agents: urls } => urls,
+        };
+
+        urls.iter()
+            .map(|url| {
+                self.index_url.join(url).map_err(|e| {
+                    A2AError::Protocol(format!("Invalid card URL '{}' in index: {}", url, e))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Target of a DNS SRV record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SrvRecord {
+    /// Host name of the target
+    pub target: String,
+
+    /// Port of the target
+    pub port: u16,
+}
+
+/// DNS lookups used by [`DnsSource`]
+#[async_trait]
+pub trait DnsResolver: Send + Sync {
+    /// Look up the TXT records of a name
+    async fn txt(&self, name: &str) -> Result<Vec<String>, A2AError>;
+
+    /// Look up the SRV records of a name
+    async fn srv(&self, _name: &str) -> Result<Vec<SrvRecord>, A2AError> {
+        Ok(Vec::new())
+    }
+}
+
+/// Discovery source reading DNS records of a domain
+pub struct DnsSource<R> {
+    domain: String,
+    resolver: R,
+}
+
+impl<R: DnsResolver> DnsSource<R> {
+    /// Create a source for the agents advertised by a domain
+    pub fn new(domain: impl Into<String>, resolver: R) -> Self {
+        Self {
+            domain: domain.into(),
+            resolver,
+        }
+    }
+}
+
+#[async_trait]
+impl<R: DnsResolver> DiscoverySource for DnsSource<R> {
+    async fn card_urls(&self, _fetcher: &dyn Fetcher) -> Result<Vec<Url>, A2AError> {
+        let mut urls = Vec::new();
+
+        for record in self.resolver.txt(&format!("_a2a.{}", self.domain)).await? {
+            let value = record.trim();
+            let value = value.strip_prefix("card=").unwrap_or(value);
+            match Url::parse(value) {
+                Ok(url) => urls.push(url),
+                Err(e) => tracing::warn!(%record, error = %e, "Ignoring invalid A2A TXT record"),
+            }
+        }
+
+        for record in self
+            .resolver
+            .srv(&format!("_a2a._tcp.{}", self.domain))
+            .await?
+        {
+            let target = record.target.trim_end_matches('.');
+            match Url::parse(&format!(
+                "https://{}:{}{}",
+                target, record.port, AGENT_CARD_PATH
+            )) {
+                Ok(url) => urls.push(url),
+                Err(e) => tracing::warn!(?record, error = %e, "Ignoring invalid A2A SRV record"),
+            }
+        }
+
+        Ok(urls)
+    }
+}
+
+/// An agent found through discovery
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveredAgent {
+    /// URL the card was fetched from
+    pub card_url: Url,
+
+    /// The agent's card
+    pub card: AgentCard,
+}
+
+impl DiscoveredAgent {
+    /// Base URL of the agent
+    ///
+    /// Uses the endpoint chosen by the default [`PreferredEndpointSelector`], falling
+    /// back to the origin of the card URL.
+    pub fn agent_url(&self) -> Url {
+        self.agent_url_with(&PreferredEndpointSelector::new())
+    }
+
+    /// Base URL of the agent, using the endpoint chosen by a selector
+    ///
+    /// Falls back to the origin of the card URL if no endpoint is selected or its URL
+    /// is invalid.
+    pub fn agent_url_with(&self, selector: &dyn EndpointSelector) -> Url {
+        selector
+            .select(&self.card)
+            .and_then(|(_, endpoint)| self.card_url.join(&endpoint.url).ok())
+            .unwrap_or_else(|| {
+                let mut url = self.card_url.clone();
+                url.set_path("/");
+                url.set_query(None);
+                url
+            })
+    }
+
+    /// Convert to a registry entry named after the agent
+    pub fn to_entry(&self) -> AgentEntry {
+        self.to_entry_with(&PreferredEndpointSelector::new())
+    }
+
+    /// Convert to a registry entry named after the agent, reached through the endpoint
+    /// chosen by a selector
+    ///
+    /// JSON-RPC endpoints are reached over WebSocket, all others over HTTP.
+    pub fn to_entry_with(&self, selector: &dyn EndpointSelector) -> AgentEntry {
+        let url = self.agent_url_with(selector);
+        let json_rpc = selector.select(&self.card).is_some_and(|(_, endpoint)| {
+            ["json-rpc", "jsonrpc"]
+                .iter()
+                .any(|binding| binding.eq_ignore_ascii_case(&endpoint.endpoint_type))
+        });
+        let transport = match json_rpc {
+            true => TransportKind::WebSocket,
+            false => TransportKind::Http,
+        };
+
+        AgentEntry {
+            name: self.card.name.clone(),
+            url,
+            transport,
+            auth: None,
+            headers: Default::default(),
+            timeout_secs: None,
+            compat: None,
+        }
+    }
+}
+
+/// Discovers agents from a set of sources and keeps a registry up to date
+pub struct AgentDiscovery {
+    fetcher: Arc<dyn Fetcher>,
+    sources: Vec<Box<dyn DiscoverySource>>,
+    refresh_interval: Duration,
+    /// Cards and entries registered by the previous refresh by name, removed once no
+    /// longer discovered
+    registered: Mutex<HashMap<String, (AgentCard, AgentEntry)>>,
+    events: ClientEvents,
+    selector: Arc<dyn EndpointSelector>,
+    executor: ExecutorHandle,
+}
+
+impl AgentDiscovery {
+    /// Create a discovery using the given fetcher and no sources
+    pub fn new(fetcher: impl Fetcher + 'static) -> Self {
+        Self {
+            fetcher: Arc::new(fetcher),
+            sources: Vec::new(),
+            refresh_interval: DEFAULT_REFRESH_INTERVAL,
+            registered: Mutex::new(HashMap::new()),
+            events: ClientEvents::default(),
+            selector: Arc::new(PreferredEndpointSelector::new()),
+            executor: ExecutorHandle::default(),
+        }
+    }
+
+    /// Add a source of card URLs
+    pub fn with_source(mut self, source: impl DiscoverySource + 'static) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    /// Set the interval between refreshes (default: 5 minutes)
+    pub fn with_refresh_interval(mut self, interval: Duration) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    /// Set the policy choosing which endpoint of each card is registered (default:
+    /// [`PreferredEndpointSelector`])
+    pub fn with_endpoint_selector(mut self, selector: impl EndpointSelector + 'static) -> Self {
+        self.selector = Arc::new(selector);
+        self
+    }
+
+    /// Publish card changes to a hub, e.g. the [`ClientConfig::events`] of the clients
+    /// routing to the discovered agents
+    ///
+    /// [`ClientConfig::events`]: crate::client::ClientConfig::events
+    pub fn with_events(mut self, events: ClientEvents) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Run periodic refreshes on an executor (default: the ambient Tokio runtime)
+    pub fn with_executor(mut self, executor: impl Executor + 'static) -> Self {
+        self.executor = ExecutorHandle::new(executor);
+        self
+    }
+
+    /// Subscribe to changes of refreshed agent cards
+    pub fn subscribe_events(&self) -> broadcast::Receiver<ClientEvent> {
+        self.events.subscribe()
+    }
+
+    /// Discover agents from all sources
+    ///
+    /// Sources and cards that cannot be fetched are logged and skipped, so one broken
+    /// agent does not hide the others.
+    pub async fn discover(&self) -> Vec<DiscoveredAgent> {
+        let mut seen = HashSet::new();
+        let mut agents = Vec::new();
+
+        for source in &self.sources {
+            let urls = match source.card_urls(self.fetcher.as_ref()).await {
+                Ok(urls) => urls,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Agent discovery source failed");
+                    continue;
+                }
+            };
+
+            for card_url in urls {
+                if !seen.insert(card_url.clone()) {
+                    continue;
+                }
+                match self.fetch_card(&card_url).await {
+                    Ok(card) => agents.push(DiscoveredAgent { card_url, card }),
+                    Err(e) => {
+                        tracing::warn!(%card_url, error = %e, "Failed to fetch agent card")
+                    }
+                }
+            }
+        }
+
+        agents
+    }
+
+    /// Discover agents and update the registry
+    ///
+    /// Discovered agents are added, or replace the agents the previous refresh
+    /// registered under the same name. Agents registered by a previous refresh that
+    /// are no longer discovered are removed. Agents registered by other means, or
+    /// replaced since the previous refresh, are left alone, and discovered agents with
+    /// their names are skipped. Cards that differ from the previous refresh are
+    /// published as [`ClientEvent::AgentCardChanged`].
+    pub async fn refresh(&self, registry: &RwLock<AgentRegistry>) {
+        let agents = self.discover().await;
+        let mut registered = self.registered.lock().await;
+        let mut current = HashMap::new();
+
+        let mut registry = registry.write().await;
+        let owned = |registry: &AgentRegistry, name: &str| {
+            registered
+                .get(name)
+                .is_some_and(|(_, entry)| registry.get(name) == Some(entry))
+        };
+        for agent in agents {
+            let entry = agent.to_entry_with(self.selector.as_ref());
+            if current.contains_key(&entry.name) {
+                tracing::warn!(name = %entry.name, "Ignoring agent with duplicate name");
+                continue;
+            }
+            if registry.get(&entry.name).is_some() && !owned(&registry, &entry.name) {
+                tracing::warn!(
+                    name = %entry.name,
+                    card_url = %agent.card_url,
+                    "Ignoring discovered agent with the name of a registered agent"
+                );
+                continue;
+            }
+            if let Err(e) = registry.upsert(entry.clone()) {
+                tracing::warn!(card_url = %agent.card_url, error = %e, "Ignoring discovered agent");
+                continue;
+            }
+
+            if let Some((previous, _)) = registered.get(&agent.card.name) {
+                let delta = previous.diff(&agent.card);
+                if !delta.is_empty() {
+                    self.events.emit(ClientEvent::AgentCardChanged {
+                        agent: agent.card.name.clone(),
+                        delta,
+                    });
+                }
+            }
+            current.insert(agent.card.name.clone(), (agent.card, entry));
+        }
+
+        let stale: Vec<_> = registered
+            .keys()
+            .filter(|name| !current.contains_key(*name) && owned(&registry, name))
+            .cloned()
+            .collect();
+        for name in stale {
+            registry.remove(&name);
+        }
+        *registered = current;
+    }
+
+    /// Refresh the registry now and then periodically in a background task
+    ///
+    /// The refreshes run on the discovery's executor, restarted if a refresh panics.
+    /// The returned handle is a Tokio task that completes when the refreshes stop;
+    /// aborting it stops them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside a Tokio runtime.
+    pub fn spawn_refresh(self: Arc<Self>, registry: Arc<RwLock<AgentRegistry>>) -> JoinHandle<()> {
+        // Dropped with the task factory, once the supervised task stopped for good
+        let (running, stopped) = oneshot::channel::<()>();
+
+        let executor = self.executor.clone();
+        let refresh = executor.spawn_restarting("a2a-discovery-refresh", move || {
+            let _running = &running;
+            let discovery = self.clone();
+            let registry = registry.clone();
+            async move {
+                let mut interval = tokio::time::interval(discovery.refresh_interval);
+                loop {
+                    interval.tick().await;
+                    discovery.refresh(&registry).await;
+                }
+            }
+        });
+
+        let refresh = AbortOnDrop(refresh);
+        tokio::spawn(async move {
+            let _refresh = refresh;
+            let _ = stopped.await;
+        })
+    }
+
+    /// Get the health of the refresh task
+    pub fn background_health(&self) -> BackgroundHealth {
+        self.executor.health()
+    }
+
+    async fn fetch_card(&self, card_url: &Url) -> Result<AgentCard, A2AError> {
+        let body = self.fetcher.fetch(card_url).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+}
+
+/// Handle aborting a background task when dropped
+struct AbortOnDrop(AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+impl std::fmt::Debug for AgentDiscovery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AgentDiscovery")
+            .field("sources", &self.sources.len())
+            .field("refresh_interval", &self.refresh_interval)
+            .field("selector", &self.selector)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::protocol::{
+        agent::{AgentSkill, EndpointConfig},
+        AgentCapabilities,
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct StaticFetcher(std::sync::Mutex<HashMap<String, Bytes>>);
+
+    impl StaticFetcher {
+        fn set(&self, url: &str, body: impl serde::Serialize) {
+            let body = Bytes::from(serde_json::to_vec(&body).unwrap());
+            self.0.lock().unwrap().insert(url.to_string(), body);
+        }
+    }
+
+    #[async_trait]
+    impl Fetcher for Arc<StaticFetcher> {
+        async fn fetch(&self, url: &Url) -> Result<Bytes, A2AError> {
+            self.0
+                .lock()
+                .unwrap()
+                .get(url.as_str())
+                .cloned()
+                .ok_or_else(|| A2AError::Transport(format!("Not found: {}", url)))
+        }
+    }
+
+    struct StaticDns;
+
+    #[async_trait]
+    impl DnsResolver for StaticDns {
+        async fn txt(&self, name: &str) -> Result<Vec<String>, A2AError> {
+            assert_eq!(name, "_a2a.example.com");
+            Ok(vec![
+                "card=https://billing.example.com/.well-known/agent-card.json".into(),
+                "not a url".into(),
+            ])
+        }
+
+        async fn srv(&self, _name: &str) -> Result<Vec<SrvRecord>, A2AError> {
+            Ok(vec![SrvRecord {
+                target: "search.example.com.".into(),
+                port: 8443,
+            }])
+        }
+    }
+
+    fn card(name: &str) -> AgentCard {
+        AgentCard::new(name, "Test agent", AgentCapabilities::default())
+    }
+
+    #[tokio::test]
+    async fn test_index_refresh() {
+        let fetcher = Arc::new(StaticFetcher::default());
+        fetcher.set(
+            "https://example.com/agents.json",
+            ["/billing/card.json", "https://other.example.com/card.json"],
+        );
+        fetcher.set(
+            "https://example.com/billing/card.json",
+            card("billing").with_endpoint(
+                "rpc",
+                EndpointConfig::new("wss://billing.example.com/ws", "json-rpc"),
+            ),
+        );
+        fetcher.set("https://other.example.com/card.json", card("other"));
+
+        let discovery = AgentDiscovery::new(fetcher.clone()).with_source(IndexSource::new(
+            "https://example.com/agents.json".parse().unwrap(),
+        ));
+        let registry = RwLock::new(AgentRegistry::default());
+
+        discovery.refresh(&registry).await;
+        {
+            let registry = registry.read().await;
+            assert_eq!(registry.len(), 2);
+            let billing = registry.get("billing").unwrap();
+            assert_eq!(billing.transport, TransportKind::WebSocket);
+            assert_eq!(billing.url.as_str(), "wss://billing.example.com/ws");
+            assert_eq!(
+                registry.get("other").unwrap().url.as_str(),
+                "https://other.example.com/"
+            );
+        }
+
+        // Agents that disappear from the index are removed on the next refresh
+        fetcher.set("https://example.com/agents.json", ["/billing/card.json"]);
+        discovery.refresh(&registry).await;
+        assert!(registry.read().await.get("other").is_none());
+
+        // Agents registered by other means are neither replaced nor removed
+        let manual = AgentEntry {
+            name: "other".to_string(),
+            url: "https://manual.example.com".parse().unwrap(),
+            transport: TransportKind::Http,
+            auth: None,
+            headers: HashMap::new(),
+            timeout_secs: None,
+            compat: None,
+        };
+        registry.write().await.register(manual.clone()).unwrap();
+        fetcher.set(
+            "https://example.com/agents.json",
+            ["/billing/card.json", "https://other.example.com/card.json"],
+        );
+        discovery.refresh(&registry).await;
+        fetcher.set("https://example.com/agents.json", ["/billing/card.json"]);
+        discovery.refresh(&registry).await;
+        assert_eq!(registry.read().await.get("other"), Some(&manual));
+    }
+
+    #[test]
+    fn test_entry_transport_follows_binding() {
+        let agent = |endpoint| DiscoveredAgent {
+            card_url: "https://example.com/card.json".parse().unwrap(),
+            card: card("agent").with_endpoint("main", endpoint),
+        };
+
+        let entry = agent(EndpointConfig::new("wss://example.com/ws", "json-rpc")).to_entry();
+        assert_eq!(entry.transport, TransportKind::WebSocket);
+        let entry = agent(EndpointConfig::new("https://example.com/a2a", "http+json")).to_entry();
+        assert_eq!(entry.transport, TransportKind::Http);
+
+        // JSON-RPC over HTTP has no transport, so the registry rejects the entry
+        let entry = agent(EndpointConfig::new("https://example.com/rpc", "json-rpc")).to_entry();
+        assert_eq!(entry.transport, TransportKind::WebSocket);
+        assert!(AgentRegistry::default().upsert(entry).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_spawn_refresh() {
+        use crate::service::TaskState;
+
+        let fetcher = Arc::new(StaticFetcher::default());
+        fetcher.set("https://example.com/agents.json", ["/card.json"]);
+        fetcher.set("https://example.com/card.json", card("billing"));
+        let discovery = Arc::new(
+            AgentDiscovery::new(fetcher)
+                .with_source(IndexSource::new(
+                    "https://example.com/agents.json".parse().unwrap(),
+                ))
+                .with_refresh_interval(Duration::from_millis(10)),
+        );
+        let registry = Arc::new(RwLock::new(AgentRegistry::default()));
+
+        let handle = discovery.clone().spawn_refresh(registry.clone());
+        while registry.read().await.get("billing").is_none() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        // Aborting the handle stops the refreshes
+        handle.abort();
+        assert!(handle.await.unwrap_err().is_cancelled());
+        let state = || {
+            discovery
+                .background_health()
+                .task("a2a-discovery-refresh")
+                .map(|task| task.state.clone())
+        };
+        while state() == Some(TaskState::Running) {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(state(), Some(TaskState::Aborted));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_publishes_card_changes() {
+        let fetcher = Arc::new(StaticFetcher::default());
+        fetcher.set("https://example.com/card.json", card("billing"));
+
+        let discovery = AgentDiscovery::new(fetcher.clone())
+            .with_source(vec![Url::parse("https://example.com/card.json").unwrap()]);
+        let mut events = discovery.subscribe_events();
+        let registry = RwLock::new(AgentRegistry::default());
+
+        // Neither the first sighting nor an unchanged card is a change
+        discovery.refresh(&registry).await;
+        discovery.refresh(&registry).await;
+        assert!(events.try_recv().is_err());
+
+        fetcher.set(
+            "https://example.com/card.json",
+            card("billing").with_skill(AgentSkill::new("refund", "Refund")),
+        );
+        discovery.refresh(&registry).await;
+        match events.try_recv().unwrap() {
+            ClientEvent::AgentCardChanged { agent, delta } => {
+                assert_eq!(agent, "billing");
+                assert_eq!(delta.added_skills.len(), 1);
+                assert!(!delta.endpoints_changed());
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dns_source() {
+        let source = DnsSource::new("example.com", StaticDns);
+        let fetcher = Arc::new(StaticFetcher::default());
+        let urls = source.card_urls(&fetcher).await.unwrap();
+
+        assert_eq!(
+            urls,
+            vec![
+                Url::parse("https://billing.example.com/.well-known/agent-card.json").unwrap(),
+                Url::parse("https://search.example.com:8443/.well-known/agent-card.json").unwrap(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Observable client events
+//!
+//! Retries and stream resumptions happen transparently to callers. Each one is
+//! published as a [`ClientEvent`] so operators can spot degraded agents. Changes to
+//! refreshed agent cards are published too, so dependent routing can react, and so are
+//! changes to the transport's [`ConnectionState`], for UIs and health endpoints.
+//!
+//! Connection states are derived from request outcomes. HTTP connection pool
+//! exhaustion and circuit breaker state are not reported: `reqwest` does not expose
+//! its pool, and the client has no circuit breaker.
+
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+
+use crate::protocol::{AgentCardDelta, OperationKind};
+
+/// Default number of events buffered per subscriber
+const DEFAULT_EVENT_CAPACITY: usize = 64;
+
+/// Event describing a resilience decision taken by the client or a change to an agent
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ClientEvent {
+    /// A failed request is about to be retried
+    Retry {
+        /// Kind of the retried operation
+        operation: OperationKind,
+        /// Number of the upcoming retry, starting at 1
+        attempt: u32,
+        /// Delay before the retry
+        delay: Duration,
+        /// Error that caused the retry
+        error: String,
+    },
+
+    /// A request failed after exhausting its retries
+    RetriesExhausted {
+        /// Kind of the failed operation
+        operation: OperationKind,
+        /// Total number of attempts made
+        attempts: u32,
+        /// Error of the last attempt
+        error: String,
+    },
+
+    /// A broken event stream was resumed by resubscribing to its task
+    StreamResumed {
+        /// Task whose events are streamed
+        task_id: String,
+        /// Number of the resubscription, starting at 1
+        attempt: u32,
+        /// Error that broke the stream, or `None` if it closed early
+        error: Option<String>,
+    },
+
+    /// The transport's connection to the agent changed state
+    ConnectionChanged {
+        /// Base URL of the transport
+        agent: String,
+        /// New state of the connection
+        state: ConnectionState,
+        /// What caused the change, if it was a failure
+        reason: Option<String>,
+    },
+
+    /// A refreshed agent card differs from the previously known one
+    AgentCardChanged {
+        /// Name of the agent, as advertised by the card
+        agent: String,
+        /// Changes from the previous card
+        delta: AgentCardDelta,
+    },
+}
+
+/// State of a transport's connection to an agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConnectionState {
+    /// Requests reach the agent
+    Connected,
+
+    /// The connection was lost and is being established again
+    Reconnecting,
+
+    /// The agent is reachable but failing requests, e.g. answering `503` or timing out
+    Degraded,
+
+    /// The agent cannot be reached
+    Disconnected,
+}
+
+/// Broadcast hub for [`ClientEvent`]s
+///
+/// Cloning the hub shares the underlying channel, so layers and the client can publish
+/// to the same subscribers. Events are also logged through `tracing`.
+#[derive(Debug, Clone)]
+pub struct ClientEvents {
+    sender: broadcast::Sender<ClientEvent>,
+}
+
+impl ClientEvents {
+    /// Create a hub buffering up to `capacity` events per subscriber
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    /// Subscribe to events emitted after this call
+    pub fn subscribe(&self) -> broadcast::Receiver<ClientEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Publish an event to all subscribers
+    pub fn emit(&self, event: ClientEvent) {
+        tracing::info!(?event, "Client event");
+
+        // Sending only fails when nobody is subscribed, which is fine
+        let _ = self.sender.send(event);
+    }
+}
+
+impl Default for ClientEvents {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_CAPACITY)
+    }
+}
@@ -0,0 +1,435 @@
+//! Listing tasks across the agents of a registry
+//!
+//! [`AgentRegistry::list_tasks_all_agents`] asks every registered agent for tasks
+//! concurrently and merges them, newest task first. The page's cursor carries each
+//! agent's position, i.e. its page token and how many tasks of that page were
+//! returned, so passing it back continues every agent where it left off:
+//!
+//! ```rust,no_run
+//! use tower_a2a::client::{federation::FederatedTaskFilter, AgentRegistry};
+//! use tower_a2a::prelude::*;
+//!
+//! # async fn example(registry: AgentRegistry) -> Result<(), A2AError> {
+//! let mut filter = FederatedTaskFilter::new().with_page_size(20);
+//! loop {
+//!     let page = registry.list_tasks_all_agents(&filter).await?;
+//!     for task in &page.tasks {
+//!         println!("{}: {}", task.agent, task.task.id);
+//!     }
+//!     let Some(cursor) = page.next_cursor else {
+//!         break;
+//!     };
+//!     filter = filter.with_cursor(cursor);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use base64::{engine::general_purpose, Engine as _};
+use futures::future;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::registry::{AgentRegistry, TransportKind},
+    protocol::{error::A2AError, task::TaskListResponse, Task, TaskStatus},
+};
+
+/// Criteria and position of a federated task listing
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FederatedTaskFilter {
+    status: Option<TaskStatus>,
+    page_size: Option<u32>,
+    cursor: Option<String>,
+}
+
+impl FederatedTaskFilter {
+    /// Create a filter for the first page of all tasks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// List only tasks with a status
+    pub fn with_status(mut self, status: TaskStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Return at most this many tasks per page (default: 50)
+    pub fn with_page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Continue from the [`next_cursor`](FederatedTaskPage::next_cursor) of a page
+    pub fn with_cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+}
+
+/// A task and the name of the agent it belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentTask {
+    /// Name of the agent in the registry
+    pub agent: String,
+
+    /// The task
+    pub task: Task,
+}
+
+/// A page of tasks merged from several agents
+#[derive(Debug, Default)]
+pub struct FederatedTaskPage {
+    /// Up to the page size of tasks of all agents, newest first
+    pub tasks: Vec<AgentTask>,
+
+    /// Cursor of the next page, `None` once every agent is exhausted
+    pub next_cursor: Option<String>,
+
+    /// Agents whose listing failed, by name
+    ///
+    /// They keep their position in the next cursor, so the next page retries them.
+    pub errors: HashMap<String, A2AError>,
+}
+
+/// Number of tasks of a page when the filter sets none
+const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// Where listing an agent's tasks continues
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Position {
+    /// Token of the agent's page to fetch, `None` for its first page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+
+    /// Number of tasks of that page already returned
+    #[serde(default, skip_serializing_if = "is_zero")]
+    skip: usize,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+/// Positions of the agents with more tasks, by agent name
+///
+/// Encoded as URL-safe base64 of a JSON object, so it is opaque to callers.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Cursor(BTreeMap<String, Position>);
+
+impl Cursor {
+    fn encode(&self) -> Option<String> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let json = serde_json::to_vec(&self.0).ok()?;
+        Some(general_purpose::URL_SAFE_NO_PAD.encode(json))
+    }
+
+    fn decode(cursor: &str) -> Result<Self, A2AError> {
+        general_purpose::URL_SAFE_NO_PAD
+            .decode(cursor)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .map(Self)
+            .ok_or_else(|| A2AError::Validation("Invalid task cursor".into()))
+    }
+}
+
+/// The fetched, not yet returned tasks of an agent
+struct Source {
+    agent: String,
+    position: Position,
+    tasks: VecDeque<Task>,
+    next_token: Option<String>,
+}
+
+impl Source {
+    /// Get the position after the returned tasks, or `None` once the agent is
+    /// exhausted
+    fn position(&self) -> Option<Position> {
+        if !self.tasks.is_empty() {
+            return Some(self.position.clone());
+        }
+        self.next_token.clone().map(|token| Position {
+            token: Some(token),
+            skip: 0,
+        })
+    }
+}
+
+impl AgentRegistry {
+    /// List tasks across all agents, querying them concurrently
+    ///
+    /// The first page asks every agent; later pages ask the agents named in the
+    /// cursor, skipping agents removed from the registry meanwhile. Tasks are merged
+    /// by creation time, newest first, and a page holds up to the filter's page size
+    /// (default: 50) of them, so later pages only hold older tasks. This assumes each
+    /// agent lists its own tasks newest first.
+    ///
+    /// Agents that fail are reported in [`FederatedTaskPage::errors`] instead of
+    /// failing the whole listing, and keep their position in the cursor, so the next
+    /// page tries them again.
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Validation` if the filter's cursor is malformed
+    pub async fn list_tasks_all_agents(
+        &self,
+        filter: &FederatedTaskFilter,
+    ) -> Result<FederatedTaskPage, A2AError> {
+        let positions = match &filter.cursor {
+            Some(cursor) => Cursor::decode(cursor)?.0,
+            None => self
+                .iter()
+                .map(|entry| (entry.name.clone(), Position::default()))
+                .collect(),
+        };
+        let page_size = filter.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+
+        let listings = positions
+            .into_iter()
+            .filter(|(name, _)| self.get(name).is_some())
+            .map(|(agent, position)| async move {
+                let listing = self
+                    .list_agent_tasks(&agent, filter, page_size, position.token.clone())
+                    .await;
+                (agent, position, listing)
+            });
+
+        let mut page = FederatedTaskPage::default();
+        let mut next = Cursor::default();
+        let mut sources = Vec::new();
+        for (agent, position, listing) in future::join_all(listings).await {
+            match listing {
+                Ok(listing) => {
+                    let mut tasks: VecDeque<_> = listing.tasks.into();
+                    tasks.drain(..position.skip.min(tasks.len()));
+                    sources.push(Source {
+                        agent,
+                        position,
+                        tasks,
+                        next_token: listing.next_token.filter(|token| !token.is_empty()),
+                    });
+                }
+                Err(e) => {
+                    tracing::warn!(%agent, error = %e, "Failed to list tasks of agent");
+                    page.errors.insert(agent.clone(), e);
+                    next.0.insert(agent, position);
+                }
+            }
+        }
+
+        while page.tasks.len() < page_size as usize {
+            // An agent's next task is only known once its next page is fetched
+            for source in &mut sources {
+                while source.tasks.is_empty() {
+                    let Some(token) = source.next_token.take() else {
+                        break;
+                    };
+                    match self
+                        .list_agent_tasks(&source.agent, filter, page_size, Some(token.clone()))
+                        .await
+                    {
+                        Ok(listing) => {
+                            source.position = Position {
+                                token: Some(token),
+                                skip: 0,
+                            };
+                            source.tasks = listing.tasks.into();
+                            source.next_token =
+                                listing.next_token.filter(|token| !token.is_empty());
+                        }
+                        Err(e) => {
+                            let agent = &source.agent;
+                            tracing::warn!(%agent, error = %e, "Failed to list tasks of agent");
+                            page.errors.insert(agent.clone(), e);
+                            next.0.insert(
+                                agent.clone(),
+                                Position {
+                                    token: Some(token),
+                                    skip: 0,
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+
+            let newest = sources
+                .iter()
+                .enumerate()
+                .filter_map(|(i, source)| Some((i, source.tasks.front()?)))
+                .min_by(|(_, a), (_, b)| newest_first(a, b))
+                .map(|(i, _)| i);
+            let Some(i) = newest else {
+                break;
+            };
+            let source = &mut sources[i];
+            let task = source.tasks.pop_front().expect("source has a task");
+            source.position.skip += 1;
+            page.tasks.push(AgentTask {
+                agent: source.agent.clone(),
+                task,
+            });
+        }
+
+        for source in &sources {
+            if let Some(position) = source.position() {
+                next.0.insert(source.agent.clone(), position);
+            }
+        }
+        page.next_cursor = next.encode();
+        Ok(page)
+    }
+
+    /// List one page of an agent's tasks
+    async fn list_agent_tasks(
+        &self,
+        name: &str,
+        filter: &FederatedTaskFilter,
+        page_size: u32,
+        token: Option<String>,
+    ) -> Result<TaskListResponse, A2AError> {
+        let status = filter.status;
+        match self.get(name).map(|entry| entry.transport) {
+            Some(TransportKind::WebSocket) => {
+                self.websocket_client(name)?
+                    .list_tasks_page(status, Some(page_size), token)
+                    .await
+            }
+            _ => {
+                self.http_client(name)?
+                    .list_tasks_page(status, Some(page_size), token)
+                    .await
+            }
+        }
+    }
+}
+
+/// Order tasks newest first, then by ID
+fn newest_first(a: &Task, b: &Task) -> std::cmp::Ordering {
+    b.created_at
+        .cmp(&a.created_at)
+        .then_with(|| a.id.cmp(&b.id))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use chrono::{Duration, Utc};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use url::Url;
+
+    use super::*;
+    use crate::{
+        client::registry::{AgentEntry, AgentManifest},
+        protocol::Message,
+    };
+
+    /// Serve two pages of tasks, created at the given minutes ago, recording requests
+    async fn serve_tasks(
+        name: &'static str,
+        pages: [&'static [i64]; 2],
+    ) -> (AgentEntry, Arc<Mutex<Vec<String>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0; 8192];
+                let n = stream.read(&mut buf).await.unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                let second = request.contains(&format!("{}-page-2", name));
+                recorded.lock().unwrap().push(request);
+
+                let minutes = pages[usize::from(second)];
+                let tasks: Vec<_> = minutes
+                    .iter()
+                    .map(|minutes| {
+                        let mut task =
+                            Task::new(format!("{}-{}", name, minutes), Message::user("Test"));
+                        task.created_at = Utc::now() - Duration::minutes(*minutes);
+                        task
+                    })
+                    .collect();
+                let next_token = (!second).then(|| format!("{}-page-2", name));
+                let body = serde_json::to_string(&TaskListResponse {
+                    total: tasks.len(),
+                    tasks,
+                    next_token,
+                })
+                .unwrap();
+
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let entry = serde_json::from_value(serde_json::json!({"name": name, "url": url})).unwrap();
+        (entry, requests)
+    }
+
+    #[tokio::test]
+    async fn test_list_tasks_all_agents() {
+        let (billing, _) = serve_tasks("billing", [&[1, 5], &[9]]).await;
+        let (search, search_requests) = serve_tasks("search", [&[3], &[]]).await;
+
+        // An agent that refuses connections
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let down: AgentEntry =
+            serde_json::from_value(serde_json::json!({"name": "down", "url": url})).unwrap();
+
+        let manifest = AgentManifest {
+            agents: vec![billing, search, down],
+            compat_profiles: Vec::new(),
+        };
+        let registry = AgentRegistry::from_manifest(manifest).unwrap();
+        let ids = |page: &FederatedTaskPage| -> Vec<String> {
+            page.tasks.iter().map(|task| task.task.id.clone()).collect()
+        };
+
+        let filter = FederatedTaskFilter::new().with_page_size(2);
+        let page = registry.list_tasks_all_agents(&filter).await.unwrap();
+        assert_eq!(ids(&page), ["billing-1", "search-3"]);
+
+        // The failed agent keeps its position
+        assert!(page.errors.contains_key("down"));
+        let cursor = Cursor::decode(page.next_cursor.as_deref().unwrap()).unwrap();
+        assert_eq!(cursor.0["down"], Position::default());
+        assert_eq!(
+            cursor.0["billing"],
+            Position {
+                token: None,
+                skip: 1
+            }
+        );
+
+        let filter = filter.with_cursor(page.next_cursor.unwrap());
+        let page = registry.list_tasks_all_agents(&filter).await.unwrap();
+        assert_eq!(ids(&page), ["billing-5", "billing-9"]);
+        assert_eq!(search_requests.lock().unwrap().len(), 2);
+        assert!(search_requests.lock().unwrap()[1].contains("page_token=search-page-2"));
+        assert!(page.errors.contains_key("down"));
+        let cursor = Cursor::decode(page.next_cursor.as_deref().unwrap()).unwrap();
+        assert_eq!(cursor.0.keys().collect::<Vec<_>>(), ["down"]);
+
+        let filter = FederatedTaskFilter::new().with_cursor("not a cursor");
+        assert!(matches!(
+            registry.list_tasks_all_agents(&filter).await,
+            Err(A2AError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,332 @@
+//! Key management for signing and verification
+//!
+//! Card verification, push notification JWT validation, JWT authentication, and
+//! message signing all get their keys from a [`KeyProvider`]. Verification keys are
+//! published as JSON Web Key Sets (RFC 7517); [`JwksKeyProvider`] fetches them over
+//! HTTPS, caches them, and refetches when a key ID it has not seen appears, so signers
+//! can rotate keys without coordination:
+//!
+//! ```rust,no_run
+//! use tower_a2a::client::keys::{JwksKeyProvider, KeyProvider};
+//!
+//! # async fn example() -> Result<(), tower_a2a::protocol::A2AError> {
+//! let keys = JwksKeyProvider::new(reqwest::Client::new());
+//! let jwks_url = "https://agent.example.com/.well-known/jwks.json".parse().unwrap();
+//! let key = keys.verification_key(&jwks_url, "2026-10").await?;
+//! println!("{} key {:?}", key.kty, key.kid);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use url::Url;
+
+use crate::{client::discovery::Fetcher, protocol::error::A2AError};
+
+/// Default time a fetched key set is reused
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(3600);
+
+/// Default minimum time between refetches triggered by unknown key IDs
+const DEFAULT_MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A JSON Web Key (RFC 7517)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Jwk {
+    /// Key type, e.g. `RSA`, `EC`, or `OKP`
+    pub kty: String,
+
+    /// Key ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+
+    /// Algorithm the key is intended for, e.g. `ES256`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+
+    /// Intended use, `sig` or `enc`
+    #[serde(default, rename = "use", skip_serializing_if = "Option::is_none")]
+    pub key_use: Option<String>,
+
+    /// Type-specific key parameters, e.g. `crv`, `x`, and `y` for EC keys
+    #[serde(flatten)]
+    pub params: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Jwk {
+    /// Check whether the key may be used for signatures
+    pub fn is_signing_key(&self) -> bool {
+        self.key_use
+            .as_deref()
+            .is_none_or(|key_use| key_use == "sig")
+    }
+}
+
+/// A JSON Web Key Set
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct JwkSet {
+    /// The keys
+    #[serde(default)]
+    pub keys: Vec<Jwk>,
+}
+
+impl JwkSet {
+    /// Find a signature key by ID
+    pub fn find(&self, kid: &str) -> Option<&Jwk> {
+        self.keys
+            .iter()
+            .find(|key| key.kid.as_deref() == Some(kid) && key.is_signing_key())
+    }
+}
+
+/// Source of signing and verification keys
+#[async_trait]
+pub trait KeyProvider: Send + Sync {
+    /// Get the key used to sign outgoing messages, cards, and notifications
+    ///
+    /// The default implementation has no signing key.
+    async fn signing_key(&self) -> Result<Jwk, A2AError> {
+        Err(A2AError::Auth("No signing key configured".into()))
+    }
+
+    /// Get the key set published at a URL
+    async fn jwks(&self, url: &Url) -> Result<JwkSet, A2AError>;
+
+    /// Get the verification key with an ID from the key set published at a URL
+    async fn verification_key(&self, url: &Url, kid: &str) -> Result<Jwk, A2AError> {
+        self.jwks(url)
+            .await?
+            .find(kid)
+            .cloned()
+            .ok_or_else(|| A2AError::Auth(format!("Unknown key '{}' for {}", kid, url)))
+    }
+}
+
+#[async_trait]
+impl<P: KeyProvider + ?Sized> KeyProvider for Arc<P> {
+    async fn signing_key(&self) -> Result<Jwk, A2AError> {
+        (**self).signing_key().await
+    }
+
+    async fn jwks(&self, url: &Url) -> Result<JwkSet, A2AError> {
+        (**self).jwks(url).await
+    }
+
+    async fn verification_key(&self, url: &Url, kid: &str) -> Result<Jwk, A2AError> {
+        (**self).verification_key(url, kid).await
+    }
+}
+
+/// A fetched key set
+#[derive(Debug, Clone)]
+struct CachedKeys {
+    keys: JwkSet,
+    fetched_at: Instant,
+}
+
+/// Key provider fetching key sets over HTTPS
+///
+/// Key sets are cached for a TTL (default: 1 hour). A lookup for a key ID missing
+/// from the cached set refetches it, at most once per minimum refresh interval
+/// (default: 1 minute) so unknown IDs cannot be used to flood the publisher.
+pub struct JwksKeyProvider {
+    fetcher: Arc<dyn Fetcher>,
+    signing_key: Option<Jwk>,
+    cache_ttl: Duration,
+    min_refresh_interval: Duration,
+    cache: Mutex<HashMap<Url, CachedKeys>>,
+}
+
+impl JwksKeyProvider {
+    /// Create a provider using the given fetcher and no signing key
+    pub fn new(fetcher: impl Fetcher + 'static) -> Self {
+        Self {
+            fetcher: Arc::new(fetcher),
+            signing_key: None,
+            cache_ttl: DEFAULT_CACHE_TTL,
+            min_refresh_interval: DEFAULT_MIN_REFRESH_INTERVAL,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the key used for signing
+    pub fn with_signing_key(mut self, key: Jwk) -> Self {
+        self.signing_key = Some(key);
+        self
+    }
+
+    /// Set how long fetched key sets are reused (default: 1 hour)
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Set the minimum time between refetches for unknown key IDs (default: 1 minute)
+    pub fn with_min_refresh_interval(mut self, interval: Duration) -> Self {
+        self.min_refresh_interval = interval;
+        self
+    }
+
+    /// Fetch a key set, bypassing the cache
+    async fn fetch(&self, url: &Url) -> Result<JwkSet, A2AError> {
+        if url.scheme() != "https" {
+            return Err(A2AError::Validation(format!(
+                "Key sets must be fetched over HTTPS: {}",
+                url
+            )));
+        }
+
+        let body = self.fetcher.fetch(url).await?;
+        let keys: JwkSet = serde_json::from_slice(&body)
+            .map_err(|e| A2AError::Protocol(format!("Invalid key set at {}: {}", url, e)))?;
+
+        self.cache.lock().await.insert(
+            url.clone(),
+            CachedKeys {
+                keys: keys.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+        tracing::debug!(url = %url, keys = keys.keys.len(), "Fetched key set");
+        Ok(keys)
+    }
+
+    /// Get a cached key set with the time it was fetched
+    async fn cached(&self, url: &Url) -> Option<CachedKeys> {
+        self.cache.lock().await.get(url).cloned()
+    }
+}
+
+#[async_trait]
+impl KeyProvider for JwksKeyProvider {
+    async fn signing_key(&self) -> Result<Jwk, A2AError> {
+        self.signing_key
+            .clone()
+            .ok_or_else(|| A2AError::Auth("No signing key configured".into()))
+    }
+
+    async fn jwks(&self, url: &Url) -> Result<JwkSet, A2AError> {
+        match self.cached(url).await {
+            Some(cached) if cached.fetched_at.elapsed() < self.cache_ttl => Ok(cached.keys),
+            _ => self.fetch(url).await,
+        }
+    }
+
+    async fn verification_key(&self, url: &Url, kid: &str) -> Result<Jwk, A2AError> {
+        let mut keys = self.jwks(url).await?;
+
+        // The signer may have rotated to a key published after the set was cached
+        if keys.find(kid).is_none() {
+            let stale = self
+                .cached(url)
+                .await
+                .is_none_or(|cached| cached.fetched_at.elapsed() >= self.min_refresh_interval);
+            if stale {
+                keys = self.fetch(url).await?;
+            }
+        }
+
+        keys.find(kid)
+            .cloned()
+            .ok_or_else(|| A2AError::Auth(format!("Unknown key '{}' for {}", kid, url)))
+    }
+}
+
+impl std::fmt::Debug for JwksKeyProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JwksKeyProvider")
+            .field(
+                "signing_key",
+                &self.signing_key.as_ref().map(|key| &key.kid),
+            )
+            .field("cache_ttl", &self.cache_ttl)
+            .field("min_refresh_interval", &self.min_refresh_interval)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use bytes::Bytes;
+    use serde_json::json;
+
+    use super::*;
+
+    /// Serves a key set containing the key IDs currently listed
+    #[derive(Default)]
+    struct RotatingFetcher {
+        kids: std::sync::Mutex<Vec<&'static str>>,
+        fetches: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Fetcher for Arc<RotatingFetcher> {
+        async fn fetch(&self, _url: &Url) -> Result<Bytes, A2AError> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            let keys: Vec<_> = self
+                .kids
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|kid| json!({"kty": "OKP", "crv": "Ed25519", "x": "AAAA", "kid": kid, "use": "sig"}))
+                .collect();
+            Ok(Bytes::from(
+                serde_json::to_vec(&json!({ "keys": keys })).unwrap(),
+            ))
+        }
+    }
+
+    fn url() -> Url {
+        "https://agent.example.com/.well-known/jwks.json"
+            .parse()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_caching_and_rotation() {
+        let fetcher = Arc::new(RotatingFetcher::default());
+        fetcher.kids.lock().unwrap().push("k1");
+        let keys = JwksKeyProvider::new(fetcher.clone()).with_min_refresh_interval(Duration::ZERO);
+
+        let key = keys.verification_key(&url(), "k1").await.unwrap();
+        assert_eq!(key.params["crv"], "Ed25519");
+        keys.verification_key(&url(), "k1").await.unwrap();
+        assert_eq!(fetcher.fetches.load(Ordering::SeqCst), 1);
+
+        // A new key ID triggers a refetch
+        fetcher.kids.lock().unwrap().push("k2");
+        keys.verification_key(&url(), "k2").await.unwrap();
+        assert_eq!(fetcher.fetches.load(Ordering::SeqCst), 2);
+
+        assert!(matches!(
+            keys.verification_key(&url(), "k3").await,
+            Err(A2AError::Auth(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_is_rate_limited() {
+        let fetcher = Arc::new(RotatingFetcher::default());
+        let keys = JwksKeyProvider::new(fetcher.clone());
+
+        assert!(keys.verification_key(&url(), "k1").await.is_err());
+        assert!(keys.verification_key(&url(), "k2").await.is_err());
+        assert_eq!(fetcher.fetches.load(Ordering::SeqCst), 1);
+
+        assert!(keys.signing_key().await.is_err());
+        let http = "http://agent.example.com/jwks.json".parse().unwrap();
+        assert!(matches!(
+            keys.jwks(&http).await,
+            Err(A2AError::Validation(_))
+        ));
+    }
+}
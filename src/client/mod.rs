@@ -1,9 +1,49 @@
 //! High-level client API for A2A protocol
 
 pub mod agent;
+pub mod balance;
 pub mod builder;
+pub mod card_cache;
+pub mod compat;
 pub mod config;
+pub mod directory;
+pub mod discovery;
+pub mod events;
+pub mod federation;
+pub mod keys;
+pub mod proxy;
+pub mod registry;
+pub mod scoped;
+pub mod selector;
+pub mod session;
+pub mod stream;
+pub mod text;
+pub mod transcript;
+pub mod usage;
+pub mod webhook;
 
 pub use agent::AgentClient;
-pub use builder::A2AClientBuilder;
-pub use config::ClientConfig;
+pub use balance::AgentService;
+pub use builder::{A2AClientBuilder, ClientStack};
+pub use card_cache::AgentCardCache;
+pub use compat::CompatProfile;
+pub use config::{
+    ClientConfig, HeaderProvider, IdMode, IdOptions, Profile, RequestOptions, StreamDropPolicy,
+};
+pub use directory::{AgentQuery, Directory};
+pub use discovery::{AgentDiscovery, DiscoverySource, DnsSource, IndexSource};
+pub use events::{ClientEvent, ClientEvents, ConnectionState};
+pub use federation::{AgentTask, FederatedTaskFilter, FederatedTaskPage};
+pub use keys::{JwksKeyProvider, KeyProvider};
+pub use proxy::{ProxyBody, ProxyService};
+pub use registry::{AgentManifest, AgentRegistry, HttpAgentService};
+pub use scoped::ScopedClient;
+pub use selector::{EndpointSelector, PreferredEndpointSelector};
+pub use session::{Conversation, FileSessionStore, InMemorySessionStore, SessionStore};
+pub use stream::{
+    AbandonGuard, DedupPolicy, EventStreamExt, OrderingPolicy, SequenceConfig, SequenceTracker,
+};
+pub use text::TextStream;
+pub use transcript::Transcript;
+pub use usage::UsageTracker;
+pub use webhook::{NotificationHandler, WebhookService, WebhookVerifier};
@@ -0,0 +1,756 @@
+//! Serving a client as a local A2A endpoint
+//!
+//! A [`ProxyService`] turns a client into a Tower service of HTTP requests, so an
+//! application can expose a local JSON-RPC endpoint that forwards to a remote agent.
+//! Forwarded calls go through the client's stack, picking up its credentials, retries,
+//! and timeouts on the way:
+//!
+//! ```rust,no_run
+//! use tower_a2a::prelude::*;
+//!
+//! # fn example() -> Result<(), A2AError> {
+//! let url = "https://agent.example.com".parse().unwrap();
+//! let proxy = A2AClientBuilder::new_http(url)
+//!     .with_bearer_auth("upstream-token".to_string())
+//!     .build()?
+//!     .into_proxy();
+//! // Mount `proxy` with axum's `route_service`, or serve it with hyper through
+//! // `hyper_util::service::TowerToHyperService`
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! `POST` requests carry JSON-RPC calls, named as [`JsonRpcCodec`](crate::codec::JsonRpcCodec)
+//! names them or as in the specification (e.g. `tasks/get`). Streaming calls are answered
+//! with server-sent events, each wrapping an event in a JSON-RPC response. Errors answered
+//! by a JSON-RPC agent are passed on with their code. `GET` requests for the agent card
+//! path return the remote agent's card, with its endpoints replaced by the proxy's
+//! JSON-RPC endpoint. Headers of incoming requests, including credentials, are not
+//! forwarded.
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::{Buf, Bytes};
+use futures::{stream::BoxStream, StreamExt};
+use hyper::{
+    body::{Body, Frame},
+    header, Method, Request, Response, StatusCode,
+};
+use serde_json::{json, Value};
+use tower_service::Service;
+use url::Url;
+
+use crate::{
+    client::{AgentClient, ClientConfig},
+    codec::SseEvent,
+    protocol::{
+        agent::EndpointConfig,
+        consts::{media_types, AGENT_CARD_PATH},
+        error::A2AError,
+        operation::A2AOperation,
+        task::TaskListResponse,
+    },
+    service::{A2ARequest, A2AResponse, ProtocolStack},
+    transport::EventStream,
+};
+
+/// Default limit of a proxied request body
+const DEFAULT_MAX_BODY_SIZE: usize = 4 * 1024 * 1024;
+
+/// JSON-RPC error codes
+mod codes {
+    pub const PARSE_ERROR: i64 = -32700;
+    pub const INVALID_REQUEST: i64 = -32600;
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    pub const INVALID_PARAMS: i64 = -32602;
+    pub const INTERNAL_ERROR: i64 = -32603;
+    pub const TASK_NOT_FOUND: i64 = -32001;
+}
+
+/// A failed JSON-RPC call
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+    data: Option<Value>,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    /// Get the JSON-RPC error object
+    fn to_json(&self) -> Value {
+        let mut error = json!({"code": self.code, "message": self.message});
+        if let Some(data) = &self.data {
+            error["data"] = data.clone();
+        }
+        error
+    }
+}
+
+impl From<A2AError> for RpcError {
+    fn from(error: A2AError) -> Self {
+        let code = match error {
+            // Errors of the agent are passed on as they are
+            A2AError::JsonRpc {
+                code,
+                message,
+                data,
+            } => {
+                return Self {
+                    code,
+                    message,
+                    data,
+                }
+            }
+            A2AError::Validation(_) => codes::INVALID_PARAMS,
+            A2AError::TaskNotFound { .. } => codes::TASK_NOT_FOUND,
+            _ => codes::INTERNAL_ERROR,
+        };
+        Self::new(code, error.to_string())
+    }
+}
+
+/// Body of a proxied response, either complete or a stream of events
+pub struct ProxyBody {
+    inner: BodyInner,
+}
+
+enum BodyInner {
+    Full(Option<Bytes>),
+    Events(BoxStream<'static, Bytes>),
+}
+
+impl ProxyBody {
+    fn full(bytes: impl Into<Bytes>) -> Self {
+        Self {
+            inner: BodyInner::Full(Some(bytes.into())),
+        }
+    }
+}
+
+impl Body for ProxyBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+        match &mut self.inner {
+            BodyInner::Full(bytes) => Poll::Ready(bytes.take().map(|b| Ok(Frame::data(b)))),
+            BodyInner::Events(events) => events
+                .poll_next_unpin(cx)
+                .map(|event| event.map(|b| Ok(Frame::data(b)))),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        matches!(self.inner, BodyInner::Full(None))
+    }
+}
+
+impl fmt::Debug for ProxyBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.inner {
+            BodyInner::Full(_) => "full",
+            BodyInner::Events(_) => "events",
+        };
+        f.debug_struct("ProxyBody").field("kind", &kind).finish()
+    }
+}
+
+/// Tower service forwarding HTTP requests to an agent through a client
+///
+/// Created with [`AgentClient::into_proxy`]. Like [`AgentService`](crate::client::AgentService),
+/// requests get the headers, timeouts, and IDs the client would give them.
+#[derive(Clone)]
+pub struct ProxyService<S> {
+    service: S,
+    config: Arc<ClientConfig>,
+    max_body_size: usize,
+    public_url: Option<Url>,
+}
+
+impl<S> ProxyService<S> {
+    /// Create a proxy from a request service and a client configuration
+    pub fn new(service: S, config: ClientConfig) -> Self {
+        Self {
+            service,
+            config: Arc::new(config),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            public_url: None,
+        }
+    }
+
+    /// Set the URL clients reach the proxy's JSON-RPC endpoint at, advertised in the
+    /// served agent card
+    ///
+    /// By default it is built from the authority and path of the card request, over
+    /// plain HTTP unless the request URI names a scheme. Set it when the proxy is
+    /// served over HTTPS or behind another proxy.
+    pub fn with_public_url(mut self, url: Url) -> Self {
+        self.public_url = Some(url);
+        self
+    }
+
+    /// Limit the size of request bodies, 4 MiB by default
+    pub fn with_max_body_size(mut self, max: usize) -> Self {
+        self.max_body_size = max;
+        self
+    }
+}
+
+impl<S> ProxyService<S>
+where
+    S: ProtocolStack + Service<A2ARequest, Response = A2AResponse, Error = A2AError>,
+    S::Future: Send,
+{
+    /// Answer a request
+    async fn respond<B>(mut self, request: Request<B>) -> Response<ProxyBody>
+    where
+        B: Body,
+        B::Error: fmt::Display,
+    {
+        let (parts, body) = request.into_parts();
+        match parts.method {
+            Method::GET if parts.uri.path().ends_with(AGENT_CARD_PATH) => {
+                let Some(url) = self.public_url.clone().or_else(|| endpoint_url(&parts)) else {
+                    return plain_response(
+                        StatusCode::BAD_REQUEST,
+                        "Cannot tell the proxy's URL without a Host header",
+                    );
+                };
+                match self.call(A2AOperation::DiscoverAgent).await {
+                    Ok(A2AResponse::AgentCard(mut card)) => {
+                        card.endpoints = HashMap::from([(
+                            "jsonrpc".to_string(),
+                            EndpointConfig {
+                                preferred: true,
+                                ..EndpointConfig::new(url, "json-rpc")
+                            },
+                        )]);
+                        json_response(StatusCode::OK, &card)
+                    }
+                    Ok(_) => {
+                        plain_response(StatusCode::BAD_GATEWAY, "Agent did not return its card")
+                    }
+                    Err(e) => plain_response(StatusCode::BAD_GATEWAY, e.to_string()),
+                }
+            }
+            Method::POST => match read_body(body, self.max_body_size).await {
+                Ok(body) => self.respond_rpc(&body).await,
+                Err(response) => response,
+            },
+            _ => plain_response(StatusCode::METHOD_NOT_ALLOWED, "Unsupported method"),
+        }
+    }
+
+    /// Answer a JSON-RPC call
+    async fn respond_rpc(&mut self, body: &[u8]) -> Response<ProxyBody> {
+        let call: Value = match serde_json::from_slice(body) {
+            Ok(call) => call,
+            Err(e) => {
+                return rpc_error(
+                    Value::Null,
+                    RpcError::new(codes::PARSE_ERROR, e.to_string()),
+                )
+            }
+        };
+        let id = call.get("id").cloned().unwrap_or(Value::Null);
+
+        let operation = match operation_from_call(call) {
+            Ok(operation) => operation,
+            Err(e) => return rpc_error(id, e),
+        };
+
+        if is_streaming(&operation) {
+            return match self.stream(operation).await {
+                Ok(events) => event_stream_response(id, events),
+                Err(e) => rpc_error(id, e.into()),
+            };
+        }
+
+        match self.call(operation).await {
+            Ok(response) => {
+                let result = match response {
+                    A2AResponse::Task(task) => serde_json::to_value(task),
+                    A2AResponse::TaskList {
+                        tasks,
+                        total,
+                        next_token,
+                    } => serde_json::to_value(TaskListResponse {
+                        tasks,
+                        total,
+                        next_token,
+                    }),
+                    A2AResponse::AgentCard(card) => serde_json::to_value(card),
+                    A2AResponse::PushNotificationConfig(config) => serde_json::to_value(config),
+                    A2AResponse::PushNotificationConfigs(configs) => serde_json::to_value(configs),
+                    A2AResponse::Empty => Ok(Value::Null),
+                };
+                match result {
+                    Ok(result) => json_response(
+                        StatusCode::OK,
+                        &json!({"jsonrpc": "2.0", "id": id, "result": result}),
+                    ),
+                    Err(e) => rpc_error(id, A2AError::from(e).into()),
+                }
+            }
+            Err(e) => rpc_error(id, e.into()),
+        }
+    }
+
+    /// Execute an operation through the stack
+    async fn call(&mut self, operation: A2AOperation) -> Result<A2AResponse, A2AError> {
+        std::future::poll_fn(|cx| self.service.poll_ready(cx)).await?;
+        let request = AgentClient::<S>::request_from(&self.config, operation).await?;
+        AgentClient::call_service(&mut self.service, &self.config, request).await
+    }
+
+    /// Open a stream past the stack, after applying the layers' changes
+    async fn stream(&self, operation: A2AOperation) -> Result<EventStream, A2AError> {
+        let request = AgentClient::<S>::request_from(&self.config, operation).await?;
+        AgentClient::call_streaming(&self.service, request).await
+    }
+}
+
+impl<S, B> Service<Request<B>> for ProxyService<S>
+where
+    S: ProtocolStack + Service<A2ARequest, Response = A2AResponse, Error = A2AError>,
+    S::Future: Send,
+    B: Body + Send + 'static,
+    B::Data: Send,
+    B::Error: fmt::Display,
+{
+    type Response = Response<ProxyBody>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness of the stack is awaited per call, on a clone
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let proxy = self.clone();
+        Box::pin(async move { Ok(proxy.respond(request).await) })
+    }
+}
+
+impl<S> fmt::Debug for ProxyService<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyService")
+            .field("agent_url", &self.config.agent_url.as_str())
+            .field("max_body_size", &self.max_body_size)
+            .field("public_url", &self.public_url.as_ref().map(Url::as_str))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Get the URL of the JSON-RPC endpoint next to the agent card a request asks for
+fn endpoint_url(parts: &hyper::http::request::Parts) -> Option<Url> {
+    let authority = match parts.uri.authority() {
+        Some(authority) => authority.as_str(),
+        None => parts.headers.get(header::HOST)?.to_str().ok()?,
+    };
+    let scheme = parts.uri.scheme_str().unwrap_or("http");
+    let path = parts.uri.path().strip_suffix(AGENT_CARD_PATH)?;
+    Url::parse(&format!(
+        "{}://{}{}/",
+        scheme,
+        authority,
+        path.trim_end_matches('/')
+    ))
+    .ok()
+}
+
+/// Check whether an operation is answered with a stream of events
+fn is_streaming(operation: &A2AOperation) -> bool {
+    matches!(
+        operation,
+        A2AOperation::SendMessage { stream: true, .. }
+            | A2AOperation::SubscribeTask { .. }
+            | A2AOperation::ResubscribeTask { .. }
+    )
+}
+
+/// Decode the operation of a JSON-RPC call
+///
+/// The params are decoded as the fields of the operation the method names, with `id`
+/// accepted for `taskId` as in the specification. The singular `task/...` method names
+/// of older clients are accepted too.
+fn operation_from_call(mut call: Value) -> Result<A2AOperation, RpcError> {
+    let method = call
+        .get("method")
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::new(codes::INVALID_REQUEST, "Missing method"))?;
+
+    let (op, stream) = match method {
+        "message/send" => ("sendMessage", Some(false)),
+        "message/stream" => ("sendMessage", Some(true)),
+        "task/get" | "tasks/get" => ("getTask", None),
+        "task/list" | "tasks/list" => ("listTasks", None),
+        "task/cancel" | "tasks/cancel" => ("cancelTask", None),
+        "task/subscribe" | "tasks/subscribe" => ("subscribeTask", None),
+        "tasks/resubscribe" => ("resubscribeTask", None),
+        "agent/discover" | "agent/getAuthenticatedExtendedCard" => ("discoverAgent", None),
+        "webhook/register" => ("registerWebhook", None),
+        "tasks/pushNotificationConfig/set" => ("setPushNotificationConfig", None),
+        "tasks/pushNotificationConfig/get" => ("getPushNotificationConfig", None),
+        "tasks/pushNotificationConfig/list" => ("listPushNotificationConfigs", None),
+        "tasks/pushNotificationConfig/delete" => ("deletePushNotificationConfig", None),
+        method => {
+            return Err(RpcError::new(
+                codes::METHOD_NOT_FOUND,
+                format!("Unknown method '{}'", method),
+            ))
+        }
+    };
+
+    let mut params = match call.get_mut("params").map(Value::take) {
+        Some(Value::Object(params)) => params,
+        None | Some(Value::Null) => Default::default(),
+        Some(_) => {
+            return Err(RpcError::new(
+                codes::INVALID_PARAMS,
+                "Params must be an object",
+            ))
+        }
+    };
+    if !params.contains_key("taskId") {
+        if let Some(id) = params.remove("id") {
+            params.insert("taskId".into(), id);
+        }
+    }
+    params.insert("op".into(), op.into());
+    if let Some(stream) = stream {
+        params.insert("stream".into(), stream.into());
+    }
+
+    serde_json::from_value(Value::Object(params))
+        .map_err(|e| RpcError::new(codes::INVALID_PARAMS, e.to_string()))
+}
+
+/// Read a request body, failing past the size limit
+async fn read_body<B>(body: B, max: usize) -> Result<Vec<u8>, Response<ProxyBody>>
+where
+    B: Body,
+    B::Error: fmt::Display,
+{
+    let mut body = std::pin::pin!(body);
+    let mut bytes = Vec::new();
+    while let Some(frame) = std::future::poll_fn(|cx| body.as_mut().poll_frame(cx)).await {
+        let frame = frame.map_err(|e| {
+            plain_response(
+                StatusCode::BAD_REQUEST,
+                format!("Failed to read request body: {}", e),
+            )
+        })?;
+        let Ok(mut data) = frame.into_data() else {
+            continue;
+        };
+        if bytes.len() + data.remaining() > max {
+            return Err(plain_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("Request body exceeds {} bytes", max),
+            ));
+        }
+        while data.has_remaining() {
+            let chunk = data.chunk();
+            bytes.extend_from_slice(chunk);
+            let read = chunk.len();
+            data.advance(read);
+        }
+    }
+    Ok(bytes)
+}
+
+/// Build a response with a status and a content type
+fn response(status: StatusCode, content_type: &str, body: ProxyBody) -> Response<ProxyBody> {
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    if let Ok(value) = content_type.parse() {
+        response.headers_mut().insert(header::CONTENT_TYPE, value);
+    }
+    response
+}
+
+fn plain_response(status: StatusCode, message: impl Into<String>) -> Response<ProxyBody> {
+    response(status, "text/plain", ProxyBody::full(message.into()))
+}
+
+fn json_response<T: serde::Serialize>(status: StatusCode, body: &T) -> Response<ProxyBody> {
+    match serde_json::to_vec(body) {
+        Ok(body) => response(status, media_types::JSON, ProxyBody::full(body)),
+        Err(e) => plain_response(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+/// Answer a JSON-RPC call with an error
+///
+/// Errors are carried in the envelope, so the HTTP status is `200 OK`.
+fn rpc_error(id: Value, error: RpcError) -> Response<ProxyBody> {
+    json_response(
+        StatusCode::OK,
+        &json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": error.to_json()
+        }),
+    )
+}
+
+/// Answer a streaming call with server-sent events
+///
+/// Heartbeats and retry hints of the agent's stream are dropped. A stream error is
+/// sent as a last event carrying a JSON-RPC error.
+fn event_stream_response(id: Value, events: EventStream) -> Response<ProxyBody> {
+    let frames = events
+        .filter(|event| std::future::ready(!matches!(event, Ok(event) if event.is_control())))
+        .scan(false, move |failed, event| {
+            if *failed {
+                return std::future::ready(None);
+            }
+            let envelope = match event {
+                Ok(SseEvent {
+                    kind, mut payload, ..
+                }) => {
+                    if let Value::Object(fields) = &mut payload {
+                        fields.entry("kind").or_insert(Value::String(kind));
+                    }
+                    json!({"jsonrpc": "2.0", "id": id, "result": payload})
+                }
+                Err(e) => {
+                    *failed = true;
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": RpcError::from(e).to_json()
+                    })
+                }
+            };
+            std::future::ready(Some(Bytes::from(format!("data: {}\n\n", envelope))))
+        });
+
+    let mut response = response(
+        StatusCode::OK,
+        media_types::EVENT_STREAM,
+        ProxyBody {
+            inner: BodyInner::Events(frames.boxed()),
+        },
+    );
+    response.headers_mut().insert(
+        header::CACHE_CONTROL,
+        header::HeaderValue::from_static("no-cache"),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{
+        client::{A2AClientBuilder, ClientStack},
+        protocol::{
+            agent::{AgentCapabilities, AgentCard, EndpointConfig},
+            Message, Task,
+        },
+        transport::{mock::MockTransport, TransportResponse},
+    };
+
+    async fn collect(body: ProxyBody) -> String {
+        let mut body = std::pin::pin!(body);
+        let mut text = String::new();
+        while let Some(frame) = std::future::poll_fn(|cx| body.as_mut().poll_frame(cx)).await {
+            let data = frame.unwrap().into_data().unwrap();
+            text.push_str(std::str::from_utf8(&data).unwrap());
+        }
+        text
+    }
+
+    async fn send(proxy: &Proxy, request: Request<String>) -> (StatusCode, String) {
+        let response = proxy.clone().oneshot(request).await.unwrap();
+        (response.status(), collect(response.into_body()).await)
+    }
+
+    async fn rpc(proxy: &Proxy, call: Value) -> Value {
+        let request = Request::post("/").body(call.to_string()).unwrap();
+        let (status, body) = send(proxy, request).await;
+        assert_eq!(status, StatusCode::OK);
+        serde_json::from_str(&body).unwrap()
+    }
+
+    type Proxy = ProxyService<ClientStack<MockTransport>>;
+
+    fn proxy() -> Proxy {
+        let transport = MockTransport::new(|request| {
+            assert_eq!(request.headers["Authorization"], "Bearer upstream");
+            let body = if request.endpoint.ends_with(AGENT_CARD_PATH) {
+                let card =
+                    AgentCard::new("Upstream", "A proxied agent", AgentCapabilities::default())
+                        .with_endpoint(
+                            "http",
+                            EndpointConfig::new("https://agent.example.com", "http+json"),
+                        );
+                serde_json::to_vec(&card)
+            } else {
+                serde_json::to_vec(&Task::new("task-1", Message::user("Hello")))
+            };
+            TransportResponse::new(200).body(Bytes::from(body.unwrap()))
+        })
+        .with_stream_handler(|request| {
+            if request.endpoint.contains("task-2") {
+                return Err(A2AError::JsonRpc {
+                    code: -32004,
+                    message: "Streaming is not supported".into(),
+                    data: Some(json!({"taskId": "task-2"})),
+                });
+            }
+            Ok(vec![
+                Ok(SseEvent::heartbeat("keep-alive")),
+                Ok(SseEvent {
+                    kind: "status-update".into(),
+                    payload: json!({"taskId": "task-1", "status": {"state": "working"}}),
+                    final_event: false,
+                    id: None,
+                }),
+                Err(A2AError::Transport("Connection reset".into())),
+            ])
+        });
+        A2AClientBuilder::new("mock://agent".parse().unwrap())
+            .with_transport(transport)
+            .with_bearer_auth("upstream".to_string())
+            .build()
+            .unwrap()
+            .into_proxy()
+    }
+
+    #[tokio::test]
+    async fn test_proxy_calls() {
+        let proxy = proxy();
+
+        let response = rpc(
+            &proxy,
+            json!({"jsonrpc": "2.0", "id": 7, "method": "tasks/get", "params": {"id": "task-1"}}),
+        )
+        .await;
+        assert_eq!(response["id"], 7);
+        assert_eq!(response["result"]["id"], "task-1");
+
+        let response = rpc(
+            &proxy,
+            json!({"jsonrpc": "2.0", "id": "a", "method": "message/send", "params": {
+                "message": Message::user("Hello")
+            }}),
+        )
+        .await;
+        assert_eq!(response["result"]["id"], "task-1");
+
+        let response = rpc(&proxy, json!({"jsonrpc": "2.0", "id": 1, "method": "nope"})).await;
+        assert_eq!(response["error"]["code"], codes::METHOD_NOT_FOUND);
+        let response = rpc(
+            &proxy,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "tasks/get", "params": []}),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], codes::INVALID_PARAMS);
+
+        let request = Request::post("/").body("{".to_string()).unwrap();
+        let (_, body) = send(&proxy, request).await;
+        assert!(body.contains(&codes::PARSE_ERROR.to_string()));
+
+        // The card points at the proxy
+        let card_request = |path: &str| {
+            Request::get(format!("{}{}", path, AGENT_CARD_PATH))
+                .header(header::HOST, "localhost:3000")
+                .body(String::new())
+                .unwrap()
+        };
+        let (status, body) = send(&proxy, card_request("/upstream")).await;
+        assert_eq!(status, StatusCode::OK);
+        let card: AgentCard = serde_json::from_str(&body).unwrap();
+        assert_eq!(card.name, "Upstream");
+        assert_eq!(card.endpoints.len(), 1);
+        assert_eq!(
+            card.endpoints["jsonrpc"].url,
+            "http://localhost:3000/upstream/"
+        );
+        assert_eq!(card.endpoints["jsonrpc"].endpoint_type, "json-rpc");
+
+        let public = proxy
+            .clone()
+            .with_public_url("https://agents.example.com/a2a".parse().unwrap());
+        let (_, body) = send(&public, card_request("")).await;
+        let card: AgentCard = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            card.endpoints["jsonrpc"].url,
+            "https://agents.example.com/a2a"
+        );
+
+        let request = Request::delete("/").body(String::new()).unwrap();
+        assert_eq!(
+            send(&proxy, request).await.0,
+            StatusCode::METHOD_NOT_ALLOWED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_proxy_stream() {
+        let call = json!({"jsonrpc": "2.0", "id": 3, "method": "tasks/resubscribe", "params": {"id": "task-1"}});
+        let request = Request::post("/").body(call.to_string()).unwrap();
+        let response = proxy().oneshot(request).await.unwrap();
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            media_types::EVENT_STREAM
+        );
+
+        let body = collect(response.into_body()).await;
+        let events: Vec<Value> = body
+            .split("\n\n")
+            .filter_map(|frame| frame.strip_prefix("data: "))
+            .map(|data| serde_json::from_str(data).unwrap())
+            .collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["result"]["kind"], "status-update");
+        assert_eq!(events[1]["error"]["code"], codes::INTERNAL_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_proxy_passes_agent_errors() {
+        let response = rpc(
+            &proxy(),
+            json!({"jsonrpc": "2.0", "id": 4, "method": "tasks/resubscribe", "params": {"id": "task-2"}}),
+        )
+        .await;
+        assert_eq!(
+            response["error"],
+            json!({
+                "code": -32004,
+                "message": "Streaming is not supported",
+                "data": {"taskId": "task-2"}
+            })
+        );
+
+        let error = RpcError::from(A2AError::TaskNotFound {
+            task_id: "task-3".into(),
+        });
+        assert_eq!(error.code, codes::TASK_NOT_FOUND);
+    }
+}
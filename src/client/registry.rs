@@ -0,0 +1,636 @@
+//! Registry of agents loaded from a manifest
+//!
+//! Platform teams can describe an inventory of agents as configuration and build
+//! clients by name:
+//!
+//! ```toml
+//! [[agents]]
+//! name = "billing"
+//! url = "https://billing.example.com"
+//! timeout_secs = 60
+//! headers = { "X-Tenant-Id" = "acme" }
+//! auth = { type = "bearer", token = "${BILLING_TOKEN}" }
+//!
+//! [[agents]]
+//! name = "search"
+//! url = "wss://search.example.com/a2a"
+//! transport = "websocket"
+//! ```
+//!
+//! Agents built on implementations with known deviations can name a
+//! [`CompatProfile`] defined in the manifest's `compat_profiles` or registered with
+//! [`AgentRegistry::register_compat_profile`].
+//!
+//! Manifests can always be read from JSON. TOML and YAML support require the
+//! `manifest-toml` and `manifest-yaml` features. Credential values of the form
+//! `${NAME}` are read from the environment so secrets stay out of the manifest.
+
+use std::{collections::HashMap, path::Path, sync::Arc, time::Duration};
+
+use futures::{future, stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tower::discover::Change;
+use url::Url;
+
+use crate::{
+    client::{A2AClientBuilder, AgentClient, AgentService, ClientStack, CompatProfile},
+    codec::JsonRpcCodec,
+    layer::AuthCredentials,
+    protocol::error::A2AError,
+    transport::{HttpTransport, Transport, WebSocketTransport},
+};
+
+/// Service of operations for an HTTP agent, as produced by [`AgentRegistry::changes`]
+pub type HttpAgentService = AgentService<ClientStack<HttpTransport>>;
+
+/// Manifest describing a set of agents
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AgentManifest {
+    /// The agents, identified by unique names
+    #[serde(default)]
+    pub agents: Vec<AgentEntry>,
+
+    /// Compatibility profiles the agents can refer to by name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub compat_profiles: Vec<CompatProfile>,
+}
+
+impl AgentManifest {
+    /// Parse a manifest from JSON
+    pub fn from_json_str(manifest: &str) -> Result<Self, A2AError> {
+        Ok(serde_json::from_str(manifest)?)
+    }
+
+    /// Parse a manifest from TOML
+    #[cfg(feature = "manifest-toml")]
+    pub fn from_toml_str(manifest: &str) -> Result<Self, A2AError> {
+        toml::from_str(manifest)
+            .map_err(|e| A2AError::Validation(format!("Invalid agent manifest: {}", e)))
+    }
+
+    /// Parse a manifest from YAML
+    #[cfg(feature = "manifest-yaml")]
+    pub fn from_yaml_str(manifest: &str) -> Result<Self, A2AError> {
+        serde_yaml::from_str(manifest)
+            .map_err(|e| A2AError::Validation(format!("Invalid agent manifest: {}", e)))
+    }
+
+    /// Read a manifest from a file, choosing the format from its extension
+    ///
+    /// Supports `.json`, and `.toml`, `.yaml`, or `.yml` when the matching feature is
+    /// enabled.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, A2AError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            A2AError::Other(format!(
+                "Failed to read agent manifest {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Self::from_json_str(&contents),
+            #[cfg(feature = "manifest-toml")]
+            Some("toml") => Self::from_toml_str(&contents),
+            #[cfg(feature = "manifest-yaml")]
+            Some("yaml" | "yml") => Self::from_yaml_str(&contents),
+            _ => Err(A2AError::Validation(format!(
+                "Unsupported agent manifest format: {}",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// Transport used to reach an agent
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportKind {
+    /// HTTP+JSON binding
+    #[default]
+    Http,
+
+    /// JSON-RPC over WebSocket
+    WebSocket,
+}
+
+/// Authentication settings of an agent
+///
+/// Values of the form `${NAME}` are read from the environment variable `NAME`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuthConfig {
+    /// Bearer token authentication
+    Bearer {
+        /// The bearer token
+        token: String,
+    },
+
+    /// API key authentication
+    ApiKey {
+        /// The API key
+        key: String,
+        /// The header carrying the key
+        header: String,
+    },
+
+    /// Basic HTTP authentication
+    Basic {
+        /// The username
+        username: String,
+        /// The password
+        password: String,
+    },
+}
+
+impl AuthConfig {
+    /// Resolve the credentials, reading referenced environment variables
+    pub fn credentials(&self) -> Result<AuthCredentials, A2AError> {
+        Ok(match self {
+            AuthConfig::Bearer { token } => AuthCredentials::bearer(resolve_env(token)?),
+            AuthConfig::ApiKey { key, header } => {
+                AuthCredentials::api_key(resolve_env(key)?, header)
+            }
+            AuthConfig::Basic { username, password } => {
+                AuthCredentials::basic(resolve_env(username)?, resolve_env(password)?)
+            }
+        })
+    }
+}
+
+/// An agent described in a manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AgentEntry {
+    /// Unique name of the agent
+    pub name: String,
+
+    /// Base URL of the agent
+    pub url: Url,
+
+    /// Transport used to reach the agent
+    #[serde(default)]
+    pub transport: TransportKind,
+
+    /// Authentication settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthConfig>,
+
+    /// Headers attached to every request
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+
+    /// Default request timeout in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+
+    /// Name of the compatibility profile of the agent's implementation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compat: Option<String>,
+}
+
+impl AgentEntry {
+    /// Apply the entry's settings to a client builder
+    pub fn configure<T: Transport>(
+        &self,
+        mut builder: A2AClientBuilder<T>,
+    ) -> Result<A2AClientBuilder<T>, A2AError> {
+        if let Some(auth) = &self.auth {
+            builder = builder.with_auth(auth.credentials()?);
+        }
+        if let Some(timeout) = self.timeout_secs {
+            builder = builder.with_timeout(Duration::from_secs(timeout));
+        }
+        for (key, value) in &self.headers {
+            builder = builder.with_default_header(key, value);
+        }
+        Ok(builder)
+    }
+}
+
+/// Agents loaded from a manifest, looked up by name
+#[derive(Debug, Clone, Default)]
+pub struct AgentRegistry {
+    agents: Vec<AgentEntry>,
+    profiles: HashMap<String, CompatProfile>,
+}
+
+impl AgentRegistry {
+    /// Create a registry from a manifest
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Validation` if agent names are empty or not unique, if an
+    /// agent's URL scheme does not match its transport, or if an agent refers to an
+    /// unknown compatibility profile
+    pub fn from_manifest(manifest: AgentManifest) -> Result<Self, A2AError> {
+        let mut registry = Self::default();
+        for profile in manifest.compat_profiles {
+            registry.register_compat_profile(profile);
+        }
+        for entry in manifest.agents {
+            registry.compat_profile(&entry)?;
+            registry.register(entry)?;
+        }
+        Ok(registry)
+    }
+
+    /// Add a compatibility profile, replacing any profile with the same name
+    pub fn register_compat_profile(&mut self, profile: CompatProfile) {
+        self.profiles.insert(profile.name.clone(), profile);
+    }
+
+    /// Get the compatibility profile named by an entry, if any
+    fn compat_profile(&self, entry: &AgentEntry) -> Result<Option<&CompatProfile>, A2AError> {
+        let Some(name) = &entry.compat else {
+            return Ok(None);
+        };
+        self.profiles.get(name).map(Some).ok_or_else(|| {
+            A2AError::Validation(format!(
+                "Agent '{}' uses unknown compatibility profile '{}'",
+                entry.name, name
+            ))
+        })
+    }
+
+    /// Apply the entry's compatibility profile and settings to a client builder
+    fn configure<T: Transport>(
+        &self,
+        entry: &AgentEntry,
+        mut builder: A2AClientBuilder<T>,
+    ) -> Result<A2AClientBuilder<T>, A2AError> {
+        if let Some(profile) = self.compat_profile(entry)? {
+            builder = builder.with_compat_profile(profile.clone());
+        }
+        entry.configure(builder)
+    }
+
+    /// Load a registry from a manifest file
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, A2AError> {
+        Self::from_manifest(AgentManifest::from_path(path)?)
+    }
+
+    /// Add an agent to the registry
+    pub fn register(&mut self, entry: AgentEntry) -> Result<(), A2AError> {
+        if self.get(&entry.name).is_some() {
+            return Err(A2AError::Validation(format!(
+                "Duplicate agent name: {}",
+                entry.name
+            )));
+        }
+        Self::check(&entry)?;
+        self.agents.push(entry);
+        Ok(())
+    }
+
+    /// Add an agent, replacing any agent with the same name
+    pub fn upsert(&mut self, entry: AgentEntry) -> Result<(), A2AError> {
+        Self::check(&entry)?;
+        match self
+            .agents
+            .iter_mut()
+            .find(|agent| agent.name == entry.name)
+        {
+            Some(agent) => *agent = entry,
+            None => self.agents.push(entry),
+        }
+        Ok(())
+    }
+
+    /// Remove an agent by name, returning it if it was registered
+    pub fn remove(&mut self, name: &str) -> Option<AgentEntry> {
+        let index = self.agents.iter().position(|agent| agent.name == name)?;
+        Some(self.agents.remove(index))
+    }
+
+    fn check(entry: &AgentEntry) -> Result<(), A2AError> {
+        if entry.name.is_empty() {
+            return Err(A2AError::Validation("Agent name must not be empty".into()));
+        }
+
+        let expected: &[&str] = match entry.transport {
+            TransportKind::Http => &["http", "https"],
+            TransportKind::WebSocket => &["ws", "wss"],
+        };
+        if !expected.contains(&entry.url.scheme()) {
+            return Err(A2AError::Validation(format!(
+                "Agent '{}' uses {:?} transport but has URL {}",
+                entry.name, entry.transport, entry.url
+            )));
+        }
+        Ok(())
+    }
+
+    /// Get an agent by name
+    pub fn get(&self, name: &str) -> Option<&AgentEntry> {
+        self.agents.iter().find(|entry| entry.name == name)
+    }
+
+    /// Iterate over the agents in manifest order
+    pub fn iter(&self) -> impl Iterator<Item = &AgentEntry> {
+        self.agents.iter()
+    }
+
+    /// Get the number of agents
+    pub fn len(&self) -> usize {
+        self.agents.len()
+    }
+
+    /// Check if the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.agents.is_empty()
+    }
+
+    /// Build an HTTP client for an agent
+    pub fn http_client(
+        &self,
+        name: &str,
+    ) -> Result<AgentClient<ClientStack<HttpTransport>>, A2AError> {
+        let entry = self.entry(name, TransportKind::Http)?;
+        self.configure(entry, A2AClientBuilder::new_http(entry.url.clone()))?
+            .build()
+    }
+
+    /// Build a WebSocket client for an agent
+    pub fn websocket_client(
+        &self,
+        name: &str,
+    ) -> Result<AgentClient<ClientStack<WebSocketTransport>>, A2AError> {
+        let entry = self.entry(name, TransportKind::WebSocket)?;
+        let builder = A2AClientBuilder::new(entry.url.clone())
+            .with_transport(WebSocketTransport::new(entry.url.clone()))
+            .with_codec(Arc::new(JsonRpcCodec::new()));
+        self.configure(entry, builder)?.build()
+    }
+
+    /// Get the changes turning the HTTP agents of a previous registry into this one's
+    ///
+    /// Agents that were removed, or no longer use HTTP, are removed. Agents that were
+    /// added, or whose entry or compatibility profile changed, are inserted with a
+    /// freshly built service; unchanged agents are left alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a service cannot be built, e.g. because a credential's
+    /// environment variable is not set
+    pub fn changes(
+        &self,
+        previous: &AgentRegistry,
+    ) -> Result<Vec<Change<String, HttpAgentService>>, A2AError> {
+        let mut changes: Vec<_> = previous
+            .iter()
+            .filter(|entry| entry.transport == TransportKind::Http)
+            .filter(|entry| {
+                self.get(&entry.name)
+                    .is_none_or(|current| current.transport != TransportKind::Http)
+            })
+            .map(|entry| Change::Remove(entry.name.clone()))
+            .collect();
+
+        for entry in self
+            .iter()
+            .filter(|entry| entry.transport == TransportKind::Http)
+        {
+            let unchanged = previous.get(&entry.name).is_some_and(|old| {
+                old == entry
+                    && matches!(
+                        (previous.compat_profile(old), self.compat_profile(entry)),
+                        (Ok(old), Ok(new)) if old == new
+                    )
+            });
+            if !unchanged {
+                let service = self.http_client(&entry.name)?.into_service();
+                changes.push(Change::Insert(entry.name.clone(), service));
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Follow updates of a registry as a stream of changes to its HTTP agents
+    ///
+    /// The stream implements `tower::discover::Discover`, keyed by agent name, so
+    /// `tower::balance` can spread operations over a changing set of replicas. The
+    /// first registry inserts all of its HTTP agents; each later one is diffed with
+    /// [`AgentRegistry::changes`]. A registry whose services cannot be built yields an
+    /// error and is skipped, so the next update is diffed with the last good one.
+    pub fn watch(
+        updates: impl Stream<Item = AgentRegistry> + Send + 'static,
+    ) -> impl Stream<Item = Result<Change<String, HttpAgentService>, A2AError>> + Send {
+        updates
+            .scan(AgentRegistry::default(), |previous, registry| {
+                let changes = match registry.changes(previous) {
+                    Ok(changes) => {
+                        *previous = registry;
+                        changes.into_iter().map(Ok).collect()
+                    }
+                    Err(e) => vec![Err(e)],
+                };
+                future::ready(Some(stream::iter(changes)))
+            })
+            .flatten()
+    }
+
+    fn entry(&self, name: &str, transport: TransportKind) -> Result<&AgentEntry, A2AError> {
+        let entry = self
+            .get(name)
+            .ok_or_else(|| A2AError::Validation(format!("Unknown agent: {}", name)))?;
+        if entry.transport != transport {
+            return Err(A2AError::Validation(format!(
+                "Agent '{}' uses {:?} transport",
+                name, entry.transport
+            )));
+        }
+        Ok(entry)
+    }
+}
+
+/// Resolve a `${NAME}` reference to the value of an environment variable
+fn resolve_env(value: &str) -> Result<String, A2AError> {
+    let Some(name) = value
+        .strip_prefix("${")
+        .and_then(|rest| rest.strip_suffix('}'))
+    else {
+        return Ok(value.to_string());
+    };
+
+    std::env::var(name)
+        .map_err(|_| A2AError::Validation(format!("Environment variable {} is not set", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"{
+        "agents": [
+            {
+                "name": "billing",
+                "url": "https://billing.example.com",
+                "timeout_secs": 60,
+                "headers": {"X-Tenant-Id": "acme"},
+                "auth": {"type": "bearer", "token": "secret"}
+            },
+            {
+                "name": "search",
+                "url": "wss://search.example.com/a2a",
+                "transport": "websocket"
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_registry_from_manifest() {
+        let manifest = AgentManifest::from_json_str(MANIFEST).unwrap();
+        let registry = AgentRegistry::from_manifest(manifest).unwrap();
+        assert_eq!(registry.len(), 2);
+
+        let billing = registry.get("billing").unwrap();
+        assert_eq!(billing.transport, TransportKind::Http);
+        assert!(matches!(
+            billing.auth.as_ref().unwrap().credentials().unwrap(),
+            AuthCredentials::Bearer(token) if token == "secret"
+        ));
+
+        let client = registry.http_client("billing").unwrap();
+        assert_eq!(client.config().timeout, Duration::from_secs(60));
+        assert_eq!(client.config().default_headers["X-Tenant-Id"], "acme");
+
+        assert!(registry.websocket_client("search").is_ok());
+        assert!(registry.http_client("search").is_err());
+        assert!(registry.http_client("unknown").is_err());
+    }
+
+    #[test]
+    fn test_registry_rejects_invalid_entries() {
+        let mut manifest = AgentManifest::from_json_str(MANIFEST).unwrap();
+        manifest.agents.push(manifest.agents[0].clone());
+        assert!(AgentRegistry::from_manifest(manifest).is_err());
+
+        let mut manifest = AgentManifest::from_json_str(MANIFEST).unwrap();
+        manifest.agents[1].transport = TransportKind::Http;
+        assert!(AgentRegistry::from_manifest(manifest).is_err());
+    }
+
+    #[test]
+    fn test_compat_profiles() {
+        let manifest = AgentManifest::from_json_str(
+            r#"{
+                "compat_profiles": [
+                    {"name": "gateway", "headers": {"X-Gateway": "a2a", "X-Tenant-Id": "none"}}
+                ],
+                "agents": [
+                    {
+                        "name": "billing",
+                        "url": "https://billing.example.com",
+                        "headers": {"X-Tenant-Id": "acme"},
+                        "compat": "gateway"
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let registry = AgentRegistry::from_manifest(manifest.clone()).unwrap();
+
+        // Headers of the entry take precedence over those of the profile
+        let client = registry.http_client("billing").unwrap();
+        assert_eq!(client.config().default_headers["X-Gateway"], "a2a");
+        assert_eq!(client.config().default_headers["X-Tenant-Id"], "acme");
+
+        let mut manifest = manifest;
+        manifest.compat_profiles.clear();
+        assert!(matches!(
+            AgentRegistry::from_manifest(manifest),
+            Err(A2AError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_watch_changes() {
+        fn keys(changes: &[Result<Change<String, HttpAgentService>, A2AError>]) -> Vec<String> {
+            changes
+                .iter()
+                .map(|change| match change {
+                    Ok(Change::Insert(name, _)) => format!("+{}", name),
+                    Ok(Change::Remove(name)) => format!("-{}", name),
+                    Err(_) => "error".to_string(),
+                })
+                .collect()
+        }
+
+        let first =
+            AgentRegistry::from_manifest(AgentManifest::from_json_str(MANIFEST).unwrap()).unwrap();
+        let replica = AgentEntry {
+            name: "billing-2".to_string(),
+            ..first.get("billing").unwrap().clone()
+        };
+        let mut second = first.clone();
+        second.register(replica.clone()).unwrap();
+        let mut third = second.clone();
+        third.remove("billing");
+        let mut broken = third.clone();
+        broken
+            .register(AgentEntry {
+                name: "billing-3".to_string(),
+                auth: Some(AuthConfig::Bearer {
+                    token: "${TOWER_A2A_TEST_UNSET_TOKEN}".to_string(),
+                }),
+                ..replica.clone()
+            })
+            .unwrap();
+        let mut fourth = third.clone();
+        fourth
+            .upsert(AgentEntry {
+                timeout_secs: Some(5),
+                ..replica
+            })
+            .unwrap();
+
+        let changes: Vec<_> = AgentRegistry::watch(stream::iter([
+            first,
+            second.clone(),
+            second,
+            third,
+            broken,
+            fourth,
+        ]))
+        .collect()
+        .await;
+        assert_eq!(
+            keys(&changes),
+            ["+billing", "+billing-2", "-billing", "error", "+billing-2"]
+        );
+    }
+
+    #[test]
+    fn test_auth_from_env() {
+        let auth = AuthConfig::Bearer {
+            token: "${TOWER_A2A_TEST_UNSET_TOKEN}".to_string(),
+        };
+        assert!(matches!(auth.credentials(), Err(A2AError::Validation(_))));
+    }
+
+    #[cfg(feature = "manifest-toml")]
+    #[test]
+    fn test_toml_manifest() {
+        let manifest = AgentManifest::from_toml_str(
+            r#"
+            [[agents]]
+            name = "billing"
+            url = "https://billing.example.com"
+            auth = { type = "api_key", key = "k", header = "X-API-Key" }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.agents[0].name, "billing");
+    }
+
+    #[cfg(feature = "manifest-yaml")]
+    #[test]
+    fn test_yaml_manifest() {
+        let manifest = AgentManifest::from_yaml_str(
+            "agents:\n  - name: search\n    url: wss://search.example.com\n    transport: websocket\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.agents[0].transport, TransportKind::WebSocket);
+    }
+}
@@ -0,0 +1,247 @@
+//! Client whose background work is bound to a scope
+//!
+//! Pollers, watchers, and stream readers spawned through a [`ScopedClient`] run in a
+//! task group owned by the client. Dropping the client aborts all of them, so callers
+//! that give up on an operation do not leave orphaned tasks polling the agent:
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use tower_a2a::{client::ScopedClient, prelude::*};
+//!
+//! # async fn example() -> Result<(), A2AError> {
+//! let url = "https://agent.example.com".parse().unwrap();
+//! let mut scope = ScopedClient::new(A2AClientBuilder::new_http(url).build()?);
+//!
+//! let task = scope.send_message(Message::user("Summarize the report")).await?;
+//! let mut updates = scope.spawn_watch(task.id, Duration::from_secs(1));
+//! if let Some(update) = updates.recv().await {
+//!     println!("{:?}", update?.1);
+//! }
+//! // Dropping `scope` stops the watcher
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    future::Future,
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+
+use futures::{Stream, StreamExt};
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::{AbortHandle, JoinSet},
+};
+use tower_service::Service;
+
+use crate::{
+    client::AgentClient,
+    codec::SseEvent,
+    protocol::{error::A2AError, Task, TaskDelta},
+    service::{A2ARequest, A2AResponse},
+};
+
+/// Capacity of the channels returned by the spawn methods
+const CHANNEL_CAPACITY: usize = 64;
+
+/// An [`AgentClient`] owning the background tasks spawned through it
+///
+/// Dereferences to the wrapped client, so all regular operations are available.
+/// Background work started with the `spawn_*` methods uses clones of the client's
+/// service and configuration, and is aborted when the scope is dropped or
+/// [`abort_all`](Self::abort_all) is called. Must be used within a Tokio runtime.
+pub struct ScopedClient<S> {
+    client: AgentClient<S>,
+    tasks: JoinSet<()>,
+}
+
+impl<S> ScopedClient<S>
+where
+    S: Service<A2ARequest, Response = A2AResponse, Error = A2AError>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    S::Future: Send,
+{
+    /// Wrap a client in a new scope
+    pub fn new(client: AgentClient<S>) -> Self {
+        Self {
+            client,
+            tasks: JoinSet::new(),
+        }
+    }
+
+    /// Register background work in the scope
+    ///
+    /// The returned handle can abort the work early; it is aborted with the scope
+    /// otherwise.
+    pub fn spawn<F>(&mut self, work: F) -> AbortHandle
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        // Reap finished tasks so long-lived scopes do not accumulate them
+        while self.tasks.try_join_next().is_some() {}
+        self.tasks.spawn(work)
+    }
+
+    /// Poll a task in the background until it reaches a terminal state
+    ///
+    /// See [`AgentClient::poll_until_complete`]. The receiver yields the final task, or
+    /// is closed without a value if the scope is dropped first.
+    pub fn spawn_poll(
+        &mut self,
+        task_id: String,
+        poll_interval: Duration,
+        max_attempts: usize,
+    ) -> oneshot::Receiver<Result<Task, A2AError>> {
+        let (tx, rx) = oneshot::channel();
+        let mut client = self.detached();
+        self.spawn(async move {
+            let poll_interval_ms = poll_interval.as_millis() as u64;
+            let result = client
+                .poll_until_complete(task_id, poll_interval_ms, max_attempts)
+                .await;
+            let _ = tx.send(result);
+        });
+        rx
+    }
+
+    /// Watch a task in the background, receiving the changes between snapshots
+    ///
+    /// See [`AgentClient::watch_task`]. The watcher also stops when the receiver is
+    /// dropped.
+    pub fn spawn_watch(
+        &mut self,
+        task_id: String,
+        poll_interval: Duration,
+    ) -> mpsc::Receiver<Result<(Task, TaskDelta), A2AError>> {
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let mut client = self.detached();
+        self.spawn(async move {
+            let updates = client.watch_task(task_id, poll_interval);
+            futures::pin_mut!(updates);
+            while let Some(update) = updates.next().await {
+                if tx.send(update).await.is_err() {
+                    break;
+                }
+            }
+        });
+        rx
+    }
+
+    /// Read an event stream in the background, e.g. one returned by
+    /// [`AgentClient::send_message_stream`]
+    ///
+    /// The reader also stops when the receiver is dropped.
+    pub fn spawn_stream<St>(&mut self, events: St) -> mpsc::Receiver<Result<SseEvent, A2AError>>
+    where
+        St: Stream<Item = Result<SseEvent, A2AError>> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        self.spawn(async move {
+            futures::pin_mut!(events);
+            while let Some(event) = events.next().await {
+                if tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+        });
+        rx
+    }
+
+    /// Get the number of background tasks that have not been reaped yet
+    pub fn active(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Abort all background tasks, keeping the client usable
+    pub fn abort_all(&mut self) {
+        self.tasks.abort_all();
+    }
+
+    /// Abort all background tasks and return the wrapped client
+    pub fn into_inner(mut self) -> AgentClient<S> {
+        self.tasks.abort_all();
+        self.client
+    }
+
+    /// Create a client for background work sharing the service and configuration
+    fn detached(&self) -> AgentClient<S> {
+        AgentClient::new(self.client.service().clone(), self.client.config().clone())
+    }
+}
+
+impl<S> Deref for ScopedClient<S> {
+    type Target = AgentClient<S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+impl<S> DerefMut for ScopedClient<S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.client
+    }
+}
+
+impl<S> std::fmt::Debug for ScopedClient<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScopedClient")
+            .field("active", &self.tasks.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use bytes::Bytes;
+
+    use crate::{
+        client::{A2AClientBuilder, ClientStack},
+        protocol::Message,
+        transport::{mock::MockTransport, TransportResponse},
+    };
+
+    use super::*;
+
+    /// Client whose agent reports a task that never finishes, counting polls
+    fn client(polls: Arc<AtomicUsize>) -> AgentClient<ClientStack<MockTransport>> {
+        let transport = MockTransport::new(move |_req| {
+            polls.fetch_add(1, Ordering::SeqCst);
+            let task = Task::new("task-123", Message::user("Test"));
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+        A2AClientBuilder::new("https://example.com".parse().unwrap())
+            .with_transport(transport)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_drop_aborts_background_work() {
+        let polls = Arc::new(AtomicUsize::new(0));
+        let mut scope = ScopedClient::new(client(polls.clone()));
+
+        let result = scope.spawn_poll("task-123".into(), Duration::from_millis(5), 0);
+        let mut updates = scope.spawn_watch("task-123".into(), Duration::from_millis(5));
+        assert!(updates.recv().await.unwrap().is_ok());
+        assert_eq!(scope.active(), 2);
+
+        drop(scope);
+        assert!(result.await.is_err());
+        assert!(updates.recv().await.is_none());
+
+        // No polling continues after the scope is gone
+        let after_drop = polls.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(polls.load(Ordering::SeqCst), after_drop);
+    }
+}
@@ -0,0 +1,135 @@
+//! Choice of endpoint when an agent card lists several bindings
+//!
+//! Cards may advertise the same agent over `http+json`, `json-rpc`, and `grpc`. An
+//! [`EndpointSelector`] decides which one discovery registers and clients connect to.
+//! The default [`PreferredEndpointSelector`] honours the card's `preferred` flag and
+//! otherwise follows the order of the bindings this client supports. Custom policies
+//! implement the trait:
+//!
+//! ```rust
+//! use tower_a2a::{
+//!     client::EndpointSelector,
+//!     protocol::{agent::EndpointConfig, AgentCard},
+//! };
+//!
+//! /// Use internal endpoints when running inside the VPC
+//! #[derive(Debug)]
+//! struct InternalFirst;
+//!
+//! impl EndpointSelector for InternalFirst {
+//!     fn select<'a>(&self, card: &'a AgentCard) -> Option<(&'a str, &'a EndpointConfig)> {
+//!         card.endpoints
+//!             .iter()
+//!             .find(|(_, endpoint)| endpoint.url.contains(".internal"))
+//!             .map(|(name, endpoint)| (name.as_str(), endpoint))
+//!     }
+//! }
+//! ```
+
+use crate::protocol::{agent::EndpointConfig, AgentCard};
+
+/// Binding types supported by this client, most desirable first
+pub const SUPPORTED_BINDINGS: &[&str] = &["http+json", "http+rest", "rest", "json-rpc"];
+
+/// Policy choosing one of the endpoints advertised by an agent card
+pub trait EndpointSelector: std::fmt::Debug + Send + Sync {
+    /// Choose an endpoint, returning its name and configuration
+    ///
+    /// Returns `None` if no endpoint is acceptable, in which case callers fall back
+    /// to the origin the card was fetched from.
+    fn select<'a>(&self, card: &'a AgentCard) -> Option<(&'a str, &'a EndpointConfig)>;
+}
+
+/// Default endpoint policy
+///
+/// Considers only endpoints whose type is one of the configured bindings. The
+/// endpoint marked `preferred` wins; otherwise the binding listed first wins, with
+/// ties broken by endpoint name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreferredEndpointSelector {
+    bindings: Vec<String>,
+}
+
+impl PreferredEndpointSelector {
+    /// Create a selector accepting the [`SUPPORTED_BINDINGS`] in their default order
+    pub fn new() -> Self {
+        Self::with_bindings(SUPPORTED_BINDINGS.iter().copied())
+    }
+
+    /// Create a selector accepting the given binding types, most desirable first
+    pub fn with_bindings(bindings: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            bindings: bindings.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Get the accepted binding types, most desirable first
+    pub fn bindings(&self) -> &[String] {
+        &self.bindings
+    }
+}
+
+impl Default for PreferredEndpointSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EndpointSelector for PreferredEndpointSelector {
+    fn select<'a>(&self, card: &'a AgentCard) -> Option<(&'a str, &'a EndpointConfig)> {
+        card.endpoints
+            .iter()
+            .filter_map(|(name, endpoint)| {
+                let rank = self
+                    .bindings
+                    .iter()
+                    .position(|binding| binding.eq_ignore_ascii_case(&endpoint.endpoint_type))?;
+                Some(((!endpoint.preferred, rank, name), (name.as_str(), endpoint)))
+            })
+            .min_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, selected)| selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::protocol::AgentCapabilities;
+
+    use super::*;
+
+    #[test]
+    fn test_preferred_endpoint_selector() {
+        let card = AgentCard::new("agent", "Test agent", AgentCapabilities::new())
+            .with_endpoint(
+                "b-rpc",
+                EndpointConfig::new("wss://a.example.com", "json-rpc"),
+            )
+            .with_endpoint(
+                "a-rpc",
+                EndpointConfig::new("wss://b.example.com", "json-rpc"),
+            )
+            .with_endpoint("grpc", EndpointConfig::new("https://c.example.com", "grpc"));
+        let selector = PreferredEndpointSelector::new();
+
+        // Unsupported bindings are skipped; ties are broken by name
+        assert_eq!(selector.select(&card).unwrap().0, "a-rpc");
+
+        let card = card.with_endpoint(
+            "http",
+            EndpointConfig::new("https://d.example.com", "http+json"),
+        );
+        assert_eq!(selector.select(&card).unwrap().0, "http");
+
+        let card = card.with_endpoint(
+            "b-rpc",
+            EndpointConfig::new("wss://a.example.com", "json-rpc").preferred(),
+        );
+        assert_eq!(selector.select(&card).unwrap().0, "b-rpc");
+
+        let grpc = PreferredEndpointSelector::with_bindings(["grpc"]);
+        assert_eq!(grpc.select(&card).unwrap().0, "grpc");
+        assert!(PreferredEndpointSelector::with_bindings(["mqtt"])
+            .select(&card)
+            .is_none());
+    }
+}
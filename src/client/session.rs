@@ -0,0 +1,288 @@
+//! Conversation persistence
+//!
+//! A [`Conversation`] captures the client-side state of a multi-turn exchange with an
+//! agent. Saving it to a [`SessionStore`] lets applications resume the conversation
+//! after a restart.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::protocol::{error::A2AError, Message, Task};
+
+/// Client-side state of a conversation with an agent
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Conversation {
+    /// Context ID assigned to the conversation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_id: Option<String>,
+
+    /// Messages exchanged so far, oldest first
+    #[serde(default)]
+    pub messages: Vec<Message>,
+
+    /// IDs of tasks that have not reached a terminal state
+    #[serde(default)]
+    pub open_tasks: Vec<String>,
+}
+
+impl Conversation {
+    /// Create an empty conversation
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty conversation within an existing context
+    pub fn with_context_id(context_id: impl Into<String>) -> Self {
+        Self {
+            context_id: Some(context_id.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Record a message, adopting its context ID if none is known yet
+    pub fn record_message(&mut self, message: Message) {
+        if self.context_id.is_none() {
+            self.context_id = message.context_id.clone();
+        }
+        self.messages.push(message);
+    }
+
+    /// Record the latest state of a task
+    ///
+    /// The task is tracked as open until it reaches a terminal state.
+    pub fn record_task(&mut self, task: &Task) {
+        if self.context_id.is_none() {
+            self.context_id = task.context_id.clone();
+        }
+
+        let open = self.open_tasks.iter().position(|id| *id == task.id);
+        match (open, task.is_terminal()) {
+            (None, false) => self.open_tasks.push(task.id.clone()),
+            (Some(index), true) => {
+                self.open_tasks.remove(index);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Storage for conversations, keyed by a session ID chosen by the application
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// Save a conversation, replacing any previous state of the session
+    async fn save(&self, session_id: &str, conversation: &Conversation) -> Result<(), A2AError>;
+
+    /// Load a conversation, returning `None` if the session is unknown
+    async fn load(&self, session_id: &str) -> Result<Option<Conversation>, A2AError>;
+
+    /// Delete a session, succeeding if it does not exist
+    async fn delete(&self, session_id: &str) -> Result<(), A2AError>;
+}
+
+#[async_trait]
+impl<S: SessionStore + ?Sized> SessionStore for Arc<S> {
+    async fn save(&self, session_id: &str, conversation: &Conversation) -> Result<(), A2AError> {
+        (**self).save(session_id, conversation).await
+    }
+
+    async fn load(&self, session_id: &str) -> Result<Option<Conversation>, A2AError> {
+        (**self).load(session_id).await
+    }
+
+    async fn delete(&self, session_id: &str) -> Result<(), A2AError> {
+        (**self).delete(session_id).await
+    }
+}
+
+/// Session store keeping conversations in memory
+///
+/// Useful for tests and long-running processes that do not need to survive restarts.
+#[derive(Debug, Clone, Default)]
+pub struct InMemorySessionStore {
+    sessions: Arc<RwLock<HashMap<String, Conversation>>>,
+}
+
+impl InMemorySessionStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SessionStore for InMemorySessionStore {
+    async fn save(&self, session_id: &str, conversation: &Conversation) -> Result<(), A2AError> {
+        self.sessions
+            .write()
+            .await
+            .insert(session_id.to_string(), conversation.clone());
+        Ok(())
+    }
+
+    async fn load(&self, session_id: &str) -> Result<Option<Conversation>, A2AError> {
+        Ok(self.sessions.read().await.get(session_id).cloned())
+    }
+
+    async fn delete(&self, session_id: &str) -> Result<(), A2AError> {
+        self.sessions.write().await.remove(session_id);
+        Ok(())
+    }
+}
+
+/// Session store keeping each conversation in a JSON file within a directory
+///
+/// Session IDs may only contain ASCII letters, digits, `-`, `_`, and `.` so they map
+/// safely to file names.
+#[derive(Debug, Clone)]
+pub struct FileSessionStore {
+    directory: PathBuf,
+}
+
+impl FileSessionStore {
+    /// Create a store in the given directory, which is created on first save
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// Get the directory holding the session files
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    fn path(&self, session_id: &str) -> Result<PathBuf, A2AError> {
+        let valid = !session_id.is_empty()
+            && !session_id.starts_with('.')
+            && session_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(A2AError::Validation(format!(
+                "Invalid session ID: {:?}",
+                session_id
+            )));
+        }
+        Ok(self.directory.join(format!("{}.json", session_id)))
+    }
+}
+
+#[async_trait]
+impl SessionStore for FileSessionStore {
+    async fn save(&self, session_id: &str, conversation: &Conversation) -> Result<(), A2AError> {
+        let path = self.path(session_id)?;
+        let contents = serde_json::to_vec_pretty(conversation)?;
+
+        tokio::fs::create_dir_all(&self.directory)
+            .await
+            .map_err(|e| io_error("create session directory", &self.directory, e))?;
+
+        // Write to a temporary file first so a crash never leaves a truncated session
+        let temp = path.with_extension("json.tmp");
+        tokio::fs::write(&temp, contents)
+            .await
+            .map_err(|e| io_error("write session", &temp, e))?;
+        tokio::fs::rename(&temp, &path)
+            .await
+            .map_err(|e| io_error("write session", &path, e))
+    }
+
+    async fn load(&self, session_id: &str) -> Result<Option<Conversation>, A2AError> {
+        let path = self.path(session_id)?;
+        match tokio::fs::read(&path).await {
+            Ok(contents) => Ok(Some(serde_json::from_slice(&contents)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error("read session", &path, e)),
+        }
+    }
+
+    async fn delete(&self, session_id: &str) -> Result<(), A2AError> {
+        let path = self.path(session_id)?;
+        match tokio::fs::remove_file(&path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(io_error("delete session", &path, e))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn io_error(action: &str, path: &Path, error: std::io::Error) -> A2AError {
+    A2AError::Other(format!(
+        "Failed to {} {}: {}",
+        action,
+        path.display(),
+        error
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::protocol::TaskStatus;
+
+    use super::*;
+
+    fn conversation() -> Conversation {
+        let mut conversation = Conversation::new();
+        let mut message = Message::user("Hello");
+        message.context_id = Some("ctx-1".to_string());
+        conversation.record_message(message);
+        conversation.record_task(&Task::new("task-1", Message::user("Hello")));
+        conversation
+    }
+
+    #[test]
+    fn test_record_task() {
+        let mut conversation = conversation();
+        assert_eq!(conversation.context_id.as_deref(), Some("ctx-1"));
+        assert_eq!(conversation.open_tasks, vec!["task-1"]);
+
+        let task = Task::new("task-1", Message::user("Hello")).with_status(TaskStatus::Completed);
+        conversation.record_task(&task);
+        assert!(conversation.open_tasks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store() {
+        let store = InMemorySessionStore::new();
+        assert!(store.load("session").await.unwrap().is_none());
+
+        store.save("session", &conversation()).await.unwrap();
+        assert_eq!(store.load("session").await.unwrap(), Some(conversation()));
+
+        store.delete("session").await.unwrap();
+        assert!(store.load("session").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_file_store() {
+        let directory = std::env::temp_dir().join(format!("a2a-sessions-{}", uuid::Uuid::now_v7()));
+        let store = FileSessionStore::new(&directory);
+
+        store.save("session-1", &conversation()).await.unwrap();
+        let reopened = FileSessionStore::new(&directory);
+        assert_eq!(
+            reopened.load("session-1").await.unwrap(),
+            Some(conversation())
+        );
+
+        reopened.delete("session-1").await.unwrap();
+        reopened.delete("session-1").await.unwrap();
+        assert!(reopened.load("session-1").await.unwrap().is_none());
+
+        assert!(matches!(
+            store.load("../escape").await,
+            Err(A2AError::Validation(_))
+        ));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,475 @@
+//! Adapters for consuming streams of A2A events
+//!
+//! Streaming responses may deliver duplicate or out-of-order events, typically after a
+//! reconnection replays part of the stream, or go silent without closing. The adapters
+//! in this module clean up such streams before they reach application code, and notice
+//! when application code stops listening.
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashSet, VecDeque},
+    fmt, future,
+    hash::{Hash, Hasher},
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use futures::{stream, Stream, StreamExt};
+
+use crate::{client::text::TextStream, codec::SseEvent, protocol::error::A2AError};
+
+/// How duplicate events are detected
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DedupPolicy {
+    /// Deliver every event
+    Disabled,
+
+    /// Drop events repeating an already delivered event ID and payload
+    ///
+    /// Events without an ID are never dropped. Events that merely inherit the previous
+    /// ID (per SSE last-event-id semantics) are only dropped if their payload is identical.
+    #[default]
+    EventId,
+
+    /// Drop events whose payload is identical to an already delivered event
+    Payload,
+}
+
+/// How events arriving out of order are handled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OrderingPolicy {
+    /// Deliver events in the order they are received
+    #[default]
+    AsReceived,
+
+    /// Drop events whose sequence is older than the last delivered event
+    ///
+    /// The sequence is taken from numeric event IDs, or else from the event timestamp
+    /// (`status.timestamp` or `timestamp` in the payload). Events without either are
+    /// always delivered.
+    DropStale,
+}
+
+/// Configuration for sequence tracking on an event stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceConfig {
+    /// Duplicate detection policy
+    pub dedup: DedupPolicy,
+
+    /// Ordering policy
+    pub ordering: OrderingPolicy,
+
+    /// Number of recent events remembered for duplicate detection
+    pub window: usize,
+}
+
+impl Default for SequenceConfig {
+    fn default() -> Self {
+        Self {
+            dedup: DedupPolicy::default(),
+            ordering: OrderingPolicy::default(),
+            window: 1024,
+        }
+    }
+}
+
+impl SequenceConfig {
+    /// Create a configuration with the default policies
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the duplicate detection policy
+    pub fn with_dedup(mut self, dedup: DedupPolicy) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Set the ordering policy
+    pub fn with_ordering(mut self, ordering: OrderingPolicy) -> Self {
+        self.ordering = ordering;
+        self
+    }
+
+    /// Set the number of recent events remembered for duplicate detection
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window;
+        self
+    }
+}
+
+/// Position of an event in the stream, used for ordering
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Sequence {
+    Id(u64),
+    Timestamp(DateTime<Utc>),
+}
+
+/// Tracks delivered events to filter duplicates and stale events
+///
+/// This is the state behind [`EventStreamExt::sequenced`], exposed for consumers that
+/// drive event delivery themselves.
+#[derive(Debug)]
+pub struct SequenceTracker {
+    config: SequenceConfig,
+    seen: HashSet<u64>,
+    recent: VecDeque<u64>,
+    last_id: Option<u64>,
+    last_timestamp: Option<DateTime<Utc>>,
+}
+
+impl SequenceTracker {
+    /// Create a new tracker
+    pub fn new(config: SequenceConfig) -> Self {
+        Self {
+            config,
+            seen: HashSet::new(),
+            recent: VecDeque::new(),
+            last_id: None,
+            last_timestamp: None,
+        }
+    }
+
+    /// Check whether an event should be delivered, recording it if so
+    ///
+    /// Heartbeats and retry hints are always delivered.
+    pub fn accept(&mut self, event: &SseEvent) -> bool {
+        if event.is_control() {
+            return true;
+        }
+
+        let fingerprint = match self.config.dedup {
+            DedupPolicy::Disabled => None,
+            DedupPolicy::EventId => event
+                .id
+                .as_ref()
+                .map(|id| Self::fingerprint(Some(id), event)),
+            DedupPolicy::Payload => Some(Self::fingerprint(None, event)),
+        };
+
+        if fingerprint.is_some_and(|f| self.seen.contains(&f)) {
+            tracing::debug!(id = ?event.id, kind = %event.kind, "Dropping duplicate event");
+            return false;
+        }
+
+        if self.config.ordering == OrderingPolicy::DropStale {
+            match Self::sequence(event) {
+                Some(Sequence::Id(id)) => {
+                    if self.last_id.is_some_and(|last| id < last) {
+                        tracing::debug!(id, "Dropping stale event");
+                        return false;
+                    }
+                    self.last_id = Some(id);
+                }
+                Some(Sequence::Timestamp(ts)) => {
+                    if self.last_timestamp.is_some_and(|last| ts < last) {
+                        tracing::debug!(timestamp = %ts, "Dropping stale event");
+                        return false;
+                    }
+                    self.last_timestamp = Some(ts);
+                }
+                None => {}
+            }
+        }
+
+        if let Some(fingerprint) = fingerprint {
+            self.remember(fingerprint);
+        }
+
+        true
+    }
+
+    /// Remember a fingerprint, evicting the oldest beyond the window
+    fn remember(&mut self, fingerprint: u64) {
+        if self.config.window == 0 {
+            return;
+        }
+
+        self.seen.insert(fingerprint);
+        self.recent.push_back(fingerprint);
+
+        while self.recent.len() > self.config.window {
+            if let Some(evicted) = self.recent.pop_front() {
+                self.seen.remove(&evicted);
+            }
+        }
+    }
+
+    /// Hash the identifying parts of an event
+    fn fingerprint(id: Option<&String>, event: &SseEvent) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        id.hash(&mut hasher);
+        event.kind.hash(&mut hasher);
+        event.payload.to_string().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Extract the sequence of an event
+    fn sequence(event: &SseEvent) -> Option<Sequence> {
+        if let Some(id) = event.id.as_ref().and_then(|id| id.parse().ok()) {
+            return Some(Sequence::Id(id));
+        }
+
+        event
+            .payload
+            .pointer("/status/timestamp")
+            .or_else(|| event.payload.get("timestamp"))
+            .and_then(|ts| ts.as_str())
+            .and_then(|ts| ts.parse().ok())
+            .map(Sequence::Timestamp)
+    }
+}
+
+/// Callback run with the task ID when a stream is abandoned
+type AbandonHandler = Box<dyn FnOnce(String) + Send>;
+
+/// Stream noticing when it is dropped before its task reached a terminal state
+///
+/// Created with [`EventStreamExt::on_abandon`]. The task ID is taken from the first
+/// event carrying one. A terminal event or the end of the stream disarms the guard.
+pub struct AbandonGuard<S> {
+    inner: Pin<Box<S>>,
+    task_id: Option<String>,
+    on_abandon: Option<AbandonHandler>,
+}
+
+impl<S> Stream for AbandonGuard<S>
+where
+    S: Stream<Item = Result<SseEvent, A2AError>>,
+{
+    type Item = Result<SseEvent, A2AError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = ready!(self.inner.as_mut().poll_next(cx));
+        match &item {
+            Some(Ok(event)) => {
+                if self.task_id.is_none() {
+                    self.task_id = event.task_id().map(String::from);
+                }
+                if event.is_terminal() {
+                    self.on_abandon = None;
+                }
+            }
+            Some(Err(_)) => {}
+            None => self.on_abandon = None,
+        }
+        Poll::Ready(item)
+    }
+}
+
+impl<S> Drop for AbandonGuard<S> {
+    fn drop(&mut self) {
+        if let (Some(on_abandon), Some(task_id)) = (self.on_abandon.take(), self.task_id.take()) {
+            tracing::debug!(%task_id, "Event stream dropped before the task finished");
+            on_abandon(task_id);
+        }
+    }
+}
+
+impl<S> fmt::Debug for AbandonGuard<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AbandonGuard")
+            .field("task_id", &self.task_id)
+            .field("armed", &self.on_abandon.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Extension methods for streams of A2A events
+pub trait EventStreamExt: Stream<Item = Result<SseEvent, A2AError>> + Sized {
+    /// Filter duplicate and out-of-order events according to `config`
+    ///
+    /// Errors are always passed through.
+    fn sequenced(self, config: SequenceConfig) -> impl Stream<Item = Result<SseEvent, A2AError>> {
+        let mut tracker = SequenceTracker::new(config);
+        self.filter(move |item| {
+            future::ready(match item {
+                Ok(event) => tracker.accept(event),
+                Err(_) => true,
+            })
+        })
+    }
+
+    /// Terminate the stream with [`A2AError::Timeout`] if no item arrives within `timeout`
+    ///
+    /// The timer is reset by every item, including heartbeats, so agents that send SSE
+    /// comments while working keep the stream alive.
+    fn idle_timeout(self, timeout: Duration) -> impl Stream<Item = Result<SseEvent, A2AError>> {
+        stream::unfold(Some(Box::pin(self)), move |inner| async move {
+            let mut inner = inner?;
+            match tokio::time::timeout(timeout, inner.next()).await {
+                Ok(Some(item)) => Some((item, Some(inner))),
+                Ok(None) => None,
+                Err(_) => {
+                    tracing::warn!(?timeout, "Event stream idle, terminating");
+                    Some((Err(A2AError::Timeout), None))
+                }
+            }
+        })
+    }
+
+    /// Turn the events into the text deltas they carry, e.g. to render tokens as they
+    /// arrive (see [`TextStream`])
+    fn text_deltas(self) -> TextStream<Self> {
+        TextStream::new(self)
+    }
+
+    /// Call `on_abandon` with the task ID if the stream is dropped before the task
+    /// reached a terminal state, e.g. to cancel the task (see [`AbandonGuard`])
+    fn on_abandon(self, on_abandon: impl FnOnce(String) + Send + 'static) -> AbandonGuard<Self> {
+        AbandonGuard {
+            inner: Box::pin(self),
+            task_id: None,
+            on_abandon: Some(Box::new(on_abandon)),
+        }
+    }
+}
+
+impl<S> EventStreamExt for S where S: Stream<Item = Result<SseEvent, A2AError>> {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn event(id: Option<&str>, payload: serde_json::Value) -> SseEvent {
+        SseEvent {
+            kind: "status-update".to_string(),
+            payload,
+            final_event: false,
+            id: id.map(String::from),
+        }
+    }
+
+    async fn collect(events: Vec<SseEvent>, config: SequenceConfig) -> Vec<SseEvent> {
+        futures::stream::iter(events.into_iter().map(Ok))
+            .sequenced(config)
+            .map(Result::unwrap)
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_dedup_by_event_id() {
+        let events = vec![
+            event(Some("1"), json!({"state": "working"})),
+            // Inherited ID with a new payload is not a duplicate
+            event(Some("1"), json!({"state": "completed"})),
+            // Replayed after a reconnect
+            event(Some("1"), json!({"state": "working"})),
+            event(None, json!({"state": "working"})),
+        ];
+
+        let delivered = collect(events, SequenceConfig::new()).await;
+        assert_eq!(delivered.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_dedup_by_payload() {
+        let events = vec![
+            event(Some("1"), json!({"state": "working"})),
+            event(Some("2"), json!({"state": "working"})),
+        ];
+
+        let config = SequenceConfig::new().with_dedup(DedupPolicy::Payload);
+        assert_eq!(collect(events.clone(), config).await.len(), 1);
+
+        let config = SequenceConfig::new().with_dedup(DedupPolicy::Disabled);
+        assert_eq!(collect(events, config).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_drop_stale_events() {
+        let events = vec![
+            event(Some("2"), json!({"n": 2})),
+            event(Some("1"), json!({"n": 1})),
+            event(Some("3"), json!({"n": 3})),
+            event(
+                None,
+                json!({"status": {"timestamp": "2024-01-01T00:00:02Z"}}),
+            ),
+            event(
+                None,
+                json!({"status": {"timestamp": "2024-01-01T00:00:01Z"}}),
+            ),
+        ];
+
+        let config = SequenceConfig::new().with_ordering(OrderingPolicy::DropStale);
+        let delivered = collect(events, config).await;
+
+        assert_eq!(delivered.len(), 3);
+        assert_eq!(delivered[1].id.as_deref(), Some("3"));
+    }
+
+    #[tokio::test]
+    async fn test_idle_timeout() {
+        let events = futures::stream::iter(vec![
+            Ok(event(None, json!({}))),
+            Ok(SseEvent::heartbeat("keep-alive")),
+        ])
+        .chain(futures::stream::pending());
+
+        let items: Vec<_> = events
+            .idle_timeout(Duration::from_millis(20))
+            .collect()
+            .await;
+
+        assert_eq!(items.len(), 3);
+        assert!(items[1].as_ref().unwrap().is_heartbeat());
+        assert!(matches!(items[2], Err(A2AError::Timeout)));
+    }
+
+    #[test]
+    fn test_heartbeats_bypass_dedup() {
+        let mut tracker =
+            SequenceTracker::new(SequenceConfig::new().with_dedup(DedupPolicy::Payload));
+
+        assert!(tracker.accept(&SseEvent::heartbeat("")));
+        assert!(tracker.accept(&SseEvent::heartbeat("")));
+    }
+
+    #[test]
+    fn test_dedup_window() {
+        let mut tracker = SequenceTracker::new(SequenceConfig::new().with_window(1));
+
+        let first = event(Some("1"), json!({}));
+        let second = event(Some("2"), json!({}));
+
+        assert!(tracker.accept(&first));
+        assert!(tracker.accept(&second));
+        // The first event has been evicted from the window
+        assert!(tracker.accept(&first));
+    }
+
+    #[tokio::test]
+    async fn test_on_abandon() {
+        let abandoned = Arc::new(Mutex::new(Vec::new()));
+        let guarded = |events: Vec<SseEvent>| {
+            let abandoned = abandoned.clone();
+            stream::iter(events.into_iter().map(Ok))
+                .on_abandon(move |task_id| abandoned.lock().unwrap().push(task_id))
+        };
+        let working = || event(None, json!({"taskId": "task-1", "state": "working"}));
+        let completed = event(None, json!({"taskId": "task-1", "state": "completed"}));
+
+        // Dropped after the task ID is known, before it finished
+        let mut events = guarded(vec![working(), working()]);
+        events.next().await.unwrap().unwrap();
+        drop(events);
+
+        // Finished, or exhausted without a terminal event
+        let mut events = guarded(vec![working(), completed]);
+        while events.next().await.is_some() {}
+        let mut events = guarded(vec![working()]);
+        while events.next().await.is_some() {}
+
+        // Dropped before the task ID is known
+        drop(guarded(vec![working()]));
+
+        assert_eq!(*abandoned.lock().unwrap(), ["task-1"]);
+    }
+}
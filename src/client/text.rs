@@ -0,0 +1,251 @@
+//! Text deltas of streamed responses
+//!
+//! Agents stream partial text in different ways: appending chunks to an artifact,
+//! resending the artifact with more text each time, or updating the status message.
+//! [`TextStream`] hides these differences and yields only the text that is new, so a
+//! chat UI can render tokens as they arrive:
+//!
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use tower_a2a::{client::EventStreamExt, prelude::*};
+//!
+//! # async fn example() -> Result<(), A2AError> {
+//! # let client = A2AClientBuilder::new_http("https://agent.example.com".parse().unwrap()).build()?;
+//! let events = client.send_message_stream(Message::user("Tell me a story")).await?;
+//! let mut text = events.text_deltas();
+//! while let Some(delta) = text.next().await {
+//!     print!("{}", delta?);
+//! }
+//! println!("\n---\n{}", text.text());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::Stream;
+use serde_json::Value;
+
+use crate::{codec::SseEvent, protocol::error::A2AError};
+
+/// Source of streamed text that is consolidated separately
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Source {
+    Artifact(String),
+    Status,
+}
+
+/// Stream of the text deltas in a stream of events
+///
+/// Created with [`EventStreamExt::text_deltas`](crate::client::EventStreamExt::text_deltas).
+/// Artifact updates with `append` set contribute their text as is; other updates are
+/// compared with the text seen so far and contribute what was added. An update that
+/// rewrites earlier text is yielded whole. Events without text are skipped and errors
+/// are passed through.
+pub struct TextStream<S> {
+    inner: Pin<Box<S>>,
+    sources: Vec<(Source, String)>,
+}
+
+impl<S> TextStream<S>
+where
+    S: Stream<Item = Result<SseEvent, A2AError>>,
+{
+    /// Wrap a stream of events
+    pub fn new(events: S) -> Self {
+        Self {
+            inner: Box::pin(events),
+            sources: Vec::new(),
+        }
+    }
+
+    /// Get the consolidated text received so far
+    ///
+    /// Artifact text is preferred, joined in the order the artifacts first appeared;
+    /// status message text is used for agents that stream no text artifacts.
+    pub fn text(&self) -> String {
+        let artifacts: Vec<_> = self
+            .sources
+            .iter()
+            .filter(|(source, _)| matches!(source, Source::Artifact(_)))
+            .map(|(_, text)| text.as_str())
+            .collect();
+        if !artifacts.is_empty() {
+            return artifacts.join("\n");
+        }
+        self.sources
+            .iter()
+            .find(|(source, _)| *source == Source::Status)
+            .map(|(_, text)| text.clone())
+            .unwrap_or_default()
+    }
+
+    /// Consume the rest of the stream, returning the consolidated text
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the event stream
+    pub async fn into_text(mut self) -> Result<String, A2AError> {
+        while let Some(delta) = futures::StreamExt::next(&mut self).await {
+            delta?;
+        }
+        Ok(self.text())
+    }
+
+    /// Record the text of an event, returning the delta
+    fn observe(&mut self, event: &SseEvent) -> Option<String> {
+        let payload = &event.payload;
+        let (source, parts, append) = match event.kind.as_str() {
+            "artifact-update" => {
+                let artifact = payload.get("artifact")?;
+                let id = artifact
+                    .get("artifactId")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                let append = payload
+                    .get("append")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                (
+                    Source::Artifact(id.to_string()),
+                    artifact.get("parts")?,
+                    append,
+                )
+            }
+            "status-update" => {
+                let parts = payload
+                    .pointer("/status/message/parts")
+                    .or_else(|| payload.pointer("/message/parts"))?;
+                (Source::Status, parts, false)
+            }
+            _ => return None,
+        };
+
+        let text: String = parts
+            .as_array()?
+            .iter()
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect();
+        if text.is_empty() {
+            return None;
+        }
+
+        let index = match self.sources.iter().position(|(known, _)| *known == source) {
+            Some(index) => index,
+            None => {
+                self.sources.push((source, String::new()));
+                self.sources.len() - 1
+            }
+        };
+        let consolidated = &mut self.sources[index].1;
+        if append {
+            consolidated.push_str(&text);
+            return Some(text);
+        }
+        let delta = match text.strip_prefix(consolidated.as_str()) {
+            Some(added) => added.to_string(),
+            None => text.clone(),
+        };
+        *consolidated = text;
+        (!delta.is_empty()).then_some(delta)
+    }
+}
+
+impl<S> Stream for TextStream<S>
+where
+    S: Stream<Item = Result<SseEvent, A2AError>>,
+{
+    type Item = Result<String, A2AError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match self.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(event))) => {
+                    if let Some(delta) = self.observe(&event) {
+                        return Poll::Ready(Some(Ok(delta)));
+                    }
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<S> fmt::Debug for TextStream<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextStream")
+            .field("sources", &self.sources)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{stream, StreamExt};
+    use serde_json::json;
+
+    use super::*;
+    use crate::client::EventStreamExt;
+
+    fn event(kind: &str, payload: Value) -> Result<SseEvent, A2AError> {
+        Ok(SseEvent {
+            kind: kind.to_string(),
+            payload,
+            final_event: false,
+            id: None,
+        })
+    }
+
+    fn artifact(id: &str, text: &str, append: bool) -> Result<SseEvent, A2AError> {
+        event(
+            "artifact-update",
+            json!({"artifact": {"artifactId": id, "parts": [{"text": text}]}, "append": append}),
+        )
+    }
+
+    fn status(text: &str) -> Result<SseEvent, A2AError> {
+        event(
+            "status-update",
+            json!({"status": {"state": "working", "message": {"parts": [{"text": text}]}}}),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_text_deltas() {
+        let events = stream::iter(vec![
+            status("Thinking"),
+            artifact("answer", "Once", false),
+            Ok(SseEvent::heartbeat("keep-alive")),
+            artifact("answer", " upon", true),
+            artifact("answer", "Once upon a time", false),
+            artifact("notes", "Fiction", false),
+            status("Thinking"),
+        ]);
+        let mut text = events.text_deltas();
+
+        let mut deltas = Vec::new();
+        while let Some(delta) = text.next().await {
+            deltas.push(delta.unwrap());
+        }
+        assert_eq!(deltas, ["Thinking", "Once", " upon", " a time", "Fiction"]);
+        assert_eq!(text.text(), "Once upon a time\nFiction");
+
+        let events = stream::iter(vec![status("Hel"), status("Hello"), status("Goodbye")]);
+        assert_eq!(events.text_deltas().into_text().await.unwrap(), "Goodbye");
+    }
+
+    #[tokio::test]
+    async fn test_text_deltas_pass_errors() {
+        let events = stream::iter(vec![status("Partial"), Err(A2AError::Timeout)]);
+        assert!(matches!(
+            events.text_deltas().into_text().await,
+            Err(A2AError::Timeout)
+        ));
+    }
+}
@@ -0,0 +1,245 @@
+//! Transcript export
+//!
+//! Renders the messages and tasks of a context into a portable transcript, either as
+//! canonical JSON for archiving and audits or as Markdown for humans.
+
+use std::fmt::Write as _;
+
+use serde_json::{json, Value};
+
+use crate::{
+    client::session::Conversation,
+    protocol::{error::A2AError, Artifact, Message, MessagePart, Role, Task},
+};
+
+/// Version of the JSON transcript format
+const TRANSCRIPT_VERSION: u32 = 1;
+
+/// Transcript of the messages and tasks exchanged within a context
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Transcript {
+    /// Context the transcript belongs to
+    pub context_id: Option<String>,
+
+    /// Messages exchanged outside of tasks, oldest first
+    pub messages: Vec<Message>,
+
+    /// Tasks created within the context
+    pub tasks: Vec<Task>,
+}
+
+impl Transcript {
+    /// Create an empty transcript
+    pub fn new(context_id: Option<String>) -> Self {
+        Self {
+            context_id,
+            ..Self::default()
+        }
+    }
+
+    /// Create a transcript from the messages of a conversation
+    pub fn from_conversation(conversation: &Conversation) -> Self {
+        Self {
+            context_id: conversation.context_id.clone(),
+            messages: conversation.messages.clone(),
+            tasks: Vec::new(),
+        }
+    }
+
+    /// Add a message
+    pub fn with_message(mut self, message: Message) -> Self {
+        self.messages.push(message);
+        self
+    }
+
+    /// Add a task
+    pub fn with_task(mut self, task: Task) -> Self {
+        self.tasks.push(task);
+        self
+    }
+
+    /// Render the transcript as canonical JSON
+    ///
+    /// Object keys are sorted and no insignificant whitespace is emitted, so identical
+    /// transcripts always produce identical output.
+    pub fn to_json(&self) -> Result<String, A2AError> {
+        // `Value` objects keep their keys sorted
+        let value = json!({
+            "version": TRANSCRIPT_VERSION,
+            "contextId": self.context_id,
+            "messages": serde_json::to_value(&self.messages)?,
+            "tasks": serde_json::to_value(&self.tasks)?,
+        });
+        Ok(serde_json::to_string(&value)?)
+    }
+
+    /// Render the transcript as Markdown
+    ///
+    /// Text and data artifacts are inlined, while file artifacts are rendered as links
+    /// where they have a URI.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Transcript\n");
+        if let Some(context_id) = &self.context_id {
+            let _ = writeln!(out, "\nContext: `{}`", context_id);
+        }
+
+        if !self.messages.is_empty() {
+            out.push_str("\n## Messages\n");
+            for message in &self.messages {
+                write_message(&mut out, message);
+            }
+        }
+
+        for task in &self.tasks {
+            let _ = writeln!(out, "\n## Task `{}`\n", task.id);
+            let _ = writeln!(out, "- Status: {}", json_str(&task.status));
+            let _ = writeln!(out, "- Created: {}", task.created_at.to_rfc3339());
+            if let Some(updated_at) = task.updated_at {
+                let _ = writeln!(out, "- Updated: {}", updated_at.to_rfc3339());
+            }
+
+            out.push_str("\n### Messages\n");
+            write_message(&mut out, &task.input);
+            for message in &task.history {
+                write_message(&mut out, message);
+            }
+
+            if !task.artifacts.is_empty() {
+                out.push_str("\n### Artifacts\n");
+                for artifact in &task.artifacts {
+                    write_artifact(&mut out, artifact);
+                }
+            }
+
+            if let Some(error) = &task.error {
+                let _ = writeln!(out, "\n### Error\n\n`{}`: {}", error.code, error.message);
+            }
+        }
+
+        out
+    }
+}
+
+fn write_message(out: &mut String, message: &Message) {
+    let role = match message.role {
+        Role::User => "User",
+        Role::Agent => "Agent",
+    };
+    let _ = writeln!(out, "\n**{}**:\n", role);
+    for part in &message.parts {
+        write_part(out, part);
+    }
+}
+
+fn write_artifact(out: &mut String, artifact: &Artifact) {
+    let title = artifact.name.as_deref().unwrap_or(&artifact.artifact_id);
+    let _ = writeln!(out, "\n#### {} (`{}`)\n", title, artifact.artifact_id);
+    if let Some(description) = &artifact.description {
+        let _ = writeln!(out, "_{}_\n", description);
+    }
+    for part in &artifact.parts {
+        write_part(out, part);
+    }
+}
+
+fn write_part(out: &mut String, part: &MessagePart) {
+    match part {
+        MessagePart::Text { text } => {
+            let _ = writeln!(out, "{}", text);
+        }
+        MessagePart::File { file } => {
+            let media_type = file.media_type.as_deref().unwrap_or("unknown type");
+            match (&file.file_with_uri, &file.file_with_bytes) {
+                (Some(uri), _) => {
+                    let _ = writeln!(out, "- [{}]({}) ({})", file.name, uri, media_type);
+                }
+                (None, Some(bytes)) => {
+                    let _ = writeln!(
+                        out,
+                        "- {} ({}, {} bytes inline)",
+                        file.name,
+                        media_type,
+                        bytes.len() / 4 * 3
+                    );
+                }
+                (None, None) => {
+                    let _ = writeln!(out, "- {} ({})", file.name, media_type);
+                }
+            }
+        }
+        MessagePart::Data { data } => {
+            let pretty = serde_json::to_string_pretty(data).unwrap_or_default();
+            let _ = writeln!(out, "```json\n{}\n```", pretty);
+        }
+    }
+}
+
+/// Render a serializable enum as its wire name
+fn json_str<T: serde::Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(Value::String(s)) => s,
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::protocol::TaskStatus;
+
+    use super::*;
+
+    fn transcript() -> Transcript {
+        let task = Task::new("task-1", Message::user("Summarize the report"))
+            .with_status(TaskStatus::Completed)
+            .with_history_message(Message::agent("Here is the summary"))
+            .with_artifact(Artifact {
+                artifact_id: "summary".to_string(),
+                name: Some("Summary".to_string()),
+                description: None,
+                parts: vec![
+                    MessagePart::text("The report is positive."),
+                    MessagePart::file_with_type(
+                        "report.pdf",
+                        "https://example.com/report.pdf",
+                        "application/pdf",
+                    ),
+                ],
+                metadata: None,
+                extensions: Vec::new(),
+                extra: Default::default(),
+            });
+
+        Transcript::new(Some("ctx-1".to_string()))
+            .with_message(Message::user("Hello"))
+            .with_task(task)
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = transcript().to_markdown();
+        assert!(markdown.contains("Context: `ctx-1`"));
+        assert!(markdown.contains("## Task `task-1`"));
+        assert!(markdown.contains("- Status: completed"));
+        assert!(markdown.contains("The report is positive."));
+        assert!(
+            markdown.contains("- [report.pdf](https://example.com/report.pdf) (application/pdf)")
+        );
+    }
+
+    #[test]
+    fn test_canonical_json() {
+        let transcript = transcript();
+        let first = transcript.to_json().unwrap();
+        assert_eq!(first, transcript.to_json().unwrap());
+
+        let value: Value = serde_json::from_str(&first).unwrap();
+        assert_eq!(value["version"], 1);
+        assert_eq!(value["contextId"], "ctx-1");
+        assert_eq!(value["tasks"][0]["id"], "task-1");
+
+        let keys: Vec<_> = value.as_object().unwrap().keys().cloned().collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+    }
+}
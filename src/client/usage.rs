@@ -0,0 +1,188 @@
+//! Aggregate usage accounting
+//!
+//! A [`UsageTracker`] adds up the [`UsageInfo`] reported in the tasks returned to one
+//! or more clients, per account, so platform teams can bill internal users. Accounts
+//! are named by a request header, e.g. the tenant ID, or by the agent URL:
+//!
+//! ```rust,no_run
+//! use tower_a2a::{client::UsageTracker, prelude::*};
+//!
+//! let tracker = UsageTracker::new().with_account_header("X-Tenant-Id");
+//! let url = "https://agent.example.com".parse().unwrap();
+//! let client = A2AClientBuilder::new_http(url)
+//!     .with_default_header("X-Tenant-Id", "acme")
+//!     .with_usage_tracker(tracker.clone())
+//!     .build()
+//!     .unwrap();
+//!
+//! // At the end of a billing period
+//! for (account, usage) in tracker.take() {
+//!     println!("{}: {} tokens", account, usage.total_tokens);
+//! }
+//! ```
+//!
+//! Only tasks returned by request/response operations are counted; tasks assembled
+//! from streams are not. Agents report the usage of a task so far, so fetching a task
+//! again only records what it consumed since the last time the tracker saw it.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    protocol::usage::{UsageInfo, UsageKeys},
+    service::{A2AResponse, RequestContext},
+};
+
+/// Usage totals per account, shared by clones
+#[derive(Debug, Clone, Default)]
+pub struct UsageTracker {
+    account_header: Option<String>,
+    totals: Arc<Mutex<HashMap<String, UsageInfo>>>,
+    seen: Arc<Mutex<HashMap<String, UsageInfo>>>,
+}
+
+impl UsageTracker {
+    /// Create a tracker accounting usage per agent URL
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Account usage to the value of a request header, e.g. `X-Tenant-Id`
+    ///
+    /// Requests without the header are accounted to the agent URL.
+    pub fn with_account_header(mut self, name: impl Into<String>) -> Self {
+        self.account_header = Some(name.into());
+        self
+    }
+
+    /// Add usage to an account
+    pub fn record(&self, account: &str, usage: UsageInfo) {
+        if let Ok(mut totals) = self.totals.lock() {
+            *totals.entry(account.to_string()).or_default() += usage;
+        }
+    }
+
+    /// Get the usage of one account
+    pub fn account(&self, account: &str) -> UsageInfo {
+        self.totals
+            .lock()
+            .ok()
+            .and_then(|totals| totals.get(account).copied())
+            .unwrap_or_default()
+    }
+
+    /// Get the usage of all accounts
+    pub fn accounts(&self) -> HashMap<String, UsageInfo> {
+        self.totals
+            .lock()
+            .map(|totals| totals.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get the usage summed over all accounts
+    pub fn total(&self) -> UsageInfo {
+        let mut total = UsageInfo::default();
+        for usage in self.accounts().into_values() {
+            total += usage;
+        }
+        total
+    }
+
+    /// Take the usage of all accounts, starting over from zero
+    pub fn take(&self) -> HashMap<String, UsageInfo> {
+        self.totals
+            .lock()
+            .map(|mut totals| std::mem::take(&mut *totals))
+            .unwrap_or_default()
+    }
+
+    /// Get the account a request is billed to
+    pub(crate) fn account_for(&self, context: &RequestContext) -> String {
+        self.account_header
+            .as_ref()
+            .and_then(|name| {
+                context
+                    .metadata
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            })
+            .map(|(_, value)| value.clone())
+            .unwrap_or_else(|| context.agent_url.to_string())
+    }
+
+    /// Record the usage reported in a response since the task was last seen, if any
+    pub(crate) fn observe(&self, account: &str, keys: &UsageKeys, response: &A2AResponse) {
+        let A2AResponse::Task(task) = response else {
+            return;
+        };
+        let Some(usage) = keys.extract(task) else {
+            return;
+        };
+        let delta = match self.seen.lock() {
+            Ok(mut seen) => match seen.insert(task.id.clone(), usage) {
+                Some(earlier) => usage.since(&earlier),
+                None => usage,
+            },
+            Err(_) => return,
+        };
+        self.record(account, delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        client::A2AClientBuilder,
+        protocol::{Message, Task},
+        transport::{mock::MockTransport, TransportResponse},
+    };
+
+    #[tokio::test]
+    async fn test_usage_tracking() {
+        let calls = Arc::new(std::sync::atomic::AtomicU64::new(0));
+        let transport = MockTransport::new(move |_| {
+            let turns = calls
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+                .min(1)
+                + 1;
+            let mut task = Task::new("task-123", Message::user("Hello"));
+            task.extra.insert(
+                "metadata".into(),
+                json!({"usage": {"input_tokens": 100 * turns, "output_tokens": 20 * turns}}),
+            );
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+        let tracker = UsageTracker::new().with_account_header("x-tenant-id");
+        let mut client = A2AClientBuilder::new("mock://agent".parse().unwrap())
+            .with_transport(transport)
+            .with_default_header("X-Tenant-Id", "acme")
+            .with_usage_tracker(tracker.clone())
+            .build()
+            .unwrap();
+
+        client.get_task("task-123".into()).await.unwrap();
+        client.send_message(Message::user("Again")).await.unwrap();
+        client.get_task("task-123".into()).await.unwrap();
+        tracker.record("mock://agent", UsageInfo::default());
+
+        assert_eq!(tracker.account("acme").input_tokens, 200);
+        assert_eq!(tracker.total().total_tokens, 240);
+
+        tracker.record(
+            "mock://agent",
+            UsageInfo {
+                total_tokens: u64::MAX,
+                ..Default::default()
+            },
+        );
+        assert_eq!(tracker.total().total_tokens, u64::MAX);
+        assert_eq!(tracker.take().len(), 2);
+        assert_eq!(tracker.total(), UsageInfo::default());
+    }
+}
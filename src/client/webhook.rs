@@ -0,0 +1,546 @@
+//! Receiving push notifications
+//!
+//! Agents that support push notifications call a webhook registered with
+//! [`RegisterWebhook`](crate::protocol::operation::A2AOperation::RegisterWebhook)
+//! instead of keeping a stream open. [`WebhookService`] is a Tower service receiving
+//! those calls: it checks the notification token and any [`WebhookVerifier`]s, decodes
+//! the body into a [`StreamEvent`], and hands it to a [`NotificationHandler`]:
+//!
+//! ```rust
+//! use tower_a2a::{
+//!     client::webhook::WebhookService,
+//!     protocol::push::{InMemoryReplayCache, ReplayGuard},
+//! };
+//!
+//! let (service, events) = WebhookService::channel(64);
+//! let service = service
+//!     .with_token("registered-token")
+//!     .with_verifier(ReplayGuard::new(InMemoryReplayCache::new()));
+//!
+//! # async fn consume(
+//! #     mut events: tokio::sync::broadcast::Receiver<tower_a2a::protocol::event::StreamEvent>,
+//! # ) {
+//! while let Ok(event) = events.recv().await {
+//!     println!("{:?} is now {:?}", event.task_id(), event);
+//! }
+//! # }
+//! ```
+//!
+//! The service takes `http::Request`s with any body, so it can be mounted with axum's
+//! `route_service` or served by hyper through `hyper_util::service::TowerToHyperService`.
+//! Deliveries are answered with `200 OK` once handled, `401` if they fail a check, `400`
+//! if they cannot be decoded, and `500` if the handler fails, so the agent retries.
+
+use std::{
+    convert::Infallible,
+    fmt,
+    future::{poll_fn, Future},
+    pin::{pin, Pin},
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use bytes::Buf;
+use hmac::{Hmac, Mac};
+use hyper::{body::Body, HeaderMap, Method, Request, Response, StatusCode};
+use serde_json::Value;
+use sha2::Sha256;
+use tokio::sync::{broadcast, mpsc};
+use tower_service::Service;
+
+use crate::{
+    codec::SseEvent,
+    protocol::{
+        consts::headers,
+        error::A2AError,
+        event::StreamEvent,
+        push::{DeliveryStamp, ReplayCache, ReplayGuard},
+    },
+};
+
+/// Default limit of a notification body
+const DEFAULT_MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// Receiver of decoded push notifications
+#[async_trait]
+pub trait NotificationHandler: Send + Sync {
+    /// Handle a notification
+    ///
+    /// Returning an error answers the delivery with `500`, so the agent retries it.
+    async fn handle(&self, event: StreamEvent) -> Result<(), A2AError>;
+}
+
+#[async_trait]
+impl<F> NotificationHandler for F
+where
+    F: Fn(StreamEvent) -> Result<(), A2AError> + Send + Sync,
+{
+    async fn handle(&self, event: StreamEvent) -> Result<(), A2AError> {
+        self(event)
+    }
+}
+
+#[async_trait]
+impl NotificationHandler for broadcast::Sender<StreamEvent> {
+    /// Broadcast the notification, dropping it if nobody is subscribed
+    async fn handle(&self, event: StreamEvent) -> Result<(), A2AError> {
+        if self.send(event).is_err() {
+            tracing::debug!("Dropping push notification without subscribers");
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NotificationHandler for mpsc::Sender<StreamEvent> {
+    /// Queue the notification, waiting for room in the channel
+    async fn handle(&self, event: StreamEvent) -> Result<(), A2AError> {
+        self.send(event)
+            .await
+            .map_err(|_| A2AError::Other("Push notification receiver was dropped".into()))
+    }
+}
+
+/// Check of an incoming delivery, e.g. of its signature
+#[async_trait]
+pub trait WebhookVerifier: Send + Sync {
+    /// Accept or reject a delivery by its headers and raw body
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Auth` to reject the delivery
+    async fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), A2AError>;
+
+    /// Undo what [`verify`](Self::verify) recorded for a delivery that was then not
+    /// handled, e.g. because it could not be decoded or the handler failed
+    ///
+    /// Does nothing by default.
+    async fn release(&self, _headers: &HeaderMap) {}
+}
+
+#[async_trait]
+impl<F> WebhookVerifier for F
+where
+    F: Fn(&HeaderMap, &[u8]) -> Result<(), A2AError> + Send + Sync,
+{
+    async fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), A2AError> {
+        self(headers, body)
+    }
+}
+
+#[async_trait]
+impl<C: ReplayCache> WebhookVerifier for ReplayGuard<C> {
+    /// Reject deliveries without a fresh, unused delivery stamp
+    async fn verify(&self, headers: &HeaderMap, _body: &[u8]) -> Result<(), A2AError> {
+        ReplayGuard::verify(self, &stamp_of(headers)?).await
+    }
+
+    /// Forget the nonce of a delivery that was not handled, so it can be redelivered
+    async fn release(&self, headers: &HeaderMap) {
+        if let Ok(stamp) = stamp_of(headers) {
+            ReplayGuard::release(self, &stamp).await;
+        }
+    }
+}
+
+/// Read the delivery stamp of a request
+fn stamp_of(headers: &HeaderMap) -> Result<DeliveryStamp, A2AError> {
+    DeliveryStamp::from_headers(
+        headers
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?))),
+    )
+}
+
+/// Check a received token against the expected one in constant time
+///
+/// Both are hashed with the expected token as key, so neither the position of the
+/// first difference nor the length of the expected token is revealed.
+fn token_matches(expected: &str, received: &[u8]) -> bool {
+    let mac = |data: &[u8]| {
+        let mut mac = Hmac::<Sha256>::new_from_slice(expected.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(data);
+        mac
+    };
+    mac(received)
+        .verify_slice(&mac(expected.as_bytes()).finalize().into_bytes())
+        .is_ok()
+}
+
+/// Tower service receiving push notifications
+///
+/// Only `POST` deliveries are accepted. Bodies may be a task, a message, or an event
+/// tagged with its `kind`, a stream response wrapping one of them (e.g.
+/// `{"statusUpdate": {...}}`), or a JSON-RPC envelope carrying either in its `params`
+/// or `result`. Clones share the handler and verifiers.
+#[derive(Clone)]
+pub struct WebhookService {
+    handler: Arc<dyn NotificationHandler>,
+    token: Option<String>,
+    verifiers: Vec<Arc<dyn WebhookVerifier>>,
+    max_body_size: usize,
+}
+
+impl WebhookService {
+    /// Create a service handing notifications to a handler
+    pub fn new(handler: impl NotificationHandler + 'static) -> Self {
+        Self {
+            handler: Arc::new(handler),
+            token: None,
+            verifiers: Vec::new(),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+
+    /// Create a service broadcasting notifications, buffering up to `capacity` per
+    /// subscriber
+    ///
+    /// Further subscribers can be created with `resubscribe` on the returned receiver.
+    pub fn channel(capacity: usize) -> (Self, broadcast::Receiver<StreamEvent>) {
+        let (sender, receiver) = broadcast::channel(capacity);
+        (Self::new(sender), receiver)
+    }
+
+    /// Require the token registered with the webhook in the
+    /// [`NOTIFICATION_TOKEN`](headers::NOTIFICATION_TOKEN) header
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Add a check deliveries must pass, e.g. a signature or replay check
+    ///
+    /// Verifiers run in the order they were added, after the token check.
+    pub fn with_verifier(mut self, verifier: impl WebhookVerifier + 'static) -> Self {
+        self.verifiers.push(Arc::new(verifier));
+        self
+    }
+
+    /// Limit the size of notification bodies, 1 MiB by default
+    pub fn with_max_body_size(mut self, max: usize) -> Self {
+        self.max_body_size = max;
+        self
+    }
+
+    /// Check, decode, and handle a delivery
+    async fn receive<B>(&self, request: Request<B>) -> Result<(), (StatusCode, String)>
+    where
+        B: Body,
+        B::Error: fmt::Display,
+    {
+        if request.method() != Method::POST {
+            return Err((
+                StatusCode::METHOD_NOT_ALLOWED,
+                "Push notifications must be POSTed".into(),
+            ));
+        }
+
+        let (parts, body) = request.into_parts();
+        if let Some(token) = &self.token {
+            let received = parts.headers.get(headers::NOTIFICATION_TOKEN);
+            if !received.is_some_and(|received| token_matches(token, received.as_bytes())) {
+                return Err((
+                    StatusCode::UNAUTHORIZED,
+                    "Missing or invalid notification token".into(),
+                ));
+            }
+        }
+
+        let body = read_body(body, self.max_body_size).await?;
+        for (i, verifier) in self.verifiers.iter().enumerate() {
+            if let Err(e) = verifier.verify(&parts.headers, &body).await {
+                self.release(&self.verifiers[..i], &parts.headers).await;
+                return Err(rejection(e));
+            }
+        }
+
+        // Verifiers only record deliveries that were handled
+        let handled = match decode_notification(&body) {
+            Ok(event) => self.handler.handle(event).await.map_err(|e| {
+                tracing::warn!(error = %e, "Push notification handler failed");
+                (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+            }),
+            Err(e) => Err(rejection(e)),
+        };
+        if handled.is_err() {
+            self.release(&self.verifiers, &parts.headers).await;
+        }
+        handled
+    }
+
+    /// Undo the records of verifiers for a delivery that was not handled
+    async fn release(&self, verifiers: &[Arc<dyn WebhookVerifier>], headers: &HeaderMap) {
+        for verifier in verifiers {
+            verifier.release(headers).await;
+        }
+    }
+}
+
+impl fmt::Debug for WebhookService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookService")
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field("verifiers", &self.verifiers.len())
+            .field("max_body_size", &self.max_body_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> Service<Request<B>> for WebhookService
+where
+    B: Body + Send + 'static,
+    B::Data: Send,
+    B::Error: fmt::Display,
+{
+    type Response = Response<String>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move {
+            let (status, body) = match service.receive(request).await {
+                Ok(()) => (StatusCode::OK, String::new()),
+                Err((status, message)) => {
+                    tracing::debug!(%status, %message, "Rejected push notification");
+                    (status, message)
+                }
+            };
+            let mut response = Response::new(body);
+            *response.status_mut() = status;
+            Ok(response)
+        })
+    }
+}
+
+/// Map a failed check or decode to the status answering it
+fn rejection(error: A2AError) -> (StatusCode, String) {
+    let status = match error {
+        A2AError::Auth(_) => StatusCode::UNAUTHORIZED,
+        _ => StatusCode::BAD_REQUEST,
+    };
+    (status, error.to_string())
+}
+
+/// Read a request body, failing past the size limit
+async fn read_body<B>(body: B, max: usize) -> Result<Vec<u8>, (StatusCode, String)>
+where
+    B: Body,
+    B::Error: fmt::Display,
+{
+    let mut body = pin!(body);
+    let mut bytes = Vec::new();
+    while let Some(frame) = poll_fn(|cx| body.as_mut().poll_frame(cx)).await {
+        let frame = frame.map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Failed to read notification body: {}", e),
+            )
+        })?;
+        let Ok(mut data) = frame.into_data() else {
+            continue;
+        };
+        if bytes.len() + data.remaining() > max {
+            return Err((
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("Notification body exceeds {} bytes", max),
+            ));
+        }
+        while data.has_remaining() {
+            let chunk = data.chunk();
+            bytes.extend_from_slice(chunk);
+            let read = chunk.len();
+            data.advance(read);
+        }
+    }
+    Ok(bytes)
+}
+
+/// Decode the body of a push notification
+///
+/// JSON-RPC envelopes are unwrapped first. Payloads tagged with a `kind` are decoded
+/// as that event, stream responses by the key wrapping the payload, and anything else
+/// as a task.
+///
+/// # Errors
+///
+/// Returns `A2AError::Protocol` if the body is not a notification
+pub fn decode_notification(body: &[u8]) -> Result<StreamEvent, A2AError> {
+    let mut payload: Value = serde_json::from_slice(body)
+        .map_err(|e| A2AError::Protocol(format!("Invalid push notification: {}", e)))?;
+
+    if payload.get("jsonrpc").is_some() {
+        let mut inner = payload
+            .as_object_mut()
+            .and_then(|envelope| {
+                envelope
+                    .remove("params")
+                    .or_else(|| envelope.remove("result"))
+            })
+            .ok_or_else(|| {
+                A2AError::Protocol("Push notification envelope has no payload".into())
+            })?;
+        if let Some(result) = inner.get_mut("result").map(Value::take) {
+            inner = result;
+        }
+        payload = inner;
+    }
+
+    const WRAPPERS: [(&str, &str); 4] = [
+        ("task", "task"),
+        ("message", "message"),
+        ("statusUpdate", "status-update"),
+        ("artifactUpdate", "artifact-update"),
+    ];
+    let (kind, payload) = match payload.get("kind").and_then(Value::as_str) {
+        Some(kind) => (kind.to_string(), payload),
+        None => match WRAPPERS
+            .iter()
+            .find_map(|(key, kind)| Some((*kind, payload.get(key)?.clone())))
+        {
+            Some((kind, inner)) if payload.as_object().is_some_and(|o| o.len() == 1) => {
+                (kind.to_string(), inner)
+            }
+            _ => ("task".to_string(), payload),
+        },
+    };
+
+    StreamEvent::try_from(SseEvent {
+        kind,
+        payload,
+        final_event: false,
+        id: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::protocol::{message::Message, push::InMemoryReplayCache, task::Task, TaskStatus};
+
+    async fn send(service: &WebhookService, request: Request<String>) -> StatusCode {
+        service.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_webhook_delivery() {
+        let (service, mut events) = WebhookService::channel(8);
+        let service = service
+            .with_token("secret")
+            .with_verifier(ReplayGuard::new(InMemoryReplayCache::new()))
+            .with_max_body_size(4096);
+
+        let task = Task::new("task-1", Message::user("Hello"));
+        let stamp = DeliveryStamp::generate();
+        let request = |token: &str, stamp: &DeliveryStamp, body: String| {
+            let [nonce, timestamp] = stamp.headers();
+            Request::post("/webhook")
+                .header(headers::NOTIFICATION_TOKEN, token)
+                .header(nonce.0, nonce.1)
+                .header(timestamp.0, timestamp.1)
+                .body(body)
+                .unwrap()
+        };
+
+        let body = serde_json::to_string(&task).unwrap();
+        assert_eq!(
+            send(&service, request("secret", &stamp, body.clone())).await,
+            StatusCode::OK
+        );
+        assert_eq!(events.recv().await.unwrap(), StreamEvent::Task(task));
+
+        // Replayed, unauthenticated, and unstamped deliveries are rejected
+        assert_eq!(
+            send(&service, request("secret", &stamp, body.clone())).await,
+            StatusCode::UNAUTHORIZED
+        );
+        let fresh = DeliveryStamp::generate();
+        assert_eq!(
+            send(&service, request("wrong", &fresh, body.clone())).await,
+            StatusCode::UNAUTHORIZED
+        );
+        let unstamped = Request::post("/webhook")
+            .header(headers::NOTIFICATION_TOKEN, "secret")
+            .body(body.clone())
+            .unwrap();
+        assert_eq!(send(&service, unstamped).await, StatusCode::UNAUTHORIZED);
+
+        let update = json!({"statusUpdate": {"taskId": "task-1", "status": {"state": "completed"}, "final": true}});
+        assert_eq!(
+            send(&service, request("secret", &fresh, update.to_string())).await,
+            StatusCode::OK
+        );
+        let event = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            event,
+            StreamEvent::StatusUpdate(update) if update.status == TaskStatus::Completed
+        ));
+
+        let garbage = request("secret", &DeliveryStamp::generate(), "{".into());
+        assert_eq!(send(&service, garbage).await, StatusCode::BAD_REQUEST);
+        let large = request("secret", &DeliveryStamp::generate(), "x".repeat(5000));
+        assert_eq!(send(&service, large).await, StatusCode::PAYLOAD_TOO_LARGE);
+        let get = Request::get("/webhook").body(String::new()).unwrap();
+        assert_eq!(send(&service, get).await, StatusCode::METHOD_NOT_ALLOWED);
+
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_handler() {
+        let service = WebhookService::new(|event: StreamEvent| match event.task_id() {
+            Some("task-1") => Ok(()),
+            _ => Err(A2AError::Other("Unknown task".into())),
+        });
+
+        let envelope = json!({
+            "jsonrpc": "2.0",
+            "method": "tasks/pushNotification",
+            "params": {"kind": "status-update", "taskId": "task-1", "state": "working"}
+        });
+        let request = Request::post("/").body(envelope.to_string()).unwrap();
+        assert_eq!(send(&service, request).await, StatusCode::OK);
+
+        let other = json!({"kind": "status-update", "taskId": "task-2", "state": "working"});
+        let request = Request::post("/").body(other.to_string()).unwrap();
+        assert_eq!(
+            send(&service, request).await,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+
+        // Deliveries that were not handled can be redelivered
+        let service = service.with_verifier(ReplayGuard::new(InMemoryReplayCache::new()));
+        let stamp = DeliveryStamp::generate();
+        let stamped = |body: &Value| {
+            let [nonce, timestamp] = stamp.headers();
+            Request::post("/")
+                .header(nonce.0, nonce.1)
+                .header(timestamp.0, timestamp.1)
+                .body(body.to_string())
+                .unwrap()
+        };
+        assert_eq!(
+            send(&service, stamped(&other)).await,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(send(&service, stamped(&envelope)).await, StatusCode::OK);
+        assert_eq!(
+            send(&service, stamped(&envelope)).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+}
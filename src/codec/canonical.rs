@@ -0,0 +1,217 @@
+//! JSON canonicalization for signatures
+//!
+//! Signatures over messages and agent cards need a byte representation that does not
+//! depend on how a document was serialized. [`Jcs`] implements the JSON
+//! Canonicalization Scheme ([RFC 8785](https://www.rfc-editor.org/rfc/rfc8785)):
+//! object keys are sorted by their UTF-16 code units, whitespace is removed, strings
+//! use minimal escaping, and numbers are formatted like ECMAScript's
+//! `Number.prototype.toString`.
+//!
+//! ```rust
+//! use serde_json::json;
+//! use tower_a2a::codec::canonical::canonicalize;
+//!
+//! let value = json!({"b": [1.50, "\u{20ac}"], "a": null});
+//! assert_eq!(canonicalize(&value), r#"{"a":null,"b":[1.5,"€"]}"#);
+//! ```
+
+use std::fmt::Write;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::protocol::error::A2AError;
+
+/// Produces the canonical bytes of a JSON document
+///
+/// Signing and verification take a canonicalizer so deployments that agree on a
+/// different scheme can plug it in.
+pub trait Canonicalizer: Send + Sync {
+    /// Canonicalize a JSON value
+    fn canonicalize(&self, value: &Value) -> Vec<u8>;
+
+    /// Serialize and canonicalize a value
+    fn to_canonical_vec<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>, A2AError>
+    where
+        Self: Sized,
+    {
+        Ok(self.canonicalize(&serde_json::to_value(value)?))
+    }
+}
+
+/// The JSON Canonicalization Scheme (RFC 8785)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Jcs;
+
+impl Canonicalizer for Jcs {
+    fn canonicalize(&self, value: &Value) -> Vec<u8> {
+        canonicalize(value).into_bytes()
+    }
+}
+
+/// Canonicalize a JSON value with JCS
+pub fn canonicalize(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+/// Serialize a value and canonicalize it with JCS
+pub fn to_canonical_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, A2AError> {
+    Jcs.to_canonical_vec(value)
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => {
+            // I-JSON numbers are IEEE 754 doubles; serde_json never holds non-finite values
+            write_number(out, n.as_f64().unwrap_or_default())
+        }
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, item);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Format a finite double like ECMAScript's `Number.prototype.toString`
+fn write_number(out: &mut String, n: f64) {
+    if n == 0.0 {
+        // Also covers -0
+        out.push('0');
+        return;
+    }
+    if n < 0.0 {
+        out.push('-');
+    }
+
+    // Shortest round-trip digits and decimal exponent, e.g. "1.2345e6"
+    let scientific = format!("{:e}", n.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    // Position of the decimal point relative to the digits
+    let point = exponent.parse::<i32>().unwrap_or_default() + 1;
+
+    if k <= point && point <= 21 {
+        out.push_str(&digits);
+        out.extend(std::iter::repeat_n('0', (point - k) as usize));
+    } else if 0 < point && point <= 21 {
+        let (int, frac) = digits.split_at(point as usize);
+        let _ = write!(out, "{}.{}", int, frac);
+    } else if -6 < point && point <= 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat_n('0', (-point) as usize));
+        out.push_str(&digits);
+    } else {
+        let (first, rest) = digits.split_at(1);
+        out.push_str(first);
+        if !rest.is_empty() {
+            let _ = write!(out, ".{}", rest);
+        }
+        let exponent = point - 1;
+        let _ = write!(
+            out,
+            "e{}{}",
+            if exponent < 0 { '-' } else { '+' },
+            exponent.abs()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_rfc_example() {
+        let value: Value = serde_json::from_str(
+            r#"{
+                "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+                "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+                "literals": [null, true, false]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            canonicalize(&value),
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        let cases = [
+            (json!(0), "0"),
+            (json!(-0.0), "0"),
+            (json!(100), "100"),
+            (json!(-1.5), "-1.5"),
+            (json!(1e21), "1e+21"),
+            (json!(1e20), "100000000000000000000"),
+            (json!(0.000001), "0.000001"),
+            (json!(1.5e-7), "1.5e-7"),
+            (json!(9007199254740993u64), "9007199254740992"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(canonicalize(&value), expected);
+        }
+    }
+
+    #[test]
+    fn test_key_order() {
+        // U+1F600 sorts after U+E000 in UTF-8 but before it in UTF-16
+        let value = json!({"\u{1f600}": 1, "\u{e000}": 2, "b": 3, "a": {"d": 4, "c": 5}});
+        assert_eq!(
+            canonicalize(&value),
+            "{\"a\":{\"c\":5,\"d\":4},\"b\":3,\"\u{1f600}\":1,\"\u{e000}\":2}"
+        );
+        assert_eq!(to_canonical_vec(&value).unwrap(), Jcs.canonicalize(&value));
+    }
+}
@@ -0,0 +1,330 @@
+//! Dictionary compression of payloads (experimental)
+//!
+//! Agents that exchange many small, similarly shaped messages gain little from plain
+//! compression, because each payload is too short to build up a useful history. A
+//! zstd dictionary trained on sample messages and tasks supplies that history up
+//! front. [`ZstdDictionaryCodec`] wraps another codec and compresses its payloads with
+//! a [`ZstdDictionary`]; agents advertise the dictionaries they accept with the
+//! [`ZSTD_DICTIONARY`](extensions::ZSTD_DICTIONARY) extension:
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use tower_a2a::{
+//!     codec::{JsonCodec, ZstdDictionary, ZstdDictionaryCodec},
+//!     prelude::*,
+//! };
+//!
+//! # fn example(samples: Vec<Task>) -> Result<(), A2AError> {
+//! let dictionary = ZstdDictionary::train("tasks-2024-06", &samples, 16 * 1024)?;
+//! let codec = ZstdDictionaryCodec::new(JsonCodec::new(), dictionary);
+//! let url = "https://agent.example.com".parse().unwrap();
+//! let client = A2AClientBuilder::new_http(url)
+//!     .with_codec(Arc::new(codec))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Both sides must hold the same dictionary, so check
+//! [`ZstdDictionary::is_accepted_by`] against the agent card before enabling the
+//! codec. Responses are decompressed if they start with a zstd frame and passed to the
+//! inner codec as is otherwise. Streamed events are not compressed, and the codec is
+//! meant for HTTP transports.
+//!
+//! Requires the `zstd-dict` feature.
+
+use std::{fmt, io::Read, sync::Arc};
+
+use bytes::Bytes;
+use serde::Serialize;
+use serde_json::Value;
+use zstd::dict::{DecoderDictionary, EncoderDictionary};
+
+use crate::{
+    codec::Codec,
+    protocol::{
+        agent::AgentCapabilities,
+        consts::{extensions, headers},
+        error::A2AError,
+        operation::A2AOperation,
+    },
+    service::response::A2AResponse,
+};
+
+/// Magic number starting every zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Default compression level, zstd's own default
+const DEFAULT_LEVEL: i32 = 3;
+
+/// Default limit of a decompressed response
+const DEFAULT_MAX_DECODED_SIZE: usize = 64 * 1024 * 1024;
+
+/// A zstd dictionary shared with agents under an ID
+#[derive(Clone)]
+pub struct ZstdDictionary {
+    id: String,
+    data: Arc<[u8]>,
+}
+
+impl ZstdDictionary {
+    /// Use a dictionary trained earlier, e.g. loaded from a file
+    pub fn new(id: impl Into<String>, data: impl Into<Arc<[u8]>>) -> Self {
+        Self {
+            id: id.into(),
+            data: data.into(),
+        }
+    }
+
+    /// Train a dictionary of at most `max_size` bytes on sample payloads
+    ///
+    /// Samples are serialized as JSON, so messages and tasks typical of the traffic
+    /// with an agent make good samples. Training needs a few hundred samples to find
+    /// the structure they share.
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Other` if a sample cannot be serialized or there are too few
+    /// samples to train on
+    pub fn train<T: Serialize>(
+        id: impl Into<String>,
+        samples: &[T],
+        max_size: usize,
+    ) -> Result<Self, A2AError> {
+        let samples = samples
+            .iter()
+            .map(serde_json::to_vec)
+            .collect::<Result<Vec<_>, _>>()?;
+        let data = zstd::dict::from_samples(&samples, max_size)
+            .map_err(|e| A2AError::Other(format!("Dictionary training failed: {}", e)))?;
+        Ok(Self::new(id, data))
+    }
+
+    /// Get the ID agents know the dictionary by
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the raw dictionary, e.g. to save it
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Check whether an agent accepts payloads compressed with this dictionary
+    ///
+    /// True if the agent's [`ZSTD_DICTIONARY`](extensions::ZSTD_DICTIONARY) extension
+    /// lists the dictionary's ID in its `dictionaries` parameter.
+    pub fn is_accepted_by(&self, capabilities: &AgentCapabilities) -> bool {
+        capabilities
+            .extensions
+            .iter()
+            .filter(|extension| extension.uri == extensions::ZSTD_DICTIONARY)
+            .filter_map(|extension| extension.params.as_ref()?.get("dictionaries"))
+            .filter_map(Value::as_array)
+            .flatten()
+            .any(|id| id.as_str() == Some(self.id.as_str()))
+    }
+}
+
+impl fmt::Debug for ZstdDictionary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZstdDictionary")
+            .field("id", &self.id)
+            .field("size", &self.data.len())
+            .finish()
+    }
+}
+
+/// Codec compressing the payloads of another codec with a zstd dictionary
+///
+/// Requests are sent with `Content-Encoding: zstd`, the ID of the dictionary, and the
+/// [`ZSTD_DICTIONARY`](extensions::ZSTD_DICTIONARY) extension activated.
+pub struct ZstdDictionaryCodec<C> {
+    inner: C,
+    dictionary: ZstdDictionary,
+    encoder: EncoderDictionary<'static>,
+    decoder: DecoderDictionary<'static>,
+    max_decoded_size: usize,
+}
+
+impl<C: Codec> ZstdDictionaryCodec<C> {
+    /// Wrap a codec, compressing at zstd's default level
+    pub fn new(inner: C, dictionary: ZstdDictionary) -> Self {
+        Self::with_level(inner, dictionary, DEFAULT_LEVEL)
+    }
+
+    /// Wrap a codec, compressing at a level from 1 (fastest) to 22 (smallest)
+    pub fn with_level(inner: C, dictionary: ZstdDictionary, level: i32) -> Self {
+        Self {
+            encoder: EncoderDictionary::copy(dictionary.as_bytes(), level),
+            decoder: DecoderDictionary::copy(dictionary.as_bytes()),
+            inner,
+            dictionary,
+            max_decoded_size: DEFAULT_MAX_DECODED_SIZE,
+        }
+    }
+
+    /// Limit the size of decompressed responses, 64 MiB by default
+    pub fn with_max_decoded_size(mut self, max: usize) -> Self {
+        self.max_decoded_size = max;
+        self
+    }
+
+    /// Get the dictionary
+    pub fn dictionary(&self) -> &ZstdDictionary {
+        &self.dictionary
+    }
+
+    /// Compress a payload
+    fn compress(&self, payload: &[u8]) -> Result<Bytes, A2AError> {
+        let mut compressor = zstd::bulk::Compressor::with_prepared_dictionary(&self.encoder)
+            .map_err(|e| A2AError::Other(format!("zstd compression failed: {}", e)))?;
+        compressor
+            .compress(payload)
+            .map(Bytes::from)
+            .map_err(|e| A2AError::Other(format!("zstd compression failed: {}", e)))
+    }
+
+    /// Decompress a payload, failing past the size limit
+    fn decompress(&self, payload: &[u8]) -> Result<Vec<u8>, A2AError> {
+        let error =
+            |e: std::io::Error| A2AError::Protocol(format!("zstd decompression failed: {}", e));
+        let decoder = zstd::stream::read::Decoder::with_prepared_dictionary(payload, &self.decoder)
+            .map_err(error)?;
+
+        let mut decoded = Vec::new();
+        decoder
+            .take(self.max_decoded_size as u64 + 1)
+            .read_to_end(&mut decoded)
+            .map_err(error)?;
+        if decoded.len() > self.max_decoded_size {
+            return Err(A2AError::Protocol(format!(
+                "Decompressed response exceeds {} bytes",
+                self.max_decoded_size
+            )));
+        }
+        Ok(decoded)
+    }
+}
+
+impl<C: Codec> Codec for ZstdDictionaryCodec<C> {
+    fn encode_request(&self, operation: &A2AOperation) -> Result<Bytes, A2AError> {
+        let payload = self.inner.encode_request(operation)?;
+        if payload.is_empty() {
+            return Ok(payload);
+        }
+        self.compress(&payload)
+    }
+
+    fn decode_response(
+        &self,
+        body: &[u8],
+        operation: &A2AOperation,
+    ) -> Result<A2AResponse, A2AError> {
+        if body.starts_with(&ZSTD_MAGIC) {
+            self.inner
+                .decode_response(&self.decompress(body)?, operation)
+        } else {
+            self.inner.decode_response(body, operation)
+        }
+    }
+
+    fn content_type(&self) -> &str {
+        self.inner.content_type()
+    }
+
+    fn bindings(&self) -> &[&str] {
+        self.inner.bindings()
+    }
+
+    fn headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = self.inner.headers();
+        headers.extend([
+            (headers::CONTENT_ENCODING, "zstd".to_string()),
+            (headers::COMPRESSION_DICTIONARY, self.dictionary.id.clone()),
+            (headers::EXTENSIONS, extensions::ZSTD_DICTIONARY.to_string()),
+        ]);
+        headers
+    }
+}
+
+impl<C: fmt::Debug> fmt::Debug for ZstdDictionaryCodec<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZstdDictionaryCodec")
+            .field("inner", &self.inner)
+            .field("dictionary", &self.dictionary)
+            .field("max_decoded_size", &self.max_decoded_size)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        codec::JsonCodec,
+        protocol::{agent::AgentExtension, message::Message, task::Task},
+    };
+
+    fn samples() -> Vec<Task> {
+        (0..500)
+            .map(|i| {
+                Task::new(
+                    format!("task-{}", i),
+                    Message::user(format!("Summarize report {}", i)),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_zstd_dictionary_codec() {
+        let dictionary = ZstdDictionary::train("tasks-v1", &samples(), 4096).unwrap();
+        let codec = ZstdDictionaryCodec::new(JsonCodec::new(), dictionary);
+
+        let operation = A2AOperation::SendMessage {
+            message: Message::user("Summarize report 7"),
+            stream: false,
+            context_id: None,
+            task_id: None,
+        };
+        let plain = JsonCodec::new().encode_request(&operation).unwrap();
+        let compressed = codec.encode_request(&operation).unwrap();
+        assert!(compressed.starts_with(&ZSTD_MAGIC));
+        assert!(compressed.len() < zstd::bulk::compress(&plain, DEFAULT_LEVEL).unwrap().len());
+
+        let task = serde_json::to_vec(&samples()[42]).unwrap();
+        let response = codec
+            .decode_response(&codec.compress(&task).unwrap(), &operation)
+            .unwrap();
+        assert!(matches!(response, A2AResponse::Task(task) if task.id == "task-42"));
+        assert!(codec.decode_response(&task, &operation).is_ok());
+
+        let small = ZstdDictionaryCodec::new(JsonCodec::new(), codec.dictionary().clone())
+            .with_max_decoded_size(16);
+        assert!(small
+            .decode_response(&codec.compress(&task).unwrap(), &operation)
+            .is_err());
+
+        let headers = codec.headers();
+        assert!(headers.contains(&(headers::CONTENT_ENCODING, "zstd".to_string())));
+        assert!(headers.contains(&(headers::COMPRESSION_DICTIONARY, "tasks-v1".to_string())));
+    }
+
+    #[test]
+    fn test_dictionary_negotiation() {
+        let dictionary = ZstdDictionary::new("tasks-v1", vec![0u8; 8]);
+        let mut extension = AgentExtension::new(extensions::ZSTD_DICTIONARY);
+        extension.params = Some(json!({"dictionaries": ["tasks-v0", "tasks-v1"]}));
+
+        assert!(dictionary.is_accepted_by(&AgentCapabilities::new().with_extension(extension)));
+        assert!(!dictionary.is_accepted_by(
+            &AgentCapabilities::new()
+                .with_extension(AgentExtension::new(extensions::ZSTD_DICTIONARY))
+        ));
+        assert!(!ZstdDictionary::new("tasks-v2", vec![0u8; 8])
+            .is_accepted_by(&AgentCapabilities::new()));
+    }
+}
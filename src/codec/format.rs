@@ -0,0 +1,150 @@
+//! Wire formats for codec payloads
+//!
+//! JSON is always available. CBOR and MessagePack require the `cbor` and `msgpack`
+//! features; they shrink messages with large data parts, and over WebSocket they are
+//! carried in binary frames:
+//!
+//! ```rust,ignore
+//! use tower_a2a::{codec::{JsonRpcCodec, WireFormat}, transport::WebSocketTransport};
+//!
+//! let url = "wss://agent.example.com/a2a".parse().unwrap();
+//! let codec = JsonRpcCodec::new().with_format(WireFormat::Cbor);
+//! let transport = WebSocketTransport::new(url).with_format(WireFormat::Cbor);
+//! ```
+
+use bytes::Bytes;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    codec::encode_into_buffer,
+    protocol::{
+        consts::{media_types, WEBSOCKET_SUBPROTOCOL},
+        error::A2AError,
+    },
+};
+
+/// Serialization format of encoded payloads
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WireFormat {
+    /// JSON text
+    #[default]
+    Json,
+
+    /// CBOR (RFC 8949)
+    #[cfg(feature = "cbor")]
+    Cbor,
+
+    /// MessagePack
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+}
+
+impl WireFormat {
+    /// Find the format of a media type, ignoring parameters such as `charset`
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let essence = media_type.split(';').next().unwrap_or_default().trim();
+        match essence {
+            media_types::A2A_JSON | media_types::JSON => Some(Self::Json),
+            #[cfg(feature = "cbor")]
+            media_types::CBOR => Some(Self::Cbor),
+            #[cfg(feature = "msgpack")]
+            media_types::MSGPACK => Some(Self::MessagePack),
+            _ => None,
+        }
+    }
+
+    /// Media type of payloads in this format
+    pub fn media_type(self) -> &'static str {
+        match self {
+            Self::Json => media_types::A2A_JSON,
+            #[cfg(feature = "cbor")]
+            Self::Cbor => media_types::CBOR,
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => media_types::MSGPACK,
+        }
+    }
+
+    /// WebSocket subprotocol announcing this format
+    pub fn subprotocol(self) -> &'static str {
+        match self {
+            Self::Json => WEBSOCKET_SUBPROTOCOL,
+            #[cfg(feature = "cbor")]
+            Self::Cbor => "a2a.v1.cbor",
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => "a2a.v1.msgpack",
+        }
+    }
+
+    /// Check if payloads are binary, i.e. sent in binary WebSocket frames
+    pub fn is_binary(self) -> bool {
+        self != Self::Json
+    }
+
+    /// Serialize a value in this format
+    pub(crate) fn encode<T: Serialize + ?Sized>(self, value: &T) -> Result<Bytes, A2AError> {
+        match self {
+            Self::Json => encode_into_buffer(value),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => {
+                let mut buffer = Vec::new();
+                ciborium::into_writer(value, &mut buffer)
+                    .map_err(|e| A2AError::Other(format!("CBOR encoding failed: {}", e)))?;
+                Ok(buffer.into())
+            }
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => rmp_serde::to_vec_named(value)
+                .map(Bytes::from)
+                .map_err(|e| A2AError::Other(format!("MessagePack encoding failed: {}", e))),
+        }
+    }
+
+    /// Deserialize a value from this format
+    pub(crate) fn decode<T: DeserializeOwned>(self, body: &[u8]) -> Result<T, A2AError> {
+        match self {
+            Self::Json => Ok(serde_json::from_slice(body)?),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => ciborium::from_reader(body)
+                .map_err(|e| A2AError::Protocol(format!("CBOR decoding failed: {}", e))),
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => rmp_serde::from_slice(body)
+                .map_err(|e| A2AError::Protocol(format!("MessagePack decoding failed: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_media_types() {
+        assert_eq!(
+            WireFormat::from_media_type("application/json; charset=utf-8"),
+            Some(WireFormat::Json)
+        );
+        assert_eq!(WireFormat::from_media_type("text/plain"), None);
+        assert!(!WireFormat::Json.is_binary());
+        assert_eq!(WireFormat::Json.subprotocol(), WEBSOCKET_SUBPROTOCOL);
+    }
+
+    #[cfg(all(feature = "cbor", feature = "msgpack"))]
+    #[test]
+    fn test_binary_roundtrip() {
+        use serde_json::{json, Value};
+
+        let value = json!({"jsonrpc": "2.0", "id": "req-1", "params": {"parts": [1, 2.5]}});
+
+        for format in [WireFormat::Cbor, WireFormat::MessagePack] {
+            assert!(format.is_binary());
+            assert_eq!(
+                WireFormat::from_media_type(format.media_type()),
+                Some(format)
+            );
+
+            let body = format.encode(&value).unwrap();
+            assert_ne!(body.first(), Some(&b'{'));
+            assert_eq!(format.decode::<Value>(&body).unwrap(), value);
+        }
+    }
+}
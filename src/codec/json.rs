@@ -1,20 +1,138 @@
 //! JSON codec for HTTP+JSON binding
 
 use bytes::Bytes;
-use serde_json::json;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
 
 use crate::{
-    codec::Codec,
+    codec::{encode_into_buffer, Codec},
     protocol::{
         agent::AgentCard,
+        consts::media_types,
         error::A2AError,
-        operation::A2AOperation,
-        task::{Task, TaskListResponse},
+        message::Message,
+        operation::{A2AOperation, CancelReason},
+        push::{PushNotificationConfig, TaskPushNotificationConfig},
+        task::{Task, TaskListResponse, TaskStatus},
+        Artifact,
     },
     service::response::A2AResponse,
 };
 
+/// Request body of an operation, borrowing from the operation
+///
+/// Serialized directly, without building an intermediate `serde_json::Value`.
+#[derive(Serialize)]
+#[serde(untagged, rename_all_fields = "camelCase")]
+pub(crate) enum RequestParams<'a> {
+    SendMessage {
+        message: &'a Message,
+        stream: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        context_id: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        task_id: Option<&'a str>,
+    },
+    CancelTask {
+        task_id: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<&'a CancelReason>,
+    },
+    RegisterWebhook {
+        url: &'a str,
+        events: &'a [String],
+        #[serde(skip_serializing_if = "Option::is_none")]
+        auth: Option<&'a str>,
+    },
+    SetPushNotificationConfig {
+        task_id: &'a str,
+        push_notification_config: &'a PushNotificationConfig,
+    },
+    PushNotificationConfigRef {
+        id: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        push_notification_config_id: Option<&'a str>,
+    },
+    TaskRef {
+        id: &'a str,
+    },
+    ListTasks {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        status: Option<&'a TaskStatus>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        page_size: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        page_token: Option<&'a str>,
+    },
+    // Operations without parameters
+    Empty {},
+}
+
+impl<'a> RequestParams<'a> {
+    /// Get the request body of an operation
+    pub(crate) fn from_operation(operation: &'a A2AOperation) -> Self {
+        match operation {
+            A2AOperation::SendMessage {
+                message,
+                stream,
+                context_id,
+                task_id,
+            } => RequestParams::SendMessage {
+                message,
+                stream: *stream,
+                context_id: context_id.as_deref(),
+                task_id: task_id.as_deref(),
+            },
+            A2AOperation::CancelTask { task_id, reason } => RequestParams::CancelTask {
+                task_id,
+                reason: reason.as_ref(),
+            },
+            A2AOperation::RegisterWebhook { url, events, auth } => RequestParams::RegisterWebhook {
+                url,
+                events,
+                auth: auth.as_deref(),
+            },
+            A2AOperation::SetPushNotificationConfig { task_id, config } => {
+                RequestParams::SetPushNotificationConfig {
+                    task_id,
+                    push_notification_config: config,
+                }
+            }
+            A2AOperation::GetPushNotificationConfig { task_id, config_id }
+            | A2AOperation::DeletePushNotificationConfig { task_id, config_id } => {
+                RequestParams::PushNotificationConfigRef {
+                    id: task_id,
+                    push_notification_config_id: Some(config_id),
+                }
+            }
+            A2AOperation::ListPushNotificationConfigs { task_id } => {
+                RequestParams::PushNotificationConfigRef {
+                    id: task_id,
+                    push_notification_config_id: None,
+                }
+            }
+            A2AOperation::GetTask { task_id }
+            | A2AOperation::SubscribeTask { task_id }
+            | A2AOperation::ResubscribeTask { task_id } => RequestParams::TaskRef { id: task_id },
+            A2AOperation::ListTasks {
+                status,
+                limit,
+                next_token,
+                ..
+            } => RequestParams::ListTasks {
+                status: status.as_ref(),
+                page_size: *limit,
+                page_token: next_token.as_deref(),
+            },
+            _ => RequestParams::Empty {},
+        }
+    }
+}
+
 /// JSON codec for the HTTP+JSON protocol binding
+///
+/// Fields no type declares are accepted, for interoperability with newer agents.
+/// Use [`StrictJsonCodec`] to reject them.
 #[derive(Debug, Clone, Default)]
 pub struct JsonCodec;
 
@@ -25,59 +143,209 @@ impl JsonCodec {
     }
 }
 
-impl Codec for JsonCodec {
-    fn encode_request(&self, operation: &A2AOperation) -> Result<Bytes, A2AError> {
-        let json = match operation {
-            A2AOperation::SendMessage {
-                message,
-                stream,
-                context_id,
-                task_id,
-            } => {
-                let mut obj = json!({
-                    "message": message,
-                    "stream": stream,
-                });
-
-                if let Some(ctx_id) = context_id {
-                    obj["contextId"] = json!(ctx_id);
-                }
-                if let Some(t_id) = task_id {
-                    obj["taskId"] = json!(t_id);
-                }
+/// Types of decoded response bodies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResponseType {
+    /// A task
+    Task,
 
-                obj
-            }
-            A2AOperation::CancelTask { task_id } => {
-                json!({
-                    "taskId": task_id,
+    /// A page of tasks
+    TaskList,
+
+    /// An agent card
+    AgentCard,
+}
+
+impl ResponseType {
+    /// Bit of the type in a set of types
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// JSON codec for the HTTP+JSON protocol binding that rejects unknown fields
+///
+/// Meant for integration tests, to detect fields an agent sends that the client drops
+/// or only keeps in `extra` maps (e.g. [`Task::extra`]). Decoding fails with
+/// [`A2AError::Protocol`] naming the first unknown field. Unknown fields holding a
+/// default value (`null`, `false`, `0`, `""`, `[]`, or `{}`) are not detected.
+///
+/// ```rust
+/// use tower_a2a::codec::json::{ResponseType, StrictJsonCodec};
+///
+/// let strict = StrictJsonCodec::new();
+/// let strict_cards = StrictJsonCodec::for_types(&[ResponseType::AgentCard]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct StrictJsonCodec {
+    /// Set of response types whose unknown fields are denied
+    deny_unknown: u8,
+}
+
+impl StrictJsonCodec {
+    /// Create a JSON codec rejecting unknown fields of all response types
+    pub fn new() -> Self {
+        Self {
+            deny_unknown: u8::MAX,
+        }
+    }
+
+    /// Create a JSON codec rejecting unknown fields of the given response types only
+    pub fn for_types(types: &[ResponseType]) -> Self {
+        Self {
+            deny_unknown: types.iter().fold(0, |set, ty| set | ty.bit()),
+        }
+    }
+
+    /// Check whether unknown fields of a response type are rejected
+    pub fn denies(&self, ty: ResponseType) -> bool {
+        self.deny_unknown & ty.bit() != 0
+    }
+
+    /// Decode a response body, rejecting unknown fields if its type is strict
+    fn decode<T>(&self, body: &[u8], ty: ResponseType) -> Result<T, A2AError>
+    where
+        T: DeserializeOwned + Serialize + Extra,
+    {
+        if !self.denies(ty) {
+            return Ok(serde_json::from_slice(body)?);
+        }
+
+        // Fields missing once the value is encoded again were dropped when decoding
+        let original: Value = serde_json::from_slice(body)?;
+        let value = T::deserialize(&original)?;
+        let encoded = serde_json::to_value(&value)?;
+        let unknown = value
+            .unknown_field()
+            .or_else(|| dropped_field(&original, &encoded));
+
+        match unknown {
+            Some(path) => Err(A2AError::Protocol(format!(
+                "Unknown field '{}' in {:?} response",
+                path, ty
+            ))),
+            None => Ok(value),
+        }
+    }
+}
+
+impl Default for StrictJsonCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Get the path of the first field of a decoded JSON value that its re-encoding lacks
+///
+/// Fields renamed by an alias are matched by value. Fields holding a default value
+/// are skipped, as they may have been omitted from the re-encoding.
+fn dropped_field(original: &Value, encoded: &Value) -> Option<String> {
+    match (original, encoded) {
+        (Value::Object(original), Value::Object(encoded)) => {
+            original
+                .iter()
+                .find_map(|(key, value)| match encoded.get(key) {
+                    Some(encoded) => {
+                        dropped_field(value, encoded).map(|path| format!("{}.{}", key, path))
+                    }
+                    None if is_default(value) => None,
+                    None if encoded
+                        .iter()
+                        .any(|(k, v)| v == value && !original.contains_key(k)) =>
+                    {
+                        None
+                    }
+                    None => Some(key.clone()),
                 })
-            }
-            A2AOperation::RegisterWebhook { url, events, auth } => {
-                let mut obj = json!({
-                    "url": url,
-                    "events": events,
-                });
-
-                if let Some(auth_str) = auth {
-                    obj["auth"] = json!(auth_str);
-                }
+        }
+        (Value::Array(original), Value::Array(encoded)) => original
+            .iter()
+            .zip(encoded)
+            .enumerate()
+            .find_map(|(i, (original, encoded))| {
+                dropped_field(original, encoded).map(|path| format!("{}.{}", i, path))
+            }),
+        _ => None,
+    }
+}
 
-                obj
-            }
-            // GET requests typically don't have bodies
-            _ => json!({}),
-        };
+/// Check whether a JSON value is the default of its type
+fn is_default(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Bool(b) => !b,
+        Value::Number(n) => n.as_f64() == Some(0.0),
+        Value::String(s) => s.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty(),
+    }
+}
+
+/// Types keeping fields that are not part of the protocol in an `extra` map
+trait Extra {
+    /// Get the path of the first extra field, including those of nested values
+    fn unknown_field(&self) -> Option<String>;
+}
+
+/// Get the first key of an `extra` map
+fn first_key(extra: &Map<String, Value>) -> Option<String> {
+    extra.keys().next().cloned()
+}
+
+/// Get the first extra field of a sequence, prefixed with its field name and index
+fn nested<'a, T: Extra + 'a>(
+    field: &str,
+    items: impl IntoIterator<Item = &'a T>,
+) -> Option<String> {
+    items.into_iter().enumerate().find_map(|(i, item)| {
+        item.unknown_field()
+            .map(|path| format!("{}.{}.{}", field, i, path))
+    })
+}
 
-        let bytes = serde_json::to_vec(&json)?;
-        Ok(Bytes::from(bytes))
+impl Extra for Message {
+    fn unknown_field(&self) -> Option<String> {
+        first_key(&self.extra)
     }
+}
 
-    fn decode_response(
-        &self,
-        body: &[u8],
-        operation: &A2AOperation,
-    ) -> Result<A2AResponse, A2AError> {
+impl Extra for Artifact {
+    fn unknown_field(&self) -> Option<String> {
+        first_key(&self.extra)
+    }
+}
+
+impl Extra for AgentCard {
+    fn unknown_field(&self) -> Option<String> {
+        first_key(&self.extra)
+    }
+}
+
+impl Extra for Task {
+    fn unknown_field(&self) -> Option<String> {
+        first_key(&self.extra)
+            .or_else(|| {
+                self.input
+                    .unknown_field()
+                    .map(|path| format!("input.{}", path))
+            })
+            .or_else(|| nested("history", &self.history))
+            .or_else(|| nested("artifacts", &self.artifacts))
+    }
+}
+
+impl Extra for TaskListResponse {
+    fn unknown_field(&self) -> Option<String> {
+        nested("tasks", &self.tasks)
+    }
+}
+
+impl StrictJsonCodec {
+    /// Codec rejecting no unknown fields, used by [`JsonCodec`]
+    const LENIENT: Self = Self { deny_unknown: 0 };
+
+    /// Decode the response body of an operation
+    fn decode_body(&self, body: &[u8], operation: &A2AOperation) -> Result<A2AResponse, A2AError> {
         // Empty responses
         if body.is_empty() {
             return Ok(A2AResponse::Empty);
@@ -85,11 +353,11 @@ impl Codec for JsonCodec {
 
         match operation {
             A2AOperation::SendMessage { .. } | A2AOperation::GetTask { .. } => {
-                let task: Task = serde_json::from_slice(body)?;
+                let task: Task = self.decode(body, ResponseType::Task)?;
                 Ok(A2AResponse::Task(Box::new(task)))
             }
             A2AOperation::ListTasks { .. } => {
-                let list: TaskListResponse = serde_json::from_slice(body)?;
+                let list: TaskListResponse = self.decode(body, ResponseType::TaskList)?;
                 Ok(A2AResponse::TaskList {
                     tasks: list.tasks,
                     total: list.total,
@@ -97,30 +365,80 @@ impl Codec for JsonCodec {
                 })
             }
             A2AOperation::DiscoverAgent => {
-                let card: AgentCard = serde_json::from_slice(body)?;
+                let card: AgentCard = self.decode(body, ResponseType::AgentCard)?;
                 Ok(A2AResponse::AgentCard(Box::new(card)))
             }
             A2AOperation::CancelTask { .. } => {
                 // Cancel typically returns the updated task
-                let task: Task = serde_json::from_slice(body)?;
+                let task: Task = self.decode(body, ResponseType::Task)?;
                 Ok(A2AResponse::Task(Box::new(task)))
             }
-            A2AOperation::SubscribeTask { .. } => {
+            A2AOperation::SubscribeTask { .. } | A2AOperation::ResubscribeTask { .. } => {
                 // Streaming responses handled separately
                 Ok(A2AResponse::Empty)
             }
-            A2AOperation::RegisterWebhook { .. } => Ok(A2AResponse::Empty),
+            A2AOperation::RegisterWebhook { .. }
+            | A2AOperation::DeletePushNotificationConfig { .. } => Ok(A2AResponse::Empty),
+            A2AOperation::SetPushNotificationConfig { .. }
+            | A2AOperation::GetPushNotificationConfig { .. } => {
+                let config: TaskPushNotificationConfig = serde_json::from_slice(body)?;
+                Ok(A2AResponse::PushNotificationConfig(Box::new(config)))
+            }
+            A2AOperation::ListPushNotificationConfigs { .. } => {
+                let configs: Vec<TaskPushNotificationConfig> = serde_json::from_slice(body)?;
+                Ok(A2AResponse::PushNotificationConfigs(configs))
+            }
         }
     }
+}
+
+impl Codec for JsonCodec {
+    fn encode_request(&self, operation: &A2AOperation) -> Result<Bytes, A2AError> {
+        encode_into_buffer(&RequestParams::from_operation(operation))
+    }
+
+    fn decode_response(
+        &self,
+        body: &[u8],
+        operation: &A2AOperation,
+    ) -> Result<A2AResponse, A2AError> {
+        StrictJsonCodec::LENIENT.decode_body(body, operation)
+    }
+
+    fn content_type(&self) -> &str {
+        media_types::A2A_JSON
+    }
+
+    fn bindings(&self) -> &[&str] {
+        &["http+json", "http+rest", "rest"]
+    }
+}
+
+impl Codec for StrictJsonCodec {
+    fn encode_request(&self, operation: &A2AOperation) -> Result<Bytes, A2AError> {
+        JsonCodec.encode_request(operation)
+    }
+
+    fn decode_response(
+        &self,
+        body: &[u8],
+        operation: &A2AOperation,
+    ) -> Result<A2AResponse, A2AError> {
+        self.decode_body(body, operation)
+    }
 
     fn content_type(&self) -> &str {
-        "application/a2a+json"
+        JsonCodec.content_type()
+    }
+
+    fn bindings(&self) -> &[&str] {
+        JsonCodec.bindings()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use serde_json::Value;
+    use serde_json::json;
 
     use super::*;
     use crate::protocol::message::Message;
@@ -146,6 +464,33 @@ mod tests {
         assert_eq!(json["stream"], false);
     }
 
+    #[test]
+    fn test_encode_cancel_reason() {
+        let codec = JsonCodec::new();
+        let operation = A2AOperation::CancelTask {
+            task_id: "task-1".to_string(),
+            reason: Some(
+                CancelReason::new("No longer needed")
+                    .with_code("superseded")
+                    .with_metadata("replacedBy", json!("task-2")),
+            ),
+        };
+
+        let json: Value =
+            serde_json::from_slice(&codec.encode_request(&operation).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "taskId": "task-1",
+                "reason": {
+                    "message": "No longer needed",
+                    "code": "superseded",
+                    "metadata": {"replacedBy": "task-2"}
+                }
+            })
+        );
+    }
+
     #[test]
     fn test_decode_task_response() {
         let codec = JsonCodec;
@@ -173,9 +518,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_unknown_fields() {
+        let json = r#"{
+            "id": "task-123",
+            "status": "submitted",
+            "input": {"role": "user", "parts": [{"text": "Hello", "mood": "happy"}]},
+            "createdAt": "2024-01-01T00:00:00Z"
+        }"#;
+        let operation = A2AOperation::GetTask {
+            task_id: "task-123".to_string(),
+        };
+
+        assert!(JsonCodec
+            .decode_response(json.as_bytes(), &operation)
+            .is_ok());
+
+        let strict = StrictJsonCodec::new();
+        match strict.decode_response(json.as_bytes(), &operation) {
+            Err(A2AError::Protocol(msg)) => {
+                assert!(msg.contains("input.parts.0.mood"), "{}", msg)
+            }
+            other => panic!("Expected Protocol error, got {:?}", other),
+        }
+
+        // Unknown task fields are kept, unless denied
+        let extended = json
+            .replace(", \"mood\": \"happy\"", "")
+            .replace("\"createdAt\"", "\"cost\": 1, \"createdAt\"");
+        match JsonCodec.decode_response(extended.as_bytes(), &operation) {
+            Ok(A2AResponse::Task(task)) => assert_eq!(task.extra["cost"], 1),
+            other => panic!("Expected task, got {:?}", other),
+        }
+        match strict.decode_response(extended.as_bytes(), &operation) {
+            Err(A2AError::Protocol(msg)) => assert!(msg.contains("'cost'"), "{}", msg),
+            other => panic!("Expected Protocol error, got {:?}", other),
+        }
+
+        // Known fields holding defaults are accepted
+        let defaults = json
+            .replace(", \"mood\": \"happy\"", "")
+            .replace("\"createdAt\"", "\"history\": [], \"createdAt\"");
+        assert!(strict
+            .decode_response(defaults.as_bytes(), &operation)
+            .is_ok());
+
+        // Only agent cards are strict
+        let strict_cards = StrictJsonCodec::for_types(&[ResponseType::AgentCard]);
+        assert!(!strict_cards.denies(ResponseType::Task));
+        assert!(strict_cards
+            .decode_response(json.as_bytes(), &operation)
+            .is_ok());
+    }
+
     #[test]
     fn test_content_type() {
         let codec = JsonCodec;
-        assert_eq!(codec.content_type(), "application/a2a+json");
+        assert_eq!(codec.content_type(), media_types::A2A_JSON);
     }
 }
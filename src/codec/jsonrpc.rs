@@ -1,96 +1,211 @@
 //! JSON-RPC 2.0 codec for A2A protocol
 //!
 //! This codec wraps A2A operations in JSON-RPC 2.0 envelopes for compatibility
-//! with agents that use the JSON-RPC protocol binding.
+//! with agents that use the JSON-RPC protocol binding. Envelopes are JSON by default
+//! and can be encoded in a binary [`WireFormat`] instead.
+
+use std::borrow::Cow;
 
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{value::RawValue, Value};
 use uuid::Uuid;
 
 use crate::{
-    codec::Codec,
+    codec::{format::WireFormat, json::RequestParams, Codec},
     protocol::{error::A2AError, operation::A2AOperation},
     service::response::A2AResponse,
 };
 
-use super::json::JsonCodec;
+use super::json::{JsonCodec, StrictJsonCodec};
 
 /// JSON-RPC 2.0 request envelope
-#[derive(Debug, Serialize)]
-struct JsonRpcRequest {
-    jsonrpc: String,
-    method: String,
-    params: Value,
+#[derive(Serialize)]
+struct JsonRpcRequest<'a> {
+    jsonrpc: &'static str,
+    method: &'static str,
+    params: RequestParams<'a>,
     id: String,
 }
 
+/// The `id` of a JSON-RPC 2.0 message, ignoring all other members
+#[derive(Deserialize)]
+struct JsonRpcId<'a> {
+    #[serde(borrow, default)]
+    id: Option<Cow<'a, str>>,
+}
+
 /// JSON-RPC 2.0 response envelope
+///
+/// The result is kept as raw JSON and decoded in place by the JSON codec.
 #[derive(Debug, Deserialize)]
 #[allow(unused)]
-struct JsonRpcResponse {
+struct JsonRpcResponse<'a> {
     jsonrpc: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    result: Option<Value>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    result: Option<&'a RawValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<JsonRpcError>,
     id: Value,
 }
 
+/// JSON-RPC 2.0 response envelope in a binary format, which cannot be borrowed
+#[derive(Debug, Deserialize)]
+struct OwnedJsonRpcResponse {
+    #[serde(default)]
+    result: Option<Value>,
+    #[serde(default)]
+    error: Option<JsonRpcError>,
+}
+
 /// JSON-RPC 2.0 error object
 #[derive(Debug, Deserialize)]
-#[allow(unused)]
 struct JsonRpcError {
     code: i64,
     message: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     data: Option<Value>,
 }
 
+impl From<JsonRpcError> for A2AError {
+    fn from(error: JsonRpcError) -> Self {
+        A2AError::JsonRpc {
+            code: error.code,
+            message: error.message,
+            data: error.data,
+        }
+    }
+}
+
+/// JSON-RPC 2.0 notification (a message without an `id`)
+///
+/// Agents use notifications to push task updates and custom events over bidirectional
+/// transports such as WebSocket, without a preceding request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcNotification {
+    /// The notification method (e.g., "tasks/statusUpdate")
+    pub method: String,
+
+    /// The notification parameters
+    #[serde(default)]
+    pub params: Value,
+}
+
 /// JSON-RPC 2.0 codec that wraps A2A operations
 ///
 /// This codec implements the JSON-RPC 2.0 protocol binding for A2A.
 /// It wraps operations in JSON-RPC request envelopes and unwraps responses.
-#[derive(Debug, Clone, Default)]
-pub struct JsonRpcCodec;
+///
+/// Results are decoded by a [`JsonCodec`], or by a [`StrictJsonCodec`] given to
+/// [`with_strict_results`](Self::with_strict_results).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonRpcCodec {
+    strict: Option<StrictJsonCodec>,
+    format: WireFormat,
+}
 
 impl JsonRpcCodec {
     /// Create a new JSON-RPC codec
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Decode results with a JSON codec rejecting unknown fields
+    pub fn with_strict_results(mut self, codec: StrictJsonCodec) -> Self {
+        self.strict = Some(codec);
+        self
+    }
+
+    /// Encode envelopes in the given format (default: JSON)
+    ///
+    /// Results are still decoded by the result codec after unwrapping the envelope.
+    pub fn with_format(mut self, format: WireFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Get the format of encoded envelopes
+    pub fn format(&self) -> WireFormat {
+        self.format
+    }
+
+    /// Get the `id` of an encoded JSON-RPC request
+    ///
+    /// Transports that correlate responses themselves, such as WebSocket, use this
+    /// instead of decoding the request, so the envelope built by
+    /// [`encode_request`](Codec::encode_request) is sent unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Serialization` if the body is not a JSON object
+    pub fn request_id(body: &[u8]) -> Result<Option<String>, A2AError> {
+        let message: JsonRpcId = serde_json::from_slice(body)?;
+        Ok(message.id.map(Cow::into_owned))
+    }
+
+    /// Get the `id` of a JSON-RPC request encoded in the given format
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the body is not a map in that format
+    pub fn request_id_in(format: WireFormat, body: &[u8]) -> Result<Option<String>, A2AError> {
+        if format == WireFormat::Json {
+            return Self::request_id(body);
+        }
+
+        #[derive(Deserialize)]
+        struct OwnedId {
+            #[serde(default)]
+            id: Option<String>,
+        }
+
+        Ok(format.decode::<OwnedId>(body)?.id)
+    }
+
+    /// Convert the `error` member of a JSON-RPC message into an error
+    ///
+    /// Transports that route messages themselves, such as WebSocket, use this for the
+    /// errors ending a stream. Malformed error objects become `A2AError::Protocol`.
+    pub(crate) fn decode_error(error: Value) -> A2AError {
+        serde_json::from_value::<JsonRpcError>(error).map_or_else(
+            |e| A2AError::Protocol(format!("Malformed JSON-RPC error: {}", e)),
+            A2AError::from,
+        )
     }
 
     /// Map an A2A operation to a JSON-RPC method name
+    ///
+    /// Task methods use the specification's `tasks/...` namespace.
     fn operation_to_method(operation: &A2AOperation) -> &'static str {
         match operation {
             A2AOperation::SendMessage { stream: true, .. } => "message/stream",
             A2AOperation::SendMessage { stream: false, .. } => "message/send",
-            A2AOperation::GetTask { .. } => "task/get",
-            A2AOperation::ListTasks { .. } => "task/list",
-            A2AOperation::CancelTask { .. } => "task/cancel",
+            A2AOperation::GetTask { .. } => "tasks/get",
+            A2AOperation::ListTasks { .. } => "tasks/list",
+            A2AOperation::CancelTask { .. } => "tasks/cancel",
             A2AOperation::DiscoverAgent => "agent/discover",
-            A2AOperation::SubscribeTask { .. } => "task/subscribe",
+            A2AOperation::SubscribeTask { .. } => "tasks/subscribe",
+            A2AOperation::ResubscribeTask { .. } => "tasks/resubscribe",
             A2AOperation::RegisterWebhook { .. } => "webhook/register",
+            A2AOperation::SetPushNotificationConfig { .. } => "tasks/pushNotificationConfig/set",
+            A2AOperation::GetPushNotificationConfig { .. } => "tasks/pushNotificationConfig/get",
+            A2AOperation::ListPushNotificationConfigs { .. } => "tasks/pushNotificationConfig/list",
+            A2AOperation::DeletePushNotificationConfig { .. } => {
+                "tasks/pushNotificationConfig/delete"
+            }
         }
     }
 }
 
 impl Codec for JsonRpcCodec {
     fn encode_request(&self, operation: &A2AOperation) -> Result<Bytes, A2AError> {
-        // Encode the operation using the inner JSON codec
-        let params_bytes = JsonCodec.encode_request(operation)?;
-        let params: Value = serde_json::from_slice(&params_bytes)?;
-
-        // Wrap in JSON-RPC 2.0 envelope
-        let request = JsonRpcRequest {
-            jsonrpc: "2.0".to_string(),
-            method: Self::operation_to_method(operation).to_string(),
-            params,
+        // Wrap the JSON codec's params in a JSON-RPC 2.0 envelope
+        self.format.encode(&JsonRpcRequest {
+            jsonrpc: "2.0",
+            method: Self::operation_to_method(operation),
+            params: RequestParams::from_operation(operation),
             id: Uuid::now_v7().to_string(),
-        };
-
-        let bytes = serde_json::to_vec(&request)?;
-        Ok(Bytes::from(bytes))
+        })
     }
 
     fn decode_response(
@@ -103,16 +218,17 @@ impl Codec for JsonRpcCodec {
             return Ok(A2AResponse::Empty);
         }
 
+        if self.format.is_binary() {
+            return self.decode_binary_response(body, operation);
+        }
+
         // Parse JSON-RPC response envelope
         let jsonrpc_response: JsonRpcResponse = serde_json::from_slice(body)
             .map_err(|e| A2AError::Protocol(format!("Failed to parse JSON-RPC response: {}", e)))?;
 
         // Check for JSON-RPC error
         if let Some(error) = jsonrpc_response.error {
-            return Err(A2AError::Protocol(format!(
-                "JSON-RPC error {}: {}",
-                error.code, error.message
-            )));
+            return Err(error.into());
         }
 
         // Extract result
@@ -121,24 +237,84 @@ impl Codec for JsonRpcCodec {
         })?;
 
         // Decode the result using the inner JSON codec
-        let result_bytes = serde_json::to_vec(&result)?;
-        JsonCodec.decode_response(&result_bytes, operation)
+        self.decode_result(result.get().as_bytes(), operation)
     }
 
     fn content_type(&self) -> &str {
-        "application/a2a+json"
+        self.format.media_type()
+    }
+
+    fn bindings(&self) -> &[&str] {
+        &["json-rpc", "jsonrpc"]
+    }
+}
+
+impl JsonRpcCodec {
+    /// Decode a response envelope in a binary format
+    fn decode_binary_response(
+        &self,
+        body: &[u8],
+        operation: &A2AOperation,
+    ) -> Result<A2AResponse, A2AError> {
+        let response: OwnedJsonRpcResponse = self
+            .format
+            .decode(body)
+            .map_err(|e| A2AError::Protocol(format!("Failed to parse JSON-RPC response: {}", e)))?;
+
+        if let Some(error) = response.error {
+            return Err(error.into());
+        }
+
+        let result = response.result.ok_or_else(|| {
+            A2AError::Protocol("JSON-RPC response missing 'result' field".to_string())
+        })?;
+
+        self.decode_result(&serde_json::to_vec(&result)?, operation)
+    }
+
+    /// Decode the result of a response envelope with the JSON codec
+    fn decode_result(
+        &self,
+        result: &[u8],
+        operation: &A2AOperation,
+    ) -> Result<A2AResponse, A2AError> {
+        match &self.strict {
+            Some(codec) => codec.decode_response(result, operation),
+            None => JsonCodec.decode_response(result, operation),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::protocol::message::Message;
+    use crate::protocol::{
+        consts::media_types, message::Message, push::PushNotificationConfig, task::TaskStatus,
+    };
 
     use super::*;
 
+    #[test]
+    fn test_request_id() {
+        let operation = A2AOperation::GetTask {
+            task_id: "task-123".to_string(),
+        };
+        let bytes = JsonRpcCodec::new().encode_request(&operation).unwrap();
+        let json: Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(
+            JsonRpcCodec::request_id(&bytes).unwrap().as_deref(),
+            json["id"].as_str()
+        );
+        assert_eq!(
+            JsonRpcCodec::request_id(br#"{"jsonrpc": "2.0", "method": "ping"}"#).unwrap(),
+            None
+        );
+        assert!(JsonRpcCodec::request_id(b"\"req-1\"").is_err());
+    }
+
     #[test]
     fn test_encode_send_message() {
-        let codec = JsonRpcCodec;
+        let codec = JsonRpcCodec::new();
         let message = Message::user("Hello");
 
         let operation = A2AOperation::SendMessage {
@@ -161,7 +337,7 @@ mod tests {
 
     #[test]
     fn test_encode_streaming_message() {
-        let codec = JsonRpcCodec;
+        let codec = JsonRpcCodec::new();
         let message = Message::user("Hello");
 
         let operation = A2AOperation::SendMessage {
@@ -199,20 +375,78 @@ mod tests {
         let op = A2AOperation::GetTask {
             task_id: "task-123".to_string(),
         };
-        assert_eq!(JsonRpcCodec::operation_to_method(&op), "task/get");
+        assert_eq!(JsonRpcCodec::operation_to_method(&op), "tasks/get");
 
         let op = A2AOperation::CancelTask {
             task_id: "task-123".to_string(),
+            reason: None,
         };
-        assert_eq!(JsonRpcCodec::operation_to_method(&op), "task/cancel");
+        assert_eq!(JsonRpcCodec::operation_to_method(&op), "tasks/cancel");
+
+        let op = A2AOperation::ResubscribeTask {
+            task_id: "task-123".to_string(),
+        };
+        assert_eq!(JsonRpcCodec::operation_to_method(&op), "tasks/resubscribe");
+        let params = serde_json::to_value(RequestParams::from_operation(&op)).unwrap();
+        assert_eq!(params, serde_json::json!({"id": "task-123"}));
+
+        let op = A2AOperation::ListTasks {
+            status: Some(TaskStatus::Working),
+            limit: Some(10),
+            offset: None,
+            next_token: Some("page-2".to_string()),
+        };
+        let params = serde_json::to_value(RequestParams::from_operation(&op)).unwrap();
+        assert_eq!(
+            params,
+            serde_json::json!({"status": "working", "pageSize": 10, "pageToken": "page-2"})
+        );
 
         let op = A2AOperation::DiscoverAgent;
         assert_eq!(JsonRpcCodec::operation_to_method(&op), "agent/discover");
     }
 
+    #[test]
+    fn test_encode_push_notification_config() {
+        let codec = JsonRpcCodec::new();
+
+        let op = A2AOperation::SetPushNotificationConfig {
+            task_id: "task-123".to_string(),
+            config: PushNotificationConfig::new("https://app.example.com/hook").with_token("t"),
+        };
+        let json: serde_json::Value =
+            serde_json::from_slice(&codec.encode_request(&op).unwrap()).unwrap();
+        assert_eq!(json["method"], "tasks/pushNotificationConfig/set");
+        assert_eq!(json["params"]["taskId"], "task-123");
+        assert_eq!(json["params"]["pushNotificationConfig"]["token"], "t");
+
+        let op = A2AOperation::GetPushNotificationConfig {
+            task_id: "task-123".to_string(),
+            config_id: "hook-1".to_string(),
+        };
+        let json: serde_json::Value =
+            serde_json::from_slice(&codec.encode_request(&op).unwrap()).unwrap();
+        assert_eq!(json["method"], "tasks/pushNotificationConfig/get");
+        assert_eq!(json["params"]["id"], "task-123");
+        assert_eq!(json["params"]["pushNotificationConfigId"], "hook-1");
+
+        let body = br#"{"jsonrpc": "2.0", "id": "1", "result": [
+            {"taskId": "task-123", "pushNotificationConfig": {"id": "hook-1", "url": "https://app.example.com/hook"}}
+        ]}"#;
+        let op = A2AOperation::ListPushNotificationConfigs {
+            task_id: "task-123".to_string(),
+        };
+        let response = codec.decode_response(body, &op).unwrap();
+        assert!(matches!(
+            response,
+            A2AResponse::PushNotificationConfigs(configs)
+                if configs[0].push_notification_config.id.as_deref() == Some("hook-1")
+        ));
+    }
+
     #[test]
     fn test_decode_success_response() {
-        let codec = JsonRpcCodec;
+        let codec = JsonRpcCodec::new();
         let json = r#"{
             "jsonrpc": "2.0",
             "result": {
@@ -243,7 +477,7 @@ mod tests {
 
     #[test]
     fn test_decode_error_response() {
-        let codec = JsonRpcCodec;
+        let codec = JsonRpcCodec::new();
         let json = r#"{
             "jsonrpc": "2.0",
             "error": {
@@ -261,17 +495,17 @@ mod tests {
         assert!(result.is_err());
 
         match result {
-            Err(A2AError::Protocol(msg)) => {
-                assert!(msg.contains("-32600"));
-                assert!(msg.contains("Invalid Request"));
+            Err(A2AError::JsonRpc { code, message, .. }) => {
+                assert_eq!(code, -32600);
+                assert_eq!(message, "Invalid Request");
             }
-            _ => panic!("Expected Protocol error"),
+            _ => panic!("Expected JSON-RPC error"),
         }
     }
 
     #[test]
     fn test_decode_missing_result() {
-        let codec = JsonRpcCodec;
+        let codec = JsonRpcCodec::new();
         let json = r#"{
             "jsonrpc": "2.0",
             "id": "req-123"
@@ -292,9 +526,33 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn test_binary_format() {
+        let codec = JsonRpcCodec::new().with_format(WireFormat::MessagePack);
+        assert_eq!(codec.content_type(), media_types::MSGPACK);
+
+        let operation = A2AOperation::GetTask {
+            task_id: "task-123".to_string(),
+        };
+        let body = codec.encode_request(&operation).unwrap();
+        let id = JsonRpcCodec::request_id_in(WireFormat::MessagePack, &body)
+            .unwrap()
+            .unwrap();
+
+        let task = crate::protocol::Task::new("task-123", Message::user("Hello"));
+        let response = WireFormat::MessagePack
+            .encode(&serde_json::json!({"jsonrpc": "2.0", "result": task, "id": id}))
+            .unwrap();
+        match codec.decode_response(&response, &operation).unwrap() {
+            A2AResponse::Task(decoded) => assert_eq!(decoded.id, "task-123"),
+            other => panic!("Unexpected response: {:?}", other),
+        }
+    }
+
     #[test]
     fn test_content_type() {
-        let codec = JsonRpcCodec;
-        assert_eq!(codec.content_type(), "application/a2a+json");
+        let codec = JsonRpcCodec::new();
+        assert_eq!(codec.content_type(), media_types::A2A_JSON);
     }
 }
@@ -0,0 +1,339 @@
+//! Incremental decoding of task lists
+//!
+//! A `ListTasks` response with thousands of tasks is large, and decoding it into a
+//! `Vec<Task>` holds every task in memory at once. [`TaskListDecoder`] instead picks
+//! the tasks out of the `tasks` array as the body arrives and yields each one as soon
+//! as it is complete, keeping only the rest of the document (the total, the
+//! continuation token, or a JSON-RPC envelope) to decode at the end.
+//!
+//! Bodies that are not JSON objects, e.g. CBOR or compressed payloads, are buffered
+//! and decoded by the codec as a whole.
+
+use crate::{
+    codec::Codec,
+    protocol::{error::A2AError, operation::A2AOperation, task::Task},
+    service::response::A2AResponse,
+};
+
+/// Item of a decoded task list
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskListItem {
+    /// A task of the list
+    Task(Box<Task>),
+
+    /// End of the list, with what the agent said about the rest of it
+    End {
+        /// Total number of tasks matching the query
+        total: usize,
+
+        /// Continuation token of the next page, if any
+        next_token: Option<String>,
+    },
+}
+
+/// How the decoder treats the body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Waiting for the first byte that is not whitespace
+    Detect,
+
+    /// Picking tasks out of a JSON document
+    Scan,
+
+    /// Buffering the body for the codec
+    Buffer,
+}
+
+/// Incremental decoder of `ListTasks` response bodies
+///
+/// The `tasks` array is looked up at the top level of the document and, for JSON-RPC
+/// envelopes, in its `result`. Tasks are decoded as they are, without the codec's
+/// checks for unknown fields.
+#[derive(Debug)]
+pub struct TaskListDecoder {
+    mode: Mode,
+
+    /// The document without the elements of the `tasks` array
+    skeleton: Vec<u8>,
+
+    /// Bytes of the task being received
+    element: Vec<u8>,
+
+    /// Nesting depth of objects and arrays
+    depth: usize,
+
+    /// Depth of the elements of the `tasks` array, while inside it
+    array_depth: Option<usize>,
+
+    /// Whether the `tasks` array was found, so later keys of that name are ignored
+    found: bool,
+
+    in_string: bool,
+    escaped: bool,
+
+    /// Last string seen at a depth where the `tasks` key may be
+    string: Option<Vec<u8>>,
+
+    /// Key whose value comes next
+    key: Option<Vec<u8>>,
+}
+
+impl TaskListDecoder {
+    /// Create a decoder for a new body
+    pub fn new() -> Self {
+        Self {
+            mode: Mode::Detect,
+            skeleton: Vec::new(),
+            element: Vec::new(),
+            depth: 0,
+            array_depth: None,
+            found: false,
+            in_string: false,
+            escaped: false,
+            string: None,
+            key: None,
+        }
+    }
+
+    /// Feed a chunk of the body, returning the tasks it completes
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Protocol` if an element of the `tasks` array is not a task
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Vec<Task>, A2AError> {
+        let mut tasks = Vec::new();
+        for &byte in chunk {
+            match self.mode {
+                Mode::Detect if byte.is_ascii_whitespace() => {}
+                Mode::Detect => {
+                    self.mode = if byte == b'{' {
+                        Mode::Scan
+                    } else {
+                        Mode::Buffer
+                    };
+                    self.scan(byte, &mut tasks)?;
+                }
+                Mode::Scan => self.scan(byte, &mut tasks)?,
+                Mode::Buffer => self.skeleton.push(byte),
+            }
+        }
+        Ok(tasks)
+    }
+
+    /// Decode the rest of the document once the body is complete
+    ///
+    /// Returns the tasks that were not yielded by [`feed`](Self::feed), which happens
+    /// if the body was buffered, along with the end of the list.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the body is truncated or the codec rejects it, e.g. because
+    /// it carries a JSON-RPC error
+    pub fn finish(
+        self,
+        codec: &dyn Codec,
+        operation: &A2AOperation,
+    ) -> Result<(Vec<Task>, TaskListItem), A2AError> {
+        if self.mode == Mode::Scan && (self.depth != 0 || self.in_string) {
+            return Err(A2AError::Protocol("Task list response is truncated".into()));
+        }
+
+        match codec.decode_response(&self.skeleton, operation)? {
+            A2AResponse::TaskList {
+                tasks,
+                total,
+                next_token,
+            } => Ok((tasks, TaskListItem::End { total, next_token })),
+            A2AResponse::Empty => Ok((
+                Vec::new(),
+                TaskListItem::End {
+                    total: 0,
+                    next_token: None,
+                },
+            )),
+            _ => Err(A2AError::Protocol(
+                "Expected task list response from list_tasks".into(),
+            )),
+        }
+    }
+
+    /// Process one byte of a JSON document
+    fn scan(&mut self, byte: u8, tasks: &mut Vec<Task>) -> Result<(), A2AError> {
+        let in_element = !self.element.is_empty();
+
+        if self.in_string {
+            if self.escaped {
+                self.escaped = false;
+            } else if byte == b'\\' {
+                self.escaped = true;
+            } else if byte == b'"' {
+                self.in_string = false;
+            }
+            if in_element {
+                self.element.push(byte);
+            } else {
+                self.skeleton.push(byte);
+                if let (true, Some(string)) = (self.in_string, self.string.as_mut()) {
+                    string.push(byte);
+                }
+            }
+            return Ok(());
+        }
+
+        // Between the elements of the tasks array
+        if self.array_depth == Some(self.depth) && !in_element {
+            match byte {
+                b',' => {}
+                b']' => {
+                    self.depth -= 1;
+                    self.array_depth = None;
+                    self.skeleton.push(byte);
+                }
+                b'{' => {
+                    self.depth += 1;
+                    self.element.push(byte);
+                }
+                byte if byte.is_ascii_whitespace() => {}
+                _ => {
+                    return Err(A2AError::Protocol(
+                        "Task list contains an element that is not a task".into(),
+                    ))
+                }
+            }
+            return Ok(());
+        }
+
+        if in_element {
+            self.element.push(byte);
+            match byte {
+                b'"' => self.in_string = true,
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' => {
+                    self.depth -= 1;
+                    if Some(self.depth) == self.array_depth {
+                        let task = serde_json::from_slice(&self.element).map_err(|e| {
+                            A2AError::Protocol(format!("Failed to decode listed task: {}", e))
+                        })?;
+                        tasks.push(task);
+                        self.element.clear();
+                    }
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        self.skeleton.push(byte);
+        match byte {
+            b'"' => {
+                self.in_string = true;
+                self.string = (self.depth <= 2).then(Vec::new);
+            }
+            b':' => self.key = self.string.take(),
+            b'[' => {
+                self.depth += 1;
+                if !self.found && self.key.take().as_deref() == Some(b"tasks") {
+                    self.found = true;
+                    self.array_depth = Some(self.depth);
+                }
+            }
+            b'{' => {
+                self.depth += 1;
+                self.key = None;
+            }
+            b'}' | b']' => self.depth = self.depth.saturating_sub(1),
+            byte if byte.is_ascii_whitespace() => {}
+            _ => self.key = None,
+        }
+        Ok(())
+    }
+}
+
+impl Default for TaskListDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        codec::{JsonCodec, JsonRpcCodec},
+        protocol::message::Message,
+    };
+
+    fn list_operation() -> A2AOperation {
+        A2AOperation::ListTasks {
+            status: None,
+            limit: None,
+            offset: None,
+            next_token: None,
+        }
+    }
+
+    fn body(count: usize) -> serde_json::Value {
+        let tasks: Vec<_> = (0..count)
+            .map(|i| Task::new(format!("task-{}", i), Message::user("Say \"[hi]\"")))
+            .collect();
+        json!({"total": 100, "tasks": tasks, "nextToken": "page-2"})
+    }
+
+    #[test]
+    fn test_decode_in_chunks() {
+        let body = serde_json::to_vec_pretty(&body(3)).unwrap();
+
+        for size in [1, 7, body.len()] {
+            let mut decoder = TaskListDecoder::new();
+            let mut ids = Vec::new();
+            for chunk in body.chunks(size) {
+                ids.extend(decoder.feed(chunk).unwrap().into_iter().map(|t| t.id));
+            }
+            assert_eq!(ids, ["task-0", "task-1", "task-2"]);
+
+            let (rest, end) = decoder
+                .finish(&JsonCodec::new(), &list_operation())
+                .unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(
+                end,
+                TaskListItem::End {
+                    total: 100,
+                    next_token: Some("page-2".into())
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_envelopes() {
+        let envelope = json!({"jsonrpc": "2.0", "id": "1", "result": body(2)});
+        let mut decoder = TaskListDecoder::new();
+        let tasks = decoder
+            .feed(&serde_json::to_vec(&envelope).unwrap())
+            .unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert!(decoder
+            .finish(&JsonRpcCodec::new(), &list_operation())
+            .is_ok());
+
+        let error =
+            json!({"jsonrpc": "2.0", "id": "1", "error": {"code": -32000, "message": "Nope"}});
+        let mut decoder = TaskListDecoder::new();
+        assert!(decoder
+            .feed(&serde_json::to_vec(&error).unwrap())
+            .unwrap()
+            .is_empty());
+        assert!(decoder
+            .finish(&JsonRpcCodec::new(), &list_operation())
+            .is_err());
+
+        let mut decoder = TaskListDecoder::new();
+        decoder.feed(br#"{"tasks": [{"id": "#).unwrap();
+        assert!(decoder
+            .finish(&JsonCodec::new(), &list_operation())
+            .is_err());
+    }
+}
@@ -1,18 +1,31 @@
 //! Serialization codecs for different protocol bindings
 
+pub mod canonical;
+#[cfg(feature = "zstd-dict")]
+pub mod compression;
+pub mod format;
 pub mod json;
 pub mod jsonrpc;
+pub mod list;
 pub mod sse;
 
-pub use json::JsonCodec;
-pub use jsonrpc::JsonRpcCodec;
+pub use canonical::{Canonicalizer, Jcs};
+#[cfg(feature = "zstd-dict")]
+pub use compression::{ZstdDictionary, ZstdDictionaryCodec};
+pub use format::WireFormat;
+pub use json::{JsonCodec, StrictJsonCodec};
+pub use jsonrpc::{JsonRpcCodec, JsonRpcNotification};
+pub use list::{TaskListDecoder, TaskListItem};
 pub use sse::{SseCodec, SseEvent};
 
 use crate::{
     protocol::{error::A2AError, operation::A2AOperation},
     service::response::A2AResponse,
 };
-use bytes::Bytes;
+use std::cell::RefCell;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::Serialize;
 
 /// Codec trait for encoding and decoding A2A protocol messages
 ///
@@ -51,4 +64,44 @@ pub trait Codec: Send + Sync {
     ///
     /// The MIME type (e.g., "application/json", "application/protobuf")
     fn content_type(&self) -> &str;
+
+    /// Get the agent card endpoint types this codec can talk to
+    ///
+    /// Used to reject agents whose card declares none of them. Codecs keep the
+    /// default, an empty list, if they cannot tell.
+    fn bindings(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Get the headers describing how request bodies are encoded
+    ///
+    /// Used by codecs that compress payloads to set `Content-Encoding` and activate
+    /// the extensions they rely on. Values of the
+    /// [`EXTENSIONS`](crate::protocol::consts::headers::EXTENSIONS) header are merged
+    /// with those of the request.
+    fn headers(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+}
+
+/// Initial capacity of the per-thread encode buffer
+const ENCODE_BUFFER_CAPACITY: usize = 8 * 1024;
+
+thread_local! {
+    static ENCODE_BUFFER: RefCell<BytesMut> = RefCell::new(BytesMut::with_capacity(ENCODE_BUFFER_CAPACITY));
+}
+
+/// Serialize a value as JSON into a reusable per-thread buffer
+///
+/// The encoded bytes are split off the buffer, so its spare capacity is reused by the
+/// next call instead of allocating a fresh `Vec` per request.
+pub(crate) fn encode_into_buffer<T: Serialize + ?Sized>(value: &T) -> Result<Bytes, A2AError> {
+    ENCODE_BUFFER.with(|buffer| {
+        let mut buffer = buffer.borrow_mut();
+        buffer.reserve(ENCODE_BUFFER_CAPACITY);
+        let result = serde_json::to_writer((&mut *buffer).writer(), value);
+        let bytes = buffer.split().freeze();
+        result?;
+        Ok(bytes)
+    })
 }
@@ -1,9 +1,13 @@
 //! Server-Sent Events (SSE) codec for streaming A2A responses
 //!
 //! This codec handles parsing SSE event streams that contain JSON-RPC 2.0 responses.
+//! Comments and `retry:` fields are surfaced as control events, so consumers can
+//! observe keep-alives and honour the reconnection delay requested by the server.
 
-use eventsource_stream::Eventsource;
-use futures::stream::{Stream, StreamExt};
+use std::{future, time::Duration};
+
+use bytes::{Bytes, BytesMut};
+use futures::stream::{self, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -21,9 +25,73 @@ pub struct SseEvent {
     /// Whether this is the final event in the stream
     #[serde(default)]
     pub final_event: bool,
+
+    /// Event ID assigned by the server (the SSE `id:` field), if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
 }
 
 impl SseEvent {
+    /// Event kind used for heartbeats
+    pub const HEARTBEAT: &'static str = "heartbeat";
+
+    /// Create a heartbeat event from an SSE comment
+    pub fn heartbeat(comment: impl Into<String>) -> Self {
+        Self {
+            kind: Self::HEARTBEAT.to_string(),
+            payload: Value::String(comment.into()),
+            final_event: false,
+            id: None,
+        }
+    }
+
+    /// Check if this event is a heartbeat (an SSE comment) rather than protocol data
+    pub fn is_heartbeat(&self) -> bool {
+        self.kind == Self::HEARTBEAT && self.payload.is_string()
+    }
+
+    /// Event kind used for reconnection delay hints
+    pub const RETRY: &'static str = "retry";
+
+    /// Create a retry hint event from an SSE `retry:` field
+    ///
+    /// The payload is the delay in milliseconds.
+    pub fn retry(delay: Duration) -> Self {
+        Self {
+            kind: Self::RETRY.to_string(),
+            payload: Value::from(u64::try_from(delay.as_millis()).unwrap_or(u64::MAX)),
+            final_event: false,
+            id: None,
+        }
+    }
+
+    /// Get the reconnection delay if this event is a retry hint
+    pub fn retry_hint(&self) -> Option<Duration> {
+        (self.kind == Self::RETRY)
+            .then(|| self.payload.as_u64())
+            .flatten()
+            .map(Duration::from_millis)
+    }
+
+    /// Check if this event is a heartbeat or retry hint rather than protocol data
+    pub fn is_control(&self) -> bool {
+        self.is_heartbeat() || self.retry_hint().is_some()
+    }
+
+    /// Get the ID of the task this event belongs to, if present
+    ///
+    /// Update events carry a `taskId`, while task snapshots (kind `task`) carry an `id`.
+    pub fn task_id(&self) -> Option<&str> {
+        self.payload
+            .get("taskId")
+            .or_else(|| {
+                (self.kind == "task")
+                    .then(|| self.payload.get("id"))
+                    .flatten()
+            })
+            .and_then(|id| id.as_str())
+    }
+
     /// Check if this event represents a terminal state
     pub fn is_terminal(&self) -> bool {
         if self.final_event {
@@ -61,58 +129,217 @@ impl SseCodec {
     /// Parse an SSE byte stream into a stream of events
     ///
     /// This method takes a byte stream (typically from reqwest) and parses it
-    /// into individual SSE events containing JSON-RPC responses.
+    /// into individual SSE events containing JSON-RPC responses. SSE comments are
+    /// passed through as heartbeat events (see [`SseEvent::is_heartbeat`]) and `retry:`
+    /// fields as retry hints (see [`SseEvent::retry_hint`]).
     pub fn parse_stream<S>(&self, byte_stream: S) -> impl Stream<Item = Result<SseEvent, A2AError>>
     where
-        S: Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Send + 'static,
+        S: Stream<Item = Result<Bytes, reqwest::Error>> + Send + 'static,
     {
-        byte_stream.eventsource().map(|result| {
-            match result {
-                Ok(event) => {
-                    // Parse the event data as JSON-RPC response
-                    let jsonrpc: Value = serde_json::from_str(&event.data).map_err(|e| {
-                        A2AError::Protocol(format!("Failed to parse SSE event data: {}", e))
-                    })?;
-
-                    // Check for JSON-RPC error
-                    if let Some(error) = jsonrpc.get("error") {
-                        let error_msg = error
-                            .get("message")
-                            .and_then(|m| m.as_str())
-                            .unwrap_or("Unknown error");
-                        return Err(A2AError::Protocol(format!(
-                            "SSE stream error: {}",
-                            error_msg
-                        )));
+        byte_stream
+            .scan(SseParser::default(), |parser, chunk| {
+                let frames: Vec<_> = match chunk {
+                    Ok(bytes) => parser.feed(&bytes).into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(A2AError::Transport(format!("SSE stream error: {}", e)))],
+                };
+                future::ready(Some(stream::iter(frames)))
+            })
+            .flatten()
+            .map(|frame| match frame? {
+                SseFrame::Comment(comment) => Ok(SseEvent::heartbeat(comment)),
+                SseFrame::Retry(millis) => Ok(SseEvent::retry(Duration::from_millis(millis))),
+                SseFrame::Event { id, data } => Self::decode_event(id, &data),
+            })
+    }
+
+    /// Decode the data of an SSE event as a JSON-RPC response
+    ///
+    /// The data is parsed straight from bytes and the result is moved into the event,
+    /// without building a `Value` for the envelope.
+    fn decode_event(id: String, data: &[u8]) -> Result<SseEvent, A2AError> {
+        // Parse the event data as JSON-RPC response
+        let jsonrpc: SseResponse = serde_json::from_slice(data)
+            .map_err(|e| A2AError::Protocol(format!("Failed to parse SSE event data: {}", e)))?;
+
+        // Check for JSON-RPC error
+        if let Some(error) = jsonrpc.error {
+            let message = error.message.unwrap_or_else(|| "Unknown error".to_string());
+            return Err(match error.code {
+                Some(code) => A2AError::JsonRpc {
+                    code,
+                    message,
+                    data: error.data,
+                },
+                None => A2AError::Protocol(format!("SSE stream error: {}", message)),
+            });
+        }
+
+        // Extract result from JSON-RPC response
+        let result = jsonrpc
+            .result
+            .ok_or_else(|| A2AError::Protocol("SSE event missing 'result' field".to_string()))?;
+
+        // Determine if this is a final event
+        let final_event = result
+            .get("final")
+            .and_then(|f| f.as_bool())
+            .unwrap_or(false);
+
+        // Extract event kind
+        let kind = result
+            .get("kind")
+            .and_then(|k| k.as_str())
+            .unwrap_or("event")
+            .to_string();
+
+        Ok(SseEvent {
+            kind,
+            payload: result,
+            final_event,
+            id: (!id.is_empty()).then_some(id),
+        })
+    }
+}
+
+/// JSON-RPC response carried in the data of an SSE event
+#[derive(Debug, Deserialize)]
+struct SseResponse {
+    #[serde(default)]
+    result: Option<Value>,
+    #[serde(default)]
+    error: Option<SseResponseError>,
+}
+
+/// Error object of a JSON-RPC response
+#[derive(Debug, Deserialize)]
+struct SseResponseError {
+    #[serde(default)]
+    code: Option<i64>,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    data: Option<Value>,
+}
+
+/// Raw frame produced by the SSE parser
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SseFrame {
+    /// A dispatched event with its last event ID and data
+    Event { id: String, data: Bytes },
+
+    /// A comment line (`: ...`), commonly used as a keep-alive
+    Comment(String),
+
+    /// A reconnection delay in milliseconds (`retry: ...`)
+    Retry(u64),
+}
+
+/// Incremental parser for the `text/event-stream` format
+///
+/// Lines may be split arbitrarily across chunks. Complete lines are processed in place
+/// and only lines spanning chunks are buffered. Fields other than `data`, `id`, and
+/// `retry` are ignored.
+#[derive(Debug, Default)]
+struct SseParser {
+    /// Bytes of the current, incomplete line
+    line: BytesMut,
+
+    /// Data buffer of the event being assembled
+    data: BytesMut,
+
+    /// Last event ID, which persists across events per the SSE spec
+    last_id: String,
+
+    /// Whether the previous chunk ended with a CR that may be followed by LF
+    pending_cr: bool,
+}
+
+impl SseParser {
+    /// Feed a chunk of bytes, returning the frames it completes
+    fn feed(&mut self, mut chunk: &[u8]) -> Vec<SseFrame> {
+        let mut frames = Vec::new();
+
+        if self.pending_cr {
+            self.pending_cr = false;
+            if let Some(rest) = chunk.strip_prefix(b"\n") {
+                chunk = rest;
+            }
+        }
+
+        while let Some(pos) = chunk.iter().position(|b| *b == b'\n' || *b == b'\r') {
+            if self.line.is_empty() {
+                self.process_line(&chunk[..pos], &mut frames);
+            } else {
+                self.line.extend_from_slice(&chunk[..pos]);
+                let line = self.line.split();
+                self.process_line(&line, &mut frames);
+            }
+
+            let rest = &chunk[pos + 1..];
+            chunk = if chunk[pos] == b'\r' {
+                match rest.first() {
+                    Some(b'\n') => &rest[1..],
+                    Some(_) => rest,
+                    None => {
+                        self.pending_cr = true;
+                        rest
                     }
+                }
+            } else {
+                rest
+            };
+        }
 
-                    // Extract result from JSON-RPC response
-                    let result = jsonrpc.get("result").ok_or_else(|| {
-                        A2AError::Protocol("SSE event missing 'result' field".to_string())
-                    })?;
-
-                    // Determine if this is a final event
-                    let final_event = result
-                        .get("final")
-                        .and_then(|f| f.as_bool())
-                        .unwrap_or(false);
-
-                    // Extract event kind
-                    let kind = result
-                        .get("kind")
-                        .and_then(|k| k.as_str())
-                        .unwrap_or("event")
-                        .to_string();
-
-                    Ok(SseEvent {
-                        kind,
-                        payload: result.clone(),
-                        final_event,
-                    })
+        self.line.extend_from_slice(chunk);
+        frames
+    }
+
+    /// Process a complete line
+    fn process_line(&mut self, line: &[u8], frames: &mut Vec<SseFrame>) {
+        // An empty line dispatches the event
+        if line.is_empty() {
+            if !self.data.is_empty() {
+                self.data.truncate(self.data.len() - 1);
+                frames.push(SseFrame::Event {
+                    id: self.last_id.clone(),
+                    data: self.data.split().freeze(),
+                });
+            }
+            return;
+        }
+
+        if let Some(comment) = line.strip_prefix(b":") {
+            let comment = comment.strip_prefix(b" ").unwrap_or(comment);
+            frames.push(SseFrame::Comment(
+                String::from_utf8_lossy(comment).into_owned(),
+            ));
+            return;
+        }
+
+        let (field, value) = match line.iter().position(|b| *b == b':') {
+            Some(pos) => {
+                let value = &line[pos + 1..];
+                (&line[..pos], value.strip_prefix(b" ").unwrap_or(value))
+            }
+            None => (line, &b""[..]),
+        };
+
+        match field {
+            b"data" => {
+                self.data.extend_from_slice(value);
+                self.data.extend_from_slice(b"\n");
+            }
+            b"id" if !value.contains(&0) => {
+                self.last_id = String::from_utf8_lossy(value).into_owned()
+            }
+            // Per the SSE spec, values that are not all ASCII digits are ignored
+            b"retry" if !value.is_empty() && value.iter().all(u8::is_ascii_digit) => {
+                if let Ok(millis) = std::str::from_utf8(value).unwrap_or_default().parse() {
+                    frames.push(SseFrame::Retry(millis));
                 }
-                Err(e) => Err(A2AError::Transport(format!("SSE stream error: {}", e))),
             }
-        })
+            _ => {}
+        }
     }
 }
 
@@ -131,6 +358,7 @@ mod tests {
                 "state": "completed"
             }),
             final_event: false,
+            id: None,
         };
         assert!(event.is_terminal());
 
@@ -138,6 +366,7 @@ mod tests {
             kind: "artifact-update".to_string(),
             payload: json!({}),
             final_event: true,
+            id: None,
         };
         assert!(event.is_terminal());
 
@@ -147,6 +376,7 @@ mod tests {
                 "state": "running"
             }),
             final_event: false,
+            id: None,
         };
         assert!(!event.is_terminal());
     }
@@ -159,6 +389,7 @@ mod tests {
                 "state": "failed"
             }),
             final_event: false,
+            id: None,
         };
         assert!(event.is_error());
 
@@ -168,6 +399,7 @@ mod tests {
                 "state": "completed"
             }),
             final_event: false,
+            id: None,
         };
         assert!(!event.is_error());
     }
@@ -179,7 +411,7 @@ mod tests {
         let codec = SseCodec;
 
         // Create a mock byte stream with SSE events
-        let sse_data = "data: {\"jsonrpc\":\"2.0\",\"result\":{\"kind\":\"status-update\",\"state\":\"running\"},\"id\":\"1\"}\n\n\
+        let sse_data = "id: evt-1\ndata: {\"jsonrpc\":\"2.0\",\"result\":{\"kind\":\"status-update\",\"state\":\"running\"},\"id\":\"1\"}\n\n\
                         data: {\"jsonrpc\":\"2.0\",\"result\":{\"kind\":\"artifact-update\",\"final\":true},\"id\":\"2\"}\n\n";
 
         let byte_stream = futures::stream::once(async move {
@@ -193,6 +425,7 @@ mod tests {
         let event1 = event_stream.next().await.unwrap().unwrap();
         assert_eq!(event1.kind, "status-update");
         assert!(!event1.final_event);
+        assert_eq!(event1.id.as_deref(), Some("evt-1"));
 
         // Second event
         let event2 = event_stream.next().await.unwrap().unwrap();
@@ -219,10 +452,63 @@ mod tests {
         assert!(result.is_err());
 
         match result {
-            Err(A2AError::Protocol(msg)) => {
-                assert!(msg.contains("Invalid Request"));
+            Err(A2AError::JsonRpc { code, message, .. }) => {
+                assert_eq!(code, -32600);
+                assert_eq!(message, "Invalid Request");
             }
-            _ => panic!("Expected Protocol error"),
+            _ => panic!("Expected JSON-RPC error"),
         }
     }
+
+    #[tokio::test]
+    async fn test_parse_sse_heartbeats() {
+        let codec = SseCodec;
+
+        // Events split across chunks with CRLF line endings
+        let chunks = vec![
+            ": keep-alive\r",
+            "\ndata: {\"jsonrpc\":\"2.0\",",
+            "\"result\":{\"kind\":\"status-update\"},\"id\":\"1\"}\r\n\r\n",
+            ":\n",
+            "retry: 2500\nretry: soon\n",
+        ];
+        let byte_stream = futures::stream::iter(
+            chunks
+                .into_iter()
+                .map(|chunk| Ok::<bytes::Bytes, reqwest::Error>(bytes::Bytes::from(chunk))),
+        );
+
+        let events: Vec<_> = codec
+            .parse_stream(byte_stream)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(events.len(), 4);
+        assert!(events[0].is_heartbeat());
+        assert_eq!(events[0].payload, json!("keep-alive"));
+        assert_eq!(events[1].kind, "status-update");
+        assert!(!events[1].is_heartbeat());
+        assert!(events[2].is_heartbeat());
+        assert_eq!(events[3].retry_hint(), Some(Duration::from_millis(2500)));
+        assert!(events[3].is_control() && !events[3].is_terminal());
+        assert_eq!(events[1].retry_hint(), None);
+    }
+
+    #[test]
+    fn test_parser_joins_data_lines() {
+        let mut parser = SseParser::default();
+        assert!(parser
+            .feed(b"id: 7\ndata: {\"a\":\ndata: 1}\nda")
+            .is_empty());
+
+        let frames = parser.feed(b"ta: \n\n");
+        assert_eq!(
+            frames,
+            [SseFrame::Event {
+                id: "7".to_string(),
+                data: Bytes::from_static(b"{\"a\":\n1}\n"),
+            }]
+        );
+    }
 }
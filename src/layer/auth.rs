@@ -1,22 +1,51 @@
 //! Authentication layer for A2A protocol
+//!
+//! Credentials are either fixed when the layer is built or fetched from a
+//! [`CredentialProvider`] for every request, so short-lived tokens can come from a
+//! secret store or a refresh loop:
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use async_trait::async_trait;
+//! use tokio::sync::RwLock;
+//! use tower_a2a::{
+//!     layer::{AuthCredentials, AuthLayer, CredentialProvider},
+//!     prelude::A2AError,
+//! };
+//!
+//! /// Token kept fresh by a background task
+//! struct RefreshedToken(Arc<RwLock<String>>);
+//!
+//! #[async_trait]
+//! impl CredentialProvider for RefreshedToken {
+//!     async fn credentials(&self) -> Result<AuthCredentials, A2AError> {
+//!         Ok(AuthCredentials::bearer(self.0.read().await.clone()))
+//!     }
+//! }
+//!
+//! let token = Arc::new(RwLock::new("initial-token".to_string()));
+//! let layer = AuthLayer::from_provider(RefreshedToken(token.clone()));
+//! ```
 
 use std::{
     future::Future,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 
+use async_trait::async_trait;
 use base64::{engine::general_purpose, Engine as _};
 use tower_layer::Layer;
 use tower_service::Service;
 
 use crate::{
-    protocol::error::A2AError,
-    service::{A2ARequest, A2AResponse},
+    protocol::{consts::headers, error::A2AError},
+    service::{A2AProtocolService, A2ARequest, A2AResponse, ProtocolStack},
 };
 
 /// Authentication credentials
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AuthCredentials {
     /// Bearer token authentication
     Bearer(String),
@@ -53,29 +82,77 @@ impl AuthCredentials {
     /// Get the header name and value for this credential
     pub fn to_header(&self) -> (String, String) {
         match self {
-            AuthCredentials::Bearer(token) => {
-                ("Authorization".to_string(), format!("Bearer {}", token))
-            }
+            AuthCredentials::Bearer(token) => (
+                headers::AUTHORIZATION.to_string(),
+                format!("Bearer {}", token),
+            ),
             AuthCredentials::ApiKey { key, header } => (header.clone(), key.clone()),
             AuthCredentials::Basic { username, password } => {
                 let credentials = format!("{}:{}", username, password);
                 let encoded = general_purpose::STANDARD.encode(credentials.as_bytes());
-                ("Authorization".to_string(), format!("Basic {}", encoded))
+                (
+                    headers::AUTHORIZATION.to_string(),
+                    format!("Basic {}", encoded),
+                )
             }
         }
     }
 }
 
+/// Source of the credentials of each request
+///
+/// Called once per request, including each retry attempt and each streaming request,
+/// so implementations that fetch from a remote store should cache what they fetch
+/// until it expires.
+#[async_trait]
+pub trait CredentialProvider: Send + Sync {
+    /// Get the credentials to authenticate the next request with
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no credentials are available; the request fails with it
+    async fn credentials(&self) -> Result<AuthCredentials, A2AError>;
+}
+
+/// Fixed credentials
+#[async_trait]
+impl CredentialProvider for AuthCredentials {
+    async fn credentials(&self) -> Result<AuthCredentials, A2AError> {
+        Ok(self.clone())
+    }
+}
+
+#[async_trait]
+impl<P: CredentialProvider + ?Sized> CredentialProvider for Arc<P> {
+    async fn credentials(&self) -> Result<AuthCredentials, A2AError> {
+        (**self).credentials().await
+    }
+}
+
 /// Authentication layer
 #[derive(Clone)]
 pub struct AuthLayer {
-    credentials: AuthCredentials,
+    provider: Option<Arc<dyn CredentialProvider>>,
 }
 
 impl AuthLayer {
     /// Create a new authentication layer
     pub fn new(credentials: AuthCredentials) -> Self {
-        Self { credentials }
+        Self::from_provider(credentials)
+    }
+
+    /// Create a layer fetching the credentials of each request from a provider
+    pub fn from_provider(provider: impl CredentialProvider + 'static) -> Self {
+        Self {
+            provider: Some(Arc::new(provider)),
+        }
+    }
+
+    /// Create a layer authenticating requests only if credentials are given
+    ///
+    /// Without credentials, requests pass through unchanged.
+    pub fn optional(credentials: Option<AuthCredentials>) -> Self {
+        credentials.map_or(Self { provider: None }, Self::new)
     }
 
     /// Create a bearer authentication layer
@@ -95,7 +172,7 @@ impl<S> Layer<S> for AuthLayer {
     fn layer(&self, inner: S) -> Self::Service {
         AuthService {
             inner,
-            credentials: self.credentials.clone(),
+            provider: self.provider.clone(),
         }
     }
 }
@@ -104,7 +181,23 @@ impl<S> Layer<S> for AuthLayer {
 #[derive(Clone)]
 pub struct AuthService<S> {
     inner: S,
-    credentials: AuthCredentials,
+    provider: Option<Arc<dyn CredentialProvider>>,
+}
+
+#[async_trait]
+impl<S: ProtocolStack> ProtocolStack for AuthService<S> {
+    type Transport = S::Transport;
+
+    fn protocol(&self) -> &A2AProtocolService<S::Transport> {
+        self.inner.protocol()
+    }
+
+    async fn prepare(&self, request: &mut A2ARequest) -> Result<(), A2AError> {
+        if let Some(provider) = &self.provider {
+            request.context.auth = Some(provider.credentials().await?);
+        }
+        self.inner.prepare(request).await
+    }
 }
 
 impl<S> Service<A2ARequest> for AuthService<S>
@@ -121,11 +214,15 @@ where
     }
 
     fn call(&mut self, mut req: A2ARequest) -> Self::Future {
-        // Inject credentials into request context
-        req.context.auth = Some(self.credentials.clone());
-
+        let provider = self.provider.clone();
         let mut inner = self.inner.clone();
-        Box::pin(async move { inner.call(req).await })
+        Box::pin(async move {
+            // Inject credentials into request context
+            if let Some(provider) = provider {
+                req.context.auth = Some(provider.credentials().await?);
+            }
+            inner.call(req).await
+        })
     }
 }
 
@@ -159,4 +256,54 @@ mod tests {
         assert_eq!(header, "Authorization");
         assert!(value.starts_with("Basic "));
     }
+
+    #[tokio::test]
+    async fn test_credential_provider() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        use tower::{service_fn, ServiceExt};
+
+        use crate::{
+            protocol::{message::Message, operation::A2AOperation, task::Task},
+            service::RequestContext,
+        };
+
+        /// Issues a new token on every call, failing once they run out
+        struct Rotating(AtomicUsize);
+
+        #[async_trait]
+        impl CredentialProvider for Rotating {
+            async fn credentials(&self) -> Result<AuthCredentials, A2AError> {
+                match self.0.fetch_add(1, Ordering::SeqCst) {
+                    n if n < 2 => Ok(AuthCredentials::bearer(format!("token-{}", n))),
+                    _ => Err(A2AError::Auth("Token expired".into())),
+                }
+            }
+        }
+
+        let inner = service_fn(|req: A2ARequest| async move {
+            let (_, value) = req.context.auth.unwrap().to_header();
+            // Echo the credentials back as the task ID
+            let task = Task::new(value, Message::user("Hello"));
+            Ok::<_, A2AError>(A2AResponse::Task(Box::new(task)))
+        });
+        let service = AuthLayer::from_provider(Rotating(AtomicUsize::new(0))).layer(inner);
+        let request = || {
+            A2ARequest::new(
+                A2AOperation::GetTask {
+                    task_id: "task-123".into(),
+                },
+                RequestContext::new("https://agent.example.com".parse().unwrap()),
+            )
+        };
+
+        for expected in ["Bearer token-0", "Bearer token-1"] {
+            let response = service.clone().oneshot(request()).await.unwrap();
+            assert_eq!(response.into_task().unwrap().id, expected);
+        }
+        assert!(matches!(
+            service.oneshot(request()).await,
+            Err(A2AError::Auth(_))
+        ));
+    }
 }
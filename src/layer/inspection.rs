@@ -0,0 +1,350 @@
+//! Content inspection of outgoing messages
+//!
+//! A [`ContentInspector`] scans the text parts of a message and reports
+//! [`Finding`]s, such as email addresses or card numbers. An [`InspectionFilter`]
+//! turns the findings into a decision (redact, block, or annotate) and plugs into
+//! the outbound side of the [`ModerationLayer`](crate::layer::ModerationLayer):
+//!
+//! ```rust,ignore
+//! use tower_a2a::layer::{InspectionAction, InspectionFilter, ModerationLayer, RegexInspector};
+//!
+//! let layer = ModerationLayer::new().with_outbound(InspectionFilter::new(
+//!     RegexInspector::pii(),
+//!     InspectionAction::Redact,
+//! ));
+//! ```
+//!
+//! The regex-based `RegexInspector` requires the `pii-regex` feature. Inspectors
+//! backed by an external detection service implement the trait directly.
+
+use std::ops::Range;
+
+use async_trait::async_trait;
+use serde_json::json;
+
+use crate::{
+    layer::moderation::{FilterVerdict, OutboundFilter},
+    protocol::{
+        error::{TaskError, TaskErrorCode},
+        Message, MessagePart,
+    },
+};
+
+/// Sensitive content found in a text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// Kind of content, e.g. `email`
+    pub kind: String,
+
+    /// Byte range of the content in the inspected text
+    pub range: Range<usize>,
+}
+
+impl Finding {
+    /// Create a finding
+    pub fn new(kind: impl Into<String>, range: Range<usize>) -> Self {
+        Self {
+            kind: kind.into(),
+            range,
+        }
+    }
+}
+
+/// Scanner for sensitive content in outgoing text
+#[async_trait]
+pub trait ContentInspector: Send + Sync {
+    /// Find sensitive content in a text
+    ///
+    /// Ranges must lie on character boundaries of the text. They may overlap. A text
+    /// with an invalid range is redacted as a whole.
+    async fn inspect(&self, text: &str) -> Vec<Finding>;
+}
+
+/// What an [`InspectionFilter`] does with a message containing findings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InspectionAction {
+    /// Replace the content with `[REDACTED:<kind>]`
+    Redact,
+
+    /// Reject the message with a `content-filtered` error
+    Block,
+
+    /// Send the message unchanged, listing the finding kinds under the
+    /// `inspection` metadata key
+    Annotate,
+}
+
+/// Outbound filter applying a [`ContentInspector`] to text parts
+#[derive(Debug, Clone)]
+pub struct InspectionFilter<I> {
+    inspector: I,
+    action: InspectionAction,
+}
+
+impl<I: ContentInspector> InspectionFilter<I> {
+    /// Create a filter taking an action on messages with findings
+    pub fn new(inspector: I, action: InspectionAction) -> Self {
+        Self { inspector, action }
+    }
+}
+
+#[async_trait]
+impl<I: ContentInspector> OutboundFilter for InspectionFilter<I> {
+    async fn filter(&self, message: &Message) -> FilterVerdict {
+        let mut parts = Vec::with_capacity(message.parts.len());
+        let mut kinds: Vec<String> = Vec::new();
+
+        for part in &message.parts {
+            let MessagePart::Text { text } = part else {
+                parts.push(part.clone());
+                continue;
+            };
+
+            let findings = self.inspector.inspect(text).await;
+            for finding in &findings {
+                if !kinds.contains(&finding.kind) {
+                    kinds.push(finding.kind.clone());
+                }
+            }
+            parts.push(MessagePart::text(redact(text, findings)));
+        }
+
+        if kinds.is_empty() {
+            return FilterVerdict::Allow;
+        }
+        tracing::debug!(?kinds, action = ?self.action, "Sensitive content found");
+
+        let mut message = message.clone();
+        match self.action {
+            InspectionAction::Redact => message.parts = parts,
+            InspectionAction::Block => {
+                return FilterVerdict::Reject(
+                    TaskError::new(
+                        TaskErrorCode::ContentFiltered,
+                        "Message contains sensitive content",
+                    )
+                    .with_details(json!({ "kinds": kinds })),
+                )
+            }
+            InspectionAction::Annotate => {
+                message
+                    .metadata
+                    .get_or_insert_with(Default::default)
+                    .insert("inspection".to_string(), json!({ "kinds": kinds }));
+            }
+        }
+        FilterVerdict::Replace(message)
+    }
+}
+
+/// Replace the ranges of findings in a text, merging overlapping ones
+///
+/// A range outside the text or off its character boundaries fails closed: the whole
+/// text is replaced, so a misbehaving inspector cannot leak what it found.
+fn redact(text: &str, mut findings: Vec<Finding>) -> String {
+    if let Some(invalid) = findings
+        .iter()
+        .find(|finding| text.get(finding.range.clone()).is_none())
+    {
+        tracing::warn!(kind = %invalid.kind, range = ?invalid.range, "Invalid finding range, redacting the whole text");
+        return format!("[REDACTED:{}]", invalid.kind);
+    }
+    findings.sort_by_key(|finding| (finding.range.start, finding.range.end));
+
+    let mut redacted = String::with_capacity(text.len());
+    let mut end = 0;
+    for finding in findings {
+        if finding.range.end <= end {
+            continue;
+        }
+        let start = finding.range.start.max(end);
+        redacted.push_str(&text[end..start]);
+        redacted.push_str(&format!("[REDACTED:{}]", finding.kind));
+        end = finding.range.end;
+    }
+    redacted.push_str(&text[end..]);
+    redacted
+}
+
+/// Reference inspector matching regular expressions
+#[cfg(feature = "pii-regex")]
+#[derive(Debug, Clone, Default)]
+pub struct RegexInspector {
+    patterns: Vec<(String, regex::Regex)>,
+}
+
+#[cfg(feature = "pii-regex")]
+impl RegexInspector {
+    /// Create an inspector without patterns
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an inspector for common PII: email addresses, phone numbers, US social
+    /// security numbers, and payment card numbers
+    ///
+    /// The patterns favour precision over recall; card numbers must pass the Luhn
+    /// check. Add organisation-specific patterns with [`RegexInspector::with_pattern`].
+    pub fn pii() -> Self {
+        [
+            ("email", r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}"),
+            (
+                "phone",
+                r"(?:\+\d{1,3}[ .-]?)?\(?\d{3}\)?[ .-]\d{3}[ .-]\d{4}\b",
+            ),
+            ("ssn", r"\b\d{3}-\d{2}-\d{4}\b"),
+            ("card", r"\b(?:\d[ -]?){12,18}\d\b"),
+        ]
+        .into_iter()
+        .fold(Self::new(), |inspector, (kind, pattern)| {
+            inspector
+                .with_pattern(kind, pattern)
+                .expect("built-in patterns are valid")
+        })
+    }
+
+    /// Add a pattern reporting findings of a kind
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Validation` if the pattern is not a valid regular expression
+    pub fn with_pattern(
+        mut self,
+        kind: impl Into<String>,
+        pattern: &str,
+    ) -> Result<Self, crate::protocol::error::A2AError> {
+        let regex = regex::Regex::new(pattern).map_err(|e| {
+            crate::protocol::error::A2AError::Validation(format!("Invalid pattern: {}", e))
+        })?;
+        self.patterns.push((kind.into(), regex));
+        Ok(self)
+    }
+}
+
+#[cfg(feature = "pii-regex")]
+#[async_trait]
+impl ContentInspector for RegexInspector {
+    async fn inspect(&self, text: &str) -> Vec<Finding> {
+        self.patterns
+            .iter()
+            .flat_map(|(kind, regex)| {
+                regex
+                    .find_iter(text)
+                    .filter(move |found| kind != "card" || luhn(found.as_str()))
+                    .map(move |found| Finding::new(kind.clone(), found.range()))
+            })
+            .collect()
+    }
+}
+
+/// Check the Luhn checksum of the digits in a string
+#[cfg(feature = "pii-regex")]
+fn luhn(number: &str) -> bool {
+    let digits: Vec<u32> = number.chars().filter_map(|c| c.to_digit(10)).collect();
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &digit)| match (i % 2, digit * 2) {
+            (0, _) => digit,
+            (_, doubled) if doubled > 9 => doubled - 9,
+            (_, doubled) => doubled,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Inspector flagging the word "secret"
+    struct Secrets;
+
+    #[async_trait]
+    impl ContentInspector for Secrets {
+        async fn inspect(&self, text: &str) -> Vec<Finding> {
+            text.match_indices("secret")
+                .map(|(start, found)| Finding::new("secret", start..start + found.len()))
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inspection_actions() {
+        let message = Message::user("my secret is secret")
+            .with_part(MessagePart::data(json!({"secret": true})));
+
+        let redact = InspectionFilter::new(Secrets, InspectionAction::Redact);
+        let FilterVerdict::Replace(redacted) = redact.filter(&message).await else {
+            panic!("Expected redacted message");
+        };
+        assert_eq!(
+            redacted.parts[0],
+            MessagePart::text("my [REDACTED:secret] is [REDACTED:secret]")
+        );
+        assert_eq!(redacted.parts[1], message.parts[1]);
+
+        let block = InspectionFilter::new(Secrets, InspectionAction::Block);
+        assert!(matches!(
+            block.filter(&message).await,
+            FilterVerdict::Reject(error) if error.code == "content-filtered"
+        ));
+
+        let annotate = InspectionFilter::new(Secrets, InspectionAction::Annotate);
+        let FilterVerdict::Replace(annotated) = annotate.filter(&message).await else {
+            panic!("Expected annotated message");
+        };
+        assert_eq!(annotated.parts, message.parts);
+        assert_eq!(
+            annotated.metadata.unwrap()["inspection"],
+            json!({"kinds": ["secret"]})
+        );
+
+        assert_eq!(
+            redact.filter(&Message::user("nothing here")).await,
+            FilterVerdict::Allow
+        );
+    }
+
+    #[test]
+    fn test_redact_overlapping_findings() {
+        let findings = vec![Finding::new("b", 4..9), Finding::new("a", 2..6)];
+        assert_eq!(
+            redact("0123456789", findings),
+            "01[REDACTED:a][REDACTED:b]9"
+        );
+    }
+
+    #[test]
+    fn test_redact_invalid_findings() {
+        let findings = vec![Finding::new("a", 0..2), Finding::new("b", 4..20)];
+        assert_eq!(redact("0123456789", findings), "[REDACTED:b]");
+
+        #[allow(clippy::reversed_empty_ranges)]
+        let findings = vec![Finding::new("a", 6..4)];
+        assert_eq!(redact("0123456789", findings), "[REDACTED:a]");
+
+        let findings = vec![Finding::new("a", 1..2)];
+        assert_eq!(redact("héllo", findings), "[REDACTED:a]");
+    }
+
+    #[cfg(feature = "pii-regex")]
+    #[tokio::test]
+    async fn test_regex_inspector() {
+        let inspector = RegexInspector::pii();
+        let text = "Mail jane@example.com or call 555-123-4567, SSN 123-45-6789, \
+                    card 4111 1111 1111 1111, order 1234 5678 9012 3456";
+
+        let mut kinds: Vec<_> = inspector
+            .inspect(text)
+            .await
+            .into_iter()
+            .map(|finding| finding.kind)
+            .collect();
+        kinds.sort();
+        assert_eq!(kinds, ["card", "email", "phone", "ssn"]);
+
+        assert!(RegexInspector::new().with_pattern("bad", "(").is_err());
+    }
+}
@@ -0,0 +1,316 @@
+//! Message transformation layer
+//!
+//! Rewrites outgoing messages before they are encoded, so every client of a platform
+//! sends the same metadata and preamble without each call site adding them:
+//!
+//! ```rust
+//! use serde_json::json;
+//! use tower_a2a::layer::{MessageTransformLayer, Preamble, StampMetadata};
+//!
+//! let layer = MessageTransformLayer::new()
+//!     .with_transform(StampMetadata::new().with("org", json!("acme")))
+//!     .with_transform(Preamble::new("Answer on behalf of {tenant}."));
+//! ```
+//!
+//! Transforms apply to `SendMessage` operations only and run in registration order.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use serde_json::Value;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{
+    protocol::{error::A2AError, operation::A2AOperation, Message, MessagePart},
+    service::{A2AProtocolService, A2ARequest, A2AResponse, ProtocolStack, RequestContext},
+};
+
+/// Rewrite of an outgoing message
+pub trait MessageTransform: Send + Sync {
+    /// Rewrite a message about to be sent
+    ///
+    /// Returning an error fails the request before anything is sent.
+    fn transform(&self, message: &mut Message, context: &RequestContext) -> Result<(), A2AError>;
+}
+
+impl<F> MessageTransform for F
+where
+    F: Fn(&mut Message, &RequestContext) + Send + Sync,
+{
+    fn transform(&self, message: &mut Message, context: &RequestContext) -> Result<(), A2AError> {
+        self(message, context);
+        Ok(())
+    }
+}
+
+/// Transform adding metadata entries to every message
+///
+/// Entries already set on the message are kept unless the transform is
+/// [`overwriting`](StampMetadata::overwriting).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StampMetadata {
+    entries: HashMap<String, Value>,
+    overwrite: bool,
+}
+
+impl StampMetadata {
+    /// Create a transform without entries
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a metadata entry
+    pub fn with(mut self, key: impl Into<String>, value: Value) -> Self {
+        self.entries.insert(key.into(), value);
+        self
+    }
+
+    /// Replace entries the message already sets
+    pub fn overwriting(mut self) -> Self {
+        self.overwrite = true;
+        self
+    }
+}
+
+impl MessageTransform for StampMetadata {
+    fn transform(&self, message: &mut Message, _context: &RequestContext) -> Result<(), A2AError> {
+        if self.entries.is_empty() {
+            return Ok(());
+        }
+
+        let metadata = message.metadata.get_or_insert_with(HashMap::new);
+        for (key, value) in &self.entries {
+            if self.overwrite || !metadata.contains_key(key) {
+                metadata.insert(key.clone(), value.clone());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Transform prepending a text part to every message
+///
+/// The text is a template: `{agent_url}` is replaced with the URL of the target agent
+/// and `{name}` with the request metadata entry `name`. Unknown placeholders are left
+/// as they are. The part is not added again if the message already starts with it,
+/// so retried requests keep a single preamble.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preamble {
+    template: String,
+}
+
+impl Preamble {
+    /// Create a preamble from a template
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+        }
+    }
+
+    /// Render the template for a request
+    pub fn render(&self, context: &RequestContext) -> String {
+        let mut text = self
+            .template
+            .replace("{agent_url}", context.agent_url.as_str());
+        for (name, value) in &context.metadata {
+            text = text.replace(&format!("{{{}}}", name), value);
+        }
+        text
+    }
+}
+
+impl MessageTransform for Preamble {
+    fn transform(&self, message: &mut Message, context: &RequestContext) -> Result<(), A2AError> {
+        let text = self.render(context);
+        if !matches!(message.parts.first(), Some(MessagePart::Text { text: first }) if *first == text)
+        {
+            message.parts.insert(0, MessagePart::text(text));
+        }
+        Ok(())
+    }
+}
+
+/// Layer applying transforms to outgoing messages
+#[derive(Clone, Default)]
+pub struct MessageTransformLayer {
+    transforms: Vec<Arc<dyn MessageTransform>>,
+}
+
+impl MessageTransformLayer {
+    /// Create a layer without transforms
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a transform, run after those already added
+    pub fn with_transform(mut self, transform: impl MessageTransform + 'static) -> Self {
+        self.transforms.push(Arc::new(transform));
+        self
+    }
+}
+
+impl std::fmt::Debug for MessageTransformLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageTransformLayer")
+            .field("transforms", &self.transforms.len())
+            .finish()
+    }
+}
+
+impl<S> Layer<S> for MessageTransformLayer {
+    type Service = MessageTransformService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MessageTransformService {
+            inner,
+            transforms: self.transforms.clone(),
+        }
+    }
+}
+
+/// Message transformation service that wraps an inner service
+#[derive(Clone)]
+pub struct MessageTransformService<S> {
+    inner: S,
+    transforms: Vec<Arc<dyn MessageTransform>>,
+}
+
+impl<S> MessageTransformService<S> {
+    /// Apply the transforms to the message a request sends
+    fn transform(&self, req: &mut A2ARequest) -> Result<(), A2AError> {
+        if let A2AOperation::SendMessage { message, .. } = &mut req.operation {
+            for transform in &self.transforms {
+                transform.transform(message, &req.context)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<S: ProtocolStack> ProtocolStack for MessageTransformService<S> {
+    type Transport = S::Transport;
+
+    fn protocol(&self) -> &A2AProtocolService<S::Transport> {
+        self.inner.protocol()
+    }
+
+    async fn prepare(&self, request: &mut A2ARequest) -> Result<(), A2AError> {
+        self.transform(request)?;
+        self.inner.prepare(request).await
+    }
+}
+
+impl<S> Service<A2ARequest> for MessageTransformService<S>
+where
+    S: Service<A2ARequest, Response = A2AResponse, Error = A2AError> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = A2AResponse;
+    type Error = A2AError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: A2ARequest) -> Self::Future {
+        if let Err(e) = self.transform(&mut req) {
+            return Box::pin(async move { Err(e) });
+        }
+
+        Box::pin(self.inner.call(req))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use bytes::Bytes;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use crate::{
+        codec::JsonCodec,
+        protocol::Task,
+        service::A2AProtocolService,
+        transport::{mock::MockTransport, TransportResponse},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_message_transforms() {
+        let sent = Arc::new(Mutex::new(None));
+        let recorded = sent.clone();
+        let transport = MockTransport::new(move |req| {
+            let body: Value = serde_json::from_slice(&req.body).unwrap();
+            *recorded.lock().unwrap() = Some(body["message"].clone());
+            let task = Task::new("task-1", Message::user("Hello"));
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+        let service = MessageTransformLayer::new()
+            .with_transform(StampMetadata::new().with("org", json!("acme")))
+            .with_transform(Preamble::new("Answer for {tenant} via {agent_url}"))
+            .with_transform(|message: &mut Message, _: &RequestContext| {
+                message.context_id = Some("ctx-1".into())
+            })
+            .layer(A2AProtocolService::new(
+                transport,
+                Arc::new(JsonCodec::new()),
+            ));
+
+        let message = Message::user("Hello").with_metadata("org", json!("mine"));
+        let preamble = Preamble::new("Answer for {tenant} via {agent_url}");
+        let context = RequestContext::new("https://example.com".parse().unwrap())
+            .with_metadata("tenant", "acme");
+        let mut expected = message.clone();
+        preamble.transform(&mut expected, &context).unwrap();
+        preamble.transform(&mut expected, &context).unwrap();
+        assert_eq!(expected.parts.len(), 2);
+
+        let req = A2ARequest::new(
+            A2AOperation::SendMessage {
+                message,
+                stream: false,
+                context_id: None,
+                task_id: None,
+            },
+            context,
+        );
+        service.clone().oneshot(req).await.unwrap();
+
+        let sent = sent.lock().unwrap().take().unwrap();
+        assert_eq!(
+            sent["parts"][0]["text"],
+            "Answer for acme via https://example.com/"
+        );
+        assert_eq!(sent["parts"][1]["text"], "Hello");
+        assert_eq!(sent["metadata"]["org"], "mine");
+        assert_eq!(sent["contextId"], "ctx-1");
+
+        // Streaming requests are transformed when prepared
+        let mut req = A2ARequest::new(
+            A2AOperation::SendMessage {
+                message: Message::user("Hello"),
+                stream: true,
+                context_id: None,
+                task_id: None,
+            },
+            RequestContext::new("https://example.com".parse().unwrap()),
+        );
+        service.prepare(&mut req).await.unwrap();
+        let A2AOperation::SendMessage { message, .. } = req.operation else {
+            unreachable!()
+        };
+        assert_eq!(message.context_id.as_deref(), Some("ctx-1"));
+    }
+}
@@ -1,7 +1,27 @@
 //! Tower Layer implementations for A2A protocol
 
 pub mod auth;
+pub mod inspection;
+pub mod message_transform;
+pub mod moderation;
+pub mod retry;
+pub mod single_flight;
+pub mod timeout;
 pub mod validation;
 
-pub use auth::{AuthCredentials, AuthLayer, AuthService};
-pub use validation::{A2AValidationLayer, A2AValidationService};
+pub use auth::{AuthCredentials, AuthLayer, AuthService, CredentialProvider};
+#[cfg(feature = "pii-regex")]
+pub use inspection::RegexInspector;
+pub use inspection::{ContentInspector, Finding, InspectionAction, InspectionFilter};
+pub use message_transform::{
+    MessageTransform, MessageTransformLayer, MessageTransformService, Preamble, StampMetadata,
+};
+pub use moderation::{
+    FilterVerdict, InboundFilter, ModerationLayer, ModerationService, OutboundFilter,
+};
+pub use retry::{A2ARetryLayer, A2ARetryService, RetryPolicy};
+pub use single_flight::{SingleFlightLayer, SingleFlightService};
+pub use timeout::{A2ATimeoutLayer, A2ATimeoutService};
+pub use validation::{
+    A2AValidationLayer, A2AValidationService, Validated, ValidationFailure, ValidationMode,
+};
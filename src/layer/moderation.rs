@@ -0,0 +1,426 @@
+//! Content moderation layer
+//!
+//! Filters inspect messages crossing the client boundary and may allow, rewrite
+//! (redact or annotate), or reject them:
+//!
+//! - [`OutboundFilter`]s see messages before they are sent to the agent. A rejection
+//!   fails the request with [`A2AError::Task`] and nothing is sent.
+//! - [`InboundFilter`]s see the agent's messages in returned tasks before they reach
+//!   the application, and the parts of each artifact as an agent message. A rejection
+//!   marks the task as [`Rejected`](TaskStatus::Rejected) with the filter's
+//!   [`TaskError`], drops the offending message, and withholds the task's artifacts.
+//!
+//! Only tasks returned by request-response calls are filtered inbound; the events of
+//! streams are not.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{
+    protocol::{
+        error::{A2AError, TaskError},
+        operation::A2AOperation,
+        Message, Role, Task, TaskStatus,
+    },
+    service::{A2AProtocolService, A2ARequest, A2AResponse, ProtocolStack},
+};
+
+/// Decision of a content filter
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterVerdict {
+    /// Pass the message through unchanged
+    Allow,
+
+    /// Replace the message, e.g. with redacted parts or added metadata
+    Replace(Message),
+
+    /// Reject the message
+    Reject(TaskError),
+}
+
+/// Filter for messages sent to the agent
+#[async_trait]
+pub trait OutboundFilter: Send + Sync {
+    /// Inspect an outgoing message
+    async fn filter(&self, message: &Message) -> FilterVerdict;
+}
+
+/// Filter for messages received from the agent
+#[async_trait]
+pub trait InboundFilter: Send + Sync {
+    /// Inspect an incoming message
+    async fn filter(&self, message: &Message) -> FilterVerdict;
+}
+
+#[async_trait]
+impl<F> OutboundFilter for F
+where
+    F: Fn(&Message) -> FilterVerdict + Send + Sync,
+{
+    async fn filter(&self, message: &Message) -> FilterVerdict {
+        self(message)
+    }
+}
+
+#[async_trait]
+impl<F> InboundFilter for F
+where
+    F: Fn(&Message) -> FilterVerdict + Send + Sync,
+{
+    async fn filter(&self, message: &Message) -> FilterVerdict {
+        self(message)
+    }
+}
+
+/// Layer applying content filters to requests and responses
+#[derive(Clone, Default)]
+pub struct ModerationLayer {
+    inbound: Vec<Arc<dyn InboundFilter>>,
+    outbound: Vec<Arc<dyn OutboundFilter>>,
+}
+
+impl ModerationLayer {
+    /// Create a layer without filters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a filter for messages received from the agent
+    pub fn with_inbound(mut self, filter: impl InboundFilter + 'static) -> Self {
+        self.inbound.push(Arc::new(filter));
+        self
+    }
+
+    /// Add a filter for messages sent to the agent
+    pub fn with_outbound(mut self, filter: impl OutboundFilter + 'static) -> Self {
+        self.outbound.push(Arc::new(filter));
+        self
+    }
+}
+
+impl std::fmt::Debug for ModerationLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModerationLayer")
+            .field("inbound", &self.inbound.len())
+            .field("outbound", &self.outbound.len())
+            .finish()
+    }
+}
+
+impl<S> Layer<S> for ModerationLayer {
+    type Service = ModerationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ModerationService {
+            inner,
+            filters: self.clone(),
+        }
+    }
+}
+
+/// Moderation service that wraps an inner service
+#[derive(Clone)]
+pub struct ModerationService<S> {
+    inner: S,
+    filters: ModerationLayer,
+}
+
+#[async_trait]
+impl<S: ProtocolStack> ProtocolStack for ModerationService<S> {
+    type Transport = S::Transport;
+
+    fn protocol(&self) -> &A2AProtocolService<S::Transport> {
+        self.inner.protocol()
+    }
+
+    /// Apply the outbound filters; inbound filters see neither the messages nor the
+    /// artifacts of streamed events
+    async fn prepare(&self, request: &mut A2ARequest) -> Result<(), A2AError> {
+        moderate_request(&self.filters.outbound, request).await?;
+        self.inner.prepare(request).await
+    }
+}
+
+impl<S> Service<A2ARequest> for ModerationService<S>
+where
+    S: Service<A2ARequest, Response = A2AResponse, Error = A2AError> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = A2AResponse;
+    type Error = A2AError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: A2ARequest) -> Self::Future {
+        // Take the service that was polled ready, leaving a fresh clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let filters = self.filters.clone();
+
+        Box::pin(async move {
+            moderate_request(&filters.outbound, &mut req).await?;
+
+            let mut response = inner.call(req).await?;
+            match &mut response {
+                A2AResponse::Task(task) => moderate_task(&filters.inbound, task).await,
+                A2AResponse::TaskList { tasks, .. } => {
+                    for task in tasks {
+                        moderate_task(&filters.inbound, task).await;
+                    }
+                }
+                _ => {}
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Apply outbound filters to the message a request sends
+async fn moderate_request(
+    filters: &[Arc<dyn OutboundFilter>],
+    req: &mut A2ARequest,
+) -> Result<(), A2AError> {
+    if let A2AOperation::SendMessage { message, .. } = &mut req.operation {
+        for filter in filters {
+            match filter.filter(message).await {
+                FilterVerdict::Allow => {}
+                FilterVerdict::Replace(replacement) => *message = replacement,
+                FilterVerdict::Reject(error) => {
+                    tracing::info!(code = %error.code, "Outbound message rejected");
+                    return Err(error.into());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Apply inbound filters to the agent messages and the artifacts of a task
+async fn moderate_task(filters: &[Arc<dyn InboundFilter>], task: &mut Task) {
+    if filters.is_empty() {
+        return;
+    }
+
+    let mut history = Vec::with_capacity(task.history.len());
+    'messages: for mut message in std::mem::take(&mut task.history) {
+        if message.role == Role::Agent {
+            for filter in filters {
+                match filter.filter(&message).await {
+                    FilterVerdict::Allow => {}
+                    FilterVerdict::Replace(replacement) => message = replacement,
+                    FilterVerdict::Reject(error) => {
+                        tracing::info!(task_id = %task.id, code = %error.code, "Inbound message rejected");
+                        task.status = TaskStatus::Rejected;
+                        task.error = Some(error);
+                        task.artifacts.clear();
+                        continue 'messages;
+                    }
+                }
+            }
+        }
+        history.push(message);
+    }
+    task.history = history;
+
+    // Artifacts are filtered as agent messages made of their parts
+    let mut artifacts = std::mem::take(&mut task.artifacts);
+    for artifact in &mut artifacts {
+        let mut message = Message::builder()
+            .role(Role::Agent)
+            .parts(std::mem::take(&mut artifact.parts))
+            .build();
+        for filter in filters {
+            match filter.filter(&message).await {
+                FilterVerdict::Allow => {}
+                FilterVerdict::Replace(replacement) => message = replacement,
+                FilterVerdict::Reject(error) => {
+                    tracing::info!(
+                        task_id = %task.id,
+                        artifact_id = %artifact.artifact_id,
+                        code = %error.code,
+                        "Inbound artifact rejected"
+                    );
+                    task.status = TaskStatus::Rejected;
+                    task.error = Some(error);
+                    return;
+                }
+            }
+        }
+        artifact.parts = message.parts;
+    }
+    task.artifacts = artifacts;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use tower::ServiceExt;
+
+    use crate::{
+        codec::JsonCodec,
+        protocol::{Artifact, MessagePart, TaskErrorCode},
+        service::{A2AProtocolService, RequestContext},
+        transport::{mock::MockTransport, TransportResponse},
+    };
+
+    use super::*;
+
+    fn text(message: &Message) -> String {
+        message
+            .parts
+            .iter()
+            .filter_map(|part| match part {
+                MessagePart::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn redact(message: &Message) -> FilterVerdict {
+        if !text(message).contains("secret") {
+            return FilterVerdict::Allow;
+        }
+        let mut redacted = message.clone();
+        redacted.parts = vec![MessagePart::text(text(message).replace("secret", "******"))];
+        FilterVerdict::Replace(redacted)
+    }
+
+    fn block(message: &Message) -> FilterVerdict {
+        if text(message).contains("forbidden") {
+            FilterVerdict::Reject(TaskError::new(
+                TaskErrorCode::ContentFiltered,
+                "Message blocked by policy",
+            ))
+        } else {
+            FilterVerdict::Allow
+        }
+    }
+
+    fn service(reply: &'static str) -> A2AProtocolService<MockTransport> {
+        service_with_artifact(reply, "Done")
+    }
+
+    fn service_with_artifact(
+        reply: &'static str,
+        output: &'static str,
+    ) -> A2AProtocolService<MockTransport> {
+        let transport = MockTransport::new(move |req| {
+            let body: serde_json::Value = serde_json::from_slice(&req.body).unwrap();
+            assert!(!body.to_string().contains("secret"));
+            let task = Task::new("task-1", Message::user("Hi"))
+                .with_status(TaskStatus::Completed)
+                .with_history_message(Message::agent(reply))
+                .with_artifact(Artifact {
+                    artifact_id: "result".to_string(),
+                    name: None,
+                    description: None,
+                    parts: vec![MessagePart::text(output)],
+                    metadata: None,
+                    extensions: Vec::new(),
+                    extra: Default::default(),
+                });
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+        A2AProtocolService::new(transport, Arc::new(JsonCodec))
+    }
+
+    fn send(text: &str) -> A2ARequest {
+        let operation = A2AOperation::SendMessage {
+            message: Message::user(text),
+            stream: false,
+            context_id: None,
+            task_id: None,
+        };
+        A2ARequest::new(
+            operation,
+            RequestContext::new("https://agent.example.com".parse().unwrap()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_outbound_filters() {
+        let layer = ModerationLayer::new()
+            .with_outbound(redact)
+            .with_outbound(block);
+
+        let response = layer
+            .layer(service("Hello"))
+            .oneshot(send("my secret"))
+            .await
+            .unwrap();
+        assert!(response.into_task().is_some());
+
+        let err = layer
+            .layer(service("Hello"))
+            .oneshot(send("forbidden words"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, A2AError::Task { source } if source.code == "content-filtered"));
+    }
+
+    #[tokio::test]
+    async fn test_inbound_filters() {
+        let layer = ModerationLayer::new()
+            .with_inbound(redact)
+            .with_inbound(block);
+
+        let task = layer
+            .layer(service("the secret is out"))
+            .oneshot(send("Hi"))
+            .await
+            .unwrap()
+            .into_task()
+            .unwrap();
+        assert_eq!(text(&task.history[0]), "the ****** is out");
+
+        let task = layer
+            .layer(service("forbidden answer"))
+            .oneshot(send("Hi"))
+            .await
+            .unwrap()
+            .into_task()
+            .unwrap();
+        assert_eq!(task.status, TaskStatus::Rejected);
+        assert!(task.history.is_empty());
+        assert_eq!(
+            task.error.unwrap().error_code(),
+            TaskErrorCode::ContentFiltered
+        );
+
+        // Artifacts are filtered too
+        let task = layer
+            .layer(service_with_artifact("Hello", "the secret is out"))
+            .oneshot(send("Hi"))
+            .await
+            .unwrap()
+            .into_task()
+            .unwrap();
+        assert_eq!(
+            task.artifacts[0].parts,
+            [MessagePart::text("the ****** is out")]
+        );
+
+        let task = layer
+            .layer(service_with_artifact("Hello", "forbidden output"))
+            .oneshot(send("Hi"))
+            .await
+            .unwrap()
+            .into_task()
+            .unwrap();
+        assert_eq!(task.status, TaskStatus::Rejected);
+        assert!(task.artifacts.is_empty());
+    }
+}
@@ -0,0 +1,398 @@
+//! Retry layer with exponential backoff
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{
+    client::events::{ClientEvent, ClientEvents},
+    protocol::error::A2AError,
+    service::{A2AProtocolService, A2ARequest, A2AResponse, ProtocolStack},
+};
+
+/// Policy deciding how failed requests are retried
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt
+    pub max_retries: u32,
+
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+
+    /// Upper bound for the delay between retries
+    pub max_backoff: Duration,
+
+    /// Factor applied to the delay after each retry
+    ///
+    /// Negative and non-finite factors are treated as 1, keeping the delay constant.
+    pub multiplier: f64,
+
+    /// Whether non-idempotent operations (e.g. message sends) are retried, which may
+    /// create duplicate tasks
+    pub retry_non_idempotent: bool,
+}
+
+impl RetryPolicy {
+    /// Create a policy with the given number of retries and default backoff
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Self::default()
+        }
+    }
+
+    /// Set the initial and maximum backoff
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Set the factor applied to the delay after each retry (default: 2)
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Enable or disable retrying non-idempotent operations
+    pub fn with_retry_non_idempotent(mut self, enabled: bool) -> Self {
+        self.retry_non_idempotent = enabled;
+        self
+    }
+
+    /// Delay before the given retry (starting at 1)
+    ///
+    /// Delays too long to represent are capped at the maximum backoff.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        if self.initial_backoff.is_zero() {
+            return Duration::ZERO;
+        }
+        let multiplier = if self.multiplier.is_finite() && self.multiplier >= 0.0 {
+            self.multiplier
+        } else {
+            1.0
+        };
+        let exponent = i32::try_from(attempt.saturating_sub(1)).unwrap_or(i32::MAX);
+        let secs = self.initial_backoff.as_secs_f64() * multiplier.powi(exponent);
+        Duration::try_from_secs_f64(secs)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+
+    /// Check whether a request may be retried after an error
+    fn should_retry(&self, req: &A2ARequest, error: &A2AError) -> bool {
+        error.is_retryable() && (self.retry_non_idempotent || req.is_idempotent())
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+/// Layer that retries requests failing with transient errors
+///
+/// Every retry is published as a [`ClientEvent`] on the configured event hub. Requests
+/// with an operation deadline are not retried once the backoff would end past it.
+#[derive(Clone, Debug, Default)]
+pub struct A2ARetryLayer {
+    policy: RetryPolicy,
+    events: ClientEvents,
+}
+
+impl A2ARetryLayer {
+    /// Create a new retry layer
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self {
+            policy,
+            events: ClientEvents::default(),
+        }
+    }
+
+    /// Publish retry events to the given hub
+    pub fn with_events(mut self, events: ClientEvents) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Get the hub retry events are published to
+    pub fn events(&self) -> &ClientEvents {
+        &self.events
+    }
+}
+
+impl<S> Layer<S> for A2ARetryLayer {
+    type Service = A2ARetryService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        A2ARetryService {
+            inner,
+            policy: self.policy.clone(),
+            events: self.events.clone(),
+        }
+    }
+}
+
+/// Retry service that wraps an inner service
+#[derive(Clone)]
+pub struct A2ARetryService<S> {
+    inner: S,
+    policy: RetryPolicy,
+    events: ClientEvents,
+}
+
+#[async_trait]
+impl<S: ProtocolStack> ProtocolStack for A2ARetryService<S> {
+    type Transport = S::Transport;
+
+    fn protocol(&self) -> &A2AProtocolService<S::Transport> {
+        self.inner.protocol()
+    }
+
+    async fn prepare(&self, request: &mut A2ARequest) -> Result<(), A2AError> {
+        self.inner.prepare(request).await
+    }
+}
+
+impl<S> Service<A2ARequest> for A2ARetryService<S>
+where
+    S: Service<A2ARequest, Response = A2AResponse, Error = A2AError> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = A2AResponse;
+    type Error = A2AError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: A2ARequest) -> Self::Future {
+        // Use the service that was polled ready for the first attempt
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let policy = self.policy.clone();
+        let events = self.events.clone();
+
+        Box::pin(async move {
+            let mut attempt = 0;
+
+            loop {
+                let error = match inner.call(req.clone()).await {
+                    Ok(response) => return Ok(response),
+                    Err(e) => e,
+                };
+
+                if !policy.should_retry(&req, &error) {
+                    return Err(error);
+                }
+
+                // Give up early if the backoff would outlast the operation deadline
+                let out_of_time = req
+                    .context
+                    .remaining()
+                    .is_some_and(|remaining| remaining <= policy.backoff(attempt + 1));
+
+                if attempt >= policy.max_retries || out_of_time {
+                    if attempt > 0 {
+                        events.emit(ClientEvent::RetriesExhausted {
+                            operation: req.kind(),
+                            attempts: attempt + 1,
+                            error: error.to_string(),
+                        });
+                    }
+                    return Err(error);
+                }
+
+                attempt += 1;
+                let delay = policy.backoff(attempt);
+                events.emit(ClientEvent::Retry {
+                    operation: req.kind(),
+                    attempt,
+                    delay,
+                    error: error.to_string(),
+                });
+
+                tokio::time::sleep(delay).await;
+                std::future::poll_fn(|cx| inner.poll_ready(cx)).await?;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+
+    use tower::ServiceExt;
+
+    use crate::{
+        protocol::{A2AOperation, OperationKind},
+        service::RequestContext,
+    };
+
+    use super::*;
+
+    /// Service failing with a transport error a fixed number of times
+    #[derive(Clone)]
+    struct Flaky {
+        failures: u32,
+        calls: Arc<AtomicU32>,
+    }
+
+    impl Service<A2ARequest> for Flaky {
+        type Response = A2AResponse;
+        type Error = A2AError;
+        type Future = std::future::Ready<Result<A2AResponse, A2AError>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), A2AError>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _req: A2ARequest) -> Self::Future {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            std::future::ready(if call < self.failures {
+                Err(A2AError::Transport("connection reset".into()))
+            } else {
+                Ok(A2AResponse::Empty)
+            })
+        }
+    }
+
+    fn request(operation: A2AOperation) -> A2ARequest {
+        A2ARequest::new(operation, RequestContext::default())
+    }
+
+    fn layer(max_retries: u32) -> A2ARetryLayer {
+        A2ARetryLayer::new(
+            RetryPolicy::new(max_retries)
+                .with_backoff(Duration::from_millis(1), Duration::from_millis(5)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_success() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let layer = layer(3);
+        let mut events = layer.events().subscribe();
+        let service = layer.layer(Flaky {
+            failures: 2,
+            calls: calls.clone(),
+        });
+
+        let result = service.oneshot(request(A2AOperation::DiscoverAgent)).await;
+
+        assert!(result.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let event = events.recv().await.unwrap();
+        assert!(matches!(
+            event,
+            ClientEvent::Retry {
+                operation: OperationKind::DiscoverAgent,
+                attempt: 1,
+                ..
+            }
+        ));
+        assert!(matches!(
+            events.recv().await.unwrap(),
+            ClientEvent::Retry { attempt: 2, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retries_exhausted() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let layer = layer(1);
+        let mut events = layer.events().subscribe();
+        let service = layer.layer(Flaky {
+            failures: 5,
+            calls: calls.clone(),
+        });
+
+        let result = service.oneshot(request(A2AOperation::DiscoverAgent)).await;
+
+        assert!(matches!(result, Err(A2AError::Transport(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        events.recv().await.unwrap();
+        assert!(matches!(
+            events.recv().await.unwrap(),
+            ClientEvent::RetriesExhausted { attempts: 2, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_deadline_stops_retries() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let service = A2ARetryLayer::new(
+            RetryPolicy::new(5).with_backoff(Duration::from_millis(50), Duration::from_secs(1)),
+        )
+        .layer(Flaky {
+            failures: 5,
+            calls: calls.clone(),
+        });
+
+        let mut req = request(A2AOperation::DiscoverAgent);
+        req.context.deadline = Some(std::time::Instant::now() + Duration::from_millis(120));
+        let result = service.oneshot(req).await;
+
+        // Backoffs of 50ms and 100ms: the second would end past the deadline
+        assert!(matches!(result, Err(A2AError::Transport(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_non_idempotent_not_retried_by_default() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let service = layer(3).layer(Flaky {
+            failures: 1,
+            calls: calls.clone(),
+        });
+
+        let operation = A2AOperation::SendMessage {
+            message: crate::protocol::Message::user("Hello"),
+            stream: false,
+            context_id: None,
+            task_id: None,
+        };
+        let result = service.oneshot(request(operation)).await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::new(5)
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(300));
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(300));
+        assert_eq!(policy.backoff(100), Duration::from_millis(300));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(300));
+
+        for multiplier in [-2.0, f64::NAN, f64::INFINITY] {
+            let policy = policy.clone().with_multiplier(multiplier);
+            assert_eq!(policy.backoff(5), Duration::from_millis(100));
+        }
+    }
+}
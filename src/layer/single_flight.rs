@@ -0,0 +1,437 @@
+//! Single-flight layer coalescing identical concurrent reads
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    hash::{Hash, Hasher},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use futures::{
+    future::{BoxFuture, Shared, WeakShared},
+    FutureExt,
+};
+use sha2::{Digest, Sha256};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{
+    layer::AuthCredentials,
+    protocol::error::A2AError,
+    service::{A2AProtocolService, A2ARequest, A2AResponse, ProtocolStack},
+};
+
+type ResponseFuture = BoxFuture<'static, Result<A2AResponse, Arc<A2AError>>>;
+type SharedResponse = Shared<ResponseFuture>;
+
+/// In-flight requests by key, with the id of their flight
+///
+/// Entries hold the response weakly: once every waiter is gone, the request is dropped
+/// and so is its entry.
+type InFlight = Arc<Mutex<HashMap<FlightKey, (u64, WeakShared<ResponseFuture>)>>>;
+
+/// Source of flight ids
+static NEXT_FLIGHT: AtomicU64 = AtomicU64::new(0);
+
+/// Removes a flight from the in-flight requests when it completes or is abandoned
+struct Landing {
+    in_flight: InFlight,
+    key: FlightKey,
+    id: u64,
+}
+
+impl Drop for Landing {
+    fn drop(&mut self) {
+        if let Ok(mut in_flight) = self.in_flight.lock() {
+            if in_flight
+                .get(&self.key)
+                .is_some_and(|(id, _)| *id == self.id)
+            {
+                in_flight.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// Identity of a request; requests with equal keys get the same response
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct FlightKey {
+    agent_url: String,
+    operation: String,
+    /// Digest of the credentials, so callers never see each other's results and no
+    /// secret is kept in the map
+    auth: Option<[u8; 32]>,
+    metadata: Vec<(String, String)>,
+}
+
+impl FlightKey {
+    /// Build the key of a request, or `None` if it must not be coalesced
+    ///
+    /// Requests without credentials are only coalesced if `anonymous` is set.
+    fn of(req: &A2ARequest, anonymous: bool) -> Option<Self> {
+        if !req.is_read_only() || req.is_streaming() {
+            return None;
+        }
+        if req.context.auth.is_none() && !anonymous {
+            return None;
+        }
+
+        let mut metadata: Vec<_> = req
+            .context
+            .metadata
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        metadata.sort();
+
+        Some(Self {
+            agent_url: req.context.agent_url.to_string(),
+            operation: serde_json::to_string(&req.operation).ok()?,
+            auth: req.context.auth.as_ref().map(digest),
+            metadata,
+        })
+    }
+}
+
+/// Hash credentials with SHA-256
+fn digest(auth: &AuthCredentials) -> [u8; 32] {
+    /// Hasher feeding the bytes of a value to SHA-256
+    struct DigestHasher(Sha256);
+
+    impl Hasher for DigestHasher {
+        fn write(&mut self, bytes: &[u8]) {
+            self.0.update(bytes);
+        }
+
+        fn finish(&self) -> u64 {
+            unreachable!("the digest is read with finalize")
+        }
+    }
+
+    let mut hasher = DigestHasher(Sha256::new());
+    auth.hash(&mut hasher);
+    hasher.0.finalize().into()
+}
+
+/// Layer that coalesces identical in-flight read-only requests
+///
+/// When several callers get the same task or discover the same agent concurrently,
+/// only the first request reaches the inner service; the others wait for it and
+/// receive a copy of its response. Requests are identical if they target the same
+/// agent with the same operation, credentials, and metadata. Writes and streaming
+/// subscriptions are always passed through.
+///
+/// Credentials are only known below an [`AuthLayer`](crate::layer::AuthLayer), so
+/// place this layer below it. Requests without credentials, e.g. those of a layer
+/// placed above the auth layer, are passed through as they may belong to anyone,
+/// unless [`with_anonymous`](Self::with_anonymous) is set for agents without
+/// authentication.
+///
+/// Services created by the same layer (and their clones) share the set of in-flight
+/// requests, so one layer can serve many clients. Errors are shared too: waiters
+/// receive an equivalent error.
+#[derive(Clone, Default)]
+pub struct SingleFlightLayer {
+    in_flight: InFlight,
+    anonymous: bool,
+}
+
+impl SingleFlightLayer {
+    /// Create a new single-flight layer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Coalesce requests without credentials too
+    ///
+    /// Only for clients whose requests never carry credentials, since requests of
+    /// different identities cannot be told apart above the auth layer.
+    pub fn with_anonymous(mut self, anonymous: bool) -> Self {
+        self.anonymous = anonymous;
+        self
+    }
+
+    /// Get the number of distinct requests currently in flight
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.lock().map(|map| map.len()).unwrap_or(0)
+    }
+}
+
+impl std::fmt::Debug for SingleFlightLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SingleFlightLayer")
+            .field("in_flight", &self.in_flight())
+            .finish()
+    }
+}
+
+impl<S> Layer<S> for SingleFlightLayer {
+    type Service = SingleFlightService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SingleFlightService {
+            inner,
+            in_flight: self.in_flight.clone(),
+            anonymous: self.anonymous,
+        }
+    }
+}
+
+/// Single-flight service that wraps an inner service
+#[derive(Clone)]
+pub struct SingleFlightService<S> {
+    inner: S,
+    in_flight: InFlight,
+    anonymous: bool,
+}
+
+#[async_trait]
+impl<S: ProtocolStack> ProtocolStack for SingleFlightService<S> {
+    type Transport = S::Transport;
+
+    fn protocol(&self) -> &A2AProtocolService<S::Transport> {
+        self.inner.protocol()
+    }
+
+    async fn prepare(&self, request: &mut A2ARequest) -> Result<(), A2AError> {
+        self.inner.prepare(request).await
+    }
+}
+
+impl<S> Service<A2ARequest> for SingleFlightService<S>
+where
+    S: Service<A2ARequest, Response = A2AResponse, Error = A2AError> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = A2AResponse;
+    type Error = A2AError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: A2ARequest) -> Self::Future {
+        let Some(key) = FlightKey::of(&req, self.anonymous) else {
+            return Box::pin(self.inner.call(req));
+        };
+
+        let Ok(mut in_flight) = self.in_flight.lock() else {
+            return Box::pin(self.inner.call(req));
+        };
+
+        let joined = in_flight.get(&key).and_then(|(_, flight)| flight.upgrade());
+        let shared = match joined {
+            Some(shared) => {
+                tracing::debug!(kind = ?req.kind(), "Joining in-flight request");
+                shared
+            }
+            None => {
+                let future = self.inner.call(req);
+                let landing = Landing {
+                    in_flight: self.in_flight.clone(),
+                    key: key.clone(),
+                    id: NEXT_FLIGHT.fetch_add(1, Ordering::Relaxed),
+                };
+                let id = landing.id;
+                let shared: SharedResponse = async move {
+                    let _landing = landing;
+                    future.await.map_err(Arc::new)
+                }
+                .boxed()
+                .shared();
+                if let Some(flight) = shared.downgrade() {
+                    in_flight.insert(key, (id, flight));
+                }
+                shared
+            }
+        };
+        drop(in_flight);
+
+        Box::pin(async move {
+            shared
+                .await
+                .map_err(|error| Arc::try_unwrap(error).unwrap_or_else(|error| copy(&error)))
+        })
+    }
+}
+
+/// Copy an error for a waiter that did not issue the request
+fn copy(error: &A2AError) -> A2AError {
+    match error {
+        A2AError::Transport(message) => A2AError::Transport(message.clone()),
+        A2AError::Protocol(message) => A2AError::Protocol(message.clone()),
+        A2AError::Validation(message) => A2AError::Validation(message.clone()),
+        A2AError::Auth(message) => A2AError::Auth(message.clone()),
+        A2AError::Task { source } => A2AError::Task {
+            source: source.clone(),
+        },
+        A2AError::Timeout => A2AError::Timeout,
+        A2AError::TaskNotFound { task_id } => A2AError::TaskNotFound {
+            task_id: task_id.clone(),
+        },
+        A2AError::AgentNotFound { agent_url } => A2AError::AgentNotFound {
+            agent_url: agent_url.clone(),
+        },
+        A2AError::RateLimitExceeded => A2AError::RateLimitExceeded,
+        A2AError::BindingMismatch { client, supported } => A2AError::BindingMismatch {
+            client: client.clone(),
+            supported: supported.clone(),
+        },
+        A2AError::JsonRpc {
+            code,
+            message,
+            data,
+        } => A2AError::JsonRpc {
+            code: *code,
+            message: message.clone(),
+            data: data.clone(),
+        },
+        A2AError::Serialization(_) | A2AError::Other(_) => A2AError::Other(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use tower::ServiceExt;
+
+    use crate::{
+        layer::AuthCredentials,
+        protocol::{message::Message, operation::A2AOperation, task::Task},
+        service::RequestContext,
+    };
+
+    use super::*;
+
+    /// Service counting calls and answering every request after a delay
+    #[derive(Clone)]
+    struct Counting(Arc<AtomicUsize>);
+
+    impl Service<A2ARequest> for Counting {
+        type Response = A2AResponse;
+        type Error = A2AError;
+        type Future = Pin<Box<dyn Future<Output = Result<A2AResponse, A2AError>> + Send>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), A2AError>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: A2ARequest) -> Self::Future {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                match req.operation {
+                    A2AOperation::GetTask { task_id } => Ok(A2AResponse::Task(Box::new(
+                        Task::new(task_id, Message::user("Test")),
+                    ))),
+                    _ => Err(A2AError::Transport("Connection reset".into())),
+                }
+            })
+        }
+    }
+
+    fn request(operation: A2AOperation) -> A2ARequest {
+        A2ARequest::new(
+            operation,
+            RequestContext::new("https://example.com".parse().unwrap()),
+        )
+    }
+
+    fn get_task(task_id: &str) -> A2ARequest {
+        request(A2AOperation::GetTask {
+            task_id: task_id.into(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_reads_are_coalesced() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let layer = SingleFlightLayer::new().with_anonymous(true);
+        let service = layer.layer(Counting(calls.clone()));
+
+        let responses = futures::future::join_all(
+            [get_task("task-1"), get_task("task-1"), get_task("task-2")]
+                .map(|req| service.clone().oneshot(req)),
+        )
+        .await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(responses.iter().all(Result::is_ok));
+        assert_eq!(layer.in_flight(), 0);
+
+        // Errors are shared, and writes are never coalesced
+        let errors = futures::future::join_all(
+            [
+                request(A2AOperation::DiscoverAgent),
+                request(A2AOperation::DiscoverAgent),
+                request(A2AOperation::CancelTask {
+                    task_id: "task-1".into(),
+                    reason: None,
+                }),
+                request(A2AOperation::CancelTask {
+                    task_id: "task-1".into(),
+                    reason: None,
+                }),
+            ]
+            .map(|req| service.clone().oneshot(req)),
+        )
+        .await;
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+        assert!(errors
+            .iter()
+            .all(|result| matches!(result, Err(A2AError::Transport(_)))));
+
+        // Requests made after completion reach the service again
+        service.oneshot(get_task("task-1")).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 6);
+    }
+
+    #[tokio::test]
+    async fn test_identities_are_not_coalesced() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let layer = SingleFlightLayer::new();
+        let service = layer.layer(Counting(calls.clone()));
+        let as_user = |token: &str| {
+            let mut req = get_task("task-1");
+            req.context.auth = Some(AuthCredentials::bearer(token));
+            req
+        };
+
+        futures::future::join_all(
+            [as_user("alice"), as_user("alice"), as_user("bob")]
+                .map(|req| service.clone().oneshot(req)),
+        )
+        .await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Without credentials the requests may belong to anyone
+        futures::future::join_all(
+            [get_task("task-1"), get_task("task-1")].map(|req| service.clone().oneshot(req)),
+        )
+        .await;
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_requests_are_removed() {
+        let layer = SingleFlightLayer::new().with_anonymous(true);
+        let service = layer.layer(Counting(Arc::default()));
+
+        let call = service.oneshot(get_task("task-1"));
+        assert!(tokio::time::timeout(Duration::from_millis(1), call)
+            .await
+            .is_err());
+        assert_eq!(layer.in_flight(), 0);
+    }
+}
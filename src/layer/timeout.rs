@@ -0,0 +1,213 @@
+//! Timeout layer enforcing per-request deadlines
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{
+    protocol::error::A2AError,
+    service::{A2AProtocolService, A2ARequest, A2AResponse, ProtocolStack},
+};
+
+/// Layer that fails requests exceeding their timeout with [`A2AError::Timeout`]
+///
+/// The timeout is taken from [`RequestContext::timeout`](crate::service::RequestContext),
+/// which the client fills in per operation kind (see
+/// [`ClientConfig::with_operation_timeout`](crate::client::ClientConfig::with_operation_timeout)).
+/// Requests without a timeout fall back to the layer's default, if any.
+///
+/// The timeout applies to a single attempt. If the request carries an operation
+/// deadline ([`RequestContext::deadline`](crate::service::RequestContext::deadline)),
+/// the attempt is additionally cut short when the deadline passes.
+#[derive(Clone, Debug, Default)]
+pub struct A2ATimeoutLayer {
+    default: Option<Duration>,
+}
+
+impl A2ATimeoutLayer {
+    /// Create a new timeout layer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the timeout applied to requests that do not specify one
+    pub fn with_default(mut self, timeout: Duration) -> Self {
+        self.default = Some(timeout);
+        self
+    }
+}
+
+impl<S> Layer<S> for A2ATimeoutLayer {
+    type Service = A2ATimeoutService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        A2ATimeoutService {
+            inner,
+            default: self.default,
+        }
+    }
+}
+
+/// Timeout service that wraps an inner service
+#[derive(Clone)]
+pub struct A2ATimeoutService<S> {
+    inner: S,
+    default: Option<Duration>,
+}
+
+#[async_trait]
+impl<S: ProtocolStack> ProtocolStack for A2ATimeoutService<S> {
+    type Transport = S::Transport;
+
+    fn protocol(&self) -> &A2AProtocolService<S::Transport> {
+        self.inner.protocol()
+    }
+
+    async fn prepare(&self, request: &mut A2ARequest) -> Result<(), A2AError> {
+        self.inner.prepare(request).await
+    }
+}
+
+impl<S> Service<A2ARequest> for A2ATimeoutService<S>
+where
+    S: Service<A2ARequest, Response = A2AResponse, Error = A2AError> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = A2AResponse;
+    type Error = A2AError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: A2ARequest) -> Self::Future {
+        let timeout = req.context.timeout.or(self.default);
+        let remaining = req.context.remaining();
+        let kind = req.kind();
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let (timeout, reason) = match (timeout, remaining) {
+                (Some(timeout), Some(remaining)) if remaining < timeout => {
+                    (remaining, "Operation deadline exceeded")
+                }
+                (Some(timeout), _) => (timeout, "Request timed out"),
+                (None, Some(remaining)) => (remaining, "Operation deadline exceeded"),
+                (None, None) => return future.await,
+            };
+
+            tokio::time::timeout(timeout, future)
+                .await
+                .unwrap_or_else(|_| {
+                    tracing::warn!(?kind, ?timeout, "{}", reason);
+                    Err(A2AError::Timeout)
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use tower::ServiceExt;
+
+    use crate::{
+        codec::JsonCodec,
+        protocol::{message::Message, operation::A2AOperation, task::Task},
+        service::{A2AProtocolService, RequestContext},
+        transport::{mock::MockTransport, TransportResponse},
+    };
+
+    use super::*;
+
+    /// Service that responds after a delay
+    #[derive(Clone)]
+    struct Delayed(Duration);
+
+    impl Service<A2ARequest> for Delayed {
+        type Response = A2AResponse;
+        type Error = A2AError;
+        type Future = Pin<Box<dyn Future<Output = Result<A2AResponse, A2AError>> + Send>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), A2AError>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _req: A2ARequest) -> Self::Future {
+            let delay = self.0;
+            Box::pin(async move {
+                tokio::time::sleep(delay).await;
+                Ok(A2AResponse::Empty)
+            })
+        }
+    }
+
+    fn request(timeout: Option<Duration>) -> A2ARequest {
+        let mut context = RequestContext::new("https://example.com".parse().unwrap());
+        context.timeout = timeout;
+        A2ARequest::new(A2AOperation::DiscoverAgent, context)
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let service = A2ATimeoutLayer::new().layer(Delayed(Duration::from_millis(200)));
+
+        let result = service
+            .clone()
+            .oneshot(request(Some(Duration::from_millis(10))))
+            .await;
+        assert!(matches!(result, Err(A2AError::Timeout)));
+
+        let result = service.oneshot(request(Some(Duration::from_secs(5)))).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_default_timeout() {
+        let service = A2ATimeoutLayer::new()
+            .with_default(Duration::from_millis(10))
+            .layer(Delayed(Duration::from_millis(200)));
+
+        let result = service.oneshot(request(None)).await;
+        assert!(matches!(result, Err(A2AError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_deadline_caps_timeout() {
+        let service = A2ATimeoutLayer::new().layer(Delayed(Duration::from_millis(200)));
+
+        let mut req = request(Some(Duration::from_secs(5)));
+        req.context.deadline = Some(std::time::Instant::now() + Duration::from_millis(10));
+
+        let result = service.oneshot(req).await;
+        assert!(matches!(result, Err(A2AError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_passes_responses() {
+        let transport = MockTransport::new(|_req| {
+            let task = Task::new("task-123", Message::user("Test"));
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+        let service =
+            A2ATimeoutLayer::new().layer(A2AProtocolService::new(transport, Arc::new(JsonCodec)));
+
+        let mut req = request(Some(Duration::from_secs(1)));
+        req.operation = A2AOperation::GetTask {
+            task_id: "task-123".to_string(),
+        };
+
+        let response = service.oneshot(req).await.unwrap();
+        assert!(matches!(response, A2AResponse::Task(_)));
+    }
+}
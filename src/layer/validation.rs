@@ -1,28 +1,217 @@
 //! Validation layer for A2A protocol requests and responses
 
 use std::{
+    fmt,
     future::Future,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 
+use async_trait::async_trait;
 use tower_layer::Layer;
 use tower_service::Service;
 
 use crate::{
     prelude::{MessagePart, TaskStatus},
     protocol::{error::A2AError, operation::A2AOperation},
-    service::{A2ARequest, A2AResponse},
+    service::{A2AProtocolService, A2ARequest, A2AResponse, ProtocolStack},
 };
 
+/// A response validation rule that was violated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationFailure {
+    /// Identifier of the rule, e.g. `task.failed_error`
+    pub rule: &'static str,
+
+    /// Human readable description of the violation
+    pub message: String,
+}
+
+impl ValidationFailure {
+    fn new(rule: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.rule)
+    }
+}
+
+/// A value together with the validation rules it violates
+///
+/// Returned where imperfect responses are still useful, e.g. a completed task that
+/// lacks artifacts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validated<T> {
+    value: T,
+    failures: Vec<ValidationFailure>,
+}
+
+impl<T> Validated<T> {
+    /// Wrap a value with the rules it violates
+    pub fn new(value: T, failures: Vec<ValidationFailure>) -> Self {
+        Self { value, failures }
+    }
+
+    /// Check whether the value passed all rules
+    pub fn is_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Get the violated rules
+    pub fn failures(&self) -> &[ValidationFailure] {
+        &self.failures
+    }
+
+    /// Get the value, regardless of violated rules
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Take the value, regardless of violated rules
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Take the value, failing with the first violated rule as strict validation does
+    pub fn into_strict(self) -> Result<T, A2AError> {
+        match self.failures.into_iter().next() {
+            Some(failure) => Err(A2AError::Validation(failure.message)),
+            None => Ok(self.value),
+        }
+    }
+
+    /// Transform the value, keeping the violated rules
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Validated<U> {
+        Validated {
+            value: f(self.value),
+            failures: self.failures,
+        }
+    }
+}
+
+/// Validate a response, collecting every violated rule
+pub fn validate_response(response: A2AResponse) -> Validated<A2AResponse> {
+    let failures = response_failures(&response);
+    Validated::new(response, failures)
+}
+
+/// Collect the rules a response violates
+fn response_failures(resp: &A2AResponse) -> Vec<ValidationFailure> {
+    let mut failures = Vec::new();
+
+    match resp {
+        A2AResponse::Task(task) => {
+            if task.id.is_empty() {
+                failures.push(ValidationFailure::new("task.id", "Task ID cannot be empty"));
+            }
+
+            // Validate task has input
+            if task.input.parts.is_empty() {
+                failures.push(ValidationFailure::new(
+                    "task.input",
+                    "Task input must have at least one part",
+                ));
+            }
+
+            // If task is completed, it should have artifacts or error
+            if task.status == TaskStatus::Completed
+                && task.artifacts.is_empty()
+                && task.error.is_none()
+            {
+                failures.push(ValidationFailure::new(
+                    "task.completed_result",
+                    "Completed task must have artifacts or error",
+                ));
+            }
+
+            // If task is failed, it should have an error
+            if task.status == TaskStatus::Failed && task.error.is_none() {
+                failures.push(ValidationFailure::new(
+                    "task.failed_error",
+                    "Failed task must have an error",
+                ));
+            }
+        }
+        A2AResponse::AgentCard(card) => {
+            if card.name.is_empty() {
+                failures.push(ValidationFailure::new(
+                    "card.name",
+                    "Agent name cannot be empty",
+                ));
+            }
+            if card.endpoints.is_empty() {
+                failures.push(ValidationFailure::new(
+                    "card.endpoints",
+                    "Agent card must have at least one endpoint",
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    failures
+}
+
+/// How the validation layer treats responses violating validation rules
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// Fail the request with [`A2AError::Validation`]
+    #[default]
+    Strict,
+
+    /// Log a warning, notify the failure handler, and return the response anyway
+    Lenient,
+}
+
+/// Callback receiving the violated rules of responses passed through in lenient mode
+pub type ValidationFailureHandler = Arc<dyn Fn(&A2AResponse, &[ValidationFailure]) + Send + Sync>;
+
 /// Layer that validates A2A protocol requests and responses
-#[derive(Clone, Debug, Default)]
-pub struct A2AValidationLayer;
+///
+/// Invalid requests are always rejected. Invalid responses are rejected in
+/// [`ValidationMode::Strict`] (the default) and passed through with a warning in
+/// [`ValidationMode::Lenient`].
+#[derive(Clone, Default)]
+pub struct A2AValidationLayer {
+    mode: ValidationMode,
+    on_failure: Option<ValidationFailureHandler>,
+}
 
 impl A2AValidationLayer {
     /// Create a new validation layer
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Set how invalid responses are treated (default: strict)
+    pub fn with_mode(mut self, mode: ValidationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Notify a callback of invalid responses passed through in lenient mode
+    pub fn with_failure_handler(
+        mut self,
+        handler: impl Fn(&A2AResponse, &[ValidationFailure]) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_failure = Some(Arc::new(handler));
+        self
+    }
+}
+
+impl fmt::Debug for A2AValidationLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("A2AValidationLayer")
+            .field("mode", &self.mode)
+            .field("on_failure", &self.on_failure.is_some())
+            .finish()
     }
 }
 
@@ -30,7 +219,11 @@ impl<S> Layer<S> for A2AValidationLayer {
     type Service = A2AValidationService<S>;
 
     fn layer(&self, inner: S) -> Self::Service {
-        A2AValidationService { inner }
+        A2AValidationService {
+            inner,
+            mode: self.mode,
+            on_failure: self.on_failure.clone(),
+        }
     }
 }
 
@@ -38,6 +231,8 @@ impl<S> Layer<S> for A2AValidationLayer {
 #[derive(Clone)]
 pub struct A2AValidationService<S> {
     inner: S,
+    mode: ValidationMode,
+    on_failure: Option<ValidationFailureHandler>,
 }
 
 impl<S> A2AValidationService<S> {
@@ -52,6 +247,10 @@ impl<S> A2AValidationService<S> {
                     ));
                 }
 
+                if let Some(hints) = message.scheduling()? {
+                    hints.validate()?;
+                }
+
                 // Validate each part (basic checks)
                 for part in &message.parts {
                     match part {
@@ -80,15 +279,14 @@ impl<S> A2AValidationService<S> {
                     }
                 }
             }
-            A2AOperation::GetTask { task_id } => {
-                if task_id.is_empty() {
-                    return Err(A2AError::Validation("Task ID cannot be empty".into()));
-                }
+            A2AOperation::GetTask { task_id } if task_id.is_empty() => {
+                return Err(A2AError::Validation("Task ID cannot be empty".into()));
             }
-            A2AOperation::CancelTask { task_id } => {
-                if task_id.is_empty() {
-                    return Err(A2AError::Validation("Task ID cannot be empty".into()));
-                }
+            A2AOperation::CancelTask { task_id, .. } if task_id.is_empty() => {
+                return Err(A2AError::Validation("Task ID cannot be empty".into()));
+            }
+            A2AOperation::ResubscribeTask { task_id } if task_id.is_empty() => {
+                return Err(A2AError::Validation("Task ID cannot be empty".into()));
             }
             A2AOperation::ListTasks { limit, offset, .. } => {
                 if let Some(limit_val) = limit {
@@ -106,6 +304,30 @@ impl<S> A2AValidationService<S> {
                     }
                 }
             }
+            A2AOperation::SetPushNotificationConfig { task_id, config } => {
+                if task_id.is_empty() {
+                    return Err(A2AError::Validation("Task ID cannot be empty".into()));
+                }
+                if config.url.is_empty() {
+                    return Err(A2AError::Validation(
+                        "Push notification URL cannot be empty".into(),
+                    ));
+                }
+            }
+            A2AOperation::GetPushNotificationConfig { task_id, config_id }
+            | A2AOperation::DeletePushNotificationConfig { task_id, config_id } => {
+                if task_id.is_empty() {
+                    return Err(A2AError::Validation("Task ID cannot be empty".into()));
+                }
+                if config_id.is_empty() {
+                    return Err(A2AError::Validation(
+                        "Push notification config ID cannot be empty".into(),
+                    ));
+                }
+            }
+            A2AOperation::ListPushNotificationConfigs { task_id } if task_id.is_empty() => {
+                return Err(A2AError::Validation("Task ID cannot be empty".into()));
+            }
             A2AOperation::RegisterWebhook { url, events, .. } => {
                 if url.is_empty() {
                     return Err(A2AError::Validation("Webhook URL cannot be empty".into()));
@@ -126,53 +348,18 @@ impl<S> A2AValidationService<S> {
 
         Ok(())
     }
+}
 
-    /// Validate an A2A response
-    fn validate_response(resp: &A2AResponse) -> Result<(), A2AError> {
-        match resp {
-            A2AResponse::Task(task) => {
-                if task.id.is_empty() {
-                    return Err(A2AError::Validation("Task ID cannot be empty".into()));
-                }
-
-                // Validate task has input
-                if task.input.parts.is_empty() {
-                    return Err(A2AError::Validation(
-                        "Task input must have at least one part".into(),
-                    ));
-                }
-
-                // If task is completed, it should have artifacts or error
-                if task.status == TaskStatus::Completed
-                    && task.artifacts.is_empty()
-                    && task.error.is_none()
-                {
-                    return Err(A2AError::Validation(
-                        "Completed task must have artifacts or error".into(),
-                    ));
-                }
+#[async_trait]
+impl<S: ProtocolStack> ProtocolStack for A2AValidationService<S> {
+    type Transport = S::Transport;
 
-                // If task is failed, it should have an error
-                if task.status == TaskStatus::Failed && task.error.is_none() {
-                    return Err(A2AError::Validation(
-                        "Failed task must have an error".into(),
-                    ));
-                }
-            }
-            A2AResponse::AgentCard(card) => {
-                if card.name.is_empty() {
-                    return Err(A2AError::Validation("Agent name cannot be empty".into()));
-                }
-                if card.endpoints.is_empty() {
-                    return Err(A2AError::Validation(
-                        "Agent card must have at least one endpoint".into(),
-                    ));
-                }
-            }
-            _ => {}
-        }
+    fn protocol(&self) -> &A2AProtocolService<S::Transport> {
+        self.inner.protocol()
+    }
 
-        Ok(())
+    async fn prepare(&self, request: &mut A2ARequest) -> Result<(), A2AError> {
+        self.inner.prepare(request).await
     }
 }
 
@@ -196,13 +383,28 @@ where
         }
 
         let mut inner = self.inner.clone();
+        let mode = self.mode;
+        let on_failure = self.on_failure.clone();
         Box::pin(async move {
-            let response = inner.call(req).await?;
-
-            // Validate response
-            Self::validate_response(&response)?;
+            let kind = req.kind();
+            let response = validate_response(inner.call(req).await?);
+            if response.is_valid() || mode == ValidationMode::Strict {
+                return response.into_strict();
+            }
 
-            Ok(response)
+            // Lenient mode: report the violations and return the response anyway
+            for failure in response.failures() {
+                tracing::warn!(
+                    ?kind,
+                    rule = failure.rule,
+                    "Invalid response: {}",
+                    failure.message
+                );
+            }
+            if let Some(on_failure) = &on_failure {
+                on_failure(response.value(), response.failures());
+            }
+            Ok(response.into_inner())
         })
     }
 }
@@ -258,6 +460,52 @@ mod tests {
         let task = Task::new("task-123", Message::user("Test"));
         let response = A2AResponse::Task(Box::new(task));
 
-        assert!(A2AValidationService::<()>::validate_response(&response).is_ok());
+        assert!(validate_response(response).is_valid());
+    }
+
+    #[tokio::test]
+    async fn test_lenient_mode_returns_invalid_responses() {
+        use std::sync::Mutex;
+
+        use tower::{service_fn, ServiceExt};
+
+        let task = Task::new("", Message::user("Test")).with_status(TaskStatus::Failed);
+        let inner = service_fn(move |_req: A2ARequest| {
+            let task = task.clone();
+            async move { Ok::<_, A2AError>(A2AResponse::Task(Box::new(task))) }
+        });
+        let request = || {
+            A2ARequest::new(
+                A2AOperation::GetTask {
+                    task_id: "task-123".into(),
+                },
+                RequestContext::new(agent_url()),
+            )
+        };
+
+        let strict = A2AValidationLayer::new().layer(inner.clone());
+        assert!(matches!(
+            strict.oneshot(request()).await,
+            Err(A2AError::Validation(_))
+        ));
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let lenient = A2AValidationLayer::new()
+            .with_mode(ValidationMode::Lenient)
+            .with_failure_handler({
+                let reported = reported.clone();
+                move |_response, failures| {
+                    reported
+                        .lock()
+                        .unwrap()
+                        .extend(failures.iter().map(|failure| failure.rule))
+                }
+            })
+            .layer(inner);
+        let response = lenient.oneshot(request()).await.unwrap();
+
+        assert!(matches!(response, A2AResponse::Task(_)));
+        assert_eq!(*reported.lock().unwrap(), ["task.id", "task.failed_error"]);
+        assert!(!validate_response(response).is_valid());
     }
 }
@@ -34,6 +34,8 @@
 //! }
 //! ```
 
+#[cfg(feature = "bench-util")]
+pub mod bench;
 pub mod client;
 pub mod codec;
 pub mod layer;
@@ -44,7 +46,7 @@ pub mod transport;
 /// Prelude module for convenient imports
 pub mod prelude {
     pub use crate::{
-        client::{A2AClientBuilder, AgentClient},
+        client::{A2AClientBuilder, AgentClient, Profile},
         protocol::error::A2AError,
         protocol::{
             A2AOperation, AgentCard, Artifact, Message, MessagePart, Role, Task, TaskStatus,
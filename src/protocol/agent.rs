@@ -3,10 +3,12 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
-
+use serde_json::{Map, Value};
 use url::Url;
 use uuid::Uuid;
 
+use crate::protocol::error::A2AError;
+
 /// Agent scope for granular access control
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -60,6 +62,30 @@ pub struct AgentCard {
     /// Granular access control scopes (not part of protocol, used for authorization)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub scopes: Option<Vec<AgentScope>>,
+
+    /// Skills the agent can perform
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<AgentSkill>,
+
+    /// Media types the agent accepts unless a skill overrides them
+    #[serde(
+        rename = "defaultInputModes",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub default_input_modes: Vec<String>,
+
+    /// Media types the agent produces unless a skill overrides them
+    #[serde(
+        rename = "defaultOutputModes",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub default_output_modes: Vec<String>,
+
+    /// Fields that are not part of the protocol, kept for re-serialization
+    #[serde(flatten, default, skip_serializing_if = "Map::is_empty")]
+    pub extra: Map<String, Value>,
 }
 
 impl AgentCard {
@@ -80,6 +106,10 @@ impl AgentCard {
             version: None,
             documentation_url: None,
             scopes: None,
+            skills: Vec::new(),
+            default_input_modes: Vec::new(),
+            default_output_modes: Vec::new(),
+            extra: Map::new(),
         }
     }
 
@@ -89,6 +119,36 @@ impl AgentCard {
         self
     }
 
+    /// Check that the card declares an endpoint of one of the given types
+    ///
+    /// Types are compared ignoring case. Cards without endpoints, and empty lists of
+    /// types, are accepted since nothing can be concluded from them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::BindingMismatch` listing the card's endpoint types otherwise
+    pub fn check_bindings(&self, bindings: &[&str]) -> Result<(), A2AError> {
+        let declared = || self.endpoints.values().map(|e| e.endpoint_type.as_str());
+        if bindings.is_empty()
+            || self.endpoints.is_empty()
+            || declared().any(|declared| {
+                bindings
+                    .iter()
+                    .any(|binding| binding.eq_ignore_ascii_case(declared))
+            })
+        {
+            return Ok(());
+        }
+
+        let mut supported: Vec<String> = declared().map(str::to_string).collect();
+        supported.sort();
+        supported.dedup();
+        Err(A2AError::BindingMismatch {
+            client: bindings.iter().map(|binding| binding.to_string()).collect(),
+            supported,
+        })
+    }
+
     /// Add authentication schemes
     pub fn with_authentication(mut self, schemes: Vec<SecurityScheme>) -> Self {
         self.authentication = Some(schemes);
@@ -100,6 +160,209 @@ impl AgentCard {
         self.version = Some(version.into());
         self
     }
+
+    /// Add a skill
+    pub fn with_skill(mut self, skill: AgentSkill) -> Self {
+        self.skills.push(skill);
+        self
+    }
+
+    /// Set the default input and output media types
+    pub fn with_default_modes(
+        mut self,
+        input: impl IntoIterator<Item = impl Into<String>>,
+        output: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.default_input_modes = input.into_iter().map(Into::into).collect();
+        self.default_output_modes = output.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Compute the changes from this card to a newer card of the same agent
+    ///
+    /// Skills are matched by `id` and endpoints by name. Descriptive fields such as
+    /// the description and documentation URL are not compared.
+    pub fn diff(&self, newer: &AgentCard) -> AgentCardDelta {
+        AgentCardDelta::between(self, newer)
+    }
+}
+
+/// Changes between two versions of an agent card
+///
+/// Produced by [`AgentCard::diff`], typically when a refreshed card replaces a cached
+/// one. Lists are sorted (skills by ID) so deltas compare equal regardless of the
+/// order of skills and endpoints in the cards.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgentCardDelta {
+    /// Skills that did not exist in the older card
+    pub added_skills: Vec<AgentSkill>,
+
+    /// IDs of skills that no longer exist
+    pub removed_skills: Vec<String>,
+
+    /// Skills whose definition changed
+    pub updated_skills: Vec<AgentSkill>,
+
+    /// Capability flags that were enabled, e.g. `"streaming"`
+    pub added_capabilities: Vec<&'static str>,
+
+    /// Capability flags that were disabled
+    pub removed_capabilities: Vec<&'static str>,
+
+    /// Whether the supported part types changed
+    pub part_types_changed: bool,
+
+    /// Names of endpoints that did not exist in the older card
+    pub added_endpoints: Vec<String>,
+
+    /// Names of endpoints that no longer exist
+    pub removed_endpoints: Vec<String>,
+
+    /// Names of endpoints whose URL, type, or preference changed
+    pub changed_endpoints: Vec<String>,
+
+    /// Whether the security schemes changed, e.g. after a credential rotation
+    pub authentication_changed: bool,
+
+    /// Whether the advertised version changed
+    pub version_changed: bool,
+}
+
+impl AgentCardDelta {
+    /// Check if the cards were equivalent
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Check if any endpoint was added, removed, or changed
+    pub fn endpoints_changed(&self) -> bool {
+        !self.added_endpoints.is_empty()
+            || !self.removed_endpoints.is_empty()
+            || !self.changed_endpoints.is_empty()
+    }
+
+    fn between(older: &AgentCard, newer: &AgentCard) -> Self {
+        let mut delta = Self::default();
+
+        for skill in &newer.skills {
+            match older.skills.iter().find(|s| s.id == skill.id) {
+                None => delta.added_skills.push(skill.clone()),
+                Some(previous) if previous != skill => delta.updated_skills.push(skill.clone()),
+                Some(_) => {}
+            }
+        }
+        delta.removed_skills = older
+            .skills
+            .iter()
+            .filter(|s| !newer.skills.iter().any(|n| n.id == s.id))
+            .map(|s| s.id.clone())
+            .collect();
+
+        let before = older.capabilities.flags();
+        let after = newer.capabilities.flags();
+        for ((name, was), (_, is)) in before.into_iter().zip(after) {
+            match (was, is) {
+                (false, true) => delta.added_capabilities.push(name),
+                (true, false) => delta.removed_capabilities.push(name),
+                _ => {}
+            }
+        }
+        delta.part_types_changed =
+            older.capabilities.supported_part_types != newer.capabilities.supported_part_types;
+
+        for (name, endpoint) in &newer.endpoints {
+            match older.endpoints.get(name) {
+                None => delta.added_endpoints.push(name.clone()),
+                Some(previous) if previous != endpoint => {
+                    delta.changed_endpoints.push(name.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        delta.removed_endpoints = older
+            .endpoints
+            .keys()
+            .filter(|name| !newer.endpoints.contains_key(*name))
+            .cloned()
+            .collect();
+
+        delta.authentication_changed = older.authentication != newer.authentication;
+        delta.version_changed = older.version != newer.version;
+
+        delta.added_skills.sort_by(|a, b| a.id.cmp(&b.id));
+        delta.removed_skills.sort();
+        delta.updated_skills.sort_by(|a, b| a.id.cmp(&b.id));
+        delta.added_capabilities.sort();
+        delta.removed_capabilities.sort();
+        delta.added_endpoints.sort();
+        delta.removed_endpoints.sort();
+        delta.changed_endpoints.sort();
+
+        delta
+    }
+}
+
+/// A skill advertised in an Agent Card
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentSkill {
+    /// Unique identifier of the skill
+    pub id: String,
+
+    /// Human readable name of the skill
+    pub name: String,
+
+    /// Description of what the skill does
+    #[serde(default)]
+    pub description: String,
+
+    /// Keywords describing the skill
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Example prompts the skill handles
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
+
+    /// Media types accepted by the skill, overriding the card's defaults
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_modes: Option<Vec<String>>,
+
+    /// Media types produced by the skill, overriding the card's defaults
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_modes: Option<Vec<String>>,
+}
+
+impl AgentSkill {
+    /// Create a new skill
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            description: String::new(),
+            tags: Vec::new(),
+            examples: Vec::new(),
+            input_modes: None,
+            output_modes: None,
+        }
+    }
+
+    /// Add tags
+    pub fn with_tags(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.tags.extend(tags.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the accepted and produced media types
+    pub fn with_modes(
+        mut self,
+        input: impl IntoIterator<Item = impl Into<String>>,
+        output: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.input_modes = Some(input.into_iter().map(Into::into).collect());
+        self.output_modes = Some(output.into_iter().map(Into::into).collect());
+        self
+    }
 }
 
 /// Agent capabilities
@@ -124,6 +387,41 @@ pub struct AgentCapabilities {
     /// Supported message part types
     #[serde(rename = "supportedPartTypes", skip_serializing_if = "Option::is_none")]
     pub supported_part_types: Option<Vec<String>>,
+
+    /// Protocol extensions the agent supports
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<AgentExtension>,
+}
+
+/// Protocol extension supported by an agent
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AgentExtension {
+    /// URI identifying the extension
+    pub uri: String,
+
+    /// How the agent uses the extension
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Whether clients must use the extension to talk to the agent
+    #[serde(default)]
+    pub required: bool,
+
+    /// Extension-specific parameters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+impl AgentExtension {
+    /// Create an optional extension
+    pub fn new(uri: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            description: None,
+            required: false,
+            params: None,
+        }
+    }
 }
 
 impl AgentCapabilities {
@@ -155,6 +453,27 @@ impl AgentCapabilities {
         self.multi_turn = true;
         self
     }
+
+    /// Add a supported extension
+    pub fn with_extension(mut self, extension: AgentExtension) -> Self {
+        self.extensions.push(extension);
+        self
+    }
+
+    /// Check whether an extension is supported
+    pub fn supports_extension(&self, uri: &str) -> bool {
+        self.extensions.iter().any(|extension| extension.uri == uri)
+    }
+
+    /// List the capability flags by their wire names
+    fn flags(&self) -> [(&'static str, bool); 4] {
+        [
+            ("streaming", self.streaming),
+            ("pushNotifications", self.push_notifications),
+            ("taskManagement", self.task_management),
+            ("multiTurn", self.multi_turn),
+        ]
+    }
 }
 
 /// API Key security scheme
@@ -282,6 +601,77 @@ mod tests {
         assert_eq!(card.endpoints.len(), 1);
     }
 
+    #[test]
+    fn test_agent_card_diff() {
+        let card = AgentCard::new("Test Agent", "A test agent", AgentCapabilities::new())
+            .with_skill(AgentSkill::new("search", "Search"))
+            .with_skill(AgentSkill::new("summarize", "Summarize"))
+            .with_endpoint(
+                "http",
+                EndpointConfig::new("https://example.com", "http+json"),
+            )
+            .with_endpoint(
+                "grpc",
+                EndpointConfig::new("https://example.com:443", "grpc"),
+            );
+        assert!(card.diff(&card.clone()).is_empty());
+
+        let mut newer = card
+            .clone()
+            .with_skill(AgentSkill::new("translate", "Translate"))
+            .with_endpoint(
+                "http",
+                EndpointConfig::new("https://v2.example.com", "http+json"),
+            )
+            .with_authentication(vec![SecurityScheme::HttpAuth(HttpAuthSecurityScheme {
+                description: None,
+                scheme: "bearer".into(),
+                bearer_format: None,
+            })]);
+        newer.skills.retain(|skill| skill.id != "summarize");
+        newer.endpoints.remove("grpc");
+        newer.capabilities = AgentCapabilities::new().with_streaming();
+
+        let delta = card.diff(&newer);
+        assert_eq!(
+            delta.added_skills,
+            vec![AgentSkill::new("translate", "Translate")]
+        );
+        assert_eq!(delta.removed_skills, vec!["summarize".to_string()]);
+        assert!(delta.updated_skills.is_empty());
+        assert_eq!(delta.added_capabilities, vec!["streaming"]);
+        assert_eq!(delta.removed_endpoints, vec!["grpc".to_string()]);
+        assert_eq!(delta.changed_endpoints, vec!["http".to_string()]);
+        assert!(delta.endpoints_changed());
+        assert!(delta.authentication_changed);
+        assert!(!delta.version_changed);
+
+        // Skill order does not matter
+        let mut reordered = newer.with_skill(AgentSkill::new("classify", "Classify"));
+        let delta = card.diff(&reordered);
+        reordered.skills.reverse();
+        assert_eq!(card.diff(&reordered), delta);
+        assert_eq!(delta.added_skills[0].id, "classify");
+    }
+
+    #[test]
+    fn test_check_bindings() {
+        let card = AgentCard::new("Agent", "Test agent", AgentCapabilities::new())
+            .with_endpoint(
+                "main",
+                EndpointConfig::new("https://a.example.com", "HTTP+JSON"),
+            )
+            .with_endpoint("grpc", EndpointConfig::new("https://b.example.com", "grpc"));
+
+        assert!(card.check_bindings(&["http+json"]).is_ok());
+        assert!(card.check_bindings(&[]).is_ok());
+        let error = card.check_bindings(&["json-rpc", "jsonrpc"]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Agent does not support the json-rpc or jsonrpc binding; it supports HTTP+JSON, grpc"
+        );
+    }
+
     #[test]
     fn test_agent_capabilities() {
         let mut caps = AgentCapabilities::default();
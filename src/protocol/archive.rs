@@ -0,0 +1,125 @@
+//! NDJSON archives of tasks
+//!
+//! Tasks are written one JSON document per line, including their history, artifacts,
+//! and errors, so archives can be streamed, concatenated, and processed with standard
+//! line-oriented tools. Used for backups and for moving tasks between stores.
+
+use futures::{Stream, StreamExt};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+use super::{error::A2AError, task::Task};
+
+/// Write tasks to an NDJSON archive, returning the number of tasks written
+pub async fn export_tasks<S, W>(tasks: S, writer: &mut W) -> Result<usize, A2AError>
+where
+    S: Stream<Item = Task>,
+    W: AsyncWrite + Unpin,
+{
+    let mut tasks = std::pin::pin!(tasks);
+    let mut count = 0;
+
+    while let Some(task) = tasks.next().await {
+        let mut line = serde_json::to_vec(&task)?;
+        line.push(b'\n');
+        writer.write_all(&line).await.map_err(io_error)?;
+        count += 1;
+    }
+
+    writer.flush().await.map_err(io_error)?;
+    Ok(count)
+}
+
+/// Read tasks from an NDJSON archive
+///
+/// Blank lines are skipped. A line that is not a valid task yields an
+/// `A2AError::Validation` naming the line number, after which reading continues.
+/// A failure to read yields an error and ends the stream.
+pub fn import_tasks<R>(reader: R) -> impl Stream<Item = Result<Task, A2AError>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    futures::stream::unfold(
+        (Some(reader.lines()), 0usize),
+        |(lines, mut number)| async move {
+            let mut lines = lines?;
+            loop {
+                number += 1;
+                let line = match lines.next_line().await {
+                    Ok(Some(line)) => line,
+                    Ok(None) => return None,
+                    Err(e) => return Some((Err(io_error(e)), (None, number))),
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                let task = serde_json::from_str(&line).map_err(|e| {
+                    A2AError::Validation(format!("Invalid task on line {}: {}", number, e))
+                });
+                return Some((task, (Some(lines), number)));
+            }
+        },
+    )
+}
+
+fn io_error(error: std::io::Error) -> A2AError {
+    A2AError::Other(format!("Task archive I/O failed: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::protocol::{Artifact, Message, MessagePart, TaskStatus};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_roundtrip() {
+        let tasks = vec![
+            Task::new("task-1", Message::user("Hello"))
+                .with_status(TaskStatus::Completed)
+                .with_artifact(Artifact {
+                    artifact_id: "result".to_string(),
+                    name: None,
+                    description: None,
+                    parts: vec![MessagePart::data(json!({"answer": 42}))],
+                    metadata: None,
+                    extensions: Vec::new(),
+                    extra: Default::default(),
+                }),
+            Task::new("task-2", Message::user("Bye")).with_context_id("ctx-1"),
+        ];
+
+        let mut archive = Vec::new();
+        let count = export_tasks(futures::stream::iter(tasks.clone()), &mut archive)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(archive.iter().filter(|&&b| b == b'\n').count(), 2);
+
+        archive.extend_from_slice(b"\nnot json\n");
+        let imported: Vec<_> = import_tasks(archive.as_slice()).collect().await;
+        assert_eq!(imported.len(), 3);
+        assert_eq!(imported[0].as_ref().unwrap(), &tasks[0]);
+        assert_eq!(imported[1].as_ref().unwrap(), &tasks[1]);
+        assert!(imported[2]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("line 4"));
+    }
+
+    #[tokio::test]
+    async fn test_import_ends_on_read_error() {
+        // Invalid UTF-8 fails the read
+        let archive = b"\xff\xfe\n{}\n";
+        let imported: Vec<_> = import_tasks(&archive[..]).collect().await;
+        assert_eq!(imported.len(), 1);
+        assert!(imported[0]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("I/O failed"));
+    }
+}
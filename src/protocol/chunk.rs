@@ -0,0 +1,327 @@
+//! Splitting oversized text into chunks and reassembling it
+
+use serde_json::{json, Value};
+
+use super::{
+    error::A2AError,
+    message::{Message, MessagePart, Role},
+};
+
+/// Metadata key recording how a message was chunked
+pub const CHUNK_METADATA_KEY: &str = "chunk";
+
+/// Splits long text into chunks no larger than a configured size
+///
+/// Sizes are measured in UTF-8 bytes, since that is what agents limit. Chunks end on
+/// whitespace where possible and never split a character. Consecutive chunks may
+/// overlap to give the receiving agent some context across boundaries; the overlap is
+/// recorded in metadata so [`TextChunker::reassemble`] can remove it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextChunker {
+    chunk_size: usize,
+    overlap: usize,
+}
+
+/// A chunk of text produced by [`TextChunker::split`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextChunk {
+    /// Position of the chunk, starting at zero
+    pub index: usize,
+
+    /// Total number of chunks
+    pub total: usize,
+
+    /// Number of leading bytes repeated from the previous chunk
+    pub overlap: usize,
+
+    /// The chunk's text, including the overlap
+    pub text: String,
+}
+
+impl TextChunker {
+    /// Create a chunker producing chunks of at most `chunk_size` bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero
+    pub fn new(chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "Chunk size must be positive");
+        Self {
+            chunk_size,
+            overlap: 0,
+        }
+    }
+
+    /// Set the number of bytes repeated at the start of each chunk
+    ///
+    /// # Panics
+    ///
+    /// Panics if `overlap` is not smaller than the chunk size
+    pub fn with_overlap(mut self, overlap: usize) -> Self {
+        assert!(
+            overlap < self.chunk_size,
+            "Chunk overlap must be smaller than the chunk size"
+        );
+        self.overlap = overlap;
+        self
+    }
+
+    /// Split text into chunks
+    pub fn split(&self, text: &str) -> Vec<TextChunk> {
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        let mut overlap = 0;
+
+        while start < text.len() {
+            let mut end = floor_char_boundary(text, start + self.chunk_size);
+            if end < text.len() {
+                // Prefer to break after the last whitespace in the window
+                if let Some((offset, ws)) = text[start + overlap..end]
+                    .char_indices()
+                    .rfind(|(_, c)| c.is_whitespace())
+                {
+                    end = start + overlap + offset + ws.len_utf8();
+                }
+            }
+            if end == start {
+                // A single character larger than the chunk size
+                end = start + text[start..].chars().next().map_or(1, char::len_utf8);
+            }
+
+            chunks.push(TextChunk {
+                index: chunks.len(),
+                total: 0,
+                overlap,
+                text: text[start..end].to_string(),
+            });
+
+            if end == text.len() {
+                break;
+            }
+
+            let next = floor_char_boundary(text, end - self.overlap.min(end));
+            (start, overlap) = if next > start {
+                (next, end - next)
+            } else {
+                (end, 0)
+            };
+        }
+
+        let total = chunks.len();
+        for chunk in &mut chunks {
+            chunk.total = total;
+        }
+        chunks
+    }
+
+    /// Split text into the text parts of a single message
+    ///
+    /// The overlap of each part is recorded in the message's chunk metadata.
+    pub fn message(&self, role: Role, text: &str) -> Message {
+        let chunks = self.split(text);
+        let overlaps: Vec<usize> = chunks.iter().map(|chunk| chunk.overlap).collect();
+        let mut message = Message::new(role, "");
+        message.parts = chunks
+            .into_iter()
+            .map(|chunk| MessagePart::text(chunk.text))
+            .collect();
+        if message.parts.is_empty() {
+            message.parts.push(MessagePart::text(""));
+        }
+        message.with_metadata(CHUNK_METADATA_KEY, json!({ "overlaps": overlaps }))
+    }
+
+    /// Split text into sequential messages within a context
+    ///
+    /// Each message carries its position and overlap in its chunk metadata.
+    pub fn messages(&self, role: Role, context_id: impl Into<String>, text: &str) -> Vec<Message> {
+        let context_id = context_id.into();
+        self.split(text)
+            .into_iter()
+            .map(|chunk| {
+                let mut message = Message::new(role, chunk.text);
+                message.context_id = Some(context_id.clone());
+                message.with_metadata(
+                    CHUNK_METADATA_KEY,
+                    json!({
+                        "index": chunk.index,
+                        "total": chunk.total,
+                        "overlap": chunk.overlap,
+                    }),
+                )
+            })
+            .collect()
+    }
+
+    /// Reassemble text split by [`TextChunker::messages`] or [`TextChunker::message`]
+    ///
+    /// Sequential messages may be given in any order. A single message without chunk
+    /// metadata has its text parts concatenated.
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Validation` if chunks are missing, duplicated, or carry
+    /// inconsistent metadata
+    pub fn reassemble(messages: &[Message]) -> Result<String, A2AError> {
+        if let [message] = messages {
+            if chunk_metadata(message).is_none_or(|chunk| chunk.get("overlaps").is_some()) {
+                return reassemble_parts(message);
+            }
+        }
+
+        let mut chunks = messages
+            .iter()
+            .map(|message| {
+                let chunk = chunk_metadata(message)
+                    .ok_or_else(|| A2AError::Validation("Message has no chunk metadata".into()))?;
+                let field = |name: &str| {
+                    chunk
+                        .get(name)
+                        .and_then(Value::as_u64)
+                        .map(|value| value as usize)
+                        .ok_or_else(|| {
+                            A2AError::Validation(format!("Chunk metadata is missing '{}'", name))
+                        })
+                };
+                Ok((field("index")?, field("total")?, field("overlap")?, message))
+            })
+            .collect::<Result<Vec<_>, A2AError>>()?;
+        chunks.sort_by_key(|(index, ..)| *index);
+
+        let mut text = String::new();
+        for (position, (index, total, overlap, message)) in chunks.iter().enumerate() {
+            if *index != position || *total != messages.len() {
+                return Err(A2AError::Validation(format!(
+                    "Expected chunk {} of {}, got chunk {} of {}",
+                    position + 1,
+                    messages.len(),
+                    index + 1,
+                    total
+                )));
+            }
+            text.push_str(strip_overlap(&text_of(message), *overlap)?);
+        }
+        Ok(text)
+    }
+}
+
+fn reassemble_parts(message: &Message) -> Result<String, A2AError> {
+    let overlaps = chunk_metadata(message)
+        .and_then(|chunk| chunk.get("overlaps"))
+        .and_then(Value::as_array);
+
+    let mut text = String::new();
+    for (index, part) in message.parts.iter().enumerate() {
+        if let MessagePart::Text { text: part } = part {
+            let overlap = overlaps
+                .and_then(|overlaps| overlaps.get(index))
+                .and_then(Value::as_u64)
+                .unwrap_or(0) as usize;
+            text.push_str(strip_overlap(part, overlap)?);
+        }
+    }
+    Ok(text)
+}
+
+fn chunk_metadata(message: &Message) -> Option<&Value> {
+    message.metadata.as_ref()?.get(CHUNK_METADATA_KEY)
+}
+
+fn text_of(message: &Message) -> String {
+    message
+        .parts
+        .iter()
+        .filter_map(|part| match part {
+            MessagePart::Text { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn strip_overlap(text: &str, overlap: usize) -> Result<&str, A2AError> {
+    text.get(overlap..).ok_or_else(|| {
+        A2AError::Validation(format!(
+            "Chunk overlap of {} bytes is invalid for a chunk of {} bytes",
+            overlap,
+            text.len()
+        ))
+    })
+}
+
+/// Largest character boundary of `text` not after `index`
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    if index >= text.len() {
+        return text.len();
+    }
+    (0..=index)
+        .rev()
+        .find(|&i| text.is_char_boundary(i))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str =
+        "The quick brown fox jumps over the lazy dog. Pack my box with five dozen liquor jugs.";
+
+    #[test]
+    fn test_split_respects_size() {
+        let chunks = TextChunker::new(16).split(TEXT);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|chunk| chunk.text.len() <= 16));
+        assert!(chunks.iter().all(|chunk| chunk.total == chunks.len()));
+        assert_eq!(chunks.concat_text(), TEXT);
+
+        // Multi-byte characters are never split
+        let chunks = TextChunker::new(3).split("héllo wörld");
+        assert_eq!(chunks.concat_text(), "héllo wörld");
+    }
+
+    #[test]
+    fn test_messages_roundtrip_with_overlap() {
+        let chunker = TextChunker::new(20).with_overlap(6);
+        let mut messages = chunker.messages(Role::User, "ctx-1", TEXT);
+        assert!(messages.len() > 1);
+        assert!(messages
+            .iter()
+            .all(|message| message.context_id.as_deref() == Some("ctx-1")));
+        assert!(messages[1..]
+            .iter()
+            .any(|message| chunk_metadata(message).unwrap()["overlap"] != 0));
+
+        messages.reverse();
+        assert_eq!(TextChunker::reassemble(&messages).unwrap(), TEXT);
+
+        messages.remove(1);
+        assert!(TextChunker::reassemble(&messages).is_err());
+    }
+
+    #[test]
+    fn test_message_parts_roundtrip() {
+        let message = TextChunker::new(12)
+            .with_overlap(4)
+            .message(Role::Agent, TEXT);
+        assert!(message.parts.len() > 1);
+        assert_eq!(
+            TextChunker::reassemble(std::slice::from_ref(&message)).unwrap(),
+            TEXT
+        );
+
+        let plain = Message::user("Hello").with_part(MessagePart::text(", world"));
+        assert_eq!(TextChunker::reassemble(&[plain]).unwrap(), "Hello, world");
+    }
+
+    trait ConcatText {
+        fn concat_text(&self) -> String;
+    }
+
+    impl ConcatText for Vec<TextChunk> {
+        fn concat_text(&self) -> String {
+            self.iter()
+                .map(|chunk| &chunk.text[chunk.overlap..])
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Protocol constants: versions, header names, media types, and well-known paths
+
+/// A2A protocol version sent in the [`A2A_VERSION`](headers::A2A_VERSION) header
+pub const PROTOCOL_VERSION: &str = "1.0";
+
+/// Path of the agent card relative to an agent's origin
+pub const AGENT_CARD_PATH: &str = "/.well-known/agent-card.json";
+
+/// WebSocket subprotocol for JSON-RPC messages in JSON text frames
+pub const WEBSOCKET_SUBPROTOCOL: &str = "a2a.v1.json";
+
+/// HTTP header names
+pub mod headers {
+    /// Protocol version of a request
+    pub const A2A_VERSION: &str = "A2A-Version";
+
+    /// Media types the client accepts
+    pub const ACCEPT: &str = "Accept";
+
+    /// Languages the client prefers for text, in order of preference
+    pub const ACCEPT_LANGUAGE: &str = "Accept-Language";
+
+    /// Media type of the request body
+    pub const CONTENT_TYPE: &str = "Content-Type";
+
+    /// Compression applied to the request body
+    pub const CONTENT_ENCODING: &str = "Content-Encoding";
+
+    /// Credentials for bearer and basic authentication
+    pub const AUTHORIZATION: &str = "Authorization";
+
+    /// Per-request correlation ID
+    pub const CORRELATION_ID: &str = "X-Correlation-Id";
+
+    /// Nonce of a push notification delivery
+    pub const PUSH_NONCE: &str = "X-A2A-Nonce";
+
+    /// Send time of a push notification delivery, in seconds since the Unix epoch
+    pub const PUSH_TIMESTAMP: &str = "X-A2A-Timestamp";
+
+    /// Token a push notification receiver registered with its webhook
+    pub const NOTIFICATION_TOKEN: &str = "X-A2A-Notification-Token";
+
+    /// Comma-separated URIs of the extensions a request activates
+    pub const EXTENSIONS: &str = "X-A2A-Extensions";
+
+    /// ID of the dictionary a compressed request body was encoded with
+    pub const COMPRESSION_DICTIONARY: &str = "X-A2A-Compression-Dictionary";
+
+    /// Headers carrying credentials, in lowercase (compare case-insensitively)
+    pub const SENSITIVE: &[&str] = &[
+        "authorization",
+        "proxy-authorization",
+        "cookie",
+        "set-cookie",
+        "x-api-key",
+    ];
+}
+
+/// URIs of protocol extensions
+pub mod extensions {
+    /// Agents accept task IDs chosen by the client in `SendMessage` and create at most
+    /// one task per ID
+    pub const CLIENT_TASK_IDS: &str = "urn:a2a:ext:client-task-ids:v1";
+
+    /// Agents accept zstd-compressed payloads encoded with one of the dictionaries
+    /// listed in the extension's `dictionaries` parameter, and compress their
+    /// responses with the same dictionary
+    pub const ZSTD_DICTIONARY: &str = "urn:a2a:ext:zstd-dictionary:v1";
+}
+
+/// Media types
+pub mod media_types {
+    /// A2A JSON payloads
+    pub const A2A_JSON: &str = "application/a2a+json";
+
+    /// Plain JSON documents, such as agent cards and key sets
+    pub const JSON: &str = "application/json";
+
+    /// Server-Sent Events streams
+    pub const EVENT_STREAM: &str = "text/event-stream";
+
+    /// CBOR payloads
+    pub const CBOR: &str = "application/cbor";
+
+    /// MessagePack payloads
+    pub const MSGPACK: &str = "application/msgpack";
+}
@@ -0,0 +1,409 @@
+//! Endpoint path templates for HTTP-based bindings
+
+use serde::{Deserialize, Serialize};
+use url::{form_urlencoded, Url};
+
+use super::{agent::AgentCard, consts::AGENT_CARD_PATH, operation::A2AOperation};
+
+/// Flavour of the HTTP binding used to map operations to routes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpBinding {
+    /// Resource-style routes (`POST /v1/tasks`, `PUT /v1/tasks/{id}`)
+    #[default]
+    Legacy,
+
+    /// Official A2A REST binding (`POST /v1/message:send`, `POST /v1/tasks/{id}:cancel`)
+    Rest,
+}
+
+/// Path templates used to build the HTTP endpoint of each A2A operation
+///
+/// Templates may contain an `{id}` placeholder which is replaced with the task ID of the
+/// operation. The `prefix` is prepended to every path except the agent card path, which
+/// is always resolved from the origin as required by the well-known URI convention.
+///
+/// # Example
+///
+/// ```rust
+/// use tower_a2a::protocol::{A2AOperation, EndpointMap};
+///
+/// let endpoints = EndpointMap::new().with_prefix("/api/a2a");
+/// let op = A2AOperation::GetTask { task_id: "task-123".into() };
+///
+/// assert_eq!(endpoints.resolve(&op), "/api/a2a/v1/tasks/task-123");
+/// ```
+///
+/// Maps can be read from configuration; omitted templates keep their defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EndpointMap {
+    /// HTTP binding flavour, which decides the verbs and query parameters
+    pub binding: HttpBinding,
+
+    /// Prefix prepended to every operation path (e.g., "/api/a2a")
+    pub prefix: String,
+
+    /// Template for sending a message that creates a new task
+    pub send_message: String,
+
+    /// Template for streaming sends, if the binding uses a dedicated path
+    pub stream_message: Option<String>,
+
+    /// Template for sending a message that continues an existing task
+    pub continue_task: String,
+
+    /// Template for fetching a task
+    pub get_task: String,
+
+    /// Template for listing tasks
+    pub list_tasks: String,
+
+    /// Template for cancelling a task
+    pub cancel_task: String,
+
+    /// Template for subscribing to task updates
+    pub subscribe_task: String,
+
+    /// Template for registering a webhook
+    pub register_webhook: String,
+
+    /// Template for setting and listing the push notification configurations of a task
+    pub push_notification_configs: String,
+
+    /// Template for getting and deleting a push notification configuration, with a
+    /// `{configId}` placeholder for its ID
+    pub push_notification_config: String,
+
+    /// Path of the agent card (not affected by `prefix`)
+    pub agent_card: String,
+}
+
+impl EndpointMap {
+    /// Create an endpoint map with the default paths
+    pub fn new() -> Self {
+        Self {
+            binding: HttpBinding::Legacy,
+            prefix: String::new(),
+            send_message: "/v1/tasks".to_string(),
+            stream_message: None,
+            continue_task: "/v1/tasks/{id}".to_string(),
+            get_task: "/v1/tasks/{id}".to_string(),
+            list_tasks: "/v1/tasks".to_string(),
+            cancel_task: "/v1/tasks/{id}:cancel".to_string(),
+            subscribe_task: "/v1/tasks/{id}:stream".to_string(),
+            register_webhook: "/v1/webhooks".to_string(),
+            push_notification_configs: "/v1/tasks/{id}/pushNotificationConfigs".to_string(),
+            push_notification_config: "/v1/tasks/{id}/pushNotificationConfigs/{configId}"
+                .to_string(),
+            agent_card: AGENT_CARD_PATH.to_string(),
+        }
+    }
+
+    /// Create an endpoint map following the official A2A REST binding
+    pub fn rest() -> Self {
+        Self {
+            binding: HttpBinding::Rest,
+            prefix: String::new(),
+            send_message: "/v1/message:send".to_string(),
+            stream_message: Some("/v1/message:stream".to_string()),
+            continue_task: "/v1/message:send".to_string(),
+            get_task: "/v1/tasks/{id}".to_string(),
+            list_tasks: "/v1/tasks".to_string(),
+            cancel_task: "/v1/tasks/{id}:cancel".to_string(),
+            subscribe_task: "/v1/tasks/{id}:subscribe".to_string(),
+            register_webhook: "/v1/webhooks".to_string(),
+            push_notification_configs: "/v1/tasks/{id}/pushNotificationConfigs".to_string(),
+            push_notification_config: "/v1/tasks/{id}/pushNotificationConfigs/{configId}"
+                .to_string(),
+            agent_card: AGENT_CARD_PATH.to_string(),
+        }
+    }
+
+    /// Derive an endpoint map from the endpoints advertised in an Agent Card
+    ///
+    /// The path of the preferred endpoint (or, failing that, the first HTTP endpoint by
+    /// name) is used as the prefix. Endpoints of type `rest` or `http+rest` select the
+    /// [`HttpBinding::Rest`] routes. Returns `None` if the card declares no usable HTTP
+    /// endpoint.
+    pub fn from_agent_card(card: &AgentCard) -> Option<Self> {
+        let mut candidates: Vec<_> = card
+            .endpoints
+            .iter()
+            .filter(|(_, config)| {
+                config.endpoint_type.starts_with("http") || config.endpoint_type == "rest"
+            })
+            .collect();
+        candidates.sort_by(|(a_name, a), (b_name, b)| {
+            b.preferred.cmp(&a.preferred).then(a_name.cmp(b_name))
+        });
+
+        let (_, config) = candidates.first()?;
+        let url = Url::parse(&config.url).ok()?;
+
+        let endpoints = match config.endpoint_type.as_str() {
+            "rest" | "http+rest" => Self::rest(),
+            _ => Self::new(),
+        };
+
+        Some(endpoints.with_prefix(url.path()))
+    }
+
+    /// Set the prefix prepended to every operation path
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        let prefix = prefix.into();
+        self.prefix = prefix.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Resolve the endpoint path for an operation
+    pub fn resolve(&self, operation: &A2AOperation) -> String {
+        if let (A2AOperation::SendMessage { stream: true, .. }, Some(template)) =
+            (operation, &self.stream_message)
+        {
+            return format!("{}{}", self.prefix, template);
+        }
+
+        let (template, task_id) = match operation {
+            A2AOperation::SendMessage {
+                task_id: Some(id), ..
+            } => (&self.continue_task, Some(id)),
+            A2AOperation::SendMessage { task_id: None, .. } => (&self.send_message, None),
+            A2AOperation::GetTask { task_id } => (&self.get_task, Some(task_id)),
+            A2AOperation::ListTasks { .. } => (&self.list_tasks, None),
+            A2AOperation::CancelTask { task_id, .. } => (&self.cancel_task, Some(task_id)),
+            A2AOperation::DiscoverAgent => return self.agent_card.clone(),
+            A2AOperation::SubscribeTask { task_id } | A2AOperation::ResubscribeTask { task_id } => {
+                (&self.subscribe_task, Some(task_id))
+            }
+            A2AOperation::RegisterWebhook { .. } => (&self.register_webhook, None),
+            A2AOperation::SetPushNotificationConfig { task_id, .. }
+            | A2AOperation::ListPushNotificationConfigs { task_id } => {
+                (&self.push_notification_configs, Some(task_id))
+            }
+            A2AOperation::GetPushNotificationConfig { task_id, .. }
+            | A2AOperation::DeletePushNotificationConfig { task_id, .. } => {
+                (&self.push_notification_config, Some(task_id))
+            }
+        };
+
+        let path = match task_id {
+            Some(id) => template.replace("{id}", id),
+            None => template.clone(),
+        };
+        let path = match operation {
+            A2AOperation::GetPushNotificationConfig { config_id, .. }
+            | A2AOperation::DeletePushNotificationConfig { config_id, .. } => {
+                path.replace("{configId}", config_id)
+            }
+            _ => path,
+        };
+
+        format!("{}{}", self.prefix, path)
+    }
+
+    /// Get the HTTP method for an operation under this binding
+    pub fn method(&self, operation: &A2AOperation) -> &'static str {
+        match self.binding {
+            HttpBinding::Legacy => operation.method(),
+            HttpBinding::Rest => match operation {
+                A2AOperation::SendMessage { .. } => "POST",
+                A2AOperation::GetTask { .. } => "GET",
+                A2AOperation::ListTasks { .. } => "GET",
+                A2AOperation::CancelTask { .. } => "POST",
+                A2AOperation::DiscoverAgent => "GET",
+                A2AOperation::SubscribeTask { .. } | A2AOperation::ResubscribeTask { .. } => "GET",
+                A2AOperation::RegisterWebhook { .. } => "POST",
+                A2AOperation::SetPushNotificationConfig { .. } => "POST",
+                A2AOperation::GetPushNotificationConfig { .. } => "GET",
+                A2AOperation::ListPushNotificationConfigs { .. } => "GET",
+                A2AOperation::DeletePushNotificationConfig { .. } => "DELETE",
+            },
+        }
+    }
+
+    /// Get the encoded query string for an operation, if any
+    ///
+    /// Both bindings send the filters and page token of `ListTasks` in the URL, as
+    /// its `GET` request has no body. Query parameter names follow the spec's
+    /// snake_case convention (`status`, `page_size`, `page_token`).
+    pub fn query(&self, operation: &A2AOperation) -> Option<String> {
+        let A2AOperation::ListTasks {
+            status,
+            limit,
+            next_token,
+            ..
+        } = operation
+        else {
+            return None;
+        };
+
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        if let Some(status) = status.and_then(|s| serde_json::to_value(s).ok()) {
+            query.append_pair("status", status.as_str().unwrap_or_default());
+        }
+        if let Some(limit) = limit {
+            query.append_pair("page_size", &limit.to_string());
+        }
+        if let Some(token) = next_token {
+            query.append_pair("page_token", token);
+        }
+
+        let query = query.finish();
+        (!query.is_empty()).then_some(query)
+    }
+}
+
+impl Default for EndpointMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::protocol::{
+        agent::{AgentCapabilities, EndpointConfig},
+        message::Message,
+        task::TaskStatus,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_default_paths() {
+        let endpoints = EndpointMap::default();
+
+        let op = A2AOperation::SendMessage {
+            message: Message::user("test"),
+            stream: false,
+            context_id: None,
+            task_id: Some("task-123".to_string()),
+        };
+        assert_eq!(endpoints.resolve(&op), "/v1/tasks/task-123");
+
+        let op = A2AOperation::CancelTask {
+            task_id: "task-123".to_string(),
+            reason: None,
+        };
+        assert_eq!(endpoints.resolve(&op), "/v1/tasks/task-123:cancel");
+    }
+
+    #[test]
+    fn test_prefix_and_templates() {
+        let mut endpoints = EndpointMap::new().with_prefix("/api/a2a/");
+        endpoints.get_task = "/tasks/{id}/details".to_string();
+
+        let op = A2AOperation::GetTask {
+            task_id: "task-123".to_string(),
+        };
+        assert_eq!(endpoints.resolve(&op), "/api/a2a/tasks/task-123/details");
+
+        // The agent card is always served from the origin
+        assert_eq!(
+            endpoints.resolve(&A2AOperation::DiscoverAgent),
+            "/.well-known/agent-card.json"
+        );
+    }
+
+    #[test]
+    fn test_from_agent_card() {
+        let card = AgentCard::new("Test", "Test agent", AgentCapabilities::default())
+            .with_endpoint(
+                "grpc",
+                EndpointConfig::new("https://example.com:50051", "grpc"),
+            )
+            .with_endpoint(
+                "rest",
+                EndpointConfig::new("https://example.com/api/a2a", "http+json").preferred(),
+            );
+
+        let endpoints = EndpointMap::from_agent_card(&card).unwrap();
+        assert_eq!(endpoints.prefix, "/api/a2a");
+
+        let card = AgentCard::new("Test", "Test agent", AgentCapabilities::default());
+        assert!(EndpointMap::from_agent_card(&card).is_none());
+    }
+
+    #[test]
+    fn test_rest_binding_routes() {
+        let endpoints = EndpointMap::rest();
+
+        let op = A2AOperation::SendMessage {
+            message: Message::user("test"),
+            stream: false,
+            context_id: None,
+            task_id: Some("task-123".to_string()),
+        };
+        assert_eq!(endpoints.resolve(&op), "/v1/message:send");
+        assert_eq!(endpoints.method(&op), "POST");
+
+        let op = A2AOperation::SendMessage {
+            message: Message::user("test"),
+            stream: true,
+            context_id: None,
+            task_id: None,
+        };
+        assert_eq!(endpoints.resolve(&op), "/v1/message:stream");
+
+        let op = A2AOperation::SubscribeTask {
+            task_id: "task-123".to_string(),
+        };
+        assert_eq!(endpoints.resolve(&op), "/v1/tasks/task-123:subscribe");
+        assert_eq!(endpoints.method(&op), "GET");
+    }
+
+    #[test]
+    fn test_push_notification_config_routes() {
+        let endpoints = EndpointMap::new().with_prefix("/a2a");
+
+        let op = A2AOperation::ListPushNotificationConfigs {
+            task_id: "task-123".to_string(),
+        };
+        assert_eq!(
+            endpoints.resolve(&op),
+            "/a2a/v1/tasks/task-123/pushNotificationConfigs"
+        );
+        assert_eq!(endpoints.method(&op), "GET");
+
+        let op = A2AOperation::DeletePushNotificationConfig {
+            task_id: "task-123".to_string(),
+            config_id: "hook-1".to_string(),
+        };
+        assert_eq!(
+            endpoints.resolve(&op),
+            "/a2a/v1/tasks/task-123/pushNotificationConfigs/hook-1"
+        );
+        assert_eq!(EndpointMap::rest().method(&op), "DELETE");
+    }
+
+    #[test]
+    fn test_list_tasks_query() {
+        let op = A2AOperation::ListTasks {
+            status: Some(TaskStatus::InputRequired),
+            limit: Some(10),
+            offset: None,
+            next_token: Some("abc 123".to_string()),
+        };
+
+        assert_eq!(
+            EndpointMap::rest().query(&op).as_deref(),
+            Some("status=input-required&page_size=10&page_token=abc+123")
+        );
+        assert_eq!(
+            EndpointMap::new().query(&op),
+            EndpointMap::rest().query(&op)
+        );
+        assert_eq!(EndpointMap::new().query(&A2AOperation::DiscoverAgent), None);
+    }
+
+    #[test]
+    fn test_from_agent_card_rest() {
+        let card = AgentCard::new("Test", "Test agent", AgentCapabilities::default())
+            .with_endpoint("rest", EndpointConfig::new("https://example.com", "rest"));
+
+        let endpoints = EndpointMap::from_agent_card(&card).unwrap();
+        assert_eq!(endpoints.binding, HttpBinding::Rest);
+        assert_eq!(endpoints.prefix, "");
+    }
+}
@@ -49,16 +49,133 @@ pub enum A2AError {
     #[error("Rate limit exceeded")]
     RateLimitExceeded,
 
+    /// The agent card declares no endpoint for the client's binding
+    #[error(
+        "Agent does not support the {} binding; it supports {}",
+        .client.join(" or "),
+        .supported.join(", ")
+    )]
+    BindingMismatch {
+        /// Endpoint types the client can talk to
+        client: Vec<String>,
+        /// Endpoint types declared by the agent card
+        supported: Vec<String>,
+    },
+
+    /// Error answered by an agent speaking JSON-RPC
+    #[error("JSON-RPC error {code}: {message}")]
+    JsonRpc {
+        /// Error code, e.g. `-32001` for an unknown task
+        code: i64,
+        /// Error message
+        message: String,
+        /// Additional information about the error
+        data: Option<serde_json::Value>,
+    },
+
     /// Generic error with custom message
     #[error("{0}")]
     Other(String),
 }
 
+impl A2AError {
+    /// Check whether the error is transient, so the request may succeed if repeated
+    ///
+    /// Transport failures, timeouts, and rate limiting are considered transient.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            A2AError::Transport(_) | A2AError::Timeout | A2AError::RateLimitExceeded
+        )
+    }
+}
+
+/// Well-known task error codes
+///
+/// Codes are parsed leniently: case is ignored and underscores are treated as dashes, so
+/// `INVALID_INPUT` and `invalid-input` are equivalent. Unknown codes are kept verbatim in
+/// [`TaskErrorCode::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TaskErrorCode {
+    /// The input was malformed or failed validation
+    InvalidInput,
+
+    /// The content type of the input is not supported
+    UnsupportedContent,
+
+    /// The agent is rate limiting requests
+    RateLimited,
+
+    /// The agent failed internally
+    Internal,
+
+    /// Processing did not finish in time
+    Timeout,
+
+    /// The content was blocked by a content filter
+    ContentFiltered,
+
+    /// Any other code
+    Other(String),
+}
+
+impl TaskErrorCode {
+    /// Get the canonical string representation of the code
+    pub fn as_str(&self) -> &str {
+        match self {
+            TaskErrorCode::InvalidInput => "invalid-input",
+            TaskErrorCode::UnsupportedContent => "unsupported-content",
+            TaskErrorCode::RateLimited => "rate-limited",
+            TaskErrorCode::Internal => "internal",
+            TaskErrorCode::Timeout => "timeout",
+            TaskErrorCode::ContentFiltered => "content-filtered",
+            TaskErrorCode::Other(code) => code,
+        }
+    }
+
+    /// Check whether a task failing with this code may succeed if resubmitted
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            TaskErrorCode::RateLimited | TaskErrorCode::Internal | TaskErrorCode::Timeout
+        )
+    }
+}
+
+impl From<&str> for TaskErrorCode {
+    fn from(code: &str) -> Self {
+        match code.to_ascii_lowercase().replace('_', "-").as_str() {
+            "invalid-input" => TaskErrorCode::InvalidInput,
+            "unsupported-content" => TaskErrorCode::UnsupportedContent,
+            "rate-limited" => TaskErrorCode::RateLimited,
+            "internal" => TaskErrorCode::Internal,
+            "timeout" => TaskErrorCode::Timeout,
+            "content-filtered" => TaskErrorCode::ContentFiltered,
+            _ => TaskErrorCode::Other(code.to_string()),
+        }
+    }
+}
+
+impl From<TaskErrorCode> for String {
+    fn from(code: TaskErrorCode) -> Self {
+        match code {
+            TaskErrorCode::Other(code) => code,
+            code => code.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for TaskErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Task-specific error with structured information
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Error)]
 #[error("{message}")]
 pub struct TaskError {
-    /// Error code (e.g., "INVALID_INPUT", "PROCESSING_FAILED")
+    /// Error code (e.g., "invalid-input"); see [`TaskErrorCode`] for well-known values
     pub code: String,
 
     /// Human-readable error message
@@ -84,6 +201,16 @@ impl TaskError {
         self.details = Some(details);
         self
     }
+
+    /// Get the error code as a [`TaskErrorCode`]
+    pub fn error_code(&self) -> TaskErrorCode {
+        TaskErrorCode::from(self.code.as_str())
+    }
+
+    /// Check whether the failed task may succeed if resubmitted
+    pub fn is_retryable(&self) -> bool {
+        self.error_code().is_retryable()
+    }
 }
 
 /// Result type alias for A2A operations
@@ -112,3 +239,26 @@ impl From<String> for A2AError {
         A2AError::Other(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_error_codes() {
+        let error = TaskError::new("RATE_LIMITED", "Slow down");
+        assert_eq!(error.error_code(), TaskErrorCode::RateLimited);
+        assert!(error.is_retryable());
+
+        let error = TaskError::new(TaskErrorCode::ContentFiltered, "Blocked");
+        assert_eq!(error.code, "content-filtered");
+        assert!(!error.is_retryable());
+
+        let error = TaskError::new("PROCESSING_FAILED", "Oops");
+        assert_eq!(
+            error.error_code(),
+            TaskErrorCode::Other("PROCESSING_FAILED".to_string())
+        );
+        assert_eq!(error.error_code().to_string(), "PROCESSING_FAILED");
+    }
+}
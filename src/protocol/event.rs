@@ -0,0 +1,350 @@
+//! Typed events of streamed responses
+//!
+//! Streams yield [`SseEvent`]s whose payload is raw JSON. [`StreamEvent`] decodes an
+//! event into the protocol type its kind names, so consumers match on fields instead
+//! of looking up keys:
+//!
+//! ```rust
+//! use tower_a2a::{
+//!     codec::SseEvent,
+//!     protocol::{event::StreamEvent, TaskStatus},
+//! };
+//!
+//! let event = SseEvent {
+//!     kind: "status-update".to_string(),
+//!     payload: serde_json::json!({
+//!         "taskId": "task-123",
+//!         "status": {"state": "working"},
+//!         "final": false
+//!     }),
+//!     final_event: false,
+//!     id: None,
+//! };
+//!
+//! match StreamEvent::try_from(event).unwrap() {
+//!     StreamEvent::StatusUpdate(update) => assert_eq!(update.status, TaskStatus::Working),
+//!     other => panic!("unexpected event: {:?}", other),
+//! }
+//! ```
+//!
+//! Heartbeats and retry hints are not protocol events; skip them with
+//! [`SseEvent::is_control`] before converting.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    codec::SseEvent,
+    protocol::{error::A2AError, message::Message, task::Task, task::TaskStatus, Artifact},
+};
+
+/// Change of a task's status, the payload of a `status-update` event
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "StatusUpdatePayload")]
+pub struct TaskStatusUpdateEvent {
+    /// ID of the task
+    pub task_id: String,
+
+    /// Context of the task, if any
+    pub context_id: Option<String>,
+
+    /// New status of the task
+    pub status: TaskStatus,
+
+    /// Message from the agent accompanying the status, e.g. a question when input is
+    /// required
+    pub message: Option<Message>,
+
+    /// When the status changed, if the agent says
+    pub timestamp: Option<DateTime<Utc>>,
+
+    /// Whether this is the last event of the stream
+    pub is_final: bool,
+
+    /// Extension metadata of the event
+    pub metadata: Option<Value>,
+}
+
+/// Status update as sent by agents
+///
+/// The state is nested in a `status` object per the specification, but some agents
+/// send it, and the message, at the top level.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StatusUpdatePayload {
+    task_id: String,
+    #[serde(default)]
+    context_id: Option<String>,
+    #[serde(default)]
+    status: Option<StatusPayload>,
+    #[serde(default)]
+    state: Option<TaskStatus>,
+    #[serde(default)]
+    message: Option<Message>,
+    #[serde(default, rename = "final")]
+    is_final: bool,
+    #[serde(default)]
+    metadata: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct StatusPayload {
+    state: TaskStatus,
+    #[serde(default)]
+    message: Option<Message>,
+    #[serde(default)]
+    timestamp: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<StatusUpdatePayload> for TaskStatusUpdateEvent {
+    type Error = String;
+
+    fn try_from(payload: StatusUpdatePayload) -> Result<Self, String> {
+        let (status, message, timestamp) = match (payload.status, payload.state) {
+            (Some(status), _) => (
+                status.state,
+                status.message.or(payload.message),
+                status.timestamp,
+            ),
+            (None, Some(state)) => (state, payload.message, None),
+            (None, None) => return Err("missing field `status`".to_string()),
+        };
+        Ok(Self {
+            task_id: payload.task_id,
+            context_id: payload.context_id,
+            status,
+            message,
+            timestamp,
+            is_final: payload.is_final,
+            metadata: payload.metadata,
+        })
+    }
+}
+
+/// New or extended artifact of a task, the payload of an `artifact-update` event
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskArtifactUpdateEvent {
+    /// ID of the task
+    pub task_id: String,
+
+    /// Context of the task, if any
+    #[serde(default)]
+    pub context_id: Option<String>,
+
+    /// The artifact, or the chunk of it this event adds
+    pub artifact: Artifact,
+
+    /// Whether the parts are appended to the artifact of the same ID sent earlier,
+    /// rather than replacing it
+    #[serde(default)]
+    pub append: bool,
+
+    /// Whether this is the last chunk of the artifact
+    #[serde(default)]
+    pub last_chunk: bool,
+
+    /// Whether this is the last event of the stream
+    #[serde(default, rename = "final")]
+    pub is_final: bool,
+
+    /// Extension metadata of the event
+    #[serde(default)]
+    pub metadata: Option<Value>,
+}
+
+/// Protocol event of a streamed response
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    /// Snapshot of the task, usually the first event of a stream
+    Task(Task),
+
+    /// Message from the agent, answering without a task
+    Message(Message),
+
+    /// Change of the task's status
+    StatusUpdate(TaskStatusUpdateEvent),
+
+    /// New or extended artifact
+    ArtifactUpdate(TaskArtifactUpdateEvent),
+}
+
+impl StreamEvent {
+    /// Get the ID of the task the event belongs to, if any
+    pub fn task_id(&self) -> Option<&str> {
+        match self {
+            Self::Task(task) => Some(&task.id),
+            Self::Message(message) => message.task_id.as_deref(),
+            Self::StatusUpdate(update) => Some(&update.task_id),
+            Self::ArtifactUpdate(update) => Some(&update.task_id),
+        }
+    }
+
+    /// Check whether the event ends the stream, i.e. is marked final or reports a
+    /// terminal status
+    pub fn is_final(&self) -> bool {
+        match self {
+            Self::Task(task) => task.status.is_terminal(),
+            Self::Message(_) => true,
+            Self::StatusUpdate(update) => update.is_final || update.status.is_terminal(),
+            Self::ArtifactUpdate(update) => update.is_final,
+        }
+    }
+}
+
+impl TryFrom<SseEvent> for StreamEvent {
+    type Error = A2AError;
+
+    /// Decode an event by its kind
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Protocol` if the kind is not a protocol event kind, e.g. a
+    /// heartbeat, or the payload does not match it
+    fn try_from(event: SseEvent) -> Result<Self, A2AError> {
+        let mut payload = event.payload;
+        if let Value::Object(fields) = &mut payload {
+            if event.kind == "task" || event.kind == "message" {
+                fields.remove("kind");
+                fields.remove("final");
+            }
+        }
+
+        let invalid = |e: serde_json::Error| {
+            A2AError::Protocol(format!("Invalid '{}' event: {}", event.kind, e))
+        };
+        match event.kind.as_str() {
+            "task" => serde_json::from_value(payload).map(Self::Task),
+            "message" => serde_json::from_value(payload).map(Self::Message),
+            "status-update" => serde_json::from_value(payload).map(Self::StatusUpdate),
+            "artifact-update" => serde_json::from_value(payload).map(Self::ArtifactUpdate),
+            kind => {
+                return Err(A2AError::Protocol(format!(
+                    "Unknown stream event kind '{}'",
+                    kind
+                )))
+            }
+        }
+        .map_err(invalid)
+    }
+}
+
+impl TryFrom<SseEvent> for TaskStatusUpdateEvent {
+    type Error = A2AError;
+
+    fn try_from(event: SseEvent) -> Result<Self, A2AError> {
+        match StreamEvent::try_from(event)? {
+            StreamEvent::StatusUpdate(update) => Ok(update),
+            other => Err(A2AError::Protocol(format!(
+                "Expected a status update, got {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+impl TryFrom<SseEvent> for TaskArtifactUpdateEvent {
+    type Error = A2AError;
+
+    fn try_from(event: SseEvent) -> Result<Self, A2AError> {
+        match StreamEvent::try_from(event)? {
+            StreamEvent::ArtifactUpdate(update) => Ok(update),
+            other => Err(A2AError::Protocol(format!(
+                "Expected an artifact update, got {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::protocol::message::MessagePart;
+
+    fn event(kind: &str, payload: Value) -> SseEvent {
+        SseEvent {
+            kind: kind.to_string(),
+            payload,
+            final_event: false,
+            id: None,
+        }
+    }
+
+    #[test]
+    fn test_status_update_event() {
+        let update = TaskStatusUpdateEvent::try_from(event(
+            "status-update",
+            json!({
+                "kind": "status-update",
+                "taskId": "task-123",
+                "contextId": "ctx-1",
+                "status": {
+                    "state": "input-required",
+                    "message": {"role": "agent", "parts": [{"kind": "text", "text": "Which city?"}]},
+                    "timestamp": "2024-06-01T12:00:00Z"
+                },
+                "final": true
+            }),
+        ))
+        .unwrap();
+        assert_eq!(update.status, TaskStatus::InputRequired);
+        assert_eq!(update.context_id.as_deref(), Some("ctx-1"));
+        assert!(update.timestamp.is_some() && update.is_final);
+        assert!(matches!(
+            &update.message.unwrap().parts[0],
+            MessagePart::Text { text } if text == "Which city?"
+        ));
+
+        let flat = StreamEvent::try_from(event(
+            "status-update",
+            json!({"taskId": "task-123", "state": "completed"}),
+        ))
+        .unwrap();
+        assert!(flat.is_final());
+        assert_eq!(flat.task_id(), Some("task-123"));
+
+        let missing = event("status-update", json!({"taskId": "task-123"}));
+        assert!(matches!(
+            StreamEvent::try_from(missing),
+            Err(A2AError::Protocol(_))
+        ));
+    }
+
+    #[test]
+    fn test_stream_event_kinds() {
+        let artifact = StreamEvent::try_from(event(
+            "artifact-update",
+            json!({
+                "taskId": "task-123",
+                "artifact": {"artifactId": "answer", "parts": [{"kind": "text", "text": "Paris"}]},
+                "append": true,
+                "lastChunk": true
+            }),
+        ))
+        .unwrap();
+        let StreamEvent::ArtifactUpdate(update) = &artifact else {
+            panic!("expected an artifact update, got {:?}", artifact);
+        };
+        assert_eq!(update.artifact.artifact_id, "answer");
+        assert!(update.append && update.last_chunk && !artifact.is_final());
+
+        let task = Task::new("task-123", Message::user("Hello"));
+        let mut payload = serde_json::to_value(&task).unwrap();
+        payload["kind"] = json!("task");
+        assert_eq!(
+            StreamEvent::try_from(event("task", payload)).unwrap(),
+            StreamEvent::Task(task)
+        );
+
+        assert!(StreamEvent::try_from(SseEvent::heartbeat("keep-alive")).is_err());
+        assert!(TaskArtifactUpdateEvent::try_from(event(
+            "status-update",
+            json!({"taskId": "task-123", "state": "working"}),
+        ))
+        .is_err());
+    }
+}
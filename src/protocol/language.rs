@@ -0,0 +1,209 @@
+//! Language tags of text parts
+//!
+//! The protocol has no field for the language of a text part, so tags travel in the
+//! message metadata: [`LANGUAGE_METADATA_KEY`] holds the language of the whole message
+//! and [`PART_LANGUAGES_METADATA_KEY`] maps part indices to the language of individual
+//! parts, for messages mixing languages. Tags are BCP 47 language tags, e.g. `en-GB`:
+//!
+//! ```rust
+//! use tower_a2a::protocol::{Message, MessagePart};
+//!
+//! let message = Message::user("Good morning")
+//!     .with_language("en-GB")
+//!     .with_part(MessagePart::text("Bonjour"))
+//!     .with_part_language(1, "fr");
+//!
+//! assert_eq!(message.part_language(0), Some("en-GB"));
+//! assert_eq!(message.text_in("fr"), Some("Bonjour"));
+//! ```
+//!
+//! Clients ask agents for a language with
+//! [`A2AClientBuilder::with_accept_languages`](crate::client::A2AClientBuilder::with_accept_languages),
+//! sent in the `Accept-Language` header and, for transports without headers, under
+//! [`ACCEPT_LANGUAGES_METADATA_KEY`] in the metadata of the messages.
+
+use serde_json::{Map, Value};
+
+use crate::protocol::{
+    error::A2AError,
+    message::{Message, MessagePart},
+};
+
+/// Metadata key carrying the language of a message's text
+pub const LANGUAGE_METADATA_KEY: &str = "a2a.language";
+
+/// Metadata key mapping part indices to the language of the part
+pub const PART_LANGUAGES_METADATA_KEY: &str = "a2a.partLanguages";
+
+/// Metadata key listing the languages the sender accepts, in order of preference
+pub const ACCEPT_LANGUAGES_METADATA_KEY: &str = "a2a.acceptLanguages";
+
+/// Check that a language tag is a well-formed BCP 47 tag
+///
+/// Only the syntax is checked: a primary language subtag of 2 to 8 letters (or `x` for
+/// private use, `i` for grandfathered tags) followed by subtags of 1 to 8 letters and
+/// digits. Whether the subtags are registered is not checked.
+///
+/// # Errors
+///
+/// Returns `A2AError::Validation` naming the tag if it is malformed
+pub fn validate_language_tag(tag: &str) -> Result<(), A2AError> {
+    let mut subtags = tag.split('-');
+    let primary = subtags.next().unwrap_or_default();
+    let primary_valid = primary.eq_ignore_ascii_case("x")
+        || primary.eq_ignore_ascii_case("i")
+        || ((2..=8).contains(&primary.len()) && primary.chars().all(|c| c.is_ascii_alphabetic()));
+    let subtags_valid = subtags.all(|subtag| {
+        (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+    });
+
+    if primary_valid && subtags_valid {
+        Ok(())
+    } else {
+        Err(A2AError::Validation(format!(
+            "Invalid language tag '{}'",
+            tag
+        )))
+    }
+}
+
+/// Format languages in order of preference as an `Accept-Language` value
+///
+/// The first language is preferred; later ones get decreasing quality values, e.g.
+/// `en-GB, en;q=0.9, fr;q=0.8`.
+pub fn accept_language(languages: &[String]) -> String {
+    languages
+        .iter()
+        .enumerate()
+        .map(|(index, language)| match index {
+            0 => language.clone(),
+            _ => format!("{};q={:.1}", language, (10 - index.min(9)) as f32 / 10.0),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Check whether a tag matches a language range, e.g. `en-GB` matches `en`
+fn matches_range(tag: &str, range: &str) -> bool {
+    tag.get(..range.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(range))
+        && matches!(tag.as_bytes().get(range.len()), None | Some(b'-'))
+}
+
+impl Message {
+    /// Set the language of the message's text
+    pub fn with_language(self, tag: impl Into<String>) -> Self {
+        self.with_metadata(LANGUAGE_METADATA_KEY, Value::String(tag.into()))
+    }
+
+    /// Get the language of the message's text, if tagged
+    pub fn language(&self) -> Option<&str> {
+        self.metadata.as_ref()?.get(LANGUAGE_METADATA_KEY)?.as_str()
+    }
+
+    /// Set the language of one part, overriding the message language for it
+    pub fn with_part_language(mut self, index: usize, tag: impl Into<String>) -> Self {
+        let languages = self
+            .metadata
+            .get_or_insert_with(Default::default)
+            .entry(PART_LANGUAGES_METADATA_KEY.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !languages.is_object() {
+            *languages = Value::Object(Map::new());
+        }
+        if let Value::Object(languages) = languages {
+            languages.insert(index.to_string(), Value::String(tag.into()));
+        }
+        self
+    }
+
+    /// Get the language of a part, falling back to the message language
+    pub fn part_language(&self, index: usize) -> Option<&str> {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get(PART_LANGUAGES_METADATA_KEY))
+            .and_then(|languages| languages.get(index.to_string()))
+            .and_then(Value::as_str)
+            .or_else(|| self.language())
+    }
+
+    /// Get the languages the sender accepts in replies, in order of preference
+    pub fn accept_languages(&self) -> Vec<&str> {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get(ACCEPT_LANGUAGES_METADATA_KEY))
+            .and_then(Value::as_array)
+            .map(|languages| languages.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get the first text part in a language
+    ///
+    /// The language is a range matched by prefix ignoring case, so `en` matches text
+    /// tagged `en-GB`.
+    pub fn text_in(&self, language: &str) -> Option<&str> {
+        self.parts
+            .iter()
+            .enumerate()
+            .find_map(|(index, part)| match part {
+                MessagePart::Text { text }
+                    if self
+                        .part_language(index)
+                        .is_some_and(|tag| matches_range(tag, language)) =>
+                {
+                    Some(text.as_str())
+                }
+                _ => None,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_languages() {
+        let message = Message::user("Hello")
+            .with_part(MessagePart::text("Hallo"))
+            .with_part(MessagePart::text("Grüezi"))
+            .with_language("en")
+            .with_part_language(1, "de-DE")
+            .with_part_language(2, "gsw");
+
+        assert_eq!(message.language(), Some("en"));
+        assert_eq!(message.part_language(0), Some("en"));
+        assert_eq!(message.part_language(2), Some("gsw"));
+        assert_eq!(message.text_in("DE"), Some("Hallo"));
+        assert_eq!(message.text_in("gs"), None);
+        assert_eq!(message.text_in("fr"), None);
+
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["metadata"][PART_LANGUAGES_METADATA_KEY]["1"], "de-DE");
+        assert_eq!(Message::user("Hello").part_language(0), None);
+    }
+
+    #[test]
+    fn test_validate_language_tag() {
+        for tag in [
+            "en",
+            "en-GB",
+            "zh-Hant-TW",
+            "sr-Latn-RS",
+            "x-klingon",
+            "de-CH-1996",
+        ] {
+            assert!(validate_language_tag(tag).is_ok(), "{}", tag);
+        }
+        for tag in ["", "e", "en_GB", "en-", "englishlanguage", "12"] {
+            assert!(validate_language_tag(tag).is_err(), "{}", tag);
+        }
+    }
+
+    #[test]
+    fn test_accept_language() {
+        let languages = ["en-GB", "en", "fr"].map(String::from);
+        assert_eq!(accept_language(&languages), "en-GB, en;q=0.9, fr;q=0.8");
+        assert_eq!(accept_language(&[]), "");
+    }
+}
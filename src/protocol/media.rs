@@ -0,0 +1,311 @@
+//! Helpers for multimodal (image, audio, video) message parts
+
+use base64::{engine::general_purpose, Engine as _};
+use url::Url;
+
+use super::{
+    error::A2AError,
+    message::{FileContent, MessagePart},
+};
+
+/// Kind of media carried by a file part
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaKind {
+    /// Images (`image/*`)
+    Image,
+
+    /// Audio (`audio/*`)
+    Audio,
+
+    /// Video (`video/*`)
+    Video,
+}
+
+impl MediaKind {
+    /// Get the kind of a MIME type, if it is a media type
+    pub fn from_mime(mime: &str) -> Option<Self> {
+        match mime.split('/').next()? {
+            "image" => Some(MediaKind::Image),
+            "audio" => Some(MediaKind::Audio),
+            "video" => Some(MediaKind::Video),
+            _ => None,
+        }
+    }
+
+    /// Detect the MIME type of media content from its leading bytes
+    pub fn sniff(data: &[u8]) -> Option<&'static str> {
+        let mime = match data {
+            [0x89, b'P', b'N', b'G', ..] => "image/png",
+            [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+            [b'G', b'I', b'F', b'8', ..] => "image/gif",
+            [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+            [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E', ..] => "audio/wav",
+            [b'I', b'D', b'3', ..] => "audio/mpeg",
+            [0xFF, 0xFB | 0xF3 | 0xF2, ..] => "audio/mpeg",
+            [b'O', b'g', b'g', b'S', ..] => "audio/ogg",
+            [b'f', b'L', b'a', b'C', ..] => "audio/flac",
+            [_, _, _, _, b'f', b't', b'y', b'p', ..] => "video/mp4",
+            [0x1A, 0x45, 0xDF, 0xA3, ..] => "video/webm",
+            _ => return None,
+        };
+        Some(mime)
+    }
+
+    /// Guess the MIME type of a file from its extension
+    pub fn mime_from_extension(path: &str) -> Option<&'static str> {
+        let (_, extension) = path.rsplit_once('.')?;
+        let mime = match extension.to_ascii_lowercase().as_str() {
+            "png" => "image/png",
+            "jpg" | "jpeg" => "image/jpeg",
+            "gif" => "image/gif",
+            "webp" => "image/webp",
+            "svg" => "image/svg+xml",
+            "mp3" => "audio/mpeg",
+            "wav" => "audio/wav",
+            "ogg" | "oga" => "audio/ogg",
+            "flac" => "audio/flac",
+            "m4a" => "audio/mp4",
+            "mp4" | "m4v" => "video/mp4",
+            "webm" => "video/webm",
+            "mov" => "video/quicktime",
+            _ => return None,
+        };
+        Some(mime)
+    }
+}
+
+/// Size limits applied when building or validating media parts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MediaLimits {
+    /// Maximum size of inline content in bytes (before base64 encoding)
+    pub max_bytes: usize,
+}
+
+impl MediaLimits {
+    /// Default maximum size of inline content (10 MiB)
+    pub const DEFAULT_MAX_BYTES: usize = 10 * 1024 * 1024;
+
+    /// Create limits with the given maximum inline size
+    pub fn new(max_bytes: usize) -> Self {
+        Self { max_bytes }
+    }
+
+    /// Create a file part with inline media content
+    ///
+    /// The MIME type is detected from the content, which must be of the given kind.
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Validation` if the content exceeds the size limit or is not
+    /// recognizable media of the expected kind
+    pub fn bytes_part(
+        &self,
+        kind: MediaKind,
+        name: impl Into<String>,
+        data: &[u8],
+    ) -> Result<MessagePart, A2AError> {
+        self.check_size(data.len())?;
+
+        let mime = MediaKind::sniff(data)
+            .filter(|mime| MediaKind::from_mime(mime) == Some(kind))
+            .ok_or_else(|| {
+                A2AError::Validation(format!("Content is not a supported {:?} format", kind))
+            })?;
+
+        Ok(MessagePart::file_with_bytes(
+            name,
+            general_purpose::STANDARD.encode(data),
+            Some(mime.to_string()),
+        ))
+    }
+
+    /// Validate a media file part, e.g. one produced by an agent
+    ///
+    /// Inline content must be valid base64 within the size limit. If a media type is
+    /// declared and the content is recognizable, both must agree. Non-file parts are
+    /// rejected.
+    pub fn validate(&self, part: &MessagePart) -> Result<(), A2AError> {
+        let MessagePart::File { file } = part else {
+            return Err(A2AError::Validation("Part is not a file part".into()));
+        };
+
+        let Some(encoded) = &file.file_with_bytes else {
+            return Ok(());
+        };
+
+        // Check the encoded size first to avoid decoding oversized content
+        self.check_size(encoded.len() / 4 * 3)?;
+        let data = general_purpose::STANDARD.decode(encoded).map_err(|e| {
+            A2AError::Validation(format!("Invalid base64 content in '{}': {}", file.name, e))
+        })?;
+        self.check_size(data.len())?;
+
+        if let (Some(declared), Some(detected)) = (&file.media_type, MediaKind::sniff(&data)) {
+            if declared != detected {
+                return Err(A2AError::Validation(format!(
+                    "Declared media type {} of '{}' does not match content ({})",
+                    declared, file.name, detected
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_size(&self, size: usize) -> Result<(), A2AError> {
+        if size > self.max_bytes {
+            return Err(A2AError::Validation(format!(
+                "Media content of {} bytes exceeds the limit of {} bytes",
+                size, self.max_bytes
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for MediaLimits {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_BYTES)
+    }
+}
+
+impl MessagePart {
+    /// Create an image part with inline content, detecting the MIME type
+    ///
+    /// Uses the default [`MediaLimits`]; see [`MediaLimits::bytes_part`] for custom limits.
+    pub fn image_bytes(name: impl Into<String>, data: &[u8]) -> Result<Self, A2AError> {
+        MediaLimits::default().bytes_part(MediaKind::Image, name, data)
+    }
+
+    /// Create an audio part with inline content, detecting the MIME type
+    pub fn audio_bytes(name: impl Into<String>, data: &[u8]) -> Result<Self, A2AError> {
+        MediaLimits::default().bytes_part(MediaKind::Audio, name, data)
+    }
+
+    /// Create a video part with inline content, detecting the MIME type
+    pub fn video_bytes(name: impl Into<String>, data: &[u8]) -> Result<Self, A2AError> {
+        MediaLimits::default().bytes_part(MediaKind::Video, name, data)
+    }
+
+    /// Create an image part referencing a URI, with the MIME type from its extension
+    pub fn image_uri(name: impl Into<String>, uri: &str) -> Result<Self, A2AError> {
+        Self::media_uri(MediaKind::Image, name, uri)
+    }
+
+    /// Create an audio part referencing a URI, with the MIME type from its extension
+    pub fn audio_uri(name: impl Into<String>, uri: &str) -> Result<Self, A2AError> {
+        Self::media_uri(MediaKind::Audio, name, uri)
+    }
+
+    /// Create a video part referencing a URI, with the MIME type from its extension
+    pub fn video_uri(name: impl Into<String>, uri: &str) -> Result<Self, A2AError> {
+        Self::media_uri(MediaKind::Video, name, uri)
+    }
+
+    /// Get the declared media type of a file part
+    pub fn media_type(&self) -> Option<&str> {
+        match self {
+            MessagePart::File { file } => file.media_type.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Get the media kind of a file part, based on its declared media type
+    pub fn media_kind(&self) -> Option<MediaKind> {
+        self.media_type().and_then(MediaKind::from_mime)
+    }
+
+    /// Check if this is an image part
+    pub fn is_image(&self) -> bool {
+        self.media_kind() == Some(MediaKind::Image)
+    }
+
+    /// Check if this is an audio part
+    pub fn is_audio(&self) -> bool {
+        self.media_kind() == Some(MediaKind::Audio)
+    }
+
+    /// Check if this is a video part
+    pub fn is_video(&self) -> bool {
+        self.media_kind() == Some(MediaKind::Video)
+    }
+
+    fn media_uri(kind: MediaKind, name: impl Into<String>, uri: &str) -> Result<Self, A2AError> {
+        let url = Url::parse(uri)
+            .map_err(|e| A2AError::Validation(format!("Invalid media URI '{}': {}", uri, e)))?;
+
+        let mime = MediaKind::mime_from_extension(url.path())
+            .filter(|mime| MediaKind::from_mime(mime) == Some(kind))
+            .ok_or_else(|| {
+                A2AError::Validation(format!(
+                    "Cannot determine a {:?} media type for '{}'",
+                    kind, uri
+                ))
+            })?;
+
+        Ok(MessagePart::File {
+            file: FileContent {
+                media_type: Some(mime.to_string()),
+                name: name.into(),
+                file_with_uri: Some(uri.to_string()),
+                file_with_bytes: None,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0];
+
+    #[test]
+    fn test_image_bytes() {
+        let part = MessagePart::image_bytes("pixel.png", PNG).unwrap();
+        assert!(part.is_image());
+        assert_eq!(part.media_type(), Some("image/png"));
+        assert!(MediaLimits::default().validate(&part).is_ok());
+
+        // Not an audio file
+        assert!(MessagePart::audio_bytes("pixel.png", PNG).is_err());
+        assert!(MessagePart::image_bytes("random.bin", b"random").is_err());
+    }
+
+    #[test]
+    fn test_size_limit() {
+        let limits = MediaLimits::new(4);
+        let result = limits.bytes_part(MediaKind::Image, "pixel.png", PNG);
+        assert!(matches!(result, Err(A2AError::Validation(_))));
+
+        let part = MessagePart::image_bytes("pixel.png", PNG).unwrap();
+        assert!(limits.validate(&part).is_err());
+    }
+
+    #[test]
+    fn test_media_uri() {
+        let part = MessagePart::video_uri("clip", "https://example.com/clip.MP4?sig=1").unwrap();
+        assert!(part.is_video());
+        assert_eq!(part.media_type(), Some("video/mp4"));
+
+        let part = MessagePart::audio_uri("song", "https://example.com/song.mp3").unwrap();
+        assert!(part.is_audio());
+        assert!(!part.is_image());
+
+        assert!(MessagePart::image_uri("song", "https://example.com/song.mp3").is_err());
+        assert!(MessagePart::image_uri("bad", "not a uri").is_err());
+    }
+
+    #[test]
+    fn test_validate_mismatched_type() {
+        let part = MessagePart::file_with_bytes(
+            "pixel.jpg",
+            general_purpose::STANDARD.encode(PNG),
+            Some("image/jpeg".to_string()),
+        );
+        assert!(MediaLimits::default().validate(&part).is_err());
+
+        let part = MessagePart::file_with_bytes("bad.png", "!!!", Some("image/png".to_string()));
+        assert!(MediaLimits::default().validate(&part).is_err());
+    }
+}
@@ -3,7 +3,7 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 /// A message in the A2A protocol
 ///
@@ -37,6 +37,10 @@ pub struct Message {
     /// Optional extensions indicating additional protocol features
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extensions: Option<HashMap<String, Value>>,
+
+    /// Fields that are not part of the protocol, kept for re-serialization
+    #[serde(flatten, default, skip_serializing_if = "Map::is_empty")]
+    pub extra: Map<String, Value>,
 }
 
 impl Message {
@@ -50,6 +54,7 @@ impl Message {
             context_id: None,
             metadata: None,
             extensions: None,
+            extra: Map::new(),
         }
     }
 
@@ -181,6 +186,7 @@ impl MessageBuilder {
             context_id: self.context_id,
             metadata: self.metadata,
             extensions: self.extensions,
+            extra: Map::new(),
         }
     }
 }
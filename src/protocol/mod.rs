@@ -1,24 +1,42 @@
 //! Core A2A protocol types and definitions
 
-use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 pub mod agent;
+pub mod archive;
+pub mod chunk;
+pub mod consts;
+pub mod endpoint;
 pub mod error;
+pub mod event;
+pub mod language;
+pub mod media;
 pub mod message;
 pub mod operation;
+pub mod push;
+pub mod scheduling;
 pub mod task;
+pub mod usage;
 
-pub use agent::{AgentCapabilities, AgentCard};
-pub use error::{A2AError, TaskError};
+pub use agent::{AgentCapabilities, AgentCard, AgentCardDelta, AgentExtension};
+pub use chunk::{TextChunk, TextChunker};
+pub use endpoint::{EndpointMap, HttpBinding};
+pub use error::{A2AError, TaskError, TaskErrorCode};
+pub use event::{StreamEvent, TaskArtifactUpdateEvent, TaskStatusUpdateEvent};
+pub use media::{MediaKind, MediaLimits};
 pub use message::{Message, MessagePart, Role};
-pub use operation::A2AOperation;
-pub use task::{Task, TaskStatus};
+pub use operation::{A2AOperation, CancelReason, OperationKind};
+pub use push::{PushNotificationConfig, TaskPushNotificationConfig};
+pub use scheduling::SchedulingHints;
+pub use task::{Task, TaskDelta, TaskStatus};
+pub use usage::{UsageInfo, UsageKeys};
 
 /// Artifacts represent task outputs
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Artifact {
     /// Unique identifier of the Artifact
+    #[serde(alias = "artifactId")]
     pub artifact_id: String,
 
     /// A human readable name for the Artifact
@@ -33,6 +51,136 @@ pub struct Artifact {
     pub metadata: Option<Value>,
 
     /// The URIs of extensions that are present or contributed to this Artifact
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub extensions: Vec<String>,
+
+    /// Fields that are not part of the protocol, kept for re-serialization
+    #[serde(flatten, default, skip_serializing_if = "Map::is_empty")]
+    pub extra: Map<String, Value>,
+}
+
+impl Artifact {
+    /// Deserialize the first data part of this artifact into `T`
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Protocol` naming the artifact and part if the artifact has no
+    /// data part or the data does not match `T`
+    pub fn data_as<T: DeserializeOwned>(&self) -> Result<T, A2AError> {
+        let (index, data) = self
+            .parts
+            .iter()
+            .enumerate()
+            .find_map(|(index, part)| match part {
+                MessagePart::Data { data } => Some((index, data)),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                A2AError::Protocol(format!("Artifact '{}' has no data part", self.artifact_id))
+            })?;
+
+        T::deserialize(data).map_err(|e| {
+            A2AError::Protocol(format!(
+                "Failed to deserialize data part {} of artifact '{}': {}",
+                index, self.artifact_id, e
+            ))
+        })
+    }
+
+    /// Check if this artifact contains a data part
+    pub fn has_data(&self) -> bool {
+        self.parts
+            .iter()
+            .any(|part| matches!(part, MessagePart::Data { .. }))
+    }
+}
+
+impl Task {
+    /// Deserialize the task's structured output into `T`
+    ///
+    /// The output is taken from the most recent artifact containing a data part (see
+    /// [`Artifact::data_as`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Protocol` if no artifact has a data part or the data does not
+    /// match `T`
+    pub fn output_as<T: DeserializeOwned>(&self) -> Result<T, A2AError> {
+        self.artifacts
+            .iter()
+            .rev()
+            .find(|artifact| artifact.has_data())
+            .ok_or_else(|| {
+                A2AError::Protocol(format!("Task '{}' has no structured output", self.id))
+            })?
+            .data_as()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Forecast {
+        city: String,
+        temperature: f64,
+    }
+
+    fn artifact(id: &str, parts: Vec<MessagePart>) -> Artifact {
+        Artifact {
+            artifact_id: id.to_string(),
+            name: None,
+            description: None,
+            parts,
+            metadata: None,
+            extensions: Vec::new(),
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_artifact_data_as() {
+        let artifact = artifact(
+            "forecast",
+            vec![
+                MessagePart::text("Sunny"),
+                MessagePart::data(json!({"city": "Paris", "temperature": 21.5})),
+            ],
+        );
+
+        let forecast: Forecast = artifact.data_as().unwrap();
+        assert_eq!(forecast.city, "Paris");
+
+        let err = artifact.data_as::<Vec<String>>().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("data part 1 of artifact 'forecast'"));
+    }
+
+    #[test]
+    fn test_task_output_as() {
+        let task = Task::new("task-123", Message::user("Weather?"))
+            .with_artifact(artifact(
+                "old",
+                vec![MessagePart::data(
+                    json!({"city": "Paris", "temperature": 20.0}),
+                )],
+            ))
+            .with_artifact(artifact(
+                "new",
+                vec![MessagePart::data(
+                    json!({"city": "Paris", "temperature": 22.0}),
+                )],
+            ))
+            .with_artifact(artifact("notes", vec![MessagePart::text("Done")]));
+
+        let forecast: Forecast = task.output_as().unwrap();
+        assert_eq!(forecast.temperature, 22.0);
+
+        let task = Task::new("task-456", Message::user("Weather?"));
+        assert!(task.output_as::<Forecast>().is_err());
+    }
 }
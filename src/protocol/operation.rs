@@ -1,12 +1,24 @@
 //! A2A protocol operations
 
-use super::{message::Message, task::TaskStatus};
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{
+    endpoint::EndpointMap, message::Message, push::PushNotificationConfig, task::TaskStatus,
+};
 
 /// A2A protocol operations
 ///
 /// This enum represents all the abstract operations defined in the A2A protocol spec.
 /// Each operation is binding-independent and can be implemented over HTTP, gRPC, etc.
-#[derive(Debug, Clone)]
+///
+/// Operations serialize to a stable, internally tagged representation suitable for
+/// persistence (e.g., `{"op": "getTask", "taskId": "..."}`). Variant and field names are
+/// part of the format and must not be renamed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase", rename_all_fields = "camelCase")]
 pub enum A2AOperation {
     /// Send a message to an agent
     SendMessage {
@@ -17,9 +29,11 @@ pub enum A2AOperation {
         stream: bool,
 
         /// Optional context ID for multi-turn conversations
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         context_id: Option<String>,
 
         /// Optional task ID to continue from
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         task_id: Option<String>,
     },
 
@@ -32,15 +46,19 @@ pub enum A2AOperation {
     /// List tasks with optional filtering
     ListTasks {
         /// Filter by task status
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         status: Option<TaskStatus>,
 
         /// Maximum number of tasks to return
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         limit: Option<u32>,
 
         /// Offset for pagination
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         offset: Option<u32>,
 
         /// Continuation token for pagination
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         next_token: Option<String>,
     },
 
@@ -48,6 +66,10 @@ pub enum A2AOperation {
     CancelTask {
         /// The task ID to cancel
         task_id: String,
+
+        /// Why the task is cancelled
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<CancelReason>,
     },
 
     /// Discover agent capabilities (fetch Agent Card)
@@ -59,39 +81,178 @@ pub enum A2AOperation {
         task_id: String,
     },
 
+    /// Resume the update stream of a task after the connection dropped
+    ResubscribeTask {
+        /// The task ID to resubscribe to
+        task_id: String,
+    },
+
     /// Register a webhook for push notifications
     RegisterWebhook {
         /// The webhook URL
         url: String,
 
         /// Events to subscribe to
+        #[serde(default)]
         events: Vec<String>,
 
         /// Optional authentication for webhook calls
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         auth: Option<String>,
     },
+
+    /// Create or replace a push notification configuration of a task
+    SetPushNotificationConfig {
+        /// The task whose updates are pushed
+        task_id: String,
+
+        /// The configuration
+        #[serde(alias = "pushNotificationConfig")]
+        config: PushNotificationConfig,
+    },
+
+    /// Get a push notification configuration of a task
+    GetPushNotificationConfig {
+        /// The task the configuration belongs to
+        task_id: String,
+
+        /// ID of the configuration
+        #[serde(alias = "pushNotificationConfigId")]
+        config_id: String,
+    },
+
+    /// List the push notification configurations of a task
+    ListPushNotificationConfigs {
+        /// The task the configurations belong to
+        task_id: String,
+    },
+
+    /// Delete a push notification configuration of a task
+    DeletePushNotificationConfig {
+        /// The task the configuration belongs to
+        task_id: String,
+
+        /// ID of the configuration
+        #[serde(alias = "pushNotificationConfigId")]
+        config_id: String,
+    },
+}
+
+/// Reason given for cancelling a task
+///
+/// Sent in the body of the cancel request, so agents can record why work stopped.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelReason {
+    /// Human-readable explanation
+    pub message: String,
+
+    /// Machine-readable code, e.g. `user-requested` or `superseded`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+
+    /// Additional details for the agent
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, Value>,
+}
+
+impl CancelReason {
+    /// Create a reason with an explanation
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the machine-readable code
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Add a metadata entry
+    pub fn with_metadata(mut self, key: impl Into<String>, value: Value) -> Self {
+        self.metadata.insert(key.into(), value);
+        self
+    }
+}
+
+/// Kind of an A2A operation, without its parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OperationKind {
+    /// Send a message (streaming or not)
+    SendMessage,
+
+    /// Get a task
+    GetTask,
+
+    /// List tasks
+    ListTasks,
+
+    /// Cancel a task
+    CancelTask,
+
+    /// Fetch the Agent Card
+    DiscoverAgent,
+
+    /// Subscribe to task updates
+    SubscribeTask,
+
+    /// Resubscribe to task updates
+    ResubscribeTask,
+
+    /// Register a webhook
+    RegisterWebhook,
+
+    /// Set a push notification configuration
+    SetPushNotificationConfig,
+
+    /// Get a push notification configuration
+    GetPushNotificationConfig,
+
+    /// List push notification configurations
+    ListPushNotificationConfigs,
+
+    /// Delete a push notification configuration
+    DeletePushNotificationConfig,
 }
 
 impl A2AOperation {
-    /// Get the HTTP endpoint path for this operation
-    pub fn endpoint(&self) -> String {
+    /// Get the kind of this operation
+    pub fn kind(&self) -> OperationKind {
         match self {
-            A2AOperation::SendMessage { task_id, .. } => {
-                if let Some(id) = task_id {
-                    format!("/v1/tasks/{}", id)
-                } else {
-                    "/v1/tasks".to_string()
-                }
+            A2AOperation::SendMessage { .. } => OperationKind::SendMessage,
+            A2AOperation::GetTask { .. } => OperationKind::GetTask,
+            A2AOperation::ListTasks { .. } => OperationKind::ListTasks,
+            A2AOperation::CancelTask { .. } => OperationKind::CancelTask,
+            A2AOperation::DiscoverAgent => OperationKind::DiscoverAgent,
+            A2AOperation::SubscribeTask { .. } => OperationKind::SubscribeTask,
+            A2AOperation::ResubscribeTask { .. } => OperationKind::ResubscribeTask,
+            A2AOperation::RegisterWebhook { .. } => OperationKind::RegisterWebhook,
+            A2AOperation::SetPushNotificationConfig { .. } => {
+                OperationKind::SetPushNotificationConfig
+            }
+            A2AOperation::GetPushNotificationConfig { .. } => {
+                OperationKind::GetPushNotificationConfig
+            }
+            A2AOperation::ListPushNotificationConfigs { .. } => {
+                OperationKind::ListPushNotificationConfigs
+            }
+            A2AOperation::DeletePushNotificationConfig { .. } => {
+                OperationKind::DeletePushNotificationConfig
             }
-            A2AOperation::GetTask { task_id } => format!("/v1/tasks/{}", task_id),
-            A2AOperation::ListTasks { .. } => "/v1/tasks".to_string(),
-            A2AOperation::CancelTask { task_id } => format!("/v1/tasks/{}:cancel", task_id),
-            A2AOperation::DiscoverAgent => "/.well-known/agent-card.json".to_string(),
-            A2AOperation::SubscribeTask { task_id } => format!("/v1/tasks/{}:stream", task_id),
-            A2AOperation::RegisterWebhook { .. } => "/v1/webhooks".to_string(),
         }
     }
 
+    /// Get the default HTTP endpoint path for this operation
+    ///
+    /// See [`EndpointMap`] for agents that expose A2A under a prefix or non-default paths.
+    pub fn endpoint(&self) -> String {
+        EndpointMap::default().resolve(self)
+    }
+
     /// Get the HTTP method for this operation
     pub fn method(&self) -> &'static str {
         match self {
@@ -107,15 +268,54 @@ impl A2AOperation {
             A2AOperation::CancelTask { .. } => "POST",
             A2AOperation::DiscoverAgent => "GET",
             A2AOperation::SubscribeTask { .. } => "GET",
+            A2AOperation::ResubscribeTask { .. } => "GET",
             A2AOperation::RegisterWebhook { .. } => "POST",
+            A2AOperation::SetPushNotificationConfig { .. } => "POST",
+            A2AOperation::GetPushNotificationConfig { .. } => "GET",
+            A2AOperation::ListPushNotificationConfigs { .. } => "GET",
+            A2AOperation::DeletePushNotificationConfig { .. } => "DELETE",
         }
     }
 
+    /// Check if this operation only reads state on the agent
+    pub fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            A2AOperation::GetTask { .. }
+                | A2AOperation::ListTasks { .. }
+                | A2AOperation::DiscoverAgent
+                | A2AOperation::SubscribeTask { .. }
+                | A2AOperation::ResubscribeTask { .. }
+                | A2AOperation::GetPushNotificationConfig { .. }
+                | A2AOperation::ListPushNotificationConfigs { .. }
+        )
+    }
+
+    /// Check if repeating this operation has the same effect as performing it once
+    ///
+    /// Read-only operations, cancellations, and deletions are idempotent. Message
+    /// sends, webhook registrations, and push notification configurations without an ID
+    /// may create new resources on every attempt, unless the client generated the ID of
+    /// the task (see [`A2ARequest::is_idempotent`](crate::service::A2ARequest::is_idempotent)).
+    pub fn is_idempotent(&self) -> bool {
+        self.is_read_only()
+            || matches!(
+                self,
+                A2AOperation::CancelTask { .. } | A2AOperation::DeletePushNotificationConfig { .. }
+            )
+            || matches!(
+                self,
+                A2AOperation::SetPushNotificationConfig { config, .. } if config.id.is_some()
+            )
+    }
+
     /// Check if this operation expects a streaming response
     pub fn is_streaming(&self) -> bool {
         matches!(
             self,
-            A2AOperation::SendMessage { stream: true, .. } | A2AOperation::SubscribeTask { .. }
+            A2AOperation::SendMessage { stream: true, .. }
+                | A2AOperation::SubscribeTask { .. }
+                | A2AOperation::ResubscribeTask { .. }
         )
     }
 }
@@ -145,6 +345,7 @@ mod tests {
 
         let op = A2AOperation::CancelTask {
             task_id: "task-123".to_string(),
+            reason: None,
         };
         assert_eq!(op.endpoint(), "/v1/tasks/task-123:cancel");
         assert_eq!(op.method(), "POST");
@@ -154,6 +355,63 @@ mod tests {
         assert_eq!(op.method(), "GET");
     }
 
+    #[test]
+    fn test_operation_classification() {
+        let op = A2AOperation::SendMessage {
+            message: Message::user("test"),
+            stream: false,
+            context_id: None,
+            task_id: None,
+        };
+        assert_eq!(op.kind(), OperationKind::SendMessage);
+        assert!(!op.is_idempotent());
+        assert!(!op.is_read_only());
+
+        let op = A2AOperation::CancelTask {
+            task_id: "task-123".to_string(),
+            reason: None,
+        };
+        assert!(op.is_idempotent());
+        assert!(!op.is_read_only());
+
+        let op = A2AOperation::DiscoverAgent;
+        assert_eq!(op.kind(), OperationKind::DiscoverAgent);
+        assert!(op.is_idempotent());
+        assert!(op.is_read_only());
+    }
+
+    #[test]
+    fn test_operation_serde() {
+        let op = A2AOperation::GetTask {
+            task_id: "task-123".to_string(),
+        };
+        let json = serde_json::to_value(&op).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"op": "getTask", "taskId": "task-123"})
+        );
+
+        let op: A2AOperation = serde_json::from_value(serde_json::json!({
+            "op": "sendMessage",
+            "message": Message::user("test"),
+            "stream": true,
+            "contextId": "ctx-1"
+        }))
+        .unwrap();
+        assert!(matches!(
+            op,
+            A2AOperation::SendMessage {
+                stream: true,
+                context_id: Some(_),
+                task_id: None,
+                ..
+            }
+        ));
+
+        let json = serde_json::to_string(&A2AOperation::DiscoverAgent).unwrap();
+        assert_eq!(json, r#"{"op":"discoverAgent"}"#);
+    }
+
     #[test]
     fn test_operation_streaming() {
         let op = A2AOperation::SendMessage {
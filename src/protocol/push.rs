@@ -0,0 +1,366 @@
+//! Push notification configuration and replay protection
+//!
+//! Agents deliver a task's updates to the webhooks configured for it with a
+//! [`PushNotificationConfig`]. Configurations are managed per task through the
+//! `tasks/pushNotificationConfig/*` operations, e.g.
+//! [`AgentClient::set_push_notification_config`](crate::client::AgentClient::set_push_notification_config).
+//!
+//! Senders stamp each delivery with a unique nonce and the time it was sent, carried
+//! in the [`NONCE_HEADER`] and [`TIMESTAMP_HEADER`] headers. Receivers check the stamp
+//! with a [`ReplayGuard`], which rejects deliveries outside the freshness window and
+//! nonces it has already seen:
+//!
+//! ```rust
+//! use tower_a2a::protocol::push::{DeliveryStamp, InMemoryReplayCache, ReplayGuard};
+//!
+//! # async fn example() {
+//! let guard = ReplayGuard::new(InMemoryReplayCache::new());
+//!
+//! let headers = DeliveryStamp::generate().headers();
+//! let stamp = DeliveryStamp::from_headers(headers.iter().map(|(k, v)| (*k, v.as_str())))
+//!     .unwrap();
+//! assert!(guard.verify(&stamp).await.is_ok());
+//! assert!(guard.verify(&stamp).await.is_err());
+//! # }
+//! ```
+//!
+//! When deliveries are signed, the signature must cover both headers so they cannot be
+//! altered in transit.
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use super::{consts::headers, error::A2AError};
+
+/// Header carrying the delivery nonce
+pub const NONCE_HEADER: &str = headers::PUSH_NONCE;
+
+/// Header carrying the delivery timestamp, in seconds since the Unix epoch
+pub const TIMESTAMP_HEADER: &str = headers::PUSH_TIMESTAMP;
+
+/// Webhook an agent delivers a task's updates to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushNotificationConfig {
+    /// ID of the configuration, assigned by the agent if not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// URL of the webhook
+    pub url: String,
+
+    /// Token the agent sends back in the
+    /// [`NOTIFICATION_TOKEN`](headers::NOTIFICATION_TOKEN) header of each delivery
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+
+    /// How the agent authenticates to the webhook
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authentication: Option<PushNotificationAuthentication>,
+}
+
+impl PushNotificationConfig {
+    /// Create a configuration delivering to a webhook URL
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            id: None,
+            url: url.into(),
+            token: None,
+            authentication: None,
+        }
+    }
+
+    /// Set the ID of the configuration
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set the token sent back with each delivery
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Set how the agent authenticates to the webhook
+    pub fn with_authentication(mut self, authentication: PushNotificationAuthentication) -> Self {
+        self.authentication = Some(authentication);
+        self
+    }
+}
+
+/// Authentication of an agent to a webhook
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushNotificationAuthentication {
+    /// Accepted schemes, e.g. `Bearer`
+    #[serde(default)]
+    pub schemes: Vec<String>,
+
+    /// Credentials for the schemes, if the webhook issued any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<String>,
+}
+
+/// Push notification configuration of a task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskPushNotificationConfig {
+    /// ID of the task
+    pub task_id: String,
+
+    /// The configuration
+    pub push_notification_config: PushNotificationConfig,
+}
+
+/// Nonce and send time of a push delivery
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryStamp {
+    /// Unique value identifying the delivery
+    pub nonce: String,
+
+    /// When the delivery was sent
+    pub timestamp: DateTime<Utc>,
+}
+
+impl DeliveryStamp {
+    /// Create a stamp with a fresh nonce and the current time
+    pub fn generate() -> Self {
+        Self {
+            nonce: Uuid::now_v7().simple().to_string(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Get the headers carrying the stamp
+    pub fn headers(&self) -> [(&'static str, String); 2] {
+        [
+            (NONCE_HEADER, self.nonce.clone()),
+            (TIMESTAMP_HEADER, self.timestamp.timestamp().to_string()),
+        ]
+    }
+
+    /// Read a stamp from delivery headers, matching names case-insensitively
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Auth` if a header is missing or malformed
+    pub fn from_headers<'a>(
+        headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, A2AError> {
+        let mut nonce = None;
+        let mut timestamp = None;
+        for (name, value) in headers {
+            if name.eq_ignore_ascii_case(NONCE_HEADER) {
+                nonce = Some(value);
+            } else if name.eq_ignore_ascii_case(TIMESTAMP_HEADER) {
+                timestamp = Some(value);
+            }
+        }
+
+        let nonce = nonce
+            .filter(|nonce| !nonce.is_empty())
+            .ok_or_else(|| A2AError::Auth(format!("Missing {} header", NONCE_HEADER)))?;
+        let timestamp = timestamp
+            .and_then(|value| value.trim().parse().ok())
+            .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+            .ok_or_else(|| {
+                A2AError::Auth(format!("Missing or invalid {} header", TIMESTAMP_HEADER))
+            })?;
+
+        Ok(Self {
+            nonce: nonce.to_string(),
+            timestamp,
+        })
+    }
+}
+
+/// Store of nonces that have already been accepted
+#[async_trait]
+pub trait ReplayCache: Send + Sync {
+    /// Record a nonce until it expires, returning `false` if it was already recorded
+    async fn insert(&self, nonce: &str, expires_at: DateTime<Utc>) -> bool;
+
+    /// Forget a recorded nonce, so its delivery is accepted again
+    async fn remove(&self, nonce: &str);
+}
+
+#[async_trait]
+impl<C: ReplayCache + ?Sized> ReplayCache for Arc<C> {
+    async fn insert(&self, nonce: &str, expires_at: DateTime<Utc>) -> bool {
+        (**self).insert(nonce, expires_at).await
+    }
+
+    async fn remove(&self, nonce: &str) {
+        (**self).remove(nonce).await
+    }
+}
+
+/// Replay cache keeping nonces in memory
+///
+/// Expired nonces are pruned on insertion. Receivers running several instances need a
+/// shared cache instead.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryReplayCache {
+    nonces: Arc<Mutex<HashMap<String, DateTime<Utc>>>>,
+}
+
+impl InMemoryReplayCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ReplayCache for InMemoryReplayCache {
+    async fn insert(&self, nonce: &str, expires_at: DateTime<Utc>) -> bool {
+        let now = Utc::now();
+        let mut nonces = self.nonces.lock().await;
+        nonces.retain(|_, expiry| *expiry > now);
+        if nonces.contains_key(nonce) {
+            return false;
+        }
+        nonces.insert(nonce.to_string(), expires_at);
+        true
+    }
+
+    async fn remove(&self, nonce: &str) {
+        self.nonces.lock().await.remove(nonce);
+    }
+}
+
+/// Validates delivery stamps against a freshness window and a replay cache
+#[derive(Debug, Clone)]
+pub struct ReplayGuard<C> {
+    cache: C,
+    window: Duration,
+}
+
+impl<C: ReplayCache> ReplayGuard<C> {
+    /// Default freshness window
+    pub const DEFAULT_WINDOW: Duration = Duration::from_secs(300);
+
+    /// Create a guard with the default freshness window
+    pub fn new(cache: C) -> Self {
+        Self {
+            cache,
+            window: Self::DEFAULT_WINDOW,
+        }
+    }
+
+    /// Set how far a delivery's timestamp may be from the current time
+    ///
+    /// The window applies in both directions to tolerate clock skew.
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Accept a delivery if it is fresh and its nonce has not been seen
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Auth` if the delivery is stale or replayed
+    pub async fn verify(&self, stamp: &DeliveryStamp) -> Result<(), A2AError> {
+        let window = chrono::Duration::from_std(self.window).unwrap_or(chrono::Duration::MAX);
+        let age = Utc::now().signed_duration_since(stamp.timestamp);
+        if age.abs() > window {
+            return Err(A2AError::Auth(format!(
+                "Push delivery timestamp {} is outside the freshness window",
+                stamp.timestamp
+            )));
+        }
+
+        // Nonces only need to be remembered while their timestamp is still fresh
+        let expires_at = stamp
+            .timestamp
+            .checked_add_signed(window)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        if !self.cache.insert(&stamp.nonce, expires_at).await {
+            return Err(A2AError::Auth(format!(
+                "Push delivery nonce {} was already used",
+                stamp.nonce
+            )));
+        }
+        Ok(())
+    }
+
+    /// Forget the nonce of a verified delivery that was not handled, so the sender
+    /// can deliver it again
+    pub async fn release(&self, stamp: &DeliveryStamp) {
+        self.cache.remove(&stamp.nonce).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_notification_config() {
+        let config = TaskPushNotificationConfig {
+            task_id: "task-1".into(),
+            push_notification_config: PushNotificationConfig::new("https://app.example.com/hook")
+                .with_token("secret")
+                .with_authentication(PushNotificationAuthentication {
+                    schemes: vec!["Bearer".into()],
+                    credentials: None,
+                }),
+        };
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value["taskId"], "task-1");
+        assert_eq!(value["pushNotificationConfig"]["token"], "secret");
+        assert!(value["pushNotificationConfig"].get("id").is_none());
+        assert_eq!(
+            serde_json::from_value::<TaskPushNotificationConfig>(value).unwrap(),
+            config
+        );
+    }
+
+    #[test]
+    fn test_stamp_headers() {
+        let stamp = DeliveryStamp::generate();
+        let headers = stamp.headers();
+        let parsed = DeliveryStamp::from_headers([
+            ("x-a2a-nonce", headers[0].1.as_str()),
+            ("X-A2A-TIMESTAMP", headers[1].1.as_str()),
+        ])
+        .unwrap();
+        assert_eq!(parsed.nonce, stamp.nonce);
+        assert_eq!(parsed.timestamp.timestamp(), stamp.timestamp.timestamp());
+
+        assert!(DeliveryStamp::from_headers([(NONCE_HEADER, "abc")]).is_err());
+        assert!(
+            DeliveryStamp::from_headers([(NONCE_HEADER, "abc"), (TIMESTAMP_HEADER, "soon")])
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replay_guard() {
+        let guard =
+            ReplayGuard::new(InMemoryReplayCache::new()).with_window(Duration::from_secs(60));
+
+        let stamp = DeliveryStamp::generate();
+        assert!(guard.verify(&stamp).await.is_ok());
+        assert!(matches!(guard.verify(&stamp).await, Err(A2AError::Auth(_))));
+
+        let stale = DeliveryStamp {
+            nonce: "stale".to_string(),
+            timestamp: Utc::now() - chrono::Duration::seconds(120),
+        };
+        assert!(guard.verify(&stale).await.is_err());
+
+        let future = DeliveryStamp {
+            nonce: "future".to_string(),
+            timestamp: Utc::now() + chrono::Duration::seconds(120),
+        };
+        assert!(guard.verify(&future).await.is_err());
+    }
+}
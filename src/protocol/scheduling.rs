@@ -0,0 +1,140 @@
+//! Scheduling hints for sent messages
+//!
+//! Agents that queue work can use hints to decide what to run first. The protocol has
+//! no field for them, so they travel in the message metadata under
+//! [`SchedulingHints::METADATA_KEY`]; agents that do not know the key ignore it:
+//!
+//! ```rust
+//! use chrono::{Duration, Utc};
+//! use tower_a2a::protocol::{scheduling::SchedulingHints, Message};
+//!
+//! let message = Message::user("Summarize the quarterly report").with_scheduling(
+//!     SchedulingHints::new()
+//!         .with_priority(10)
+//!         .with_deadline(Utc::now() + Duration::hours(1)),
+//! );
+//! ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::protocol::{error::A2AError, message::Message};
+
+/// Hints on when and in which order an agent should process a message
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulingHints {
+    /// Relative priority; higher values run first, and the default is 0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i32>,
+
+    /// Earliest time to start processing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<DateTime<Utc>>,
+
+    /// Time after which the result is no longer useful
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<DateTime<Utc>>,
+}
+
+impl SchedulingHints {
+    /// Metadata key carrying the hints
+    pub const METADATA_KEY: &'static str = "a2a.scheduling";
+
+    /// Create empty hints
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the priority
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Set the earliest start time
+    pub fn with_not_before(mut self, not_before: DateTime<Utc>) -> Self {
+        self.not_before = Some(not_before);
+        self
+    }
+
+    /// Set the deadline
+    pub fn with_deadline(mut self, deadline: DateTime<Utc>) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Check that the deadline does not precede the earliest start time
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Validation` if the window is empty
+    pub fn validate(&self) -> Result<(), A2AError> {
+        match (self.not_before, self.deadline) {
+            (Some(not_before), Some(deadline)) if deadline < not_before => {
+                Err(A2AError::Validation(format!(
+                    "Scheduling deadline {} precedes not-before time {}",
+                    deadline, not_before
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Message {
+    /// Attach scheduling hints to the message metadata
+    pub fn with_scheduling(self, hints: SchedulingHints) -> Self {
+        let value = serde_json::to_value(hints).unwrap_or_default();
+        self.with_metadata(SchedulingHints::METADATA_KEY, value)
+    }
+
+    /// Get the scheduling hints attached to the message, if any
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Validation` if the metadata entry is malformed
+    pub fn scheduling(&self) -> Result<Option<SchedulingHints>, A2AError> {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get(SchedulingHints::METADATA_KEY))
+            .map(|value| {
+                SchedulingHints::deserialize(value)
+                    .map_err(|e| A2AError::Validation(format!("Invalid scheduling hints: {}", e)))
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_scheduling_hints() {
+        let now = Utc::now();
+        let hints = SchedulingHints::new()
+            .with_priority(5)
+            .with_not_before(now)
+            .with_deadline(now + Duration::minutes(5));
+        assert!(hints.validate().is_ok());
+
+        let message = Message::user("Hello").with_scheduling(hints.clone());
+        assert_eq!(
+            message.metadata.as_ref().unwrap()[SchedulingHints::METADATA_KEY]["priority"],
+            5
+        );
+        assert_eq!(message.scheduling().unwrap(), Some(hints.clone()));
+        assert_eq!(Message::user("Hello").scheduling().unwrap(), None);
+
+        let inverted = hints.with_deadline(now - Duration::minutes(5));
+        assert!(inverted.validate().is_err());
+
+        let malformed = Message::user("Hello")
+            .with_metadata(SchedulingHints::METADATA_KEY, json!({"priority": "high"}));
+        assert!(malformed.scheduling().is_err());
+    }
+}
@@ -1,9 +1,10 @@
 //! A2A task types and lifecycle management
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-use super::{error::TaskError, message::Message, Artifact};
+use super::{error::A2AError, error::TaskError, message::Message, Artifact};
 
 /// A task in the A2A protocol
 ///
@@ -43,6 +44,14 @@ pub struct Task {
     /// Optional context ID for grouping related tasks/messages
     #[serde(rename = "contextId", skip_serializing_if = "Option::is_none")]
     pub context_id: Option<String>,
+
+    /// Fields the agent added that are not part of the protocol
+    ///
+    /// Kept so custom fields survive a decode and re-encode; read them as a typed
+    /// struct with [`Task::extra_as`]. Messages, artifacts, and agent cards keep
+    /// theirs the same way.
+    #[serde(flatten, default, skip_serializing_if = "Map::is_empty")]
+    pub extra: Map<String, Value>,
 }
 
 impl Task {
@@ -58,6 +67,7 @@ impl Task {
             created_at: Utc::now(),
             updated_at: None,
             context_id: None,
+            extra: Map::new(),
         }
     }
 
@@ -118,6 +128,101 @@ impl Task {
         self.context_id = Some(context_id.into());
         self
     }
+
+    /// Set a field that is not part of the protocol
+    pub fn with_extra(mut self, key: impl Into<String>, value: Value) -> Self {
+        self.extra.insert(key.into(), value);
+        self
+    }
+
+    /// Deserialize the fields that are not part of the protocol into a custom type
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Serialization` if the fields do not match the type
+    pub fn extra_as<T: DeserializeOwned>(&self) -> Result<T, A2AError> {
+        Ok(T::deserialize(Value::Object(self.extra.clone()))?)
+    }
+
+    /// Compute the changes from this snapshot to a newer snapshot of the same task
+    ///
+    /// Artifacts are matched by `artifact_id`. History is treated as append-only, so
+    /// messages beyond the length of this snapshot's history are reported as new.
+    pub fn diff(&self, newer: &Task) -> TaskDelta {
+        TaskDelta::between(Some(self), newer)
+    }
+}
+
+/// Changes between two snapshots of a task
+///
+/// Produced by [`Task::diff`], typically from successive polls of the same task.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskDelta {
+    /// New status, if it changed
+    pub status: Option<TaskStatus>,
+
+    /// Artifacts that did not exist in the older snapshot
+    pub new_artifacts: Vec<Artifact>,
+
+    /// Artifacts that existed in the older snapshot but whose content changed
+    pub updated_artifacts: Vec<Artifact>,
+
+    /// Messages appended to the history
+    pub new_messages: Vec<Message>,
+
+    /// Error that appeared or changed
+    pub error: Option<TaskError>,
+
+    /// Whether the task produced its first artifact
+    pub output_appeared: bool,
+}
+
+impl TaskDelta {
+    /// Describe a task as a delta from nothing, i.e. everything in it is new
+    pub fn snapshot(task: &Task) -> Self {
+        Self::between(None, task)
+    }
+
+    /// Check if the snapshots were identical
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Compute the delta between an optional older snapshot and a newer one
+    fn between(older: Option<&Task>, newer: &Task) -> Self {
+        let mut delta = Self::default();
+
+        if older.map(|t| t.status) != Some(newer.status) {
+            delta.status = Some(newer.status);
+        }
+
+        for artifact in &newer.artifacts {
+            let previous = older.and_then(|t| {
+                t.artifacts
+                    .iter()
+                    .find(|a| a.artifact_id == artifact.artifact_id)
+            });
+            match previous {
+                None => delta.new_artifacts.push(artifact.clone()),
+                Some(previous) if previous != artifact => {
+                    delta.updated_artifacts.push(artifact.clone())
+                }
+                Some(_) => {}
+            }
+        }
+
+        let seen = older.map_or(0, |t| t.history.len());
+        delta.new_messages = newer.history.iter().skip(seen).cloned().collect();
+
+        if newer.error.is_some() && older.and_then(|t| t.error.as_ref()) != newer.error.as_ref() {
+            delta.error = newer.error.clone();
+        }
+
+        delta.output_appeared =
+            older.is_none_or(|t| t.artifacts.is_empty()) && !newer.artifacts.is_empty();
+
+        delta
+    }
 }
 
 /// Task status in the A2A protocol lifecycle
@@ -146,6 +251,7 @@ pub enum TaskStatus {
     Failed,
 
     /// Task was cancelled by the client
+    #[serde(alias = "canceled")]
     Cancelled,
 
     /// Task was rejected by the agent (e.g., invalid request)
@@ -220,6 +326,40 @@ mod tests {
         assert!(task.is_processing());
     }
 
+    #[test]
+    fn test_task_extra_fields() {
+        #[derive(Deserialize)]
+        struct Billing {
+            cost: f64,
+        }
+
+        let json = serde_json::json!({
+            "id": "task-123",
+            "status": "completed",
+            "input": {"role": "user", "parts": [{"text": "Test"}], "channel": "email"},
+            "artifacts": [{
+                "artifact_id": "a-1",
+                "name": null,
+                "description": null,
+                "parts": [{"text": "Done"}],
+                "metadata": null,
+                "checksum": "abc"
+            }],
+            "createdAt": "2024-01-01T00:00:00Z",
+            "cost": 0.25
+        });
+        let task: Task = serde_json::from_value(json.clone()).unwrap();
+
+        assert_eq!(task.extra["cost"], 0.25);
+        assert_eq!(task.extra_as::<Billing>().unwrap().cost, 0.25);
+        assert_eq!(task.input.extra["channel"], "email");
+        assert_eq!(task.artifacts[0].extra["checksum"], "abc");
+        assert_eq!(serde_json::to_value(&task).unwrap(), json);
+        assert!(Task::new("task-1", Message::user("Test"))
+            .extra_as::<Billing>()
+            .is_err());
+    }
+
     #[test]
     fn test_task_lifecycle() {
         let msg = Message::user("Test");
@@ -245,6 +385,45 @@ mod tests {
         assert!(!TaskStatus::Working.requires_action());
     }
 
+    #[test]
+    fn test_task_diff() {
+        let artifact = |id: &str, text: &str| Artifact {
+            artifact_id: id.to_string(),
+            name: None,
+            description: None,
+            parts: vec![crate::protocol::MessagePart::text(text)],
+            metadata: None,
+            extensions: Vec::new(),
+            extra: Default::default(),
+        };
+
+        let old = Task::new("task-123", Message::user("Test")).with_status(TaskStatus::Working);
+        assert!(old.diff(&old).is_empty());
+
+        let new = old
+            .clone()
+            .with_artifact(artifact("a1", "partial"))
+            .with_history_message(Message::agent("Working on it"));
+        let delta = old.diff(&new);
+        assert_eq!(delta.status, None);
+        assert_eq!(delta.new_artifacts.len(), 1);
+        assert_eq!(delta.new_messages.len(), 1);
+        assert!(delta.output_appeared);
+
+        let mut newest = new.clone().with_status(TaskStatus::Completed);
+        newest.artifacts[0] = artifact("a1", "final");
+        let delta = new.diff(&newest);
+        assert_eq!(delta.status, Some(TaskStatus::Completed));
+        assert!(delta.new_artifacts.is_empty());
+        assert_eq!(delta.updated_artifacts.len(), 1);
+        assert!(delta.new_messages.is_empty());
+        assert!(!delta.output_appeared);
+
+        let delta = TaskDelta::snapshot(&newest);
+        assert_eq!(delta.status, Some(TaskStatus::Completed));
+        assert!(delta.output_appeared);
+    }
+
     #[test]
     fn test_task_serialization() {
         let msg = Message::user("Test");
@@ -0,0 +1,217 @@
+//! Token usage and cost reported by agents
+//!
+//! Agents backed by language models often report what a task consumed in the task
+//! metadata, but each vendor names the fields differently. [`UsageKeys`] locates them
+//! with JSON pointers into the task, so a [`CompatProfile`] can describe a vendor's
+//! layout:
+//!
+//! ```rust
+//! use tower_a2a::protocol::{usage::UsageKeys, Message, Task};
+//!
+//! let mut task = Task::new("task-123", Message::user("Summarize"));
+//! task.extra.insert(
+//!     "metadata".into(),
+//!     serde_json::json!({"usage": {"prompt_tokens": 120, "completion_tokens": 30}}),
+//! );
+//!
+//! let usage = task.usage().unwrap();
+//! assert_eq!(usage.total_tokens, 150);
+//! assert!(UsageKeys::gemini().extract(&task).is_none());
+//! ```
+//!
+//! [`CompatProfile`]: crate::client::CompatProfile
+
+use std::ops::AddAssign;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::protocol::task::Task;
+
+/// Tokens and cost consumed by a task
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageInfo {
+    /// Tokens read by the model
+    pub input_tokens: u64,
+
+    /// Tokens generated by the model
+    pub output_tokens: u64,
+
+    /// Tokens in total, the sum of input and output unless the agent says otherwise
+    pub total_tokens: u64,
+
+    /// Cost in the agent's currency, if reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+}
+
+impl AddAssign for UsageInfo {
+    fn add_assign(&mut self, other: Self) {
+        self.input_tokens = self.input_tokens.saturating_add(other.input_tokens);
+        self.output_tokens = self.output_tokens.saturating_add(other.output_tokens);
+        self.total_tokens = self.total_tokens.saturating_add(other.total_tokens);
+        self.cost = match (self.cost, other.cost) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+impl UsageInfo {
+    /// Get the usage added since an earlier report of the same task
+    ///
+    /// Counters that went down, e.g. because the agent reset them, count as zero.
+    pub fn since(&self, earlier: &Self) -> Self {
+        Self {
+            input_tokens: self.input_tokens.saturating_sub(earlier.input_tokens),
+            output_tokens: self.output_tokens.saturating_sub(earlier.output_tokens),
+            total_tokens: self.total_tokens.saturating_sub(earlier.total_tokens),
+            cost: match (self.cost, earlier.cost) {
+                (Some(a), Some(b)) => Some((a - b).max(0.0)),
+                (a, _) => a,
+            },
+        }
+    }
+}
+
+/// Where an agent reports usage in its tasks
+///
+/// Fields are located by JSON pointers (RFC 6901) into the serialized task, tried in
+/// order. The default keys read `/metadata/usage` with camelCase or snake_case names,
+/// including the `prompt_tokens` and `completion_tokens` of OpenAI-style agents.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct UsageKeys {
+    /// Pointers to the input token count
+    pub input_tokens: Vec<String>,
+
+    /// Pointers to the output token count
+    pub output_tokens: Vec<String>,
+
+    /// Pointers to the total token count
+    pub total_tokens: Vec<String>,
+
+    /// Pointers to the cost
+    pub cost: Vec<String>,
+}
+
+impl UsageKeys {
+    /// Keys of agents reporting Gemini's `usageMetadata`
+    pub fn gemini() -> Self {
+        Self {
+            input_tokens: vec!["/metadata/usageMetadata/promptTokenCount".to_string()],
+            output_tokens: vec!["/metadata/usageMetadata/candidatesTokenCount".to_string()],
+            total_tokens: vec!["/metadata/usageMetadata/totalTokenCount".to_string()],
+            cost: Vec::new(),
+        }
+    }
+
+    /// Extract the usage reported in a task, if any
+    pub fn extract(&self, task: &Task) -> Option<UsageInfo> {
+        let task = serde_json::to_value(task).ok()?;
+        let find = |pointers: &[String]| {
+            pointers
+                .iter()
+                .find_map(|pointer| task.pointer(pointer).and_then(Value::as_f64))
+        };
+
+        let input = find(&self.input_tokens);
+        let output = find(&self.output_tokens);
+        let total = find(&self.total_tokens);
+        let cost = find(&self.cost);
+        if input.is_none() && output.is_none() && total.is_none() && cost.is_none() {
+            return None;
+        }
+
+        let input_tokens = input.unwrap_or_default() as u64;
+        let output_tokens = output.unwrap_or_default() as u64;
+        Some(UsageInfo {
+            input_tokens,
+            output_tokens,
+            total_tokens: total.map_or(input_tokens + output_tokens, |total| total as u64),
+            cost,
+        })
+    }
+}
+
+impl Default for UsageKeys {
+    fn default() -> Self {
+        let pointers = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| format!("/metadata/usage/{}", name))
+                .collect()
+        };
+        Self {
+            input_tokens: pointers(&[
+                "inputTokens",
+                "input_tokens",
+                "promptTokens",
+                "prompt_tokens",
+            ]),
+            output_tokens: pointers(&[
+                "outputTokens",
+                "output_tokens",
+                "completionTokens",
+                "completion_tokens",
+            ]),
+            total_tokens: pointers(&["totalTokens", "total_tokens"]),
+            cost: pointers(&["cost"]),
+        }
+    }
+}
+
+impl Task {
+    /// Get the usage the agent reported in the task, read with the default
+    /// [`UsageKeys`]
+    pub fn usage(&self) -> Option<UsageInfo> {
+        UsageKeys::default().extract(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::protocol::message::Message;
+
+    fn task(metadata: Value) -> Task {
+        let mut task = Task::new("task-123", Message::user("Hello"));
+        task.extra.insert("metadata".into(), metadata);
+        task
+    }
+
+    #[test]
+    fn test_extract_usage() {
+        let usage = task(json!({"usage": {"inputTokens": 10, "outputTokens": 5, "cost": 0.25}}))
+            .usage()
+            .unwrap();
+        assert_eq!(
+            usage,
+            UsageInfo {
+                input_tokens: 10,
+                output_tokens: 5,
+                total_tokens: 15,
+                cost: Some(0.25),
+            }
+        );
+
+        let gemini = task(json!({"usageMetadata": {
+            "promptTokenCount": 7,
+            "candidatesTokenCount": 3,
+            "totalTokenCount": 12
+        }}));
+        assert!(gemini.usage().is_none());
+        assert_eq!(
+            UsageKeys::gemini().extract(&gemini).unwrap().total_tokens,
+            12
+        );
+
+        let mut total = usage;
+        total += UsageKeys::gemini().extract(&gemini).unwrap();
+        assert_eq!((total.input_tokens, total.total_tokens), (17, 27));
+        assert_eq!(total.cost, Some(0.25));
+    }
+}
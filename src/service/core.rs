@@ -1,28 +1,116 @@
 //! Core A2A protocol service implementation
 
 use std::{
+    collections::{HashMap, VecDeque},
     future::Future,
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
+use chrono::Utc;
+use futures::stream::{self, BoxStream, StreamExt};
+use serde::{Deserialize, Serialize};
 use tower_service::Service;
 
 use crate::{
-    codec::Codec,
-    protocol::{error::A2AError, operation::A2AOperation},
-    service::{A2ARequest, A2AResponse},
-    transport::{Transport, TransportRequest},
+    codec::{Codec, TaskListDecoder, TaskListItem},
+    protocol::{
+        consts::{headers, media_types, PROTOCOL_VERSION},
+        endpoint::EndpointMap,
+        error::A2AError,
+        operation::{A2AOperation, OperationKind},
+    },
+    service::{
+        deprecation::{Deprecation, SunsetPolicy},
+        transform::{ResponseTransform, ResponseTransforms},
+        A2ARequest, A2AResponse,
+    },
+    transport::{EventStream, Transport, TransportRequest},
 };
 
+/// Items of a task list decoded as the response arrives
+pub type TaskListStream = BoxStream<'static, Result<TaskListItem, A2AError>>;
+
+/// Time spent in each stage of a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StageTimings {
+    /// Kind of the operation
+    pub operation: OperationKind,
+
+    /// Building and encoding the transport request
+    pub encode: Duration,
+
+    /// Waiting for the transport, including following `202 Accepted` locations
+    pub transport: Duration,
+
+    /// Checking the status and decoding the response
+    pub decode: Duration,
+}
+
+/// Where error responses carry their details
+///
+/// Fields are located by JSON pointers (RFC 6901), tried in order. The default shape
+/// reads a top-level `message` and `taskId`; agents nesting them, e.g. under
+/// `/error/message`, need additional pointers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ErrorShape {
+    /// Pointers to the error message
+    pub message: Vec<String>,
+
+    /// Pointers to the ID of a task that was not found
+    pub task_id: Vec<String>,
+}
+
+impl ErrorShape {
+    /// Find the error message in an error body
+    fn message<'a>(&self, body: &'a serde_json::Value) -> Option<&'a str> {
+        Self::find(&self.message, body)
+    }
+
+    /// Find the task ID in an error body
+    fn task_id<'a>(&self, body: &'a serde_json::Value) -> Option<&'a str> {
+        Self::find(&self.task_id, body)
+    }
+
+    fn find<'a>(pointers: &[String], body: &'a serde_json::Value) -> Option<&'a str> {
+        pointers
+            .iter()
+            .find_map(|pointer| body.pointer(pointer).and_then(|value| value.as_str()))
+    }
+}
+
+impl Default for ErrorShape {
+    fn default() -> Self {
+        Self {
+            message: vec!["/message".to_string()],
+            task_id: vec!["/taskId".to_string()],
+        }
+    }
+}
+
+/// Callback receiving the stage timings of every completed request
+pub type TimingObserver = Arc<dyn Fn(&StageTimings) + Send + Sync>;
+
 /// Core A2A protocol service that wraps a transport
 ///
 /// This service implements the Tower `Service` trait and provides the core logic
 /// for executing A2A operations over any transport (HTTP, gRPC, WebSocket, etc.)
+///
+/// Stage timings are measured only when a [`TimingObserver`] is installed or `DEBUG`
+/// logging is enabled for this module, so they cost nothing otherwise.
 pub struct A2AProtocolService<T> {
     transport: T,
     codec: Arc<dyn Codec>,
+    endpoints: Arc<EndpointMap>,
+    timing_observer: Option<TimingObserver>,
+    transforms: Arc<ResponseTransforms>,
+    error_shape: Arc<ErrorShape>,
+    sunset_policy: SunsetPolicy,
+    /// Deprecation signalled by the latest response, shared by clones
+    deprecation: Arc<Mutex<Option<Deprecation>>>,
 }
 
 impl<T> A2AProtocolService<T>
@@ -36,28 +124,237 @@ where
     /// * `transport` - The underlying transport implementation
     /// * `codec` - The codec for serialization/deserialization
     pub fn new(transport: T, codec: Arc<dyn Codec>) -> Self {
-        Self { transport, codec }
+        Self {
+            transport,
+            codec,
+            endpoints: Arc::new(EndpointMap::default()),
+            timing_observer: None,
+            transforms: Arc::default(),
+            error_shape: Arc::default(),
+            sunset_policy: SunsetPolicy::default(),
+            deprecation: Arc::default(),
+        }
+    }
+
+    /// Use custom endpoint path templates
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoints` - The path templates used to resolve operation endpoints
+    pub fn with_endpoints(mut self, endpoints: EndpointMap) -> Self {
+        self.endpoints = Arc::new(endpoints);
+        self
+    }
+
+    /// Report the encode, transport, and decode durations of each request
+    ///
+    /// Typically used to feed a metrics system; requests that fail in a stage are not
+    /// reported.
+    pub fn with_timing_observer(
+        mut self,
+        observer: impl Fn(&StageTimings) + Send + Sync + 'static,
+    ) -> Self {
+        self.timing_observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Rewrite responses of matching agents before and after decoding
+    ///
+    /// See [`ResponseTransforms`]; the agent is identified by the request's
+    /// [`agent_url`](crate::service::RequestContext::agent_url).
+    pub fn with_transforms(mut self, transforms: ResponseTransforms) -> Self {
+        self.transforms = Arc::new(transforms);
+        self
+    }
+
+    /// Read error details from where the agent puts them (default: [`ErrorShape::default`])
+    pub fn with_error_shape(mut self, shape: ErrorShape) -> Self {
+        self.error_shape = Arc::new(shape);
+        self
+    }
+
+    /// Decide what happens to requests past the agent's sunset date (default:
+    /// [`SunsetPolicy::Warn`])
+    pub fn with_sunset_policy(mut self, policy: SunsetPolicy) -> Self {
+        self.sunset_policy = policy;
+        self
+    }
+
+    /// Get the deprecation signalled by the agent's latest response, if any
+    pub fn deprecation(&self) -> Option<Deprecation> {
+        self.deprecation.lock().ok().and_then(|seen| seen.clone())
+    }
+
+    /// Get a reference to the underlying transport
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Get the codec
+    pub fn codec(&self) -> &dyn Codec {
+        self.codec.as_ref()
+    }
+
+    /// Execute a request whose response is a stream of events
+    ///
+    /// Used for streaming sends and task subscriptions. Fails if the transport does not
+    /// support streaming. The sunset policy applies, but transports do not expose the
+    /// headers of event streams, so deprecations are only learned from other responses.
+    ///
+    /// Response transforms rewrite the payload of each event with
+    /// [`transform_value`](ResponseTransform::transform_value); events are decoded by
+    /// the caller, so [`transform_response`](ResponseTransform::transform_response)
+    /// does not apply.
+    pub async fn call_streaming(&self, req: A2ARequest) -> Result<EventStream, A2AError> {
+        Self::check_sunset(&self.deprecation, self.sunset_policy)?;
+        let transport_req = Self::build_transport_request(
+            &req,
+            self.codec.as_ref(),
+            &self.endpoints,
+            self.transport.keeps_request_body(),
+        )?
+        .header(headers::ACCEPT, media_types::EVENT_STREAM);
+
+        let events = self.transport.execute_streaming(transport_req).await?;
+        let transforms = self.transforms.matching(&req.context.agent_url);
+        if transforms.is_empty() {
+            return Ok(events);
+        }
+
+        let operation = req.operation;
+        Ok(events
+            .map(move |event| {
+                let mut event = event?;
+                if !event.is_control() {
+                    for transform in &transforms {
+                        transform.transform_value(&mut event.payload, &operation)?;
+                    }
+                }
+                Ok(event)
+            })
+            .boxed())
+    }
+
+    /// Execute a `ListTasks` request, decoding the tasks as the response arrives
+    ///
+    /// Yields each task as soon as it is complete, then a [`TaskListItem::End`] with the
+    /// total and the continuation token, so pages of any size take little memory.
+    ///
+    /// If response transforms match the agent, the whole body is read and transformed
+    /// like in [`call`](Service::call) before the tasks are yielded.
+    pub async fn call_task_list(&self, req: A2ARequest) -> Result<TaskListStream, A2AError> {
+        if !matches!(req.operation, A2AOperation::ListTasks { .. }) {
+            return Err(A2AError::Validation(
+                "Only ListTasks responses can be decoded incrementally".into(),
+            ));
+        }
+
+        Self::check_sunset(&self.deprecation, self.sunset_policy)?;
+        let transport_req = Self::build_transport_request(
+            &req,
+            self.codec.as_ref(),
+            &self.endpoints,
+            self.transport.keeps_request_body(),
+        )?;
+        let response = self.transport.execute_chunked(transport_req).await?;
+        Self::observe_deprecation(&response.headers, &self.deprecation, &req);
+        if !(200..300).contains(&response.status) {
+            let response = response.collect().await?;
+            return Err(Self::handle_error_response(&response, &self.error_shape));
+        }
+
+        let transforms = self.transforms.matching(&req.context.agent_url);
+        if !transforms.is_empty() {
+            let response = Self::parse_transport_response(
+                response.collect().await?,
+                self.codec.as_ref(),
+                &req.operation,
+                &transforms,
+                &self.error_shape,
+            )?;
+            let A2AResponse::TaskList {
+                tasks,
+                total,
+                next_token,
+            } = response
+            else {
+                return Err(A2AError::Protocol(
+                    "Transformed ListTasks response is not a task list".into(),
+                ));
+            };
+            let items = tasks
+                .into_iter()
+                .map(|task| Ok(TaskListItem::Task(Box::new(task))))
+                .chain([Ok(TaskListItem::End { total, next_token })]);
+            return Ok(stream::iter(items).boxed());
+        }
+
+        let state = (
+            response.body,
+            Some(TaskListDecoder::new()),
+            VecDeque::new(),
+            self.codec.clone(),
+            req.operation,
+        );
+        let items = stream::unfold(
+            state,
+            |(mut body, mut decoder, mut pending, codec, operation)| async move {
+                loop {
+                    if let Some(item) = pending.pop_front() {
+                        return Some((Ok(item), (body, decoder, pending, codec, operation)));
+                    }
+                    let active = decoder.as_mut()?;
+
+                    let result = match body.next().await {
+                        Some(Ok(chunk)) => active.feed(&chunk).map(|tasks| (tasks, None)),
+                        Some(Err(e)) => Err(e),
+                        None => decoder
+                            .take()?
+                            .finish(codec.as_ref(), &operation)
+                            .map(|(tasks, end)| (tasks, Some(end))),
+                    };
+                    match result {
+                        Ok((tasks, end)) => {
+                            pending.extend(tasks.into_iter().map(Box::new).map(TaskListItem::Task));
+                            pending.extend(end);
+                        }
+                        Err(e) => {
+                            return Some((Err(e), (body, None, VecDeque::new(), codec, operation)))
+                        }
+                    }
+                }
+            },
+        );
+        Ok(items.boxed())
     }
 
     /// Build a transport request from an A2A operation
+    ///
+    /// `GET` requests go without a body, unless the transport
+    /// [keeps request bodies](Transport::keeps_request_body).
     fn build_transport_request(
         req: &A2ARequest,
         codec: &dyn Codec,
+        endpoints: &EndpointMap,
+        keep_body: bool,
     ) -> Result<TransportRequest, A2AError> {
-        let endpoint = req.operation.endpoint();
-        let method = req.operation.method();
+        let mut endpoint = endpoints.resolve(&req.operation);
+        if let Some(query) = endpoints.query(&req.operation) {
+            endpoint = format!("{}?{}", endpoint, query);
+        }
+        let method = endpoints.method(&req.operation);
 
         let mut transport_req = TransportRequest::new(endpoint, method);
 
         // Add required A2A protocol headers
-        transport_req = transport_req.header("Content-Type", codec.content_type());
-        transport_req = transport_req.header("Accept", codec.content_type());
-        transport_req = transport_req.header("A2A-Version", "1.0");
+        transport_req = transport_req.header(headers::CONTENT_TYPE, codec.content_type());
+        transport_req = transport_req.header(headers::ACCEPT, codec.content_type());
+        transport_req = transport_req.header(headers::A2A_VERSION, PROTOCOL_VERSION);
 
         // Add authentication headers if present
         if let Some(auth) = &req.context.auth {
             let (header, value) = auth.to_header();
-            transport_req = transport_req.header(header, value);
+            transport_req = transport_req.header(header, value).auth(auth.clone());
         }
 
         // Add custom metadata headers
@@ -65,39 +362,160 @@ where
             transport_req = transport_req.header(key.clone(), value.clone());
         }
 
+        // Add the codec's headers, merging the extensions it activates
+        for (key, value) in codec.headers() {
+            let value = match transport_req.headers.get(key) {
+                Some(current) if key == headers::EXTENSIONS => format!("{}, {}", current, value),
+                _ => value,
+            };
+            transport_req = transport_req.header(key, value);
+        }
+
         // Encode request body (if needed)
         let body = codec.encode_request(&req.operation)?;
-        if !body.is_empty() && method != "GET" {
+        if !body.is_empty() && (keep_body || method != "GET") {
             transport_req = transport_req.body(body);
         }
 
         Ok(transport_req)
     }
 
+    /// Refuse to send a request once a response announced that the sunset date has
+    /// passed, if the policy is enforced
+    fn check_sunset(
+        seen: &Mutex<Option<Deprecation>>,
+        policy: SunsetPolicy,
+    ) -> Result<(), A2AError> {
+        if policy != SunsetPolicy::Enforce {
+            return Ok(());
+        }
+        match seen.lock().ok().and_then(|seen| seen.clone()) {
+            Some(seen) if seen.is_sunset(Utc::now()) => Err(seen.sunset_error()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Record the deprecation signalled by response headers, warning when it first
+    /// appears
+    fn observe_deprecation(
+        headers: &HashMap<String, String>,
+        seen: &Mutex<Option<Deprecation>>,
+        req: &A2ARequest,
+    ) {
+        let current = Deprecation::from_headers(headers);
+        if let Ok(mut seen) = seen.lock() {
+            if let Some(deprecation) = current.as_ref().filter(|d| seen.as_ref() != Some(*d)) {
+                tracing::warn!(
+                    agent = %req.context.agent_url,
+                    deprecated_at = ?deprecation.deprecated_at,
+                    sunset = ?deprecation.sunset,
+                    link = ?deprecation.link,
+                    "Agent API is deprecated"
+                );
+            }
+            seen.clone_from(&current);
+        }
+
+        if let (Some(deprecation), Some(recorder)) = (current, &req.context.meta) {
+            recorder.record(|meta| meta.deprecation = Some(deprecation));
+        }
+    }
+
+    /// Get the task endpoint of a `202 Accepted` response to a message send, if any
+    ///
+    /// Some REST agents accept messages asynchronously and return a `Location` header
+    /// pointing at the task resource instead of a task body. The location must share
+    /// the origin of the transport's base URL.
+    fn accepted_location(
+        transport_resp: &crate::transport::TransportResponse,
+        operation: &A2AOperation,
+        base_url: &url::Url,
+    ) -> Result<Option<String>, A2AError> {
+        if transport_resp.status != 202
+            || !transport_resp.body.is_empty()
+            || !matches!(operation, A2AOperation::SendMessage { .. })
+        {
+            return Ok(None);
+        }
+
+        let Some(location) = transport_resp
+            .headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("location"))
+            .map(|(_, value)| value)
+        else {
+            return Ok(None);
+        };
+
+        let url = base_url
+            .join(location)
+            .map_err(|e| A2AError::Protocol(format!("Invalid Location header: {}", e)))?;
+        let origin = |url: &url::Url| {
+            (
+                url.scheme().to_string(),
+                url.host_str().map(String::from),
+                url.port_or_known_default(),
+            )
+        };
+        if origin(&url) != origin(base_url) {
+            return Err(A2AError::Protocol(format!(
+                "Location header points to a different origin: {}",
+                url
+            )));
+        }
+
+        Ok(Some(match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        }))
+    }
+
     /// Parse a transport response into an A2A response
     fn parse_transport_response(
         transport_resp: crate::transport::TransportResponse,
         codec: &dyn Codec,
         operation: &A2AOperation,
+        transforms: &[Arc<dyn ResponseTransform>],
+        error_shape: &ErrorShape,
     ) -> Result<A2AResponse, A2AError> {
         // Check for error status codes
         if !transport_resp.is_success() {
-            return Err(Self::handle_error_response(&transport_resp));
+            return Err(Self::handle_error_response(&transport_resp, error_shape));
+        }
+
+        if transforms.is_empty() {
+            return codec.decode_response(&transport_resp.body, operation);
+        }
+
+        // Let the transforms rewrite the raw body, if it is JSON
+        let mut body = transport_resp.body;
+        if let Ok(mut value) = serde_json::from_slice::<serde_json::Value>(&body) {
+            for transform in transforms {
+                transform.transform_value(&mut value, operation)?;
+            }
+            body = serde_json::to_vec(&value)?.into();
         }
 
         // Decode the response body
-        codec.decode_response(&transport_resp.body, operation)
+        let mut response = codec.decode_response(&body, operation)?;
+        for transform in transforms {
+            transform.transform_response(&mut response, operation)?;
+        }
+        Ok(response)
     }
 
     /// Handle error responses from the transport
-    fn handle_error_response(transport_resp: &crate::transport::TransportResponse) -> A2AError {
+    fn handle_error_response(
+        transport_resp: &crate::transport::TransportResponse,
+        shape: &ErrorShape,
+    ) -> A2AError {
         // Try to parse error body as JSON
         if let Ok(json) = serde_json::from_slice::<serde_json::Value>(&transport_resp.body) {
-            if let Some(message) = json.get("message").and_then(|v| v.as_str()) {
+            if let Some(message) = shape.message(&json) {
                 return match transport_resp.status {
                     401 | 403 => A2AError::Auth(message.to_string()),
                     404 => {
-                        if let Some(task_id) = json.get("taskId").and_then(|v| v.as_str()) {
+                        if let Some(task_id) = shape.task_id(&json) {
                             A2AError::TaskNotFound {
                                 task_id: task_id.to_string(),
                             }
@@ -133,17 +551,88 @@ where
     fn call(&mut self, req: A2ARequest) -> Self::Future {
         let transport = self.transport.clone();
         let codec = self.codec.clone();
+        let endpoints = self.endpoints.clone();
+        let observer = self.timing_observer.clone();
+        let transforms = self.transforms.matching(&req.context.agent_url);
+        let error_shape = self.error_shape.clone();
+        let sunset_policy = self.sunset_policy;
+        let deprecation = self.deprecation.clone();
 
         Box::pin(async move {
+            let timed = observer.is_some() || tracing::enabled!(tracing::Level::DEBUG);
+            let mut stage = timed.then(Instant::now);
+            let mut lap = || -> Duration {
+                let Some(started) = stage.as_mut() else {
+                    return Duration::ZERO;
+                };
+                let elapsed = started.elapsed();
+                *started = Instant::now();
+                elapsed
+            };
+
             // Convert A2A request to transport request
-            let transport_req = Self::build_transport_request(&req, codec.as_ref())?;
+            let transport_req = Self::build_transport_request(
+                &req,
+                codec.as_ref(),
+                &endpoints,
+                transport.keeps_request_body(),
+            )?;
+            let encode = lap();
+
+            // Requests refused locally are not attempts
+            Self::check_sunset(&deprecation, sunset_policy)?;
+
+            if let Some(recorder) = &req.context.meta {
+                recorder.record(|meta| {
+                    meta.attempts += 1;
+                    meta.endpoint = Some(transport_req.endpoint.clone());
+                    meta.transport = Some(transport.base_url().scheme().to_string());
+                });
+            }
 
             // Execute via transport
-            let transport_resp = transport.execute(transport_req).await?;
+            let headers = transport_req.headers.clone();
+            let mut transport_resp = transport.execute(transport_req).await?;
+            Self::observe_deprecation(&transport_resp.headers, &deprecation, &req);
+
+            // Follow `202 Accepted` responses that point at the created task
+            if let Some(endpoint) =
+                Self::accepted_location(&transport_resp, &req.operation, transport.base_url())?
+            {
+                tracing::debug!(%endpoint, "Fetching task from Location of accepted request");
+                let mut fetch_req = TransportRequest::new(endpoint, "GET");
+                fetch_req.headers = headers;
+                transport_resp = transport.execute(fetch_req).await?;
+            }
+            let transport_time = lap();
 
             // Parse transport response to A2A response
-            let response =
-                Self::parse_transport_response(transport_resp, codec.as_ref(), &req.operation)?;
+            let response = Self::parse_transport_response(
+                transport_resp,
+                codec.as_ref(),
+                &req.operation,
+                &transforms,
+                &error_shape,
+            )?;
+
+            if timed {
+                let timings = StageTimings {
+                    operation: req.operation.kind(),
+                    encode,
+                    transport: transport_time,
+                    decode: lap(),
+                };
+                tracing::debug!(
+                    operation = ?timings.operation,
+                    encode_us = timings.encode.as_micros() as u64,
+                    transport_us = timings.transport.as_micros() as u64,
+                    decode_us = timings.decode.as_micros() as u64,
+                    "A2A request stage timings"
+                );
+                if let Some(observer) = &observer {
+                    observer(&timings);
+                }
+            }
 
             Ok(response)
         })
@@ -158,6 +647,12 @@ where
         Self {
             transport: self.transport.clone(),
             codec: self.codec.clone(),
+            endpoints: self.endpoints.clone(),
+            timing_observer: self.timing_observer.clone(),
+            transforms: self.transforms.clone(),
+            error_shape: self.error_shape.clone(),
+            sunset_policy: self.sunset_policy,
+            deprecation: self.deprecation.clone(),
         }
     }
 }
@@ -169,7 +664,7 @@ mod tests {
     use crate::{
         codec::JsonCodec,
         protocol::{message::Message, task::Task},
-        service::RequestContext,
+        service::{MetaRecorder, RequestContext},
         transport::{mock::MockTransport, TransportResponse},
     };
 
@@ -207,6 +702,231 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_service_response_transforms() {
+        use crate::service::transform::{map_response, map_value};
+
+        let transport = MockTransport::new(|_req| {
+            let mut task =
+                serde_json::to_value(Task::new("task-123", Message::user("Test"))).unwrap();
+            task["status"] = "COMPLETED".into();
+            TransportResponse::new(200).body(Bytes::from(task.to_string()))
+        });
+
+        let transforms = ResponseTransforms::new()
+            .with_transform(
+                "http://localhost:8080".parse().unwrap(),
+                map_value(|body, _| {
+                    let status = body["status"].as_str().map(str::to_lowercase);
+                    body["status"] = status.into();
+                }),
+            )
+            .with_transform(
+                "http://localhost:8080".parse().unwrap(),
+                map_response(|response, _| {
+                    if let A2AResponse::Task(task) = response {
+                        task.context_id = Some("ctx-normalized".into());
+                    }
+                }),
+            );
+        let mut service = A2AProtocolService::new(transport.clone(), Arc::new(JsonCodec::new()))
+            .with_transforms(transforms);
+
+        let operation = A2AOperation::GetTask {
+            task_id: "task-123".to_string(),
+        };
+        let request = A2ARequest::new(operation.clone(), RequestContext::default());
+        match service.call(request).await.unwrap() {
+            A2AResponse::Task(task) => {
+                assert_eq!(task.status, crate::protocol::TaskStatus::Completed);
+                assert_eq!(task.context_id.as_deref(), Some("ctx-normalized"));
+            }
+            other => panic!("Expected Task response, got {:?}", other),
+        }
+
+        // Other agents are not affected
+        let mut service = A2AProtocolService::new(transport, Arc::new(JsonCodec::new()));
+        let request = A2ARequest::new(operation, RequestContext::default());
+        assert!(service.call(request).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_response_transforms_of_lists_and_streams() {
+        use crate::{codec::SseEvent, service::transform::map_value};
+
+        let mut task = serde_json::to_value(Task::new("task-123", Message::user("Test"))).unwrap();
+        task["status"] = "COMPLETED".into();
+        let body = serde_json::json!({"tasks": [task], "total": 1}).to_string();
+        let transport = MockTransport::new(move |_req| {
+            TransportResponse::new(200).body(Bytes::from(body.clone()))
+        })
+        .with_stream_handler(move |_req| {
+            Ok(vec![
+                Ok(SseEvent::heartbeat("KEEPALIVE")),
+                Ok(SseEvent {
+                    kind: "task".to_string(),
+                    payload: task.clone(),
+                    final_event: true,
+                    id: None,
+                }),
+            ])
+        });
+
+        // Event payloads are single tasks
+        let lowercase = map_value(|body, _| {
+            let tasks = match body.get_mut("tasks").and_then(|tasks| tasks.as_array_mut()) {
+                Some(tasks) => tasks.iter_mut().collect(),
+                None => vec![body],
+            };
+            for task in tasks {
+                if let Some(lowered) = task["status"].as_str().map(str::to_lowercase) {
+                    task["status"] = lowered.into();
+                }
+            }
+        });
+        let service = A2AProtocolService::new(transport, Arc::new(JsonCodec)).with_transforms(
+            ResponseTransforms::new()
+                .with_transform("http://localhost:8080".parse().unwrap(), lowercase),
+        );
+
+        let list = A2AOperation::ListTasks {
+            status: None,
+            limit: None,
+            next_token: None,
+            offset: None,
+        };
+        let items: Vec<_> = service
+            .call_task_list(A2ARequest::new(list, RequestContext::default()))
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        match &items[..] {
+            [Ok(TaskListItem::Task(task)), Ok(TaskListItem::End { total: 1, .. })] => {
+                assert_eq!(task.status, crate::protocol::TaskStatus::Completed)
+            }
+            other => panic!("Expected one task, got {:?}", other),
+        }
+
+        let subscribe = A2AOperation::SubscribeTask {
+            task_id: "task-123".to_string(),
+        };
+        let events: Vec<_> = service
+            .call_streaming(A2ARequest::new(subscribe, RequestContext::default()))
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        let events: Vec<_> = events.into_iter().map(Result::unwrap).collect();
+        assert_eq!(events[0].payload, "KEEPALIVE");
+        assert_eq!(events[1].payload["status"], "completed");
+    }
+
+    #[tokio::test]
+    async fn test_service_custom_endpoints() {
+        let transport = MockTransport::new(|req| {
+            assert_eq!(req.endpoint, "/api/a2a/v1/tasks/task-123");
+
+            let task = Task::new("task-123", Message::user("Test"));
+            let json = serde_json::to_vec(&task).unwrap();
+            TransportResponse::new(200).body(Bytes::from(json))
+        });
+
+        let codec = Arc::new(JsonCodec);
+        let mut service = A2AProtocolService::new(transport, codec)
+            .with_endpoints(EndpointMap::new().with_prefix("/api/a2a"));
+
+        let operation = A2AOperation::GetTask {
+            task_id: "task-123".to_string(),
+        };
+
+        let request = A2ARequest::new(operation, RequestContext::default());
+        let response = service.call(request).await.unwrap();
+
+        assert!(matches!(response, A2AResponse::Task(_)));
+    }
+
+    #[tokio::test]
+    async fn test_service_rest_binding() {
+        let transport = MockTransport::new(|req| {
+            assert_eq!(req.endpoint, "/v1/tasks?page_size=5");
+            assert_eq!(req.method, "GET");
+            assert!(req.body.is_empty());
+
+            TransportResponse::new(200).body(Bytes::from(r#"{"tasks": [], "total": 0}"#))
+        });
+
+        let codec = Arc::new(JsonCodec);
+        let mut service =
+            A2AProtocolService::new(transport, codec).with_endpoints(EndpointMap::rest());
+
+        let operation = A2AOperation::ListTasks {
+            status: None,
+            limit: Some(5),
+            offset: None,
+            next_token: None,
+        };
+
+        let request = A2ARequest::new(operation, RequestContext::default());
+        let response = service.call(request).await.unwrap();
+
+        assert!(matches!(response, A2AResponse::TaskList { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_service_follows_accepted_location() {
+        let transport = MockTransport::new(|req| match req.method.as_str() {
+            "POST" => TransportResponse::new(202).header("location", "/v1/tasks/task-789"),
+            _ => {
+                assert_eq!(req.endpoint, "/v1/tasks/task-789");
+                assert!(req.headers.contains_key(headers::A2A_VERSION));
+
+                let task = Task::new("task-789", Message::user("Test"));
+                TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+            }
+        });
+
+        let codec = Arc::new(JsonCodec);
+        let mut service = A2AProtocolService::new(transport, codec);
+
+        let operation = A2AOperation::SendMessage {
+            message: Message::user("Hello"),
+            stream: false,
+            context_id: None,
+            task_id: None,
+        };
+
+        let request = A2ARequest::new(operation, RequestContext::default());
+        let response = service.call(request).await.unwrap();
+
+        match response {
+            A2AResponse::Task(task) => assert_eq!(task.id, "task-789"),
+            _ => panic!("Expected task response"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_service_rejects_foreign_location() {
+        let transport = MockTransport::new(|_req| {
+            TransportResponse::new(202).header("Location", "https://elsewhere.example/tasks/1")
+        });
+
+        let codec = Arc::new(JsonCodec);
+        let mut service = A2AProtocolService::new(transport, codec);
+
+        let operation = A2AOperation::SendMessage {
+            message: Message::user("Hello"),
+            stream: false,
+            context_id: None,
+            task_id: None,
+        };
+
+        let request = A2ARequest::new(operation, RequestContext::default());
+        let result = service.call(request).await;
+
+        assert!(matches!(result, Err(A2AError::Protocol(_))));
+    }
+
     #[tokio::test]
     async fn test_service_error_handling() {
         // Create a mock transport that returns an error
@@ -229,4 +949,81 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), A2AError::Auth(_)));
     }
+
+    #[tokio::test]
+    async fn test_timing_observer() {
+        let transport = MockTransport::new(|_req| {
+            std::thread::sleep(Duration::from_millis(5));
+            let task = Task::new("task-123", Message::user("Test"));
+            TransportResponse::new(200).body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+        let timings = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = timings.clone();
+        let mut service = A2AProtocolService::new(transport, Arc::new(JsonCodec))
+            .with_timing_observer(move |t| recorded.lock().unwrap().push(*t));
+
+        let operation = A2AOperation::GetTask {
+            task_id: "task-123".to_string(),
+        };
+        service
+            .call(A2ARequest::new(operation, RequestContext::default()))
+            .await
+            .unwrap();
+
+        let timings = timings.lock().unwrap();
+        assert_eq!(timings.len(), 1);
+        assert_eq!(timings[0].operation, OperationKind::GetTask);
+        assert!(timings[0].transport >= Duration::from_millis(5));
+    }
+
+    #[tokio::test]
+    async fn test_sunset_policy() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = calls.clone();
+        let transport = MockTransport::new(move |_req| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let task = Task::new("task-123", Message::user("Test"));
+            TransportResponse::new(200)
+                .header("Sunset", "Sat, 31 Dec 2022 23:59:59 GMT")
+                .body(Bytes::from(serde_json::to_vec(&task).unwrap()))
+        });
+        let get_task = || {
+            let recorder = MetaRecorder::new();
+            let context = RequestContext::default().with_meta_recorder(recorder.clone());
+            let operation = A2AOperation::GetTask {
+                task_id: "task-123".to_string(),
+            };
+            (A2ARequest::new(operation, context), recorder)
+        };
+
+        let mut service = A2AProtocolService::new(transport.clone(), Arc::new(JsonCodec::new()));
+        let (request, recorder) = get_task();
+        assert!(service.call(request).await.is_ok());
+        let sunset = recorder.snapshot().deprecation.unwrap().sunset;
+        assert_eq!(service.deprecation().unwrap().sunset, sunset);
+
+        let mut service = service.with_sunset_policy(SunsetPolicy::Enforce);
+        let (request, recorder) = get_task();
+        assert!(matches!(
+            service.call(request).await,
+            Err(A2AError::Protocol(_))
+        ));
+        assert_eq!(recorder.snapshot().attempts, 0);
+        assert!(recorder.snapshot().endpoint.is_none());
+        let (request, _) = get_task();
+        assert!(matches!(
+            service.call_streaming(request).await,
+            Err(A2AError::Protocol(_))
+        ));
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // The response announcing the sunset is returned, later requests are refused
+        let mut service = A2AProtocolService::new(transport, Arc::new(JsonCodec::new()))
+            .with_sunset_policy(SunsetPolicy::Enforce);
+        let (request, _) = get_task();
+        assert!(service.call(request).await.is_ok());
+        let (request, _) = get_task();
+        assert!(service.call(request).await.is_err());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
 }
@@ -0,0 +1,177 @@
+//! Deprecation and sunset signals of agents
+//!
+//! Agents announce that an API is going away with the `Deprecation` header (RFC 9745)
+//! and the date it stops working with the `Sunset` header (RFC 8594), optionally
+//! linking to migration notes:
+//!
+//! ```text
+//! Deprecation: @1767225600
+//! Sunset: Wed, 30 Jun 2027 23:59:59 GMT
+//! Link: <https://agent.example.com/migration>; rel="deprecation"
+//! ```
+//!
+//! [`A2AProtocolService`](crate::service::A2AProtocolService) parses these headers on
+//! every response, logs a warning when they first appear, and reports them in
+//! [`ResponseMeta::deprecation`](crate::service::ResponseMeta::deprecation). With
+//! [`SunsetPolicy::Enforce`], requests are refused once a response announced that the
+//! sunset date has passed.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::protocol::error::A2AError;
+
+/// Deprecation announced by an agent
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Deprecation {
+    /// When the API was or will be deprecated, if the agent gave a date
+    pub deprecated_at: Option<DateTime<Utc>>,
+
+    /// When the API stops working, if announced
+    pub sunset: Option<DateTime<Utc>>,
+
+    /// Link to deprecation or migration information
+    pub link: Option<String>,
+}
+
+impl Deprecation {
+    /// Read the deprecation signalled by response headers, if any
+    ///
+    /// Header names are matched ignoring case. `Deprecation` accepts structured dates
+    /// (`@<unix seconds>`), HTTP dates, and the legacy `true`.
+    pub fn from_headers(headers: &HashMap<String, String>) -> Option<Self> {
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.trim())
+        };
+
+        let deprecation = header("deprecation").filter(|value| *value != "false");
+        let sunset = header("sunset").and_then(parse_http_date);
+        if deprecation.is_none() && sunset.is_none() {
+            return None;
+        }
+
+        Some(Self {
+            deprecated_at: deprecation.and_then(|value| match value.strip_prefix('@') {
+                Some(seconds) => DateTime::from_timestamp(seconds.parse().ok()?, 0),
+                None => parse_http_date(value),
+            }),
+            sunset,
+            link: header("link").and_then(|links| {
+                deprecation_link(links, "deprecation").or_else(|| deprecation_link(links, "sunset"))
+            }),
+        })
+    }
+
+    /// Check whether the sunset date has passed
+    pub fn is_sunset(&self, now: DateTime<Utc>) -> bool {
+        self.sunset.is_some_and(|sunset| sunset <= now)
+    }
+
+    /// Get the error failing requests past the sunset date
+    pub(crate) fn sunset_error(&self) -> A2AError {
+        let sunset = self
+            .sunset
+            .map(|sunset| sunset.to_rfc2822())
+            .unwrap_or_default();
+        let link = self
+            .link
+            .as_ref()
+            .map(|link| format!("; see {}", link))
+            .unwrap_or_default();
+        A2AError::Protocol(format!("Agent API was sunset on {}{}", sunset, link))
+    }
+}
+
+/// What to do with requests to an agent past its announced sunset date
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SunsetPolicy {
+    /// Log a warning and carry on
+    #[default]
+    Warn,
+
+    /// Fail requests with `A2AError::Protocol`, without sending them, once a response
+    /// announced that the sunset date has passed
+    ///
+    /// The response carrying the announcement is still returned, so a request the agent
+    /// already acted on does not appear to have failed.
+    Enforce,
+}
+
+/// Parse an HTTP date, e.g. `Wed, 30 Jun 2027 23:59:59 GMT`
+fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+/// Find the URL of a `Link` header entry with the given relation
+fn deprecation_link(links: &str, relation: &str) -> Option<String> {
+    links.split(',').find_map(|link| {
+        let mut params = link.split(';').map(str::trim);
+        let url = params.next()?.strip_prefix('<')?.strip_suffix('>')?;
+        params
+            .filter_map(|param| param.strip_prefix("rel="))
+            .any(|rel| {
+                rel.trim_matches('"')
+                    .split_whitespace()
+                    .any(|rel| rel.eq_ignore_ascii_case(relation))
+            })
+            .then(|| url.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_deprecation_from_headers() {
+        let deprecation = Deprecation::from_headers(&headers(&[
+            ("deprecation", "@1767225600"),
+            ("Sunset", "Wed, 30 Jun 2027 23:59:59 GMT"),
+            (
+                "link",
+                r#"<https://example.com/docs>; rel="help", <https://example.com/migrate>; rel="deprecation""#,
+            ),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            deprecation.deprecated_at.unwrap().to_rfc3339(),
+            "2026-01-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            deprecation.sunset.unwrap().to_rfc3339(),
+            "2027-06-30T23:59:59+00:00"
+        );
+        assert_eq!(deprecation.link.unwrap(), "https://example.com/migrate");
+        assert!(
+            !Deprecation::from_headers(&headers(&[("Deprecation", "true")]))
+                .unwrap()
+                .is_sunset(Utc::now())
+        );
+
+        assert!(Deprecation::from_headers(&headers(&[("Deprecation", "false")])).is_none());
+        assert!(Deprecation::from_headers(&headers(&[("Location", "/tasks/1")])).is_none());
+    }
+
+    #[test]
+    fn test_is_sunset() {
+        let deprecation =
+            Deprecation::from_headers(&headers(&[("Sunset", "Sat, 31 Dec 2022 23:59:59 GMT")]))
+                .unwrap();
+        assert!(deprecation.deprecated_at.is_none());
+        assert!(deprecation.is_sunset(Utc::now()));
+    }
+}
@@ -0,0 +1,448 @@
+//! Executors running background tasks
+//!
+//! Some work outlives the request that started it: the WebSocket reader, eager
+//! connects and agent card prefetches, and discovery refreshes. By default it is
+//! spawned on the ambient Tokio runtime. Applications keeping the client's work on a
+//! dedicated runtime, or tracking the tasks they spawn, inject an [`Executor`]
+//! instead:
+//!
+//! ```rust,no_run
+//! use tower_a2a::{prelude::*, service::TokioExecutor};
+//!
+//! let runtime = tokio::runtime::Runtime::new().unwrap();
+//! let url = "https://agent.example.com".parse().unwrap();
+//! let client = A2AClientBuilder::new_http(url)
+//!     .with_executor(TokioExecutor::with_handle(runtime.handle().clone()))
+//!     .with_eager_connect(true)
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! The executor decides where tasks run, not which runtime the client needs: the
+//! client requires Tokio. The WebSocket reader uses Tokio's I/O, and timeouts, backoff
+//! delays, and refresh intervals use its timer, so executors must run tasks on a
+//! Tokio runtime, and requests must be awaited with one entered.
+//!
+//! Every task has a name, e.g. `a2a-ws-reader`. Tasks spawned through an
+//! [`ExecutorHandle`] run in a `tracing` span carrying the name and are supervised:
+//! panics are logged and reported by [`ExecutorHandle::health`], and tasks spawned with
+//! [`ExecutorHandle::spawn_restarting`] are restarted. Built with
+//! `RUSTFLAGS="--cfg tokio_unstable"`, [`TokioExecutor`] also passes the names on to
+//! tokio-console.
+
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    fmt,
+    future::Future,
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use futures::{
+    future::{self, AbortHandle},
+    FutureExt,
+};
+use tokio::runtime::Handle;
+use tracing::Instrument;
+
+/// Boxed background task
+pub type BackgroundTask = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Number of times a restarting task is restarted after panicking
+pub const MAX_RESTARTS: u32 = 3;
+
+/// Runtime that background tasks are spawned on
+pub trait Executor: Send + Sync {
+    /// Run a named task in the background, without waiting for it
+    fn spawn(&self, name: &str, task: BackgroundTask);
+}
+
+impl<F> Executor for F
+where
+    F: Fn(&str, BackgroundTask) + Send + Sync,
+{
+    fn spawn(&self, name: &str, task: BackgroundTask) {
+        self(name, task)
+    }
+}
+
+/// Executor spawning on a Tokio runtime
+#[derive(Debug, Clone, Default)]
+pub struct TokioExecutor {
+    handle: Option<Handle>,
+}
+
+impl TokioExecutor {
+    /// Spawn on the runtime that is current when a task is spawned
+    ///
+    /// Tasks spawned outside a Tokio runtime are dropped with a warning.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn on a specific runtime
+    pub fn with_handle(handle: Handle) -> Self {
+        Self {
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Executor for TokioExecutor {
+    fn spawn(&self, name: &str, task: BackgroundTask) {
+        let Some(handle) = self.handle.clone().or_else(|| Handle::try_current().ok()) else {
+            tracing::warn!(
+                task = name,
+                "Background task skipped outside a Tokio runtime"
+            );
+            return;
+        };
+
+        #[cfg(tokio_unstable)]
+        if let Err(e) = tokio::task::Builder::new()
+            .name(name)
+            .spawn_on(task, &handle)
+        {
+            tracing::warn!(task = name, error = %e, "Failed to spawn background task");
+        }
+        #[cfg(not(tokio_unstable))]
+        drop(handle.spawn(task));
+    }
+}
+
+/// State of a supervised background task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskState {
+    /// The task is running
+    Running,
+
+    /// The task completed
+    Finished,
+
+    /// The task was aborted through its handle
+    Aborted,
+
+    /// The task panicked and was not restarted
+    Panicked {
+        /// The panic message
+        message: String,
+    },
+}
+
+/// Health of a supervised background task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskHealth {
+    /// Name of the task
+    pub name: String,
+
+    /// Current state
+    pub state: TaskState,
+
+    /// Number of times the task was restarted after panicking
+    pub restarts: u32,
+}
+
+/// Health of the background tasks spawned through an executor
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BackgroundHealth {
+    /// The tasks ordered by name, then from oldest to most recent
+    ///
+    /// Running and panicked tasks are all listed, even if they share a name. Of the
+    /// tasks that finished or were aborted, only the most recent of each name is kept.
+    pub tasks: Vec<TaskHealth>,
+}
+
+impl BackgroundHealth {
+    /// Check that no task has panicked for good
+    pub fn is_healthy(&self) -> bool {
+        !self
+            .tasks
+            .iter()
+            .any(|task| matches!(task.state, TaskState::Panicked { .. }))
+    }
+
+    /// Get the health of the most recent task of a name
+    pub fn task(&self, name: &str) -> Option<&TaskHealth> {
+        self.tasks.iter().rev().find(|task| task.name == name)
+    }
+}
+
+/// Shared handle to an executor, supervising the tasks spawned through it
+///
+/// Defaults to [`TokioExecutor::new`]. Clones share the record of spawned tasks.
+#[derive(Clone)]
+pub struct ExecutorHandle {
+    executor: Arc<dyn Executor>,
+    tasks: Arc<Mutex<Tasks>>,
+}
+
+/// Health records of spawned tasks by spawn order
+#[derive(Default)]
+struct Tasks {
+    next_id: u64,
+    health: BTreeMap<u64, TaskHealth>,
+}
+
+impl ExecutorHandle {
+    /// Share an executor
+    pub fn new(executor: impl Executor + 'static) -> Self {
+        Self {
+            executor: Arc::new(executor),
+            tasks: Arc::default(),
+        }
+    }
+
+    /// Run a named future in the background
+    ///
+    /// The returned handle aborts the task; dropping it does not.
+    pub fn spawn(
+        &self,
+        name: &str,
+        task: impl Future<Output = ()> + Send + 'static,
+    ) -> AbortHandle {
+        let mut task = Some(task);
+        self.supervise(name, false, move || {
+            Box::pin(task.take().expect("tasks without restarts run once"))
+        })
+    }
+
+    /// Run a named task in the background, restarting it up to [`MAX_RESTARTS`] times
+    /// if it panics
+    pub fn spawn_restarting<F, Fut>(&self, name: &str, task: F) -> AbortHandle
+    where
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.supervise(name, true, move || Box::pin(task()))
+    }
+
+    /// Get the health of the tasks spawned through this handle and its clones
+    pub fn health(&self) -> BackgroundHealth {
+        let mut tasks: Vec<_> = self
+            .tasks
+            .lock()
+            .map(|tasks| tasks.health.values().cloned().collect())
+            .unwrap_or_default();
+        // Stable, so tasks of a name stay in spawn order
+        tasks.sort_by(|a, b| a.name.cmp(&b.name));
+        BackgroundHealth { tasks }
+    }
+
+    fn supervise(
+        &self,
+        name: &str,
+        restart: bool,
+        mut make: impl FnMut() -> BackgroundTask + Send + 'static,
+    ) -> AbortHandle {
+        let record = Record::new(self.tasks.clone(), name);
+
+        let supervisor = record.clone();
+        let (supervised, handle) = future::abortable(async move {
+            let mut restarts = 0;
+            loop {
+                let Err(panic) = AssertUnwindSafe(make()).catch_unwind().await else {
+                    return supervisor.set(TaskState::Finished, restarts);
+                };
+
+                let message = panic_message(panic.as_ref());
+                tracing::error!(%message, restarts, "Background task panicked");
+                if !restart || restarts >= MAX_RESTARTS {
+                    return supervisor.set(TaskState::Panicked { message }, restarts);
+                }
+                restarts += 1;
+                supervisor.set(TaskState::Running, restarts);
+            }
+        });
+
+        let span = tracing::info_span!("a2a_task", task = name);
+        let task = async move {
+            if supervised.await.is_err() {
+                record.update(|health| health.state = TaskState::Aborted);
+            }
+        };
+        self.executor.spawn(name, Box::pin(task.instrument(span)));
+        handle
+    }
+}
+
+impl Default for ExecutorHandle {
+    fn default() -> Self {
+        Self::new(TokioExecutor::new())
+    }
+}
+
+impl fmt::Debug for ExecutorHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecutorHandle").finish_non_exhaustive()
+    }
+}
+
+/// Entry of one task in the record shared by an executor handle and its clones
+#[derive(Clone)]
+struct Record {
+    tasks: Arc<Mutex<Tasks>>,
+    id: u64,
+    name: String,
+}
+
+impl Record {
+    /// Record a running task, forgetting the ended tasks of the same name
+    fn new(tasks: Arc<Mutex<Tasks>>, name: &str) -> Self {
+        let id = match tasks.lock() {
+            Ok(mut tasks) => {
+                tasks.health.retain(|_, health| {
+                    health.name != name
+                        || !matches!(health.state, TaskState::Finished | TaskState::Aborted)
+                });
+                tasks.next_id += 1;
+                tasks.next_id
+            }
+            Err(_) => 0,
+        };
+        let record = Self {
+            tasks,
+            id,
+            name: name.to_string(),
+        };
+        record.set(TaskState::Running, 0);
+        record
+    }
+
+    fn set(&self, state: TaskState, restarts: u32) {
+        let health = TaskHealth {
+            name: self.name.clone(),
+            state,
+            restarts,
+        };
+        if let Ok(mut tasks) = self.tasks.lock() {
+            tasks.health.insert(self.id, health);
+        }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut TaskHealth)) {
+        if let Ok(mut tasks) = self.tasks.lock() {
+            if let Some(health) = tasks.health.get_mut(&self.id) {
+                f(health);
+            }
+        }
+    }
+}
+
+/// Get the message of a panic payload
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    /// Tasks queued by name
+    type Queue = Arc<Mutex<Vec<(String, BackgroundTask)>>>;
+
+    /// Executor queueing tasks to run them on demand
+    fn queue() -> (ExecutorHandle, Queue) {
+        let queued: Queue = Arc::default();
+        let queue = queued.clone();
+        let executor = ExecutorHandle::new(move |name: &str, task| {
+            queue.lock().unwrap().push((name.to_string(), task))
+        });
+        (executor, queued)
+    }
+
+    #[test]
+    fn test_custom_executor() {
+        let (executor, queued) = queue();
+        let (tx, rx) = std::sync::mpsc::channel();
+        executor.spawn("a2a-test", async move { tx.send("ran").unwrap() });
+        assert!(rx.try_recv().is_err());
+
+        for (name, task) in queued.lock().unwrap().drain(..) {
+            assert_eq!(name, "a2a-test");
+            futures::executor::block_on(task);
+        }
+        assert_eq!(rx.try_recv().unwrap(), "ran");
+    }
+
+    #[test]
+    fn test_tokio_executor_with_handle() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let executor = ExecutorHandle::new(TokioExecutor::with_handle(runtime.handle().clone()));
+
+        // No runtime is current on this thread, so the task must go to the handle
+        let (tx, rx) = std::sync::mpsc::channel();
+        executor.spawn("a2a-test", async move { tx.send("ran").unwrap() });
+        let ran = rx.recv_timeout(std::time::Duration::from_secs(5));
+        assert_eq!(ran.unwrap(), "ran");
+    }
+
+    #[test]
+    fn test_supervised_tasks() {
+        let (executor, queued) = queue();
+        executor.spawn("done", async {});
+        executor.spawn("crashed", async { panic!("reader failed") });
+        let attempts = Arc::new(AtomicU32::new(0));
+        let counter = attempts.clone();
+        executor.spawn_restarting("flaky", move || {
+            let attempt = counter.fetch_add(1, Ordering::SeqCst);
+            async move { assert!(attempt >= 2, "attempt {} failed", attempt) }
+        });
+        executor.spawn("stuck", future::pending()).abort();
+        assert!(executor
+            .health()
+            .tasks
+            .iter()
+            .all(|task| task.state == TaskState::Running));
+
+        for (_, task) in queued.lock().unwrap().drain(..) {
+            futures::executor::block_on(task);
+        }
+        let health = executor.clone().health();
+        assert!(!health.is_healthy());
+        assert_eq!(health.task("done").unwrap().state, TaskState::Finished);
+        assert_eq!(
+            health.task("crashed").unwrap().state,
+            TaskState::Panicked {
+                message: "reader failed".into()
+            }
+        );
+        let flaky = health.task("flaky").unwrap();
+        assert_eq!((&flaky.state, flaky.restarts), (&TaskState::Finished, 2));
+        assert_eq!(health.task("stuck").unwrap().state, TaskState::Aborted);
+    }
+
+    #[test]
+    fn test_tasks_sharing_a_name() {
+        let (executor, queued) = queue();
+        executor.spawn("reader", async { panic!("first reader failed") });
+        executor.spawn("reader", async {});
+        for (_, task) in queued.lock().unwrap().drain(..) {
+            futures::executor::block_on(task);
+        }
+
+        // A later task of the same name does not hide a panic
+        let health = executor.health();
+        assert!(!health.is_healthy());
+        assert_eq!(health.tasks.len(), 2);
+        assert_eq!(health.task("reader").unwrap().state, TaskState::Finished);
+
+        // Ended tasks are forgotten once another task of the name is spawned
+        executor.spawn("reader", future::pending());
+        let states: Vec<_> = executor
+            .health()
+            .tasks
+            .into_iter()
+            .map(|task| task.state)
+            .collect();
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[1], TaskState::Running);
+    }
+}
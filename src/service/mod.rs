@@ -1,9 +1,22 @@
 //! Tower Service implementations
 
 pub mod core;
+pub mod deprecation;
+pub mod executor;
+pub mod namespace;
 pub mod request;
 pub mod response;
+pub mod stack;
+pub mod transform;
 
-pub use core::A2AProtocolService;
+pub use core::{A2AProtocolService, ErrorShape, StageTimings, TaskListStream, TimingObserver};
+pub use deprecation::{Deprecation, SunsetPolicy};
+pub use executor::{
+    BackgroundHealth, BackgroundTask, Executor, ExecutorHandle, TaskHealth, TaskState,
+    TokioExecutor,
+};
+pub use namespace::ContextNamespace;
 pub use request::{A2ARequest, RequestContext};
-pub use response::A2AResponse;
+pub use response::{A2AResponse, MetaRecorder, ResponseMeta};
+pub use stack::ProtocolStack;
+pub use transform::{ResponseTransform, ResponseTransforms};
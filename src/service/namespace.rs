@@ -0,0 +1,305 @@
+//! Namespacing of context IDs per tenant
+//!
+//! Agents group messages into conversations by `contextId`. When one client serves
+//! several tenants, two tenants picking the same context ID would share a
+//! conversation on the agent, and with it each other's history. A
+//! [`ContextNamespace`] prefixes the context IDs of outgoing messages with the
+//! tenant's namespace and strips it from responses, so callers keep using their own
+//! IDs while the agent only sees namespaced ones:
+//!
+//! ```rust
+//! use tower_a2a::service::ContextNamespace;
+//!
+//! let namespace = ContextNamespace::prefix("acme");
+//! assert_eq!(namespace.namespace("ctx-1"), "4:acme:ctx-1");
+//! assert_eq!(namespace.strip("4:acme:ctx-1").unwrap(), "ctx-1");
+//! assert!(namespace.strip("6:globex:ctx-1").is_err());
+//! ```
+//!
+//! [`ContextNamespace::hmac`] derives the namespace from a tenant key instead, so
+//! agents cannot tell which tenant a conversation belongs to.
+
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+
+use crate::{
+    codec::SseEvent,
+    protocol::{error::A2AError, operation::A2AOperation, Message, Task},
+    service::A2AResponse,
+};
+
+/// Number of bytes of the HMAC kept in derived namespaces
+const HMAC_TAG_LEN: usize = 12;
+
+/// Namespace of a tenant's context IDs
+///
+/// Applied to a request, outgoing messages starting a task without a context ID get a
+/// fresh one, so every context the agent returns can be checked against the
+/// namespace. Context IDs of other namespaces in responses fail the request with
+/// `A2AError::Validation`, except in task listings, which leave out the tasks of other
+/// tenants and tasks without a context.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContextNamespace {
+    prefix: String,
+}
+
+impl ContextNamespace {
+    /// Namespace context IDs with the tenant's name, as `{length}:{tenant}:{id}`
+    ///
+    /// The length of the name keeps namespaces apart even if names contain colons,
+    /// e.g. tenant `a` with context `b:c` and tenant `a:b` with context `c`.
+    pub fn prefix(tenant: impl AsRef<str>) -> Self {
+        let tenant = tenant.as_ref();
+        Self {
+            prefix: format!("{}:{}:", tenant.len(), tenant),
+        }
+    }
+
+    /// Namespace context IDs with a tag derived from the tenant's name and key
+    ///
+    /// The tag is the hex-encoded, truncated HMAC-SHA256 of the tenant's name, so it
+    /// is stable for a key but does not disclose the tenant.
+    pub fn hmac(tenant: impl AsRef<str>, key: &[u8]) -> Self {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(tenant.as_ref().as_bytes());
+        let tag: String = mac.finalize().into_bytes()[..HMAC_TAG_LEN]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        Self {
+            prefix: format!("{}:", tag),
+        }
+    }
+
+    /// Get the prefix of namespaced context IDs
+    pub fn as_str(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Namespace a context ID
+    pub fn namespace(&self, context_id: &str) -> String {
+        format!("{}{}", self.prefix, context_id)
+    }
+
+    /// Strip the namespace from a context ID
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Validation` if the ID is not in this namespace
+    pub fn strip<'a>(&self, context_id: &'a str) -> Result<&'a str, A2AError> {
+        context_id.strip_prefix(&self.prefix).ok_or_else(|| {
+            A2AError::Validation(format!(
+                "Context ID '{}' is outside the tenant namespace",
+                context_id
+            ))
+        })
+    }
+
+    /// Namespace the context IDs of an outgoing operation
+    ///
+    /// Messages starting a task without a context ID get a fresh one. Messages
+    /// continuing a task keep the task's context, so they are sent without one.
+    pub fn apply(&self, operation: &mut A2AOperation) {
+        let A2AOperation::SendMessage {
+            message,
+            context_id,
+            task_id,
+            ..
+        } = operation
+        else {
+            return;
+        };
+
+        let id = match context_id.as_deref().or(message.context_id.as_deref()) {
+            Some(id) => self.namespace(id),
+            None if task_id.is_some() || message.task_id.is_some() => return,
+            None => self.namespace(&uuid::Uuid::now_v7().to_string()),
+        };
+        if context_id.is_some() {
+            *context_id = Some(id.clone());
+        }
+        message.context_id = Some(id);
+    }
+
+    /// Strip the namespace from the context IDs of a response
+    ///
+    /// Tasks of other namespaces, and tasks without a context, are removed from task
+    /// lists.
+    pub fn strip_response(&self, response: &mut A2AResponse) -> Result<(), A2AError> {
+        match response {
+            A2AResponse::Task(task) => self.strip_task(task),
+            A2AResponse::TaskList { tasks, .. } => {
+                tasks.retain_mut(|task| self.strip_listed(task).is_ok());
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Strip the namespace from a task of a listing, which must have a context in the
+    /// namespace
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Validation` if the task has no context or one outside the
+    /// namespace
+    pub fn strip_listed(&self, task: &mut Task) -> Result<(), A2AError> {
+        if task.context_id.is_none() {
+            return Err(A2AError::Validation(format!(
+                "Task '{}' has no context ID",
+                task.id
+            )));
+        }
+        self.strip_task(task)
+    }
+
+    /// Strip the namespace from the context IDs of a task and its messages
+    pub fn strip_task(&self, task: &mut Task) -> Result<(), A2AError> {
+        self.strip_field(&mut task.context_id)?;
+        self.strip_message(&mut task.input)?;
+        for message in &mut task.history {
+            self.strip_message(message)?;
+        }
+        Ok(())
+    }
+
+    /// Strip the namespace from the context ID of a message
+    pub fn strip_message(&self, message: &mut Message) -> Result<(), A2AError> {
+        self.strip_field(&mut message.context_id)
+    }
+
+    /// Strip the namespace from the context IDs of a streamed event
+    ///
+    /// Only the protocol fields are touched: the event's `contextId` and those of the
+    /// messages it carries in `status.message`, `input`, and `history`. Data parts and
+    /// metadata are left alone.
+    pub fn strip_event(&self, event: &mut SseEvent) -> Result<(), A2AError> {
+        let payload = &mut event.payload;
+        self.strip_value(payload)?;
+        for pointer in ["/status/message", "/input"] {
+            if let Some(message) = payload.pointer_mut(pointer) {
+                self.strip_value(message)?;
+            }
+        }
+        if let Some(Value::Array(history)) = payload.get_mut("history") {
+            history
+                .iter_mut()
+                .try_for_each(|message| self.strip_value(message))?;
+        }
+        Ok(())
+    }
+
+    fn strip_field(&self, context_id: &mut Option<String>) -> Result<(), A2AError> {
+        if let Some(id) = context_id {
+            *id = self.strip(id)?.to_string();
+        }
+        Ok(())
+    }
+
+    /// Strip the namespace from the `contextId` of a JSON object
+    fn strip_value(&self, value: &mut Value) -> Result<(), A2AError> {
+        if let Some(Value::String(id)) = value.get_mut("contextId") {
+            *id = self.strip(id)?.to_string();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_context_namespace() {
+        let namespace = ContextNamespace::hmac("acme", b"secret");
+        assert_eq!(namespace, ContextNamespace::hmac("acme", b"secret"));
+        assert_ne!(namespace, ContextNamespace::hmac("acme", b"other"));
+        assert!(!namespace.as_str().contains("acme"));
+
+        let mut operation = A2AOperation::SendMessage {
+            message: Message::user("Hello"),
+            stream: false,
+            context_id: Some("ctx-1".into()),
+            task_id: None,
+        };
+        namespace.apply(&mut operation);
+        let A2AOperation::SendMessage { message, .. } = &operation else {
+            unreachable!();
+        };
+        let sent = message.context_id.clone().unwrap();
+        assert_eq!(sent, namespace.namespace("ctx-1"));
+
+        // Messages continuing a task keep its context
+        let mut operation = A2AOperation::SendMessage {
+            message: Message::user("Yes"),
+            stream: false,
+            context_id: None,
+            task_id: Some("task-1".into()),
+        };
+        namespace.apply(&mut operation);
+        let A2AOperation::SendMessage { message: reply, .. } = &operation else {
+            unreachable!();
+        };
+        assert!(reply.context_id.is_none());
+
+        let mut task = Task::new("task-1", message.clone());
+        task.context_id = Some(sent.clone());
+        let mut other = task.clone();
+        other.context_id = Some("ctx-1".into());
+        let mut unscoped = Task::new("task-2", Message::user("Hello"));
+        unscoped.context_id = None;
+
+        let mut response = A2AResponse::TaskList {
+            tasks: vec![task.clone(), other, unscoped],
+            total: 3,
+            next_token: None,
+        };
+        namespace.strip_response(&mut response).unwrap();
+        let tasks = response.into_task_list().unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].context_id.as_deref(), Some("ctx-1"));
+        assert_eq!(tasks[0].input.context_id.as_deref(), Some("ctx-1"));
+
+        let mut event = SseEvent {
+            kind: "status-update".into(),
+            payload: json!({
+                "taskId": "task-1",
+                "contextId": sent,
+                "status": {
+                    "state": "working",
+                    "message": {
+                        "role": "agent",
+                        "contextId": sent,
+                        "parts": [{"kind": "data", "data": {"contextId": "user data"}}]
+                    }
+                }
+            }),
+            final_event: false,
+            id: None,
+        };
+        namespace.strip_event(&mut event).unwrap();
+        assert_eq!(event.payload["contextId"], "ctx-1");
+        assert_eq!(event.payload["status"]["message"]["contextId"], "ctx-1");
+        assert_eq!(
+            event.payload["status"]["message"]["parts"][0]["data"]["contextId"],
+            "user data"
+        );
+        assert!(namespace.strip_event(&mut event).is_err());
+    }
+
+    #[test]
+    fn test_prefixes_are_unambiguous() {
+        let (a, ab) = (
+            ContextNamespace::prefix("a"),
+            ContextNamespace::prefix("a:b"),
+        );
+        assert_ne!(a.namespace("b:c"), ab.namespace("c"));
+        assert!(a.strip(&ab.namespace("c")).is_err());
+        assert!(ab.strip(&a.namespace("b:c")).is_err());
+        assert_eq!(a.strip(&a.namespace("b:c")).unwrap(), "b:c");
+    }
+}
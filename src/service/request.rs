@@ -1,15 +1,36 @@
 //! A2A service request types
 
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
 
+use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::{layer::auth::AuthCredentials, protocol::operation::A2AOperation};
+use crate::{
+    layer::auth::AuthCredentials,
+    protocol::{
+        consts::{extensions, headers},
+        operation::{A2AOperation, OperationKind},
+    },
+    service::{namespace::ContextNamespace, response::MetaRecorder},
+};
+
+/// Version of the serialized request format
+const REQUEST_FORMAT_VERSION: u32 = 1;
 
 /// A request to the A2A service
 ///
 /// This wraps an A2A operation with additional context needed for execution
-#[derive(Debug, Clone)]
+///
+/// Requests serialize to a versioned representation for persistence and queuing. The
+/// authentication credentials, metadata headers carrying credentials (see
+/// [`headers::SENSITIVE`]), the operation deadline, the response meta recorder, and the
+/// context namespace are never serialized; the credentials must be re-applied (e.g.,
+/// by `AuthLayer`) when a stored request is replayed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "StoredRequest", try_from = "StoredRequest")]
 pub struct A2ARequest {
     /// The A2A operation to execute
     pub operation: A2AOperation,
@@ -23,6 +44,92 @@ impl A2ARequest {
     pub fn new(operation: A2AOperation, context: RequestContext) -> Self {
         Self { operation, context }
     }
+
+    /// Get the kind of the requested operation
+    pub fn kind(&self) -> OperationKind {
+        self.operation.kind()
+    }
+
+    /// Check if the request can be repeated safely (see [`A2AOperation::is_idempotent`])
+    ///
+    /// Messages starting a task with a client-generated ID are idempotent too: the
+    /// request activates the [`CLIENT_TASK_IDS`](extensions::CLIENT_TASK_IDS) extension, so the agent creates the task
+    /// only once however often the message is sent.
+    pub fn is_idempotent(&self) -> bool {
+        self.operation.is_idempotent()
+            || matches!(
+                self.operation,
+                A2AOperation::SendMessage {
+                    task_id: Some(_),
+                    ..
+                }
+            ) && self.context.has_extension(extensions::CLIENT_TASK_IDS)
+    }
+
+    /// Check if the request only reads state (see [`A2AOperation::is_read_only`])
+    pub fn is_read_only(&self) -> bool {
+        self.operation.is_read_only()
+    }
+
+    /// Check if the request expects a streaming response
+    pub fn is_streaming(&self) -> bool {
+        self.operation.is_streaming()
+    }
+}
+
+/// Serialized representation of an [`A2ARequest`]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredRequest {
+    version: u32,
+    operation: A2AOperation,
+    agent_url: Url,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timeout_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    metadata: HashMap<String, String>,
+}
+
+impl From<A2ARequest> for StoredRequest {
+    fn from(req: A2ARequest) -> Self {
+        Self {
+            version: REQUEST_FORMAT_VERSION,
+            operation: req.operation,
+            agent_url: req.context.agent_url,
+            timeout_ms: req.context.timeout.map(|t| t.as_millis() as u64),
+            metadata: req
+                .context
+                .metadata
+                .into_iter()
+                .filter(|(key, _)| !headers::SENSITIVE.contains(&key.to_ascii_lowercase().as_str()))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<StoredRequest> for A2ARequest {
+    type Error = String;
+
+    fn try_from(stored: StoredRequest) -> Result<Self, Self::Error> {
+        if stored.version != REQUEST_FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported request format version: {}",
+                stored.version
+            ));
+        }
+
+        let context = RequestContext {
+            agent_url: stored.agent_url,
+            auth: None,
+            timeout: stored.timeout_ms.map(Duration::from_millis),
+            deadline: None,
+            metadata: stored.metadata,
+            meta: None,
+            context_namespace: None,
+        };
+
+        Ok(Self::new(stored.operation, context))
+    }
 }
 
 /// Request context containing metadata and configuration
@@ -34,11 +141,21 @@ pub struct RequestContext {
     /// Authentication credentials (if any)
     pub auth: Option<AuthCredentials>,
 
-    /// Request timeout
+    /// Request timeout, applied to each attempt
     pub timeout: Option<Duration>,
 
+    /// Point in time by which the whole operation must complete, including retries
+    /// and reconnects
+    pub deadline: Option<Instant>,
+
     /// Additional metadata headers
     pub metadata: HashMap<String, String>,
+
+    /// Recorder of response details, if the caller asked for them
+    pub meta: Option<MetaRecorder>,
+
+    /// Namespace of the tenant's context IDs, applied by the client
+    pub context_namespace: Option<ContextNamespace>,
 }
 
 impl RequestContext {
@@ -48,7 +165,10 @@ impl RequestContext {
             agent_url,
             auth: None,
             timeout: Some(Duration::from_secs(30)),
+            deadline: None,
             metadata: HashMap::new(),
+            meta: None,
+            context_namespace: None,
         }
     }
 
@@ -64,11 +184,64 @@ impl RequestContext {
         self
     }
 
+    /// Set the operation deadline
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Get the time left until the operation deadline, if one is set
+    ///
+    /// Returns zero once the deadline has passed.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Record response details (attempts, endpoint, transport) into a recorder
+    pub fn with_meta_recorder(mut self, recorder: MetaRecorder) -> Self {
+        self.meta = Some(recorder);
+        self
+    }
+
+    /// Namespace the context IDs of the tenant the request is made for
+    pub fn with_context_namespace(mut self, namespace: ContextNamespace) -> Self {
+        self.context_namespace = Some(namespace);
+        self
+    }
+
     /// Add a metadata header
     pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.metadata.insert(key.into(), value.into());
         self
     }
+
+    /// Activate an extension, appending it to the extensions header already set
+    pub fn add_extension(&mut self, uri: &str) {
+        if self.has_extension(uri) {
+            return;
+        }
+        match self
+            .metadata
+            .iter_mut()
+            .find(|(key, _)| key.eq_ignore_ascii_case(headers::EXTENSIONS))
+        {
+            Some((_, value)) => *value = format!("{}, {}", value, uri),
+            None => {
+                self.metadata
+                    .insert(headers::EXTENSIONS.to_string(), uri.to_string());
+            }
+        }
+    }
+
+    /// Check if the request activates an extension
+    pub fn has_extension(&self, uri: &str) -> bool {
+        self.metadata
+            .iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case(headers::EXTENSIONS))
+            .flat_map(|(_, value)| value.split(','))
+            .any(|activated| activated.trim() == uri)
+    }
 }
 
 impl Default for RequestContext {
@@ -77,7 +250,10 @@ impl Default for RequestContext {
             agent_url: Url::parse("http://localhost:8080").unwrap(),
             auth: None,
             timeout: Some(Duration::from_secs(30)),
+            deadline: None,
             metadata: HashMap::new(),
+            meta: None,
+            context_namespace: None,
         }
     }
 }
@@ -116,4 +292,40 @@ mod tests {
 
         assert_eq!(request.context.agent_url, agent_url());
     }
+
+    #[test]
+    fn test_request_serde_roundtrip() {
+        let context = RequestContext::new(agent_url())
+            .with_auth(AuthCredentials::bearer("secret"))
+            .with_timeout(Duration::from_secs(5))
+            .with_metadata("X-Tenant-Id", "tenant-1")
+            .with_metadata("X-Api-Key", "secret-key");
+        let operation = A2AOperation::CancelTask {
+            task_id: "task-123".to_string(),
+            reason: None,
+        };
+
+        let json = serde_json::to_value(A2ARequest::new(operation, context)).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["timeoutMs"], 5000);
+        assert!(!json.to_string().contains("secret"));
+
+        let req: A2ARequest = serde_json::from_value(json).unwrap();
+        assert_eq!(req.kind(), OperationKind::CancelTask);
+        assert_eq!(req.context.timeout, Some(Duration::from_secs(5)));
+        assert!(req.context.auth.is_none());
+        assert_eq!(req.context.metadata["X-Tenant-Id"], "tenant-1");
+        assert!(!req.context.metadata.contains_key("X-Api-Key"));
+    }
+
+    #[test]
+    fn test_request_unknown_version() {
+        let json = serde_json::json!({
+            "version": 99,
+            "operation": {"op": "discoverAgent"},
+            "agentUrl": "https://example.com/"
+        });
+
+        assert!(serde_json::from_value::<A2ARequest>(json).is_err());
+    }
 }
@@ -1,6 +1,14 @@
 //! A2A service response types
 
-use crate::protocol::{agent::AgentCard, task::Task};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    protocol::{agent::AgentCard, push::TaskPushNotificationConfig, task::Task},
+    service::Deprecation,
+};
 
 /// Response from an A2A service operation
 #[derive(Debug, Clone)]
@@ -23,6 +31,13 @@ pub enum A2AResponse {
     /// Agent card response (from DiscoverAgent)
     AgentCard(Box<AgentCard>),
 
+    /// Push notification configuration response (from SetPushNotificationConfig and
+    /// GetPushNotificationConfig)
+    PushNotificationConfig(Box<TaskPushNotificationConfig>),
+
+    /// Push notification configurations response (from ListPushNotificationConfigs)
+    PushNotificationConfigs(Vec<TaskPushNotificationConfig>),
+
     /// Empty response (for operations with no return value)
     Empty,
 }
@@ -58,6 +73,51 @@ impl A2AResponse {
     }
 }
 
+/// Details of how a response was obtained, for SLO tracking
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseMeta {
+    /// Number of times the request was sent, including retries
+    pub attempts: u32,
+
+    /// Time from building the request to receiving the response
+    pub latency: Duration,
+
+    /// Endpoint of the last attempt
+    pub endpoint: Option<String>,
+
+    /// URL scheme of the transport used, e.g. `https` or `wss`
+    pub transport: Option<String>,
+
+    /// Deprecation announced by the agent on the last attempt
+    pub deprecation: Option<Deprecation>,
+}
+
+/// Handle collecting [`ResponseMeta`] while a request passes through the stack
+///
+/// Set on [`RequestContext::meta`](crate::service::RequestContext::meta); clones of
+/// a request share the recorder, so every retry is counted.
+#[derive(Debug, Clone, Default)]
+pub struct MetaRecorder(Arc<Mutex<ResponseMeta>>);
+
+impl MetaRecorder {
+    /// Create an empty recorder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the recorded details
+    pub fn record(&self, update: impl FnOnce(&mut ResponseMeta)) {
+        if let Ok(mut meta) = self.0.lock() {
+            update(&mut meta);
+        }
+    }
+
+    /// Get a copy of the recorded details
+    pub fn snapshot(&self) -> ResponseMeta {
+        self.0.lock().map(|meta| meta.clone()).unwrap_or_default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::protocol::message::Message;
@@ -0,0 +1,46 @@
+//! Service stacks ending in the protocol service
+//!
+//! Streaming, connecting, and transport-specific features bypass the Tower stack of a
+//! client and talk to the [`A2AProtocolService`] at its bottom. [`ProtocolStack`] gives
+//! them access to it, and lets the layers apply to such requests what they would have
+//! applied to a call, e.g. authentication.
+
+use async_trait::async_trait;
+
+use crate::{
+    protocol::error::A2AError,
+    service::{A2AProtocolService, A2ARequest},
+    transport::Transport,
+};
+
+/// Service stack ending in an [`A2AProtocolService`]
+///
+/// Implemented by the protocol service and by the client's layers wrapping one.
+#[async_trait]
+pub trait ProtocolStack: Clone + Send + Sync + 'static {
+    /// Transport of the protocol service
+    type Transport: Transport;
+
+    /// Get the protocol service at the bottom of the stack
+    fn protocol(&self) -> &A2AProtocolService<Self::Transport>;
+
+    /// Apply the layers' changes to a request sent past the stack, e.g. a streaming
+    /// request
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a layer cannot apply its changes, e.g. because credentials
+    /// could not be fetched
+    async fn prepare(&self, _request: &mut A2ARequest) -> Result<(), A2AError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T: Transport> ProtocolStack for A2AProtocolService<T> {
+    type Transport = T;
+
+    fn protocol(&self) -> &A2AProtocolService<T> {
+        self
+    }
+}
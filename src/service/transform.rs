@@ -0,0 +1,180 @@
+//! Response transformation hooks
+//!
+//! Some agents deviate from the protocol in small ways, such as status strings in a
+//! different casing. Rather than forking a codec, register a [`ResponseTransform`]
+//! for the agent's URL. Transforms can rewrite the raw JSON body before it is decoded,
+//! the decoded [`A2AResponse`], or both:
+//!
+//! ```rust
+//! use tower_a2a::service::transform::{map_value, ResponseTransforms};
+//!
+//! let transforms = ResponseTransforms::new().with_transform(
+//!     "https://legacy.example.com".parse().unwrap(),
+//!     map_value(|body, _operation| {
+//!         if let Some(status) = body.get_mut("status") {
+//!             if let Some(lowered) = status.as_str().map(str::to_lowercase) {
+//!                 *status = lowered.into();
+//!             }
+//!         }
+//!     }),
+//! );
+//! ```
+
+use std::sync::Arc;
+
+use serde_json::Value;
+use url::Url;
+
+use crate::{
+    protocol::{error::A2AError, operation::A2AOperation},
+    service::A2AResponse,
+};
+
+/// Hook rewriting the responses of an agent
+///
+/// Both methods default to leaving the response unchanged.
+pub trait ResponseTransform: Send + Sync {
+    /// Rewrite the response body, parsed as JSON, before the codec decodes it
+    ///
+    /// Not called for empty or non-JSON bodies. For JSON-RPC agents the value is the
+    /// whole envelope. For event streams it is called with the payload of each event
+    /// instead, as events are decoded by the caller.
+    fn transform_value(
+        &self,
+        _body: &mut Value,
+        _operation: &A2AOperation,
+    ) -> Result<(), A2AError> {
+        Ok(())
+    }
+
+    /// Rewrite the decoded response
+    fn transform_response(
+        &self,
+        _response: &mut A2AResponse,
+        _operation: &A2AOperation,
+    ) -> Result<(), A2AError> {
+        Ok(())
+    }
+}
+
+/// Transform rewriting the raw JSON body, created by [`map_value`]
+struct MapValue<F>(F);
+
+impl<F> ResponseTransform for MapValue<F>
+where
+    F: Fn(&mut Value, &A2AOperation) + Send + Sync,
+{
+    fn transform_value(&self, body: &mut Value, operation: &A2AOperation) -> Result<(), A2AError> {
+        (self.0)(body, operation);
+        Ok(())
+    }
+}
+
+/// Transform rewriting the decoded response, created by [`map_response`]
+struct MapResponse<F>(F);
+
+impl<F> ResponseTransform for MapResponse<F>
+where
+    F: Fn(&mut A2AResponse, &A2AOperation) + Send + Sync,
+{
+    fn transform_response(
+        &self,
+        response: &mut A2AResponse,
+        operation: &A2AOperation,
+    ) -> Result<(), A2AError> {
+        (self.0)(response, operation);
+        Ok(())
+    }
+}
+
+/// Create a transform rewriting the raw JSON body with a function
+pub fn map_value(
+    f: impl Fn(&mut Value, &A2AOperation) + Send + Sync + 'static,
+) -> impl ResponseTransform {
+    MapValue(f)
+}
+
+/// Create a transform rewriting the decoded response with a function
+pub fn map_response(
+    f: impl Fn(&mut A2AResponse, &A2AOperation) + Send + Sync + 'static,
+) -> impl ResponseTransform {
+    MapResponse(f)
+}
+
+/// Transforms registered per agent URL
+///
+/// A transform applies to requests whose agent URL has the same origin as the
+/// registered URL and a path within its path. Transforms run in registration
+/// order.
+#[derive(Clone, Default)]
+pub struct ResponseTransforms {
+    entries: Vec<(Url, Arc<dyn ResponseTransform>)>,
+}
+
+impl ResponseTransforms {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a transform for an agent URL
+    pub fn with_transform(
+        mut self,
+        agent_url: Url,
+        transform: impl ResponseTransform + 'static,
+    ) -> Self {
+        self.entries.push((agent_url, Arc::new(transform)));
+        self
+    }
+
+    /// Check if no transform is registered
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get the transforms applying to an agent URL
+    pub fn matching(&self, agent_url: &Url) -> Vec<Arc<dyn ResponseTransform>> {
+        self.entries
+            .iter()
+            .filter(|(url, _)| {
+                let prefix = url.path().trim_end_matches('/');
+                url.origin() == agent_url.origin()
+                    && agent_url
+                        .path()
+                        .strip_prefix(prefix)
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+            .map(|(_, transform)| transform.clone())
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for ResponseTransforms {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.entries.iter().map(|(url, _)| url.as_str()))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching() {
+        let transforms = ResponseTransforms::new()
+            .with_transform(
+                "https://example.com/agents/billing".parse().unwrap(),
+                map_value(|_, _| {}),
+            )
+            .with_transform("https://example.com".parse().unwrap(), map_value(|_, _| {}));
+
+        let matching = |url: &str| transforms.matching(&url.parse().unwrap()).len();
+        assert_eq!(matching("https://example.com/agents/billing/v1"), 2);
+        assert_eq!(matching("https://example.com/agents/search"), 1);
+        assert_eq!(matching("https://example.com/agents/billing-v2"), 1);
+        assert_eq!(matching("http://example.com/agents/billing"), 0);
+        assert_eq!(matching("https://other.example.com"), 0);
+    }
+}
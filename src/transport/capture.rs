@@ -0,0 +1,452 @@
+//! Wire-level capture of transport exchanges for debugging
+//!
+//! [`CaptureTransport`] wraps any transport and hands snapshots of each request and
+//! response to user callbacks. Credentials are redacted from the snapshots and bodies
+//! are truncated, so captures can be shared with support engineers.
+//!
+//! Only available with the `wire-capture` feature.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use futures::{stream, StreamExt};
+use url::Url;
+
+use crate::{
+    client::events::{ClientEvents, ConnectionState},
+    protocol::{consts::headers, error::A2AError},
+    service::ExecutorHandle,
+    transport::{
+        BodyStream, ChunkedResponse, EventStream, Transport, TransportRequest, TransportResponse,
+    },
+};
+
+/// Placeholder replacing redacted header values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Default maximum number of body bytes kept in a snapshot
+const DEFAULT_MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Snapshot of an outgoing request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedRequest {
+    /// Identifier shared by the request and its response
+    pub exchange_id: u64,
+
+    /// Request method
+    pub method: String,
+
+    /// Request endpoint
+    pub endpoint: String,
+
+    /// Headers, with credentials redacted
+    pub headers: BTreeMap<String, String>,
+
+    /// Body as (lossy) UTF-8, possibly truncated
+    pub body: String,
+
+    /// Whether the body was truncated
+    pub body_truncated: bool,
+
+    /// Whether the request expects a stream of events
+    pub streaming: bool,
+}
+
+/// Snapshot of the outcome of a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedResponse {
+    /// Identifier shared by the request and its response
+    pub exchange_id: u64,
+
+    /// Status code, if a response was received
+    pub status: Option<u16>,
+
+    /// Headers, with credentials redacted
+    pub headers: BTreeMap<String, String>,
+
+    /// Body as (lossy) UTF-8, possibly truncated
+    pub body: String,
+
+    /// Whether the body was truncated
+    pub body_truncated: bool,
+
+    /// Transport error, if the request failed
+    pub error: Option<String>,
+
+    /// Time between sending the request and receiving the response
+    pub elapsed: Duration,
+}
+
+type RequestHook = Arc<dyn Fn(&CapturedRequest) + Send + Sync>;
+type ResponseHook = Arc<dyn Fn(&CapturedResponse) + Send + Sync>;
+
+/// Transport wrapper invoking capture hooks around every exchange
+///
+/// For streaming requests only the request is captured, since the events are
+/// consumed lazily by the caller. Responses read chunk by chunk are captured once the
+/// caller has read the whole body.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tower_a2a::transport::{capture::CaptureTransport, HttpTransport};
+///
+/// let url: url::Url = "https://agent.example.com".parse().unwrap();
+/// let transport = CaptureTransport::new(HttpTransport::new(url))
+///     .on_request(|request| eprintln!("--> {:?}", request))
+///     .on_response(|response| eprintln!("<-- {:?}", response));
+/// ```
+#[derive(Clone)]
+pub struct CaptureTransport<T> {
+    inner: T,
+    on_request: Option<RequestHook>,
+    on_response: Option<ResponseHook>,
+    redacted_headers: HashSet<String>,
+    max_body_bytes: usize,
+    next_id: Arc<AtomicU64>,
+}
+
+impl<T> CaptureTransport<T> {
+    /// Wrap a transport without any hooks installed
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            on_request: None,
+            on_response: None,
+            redacted_headers: headers::SENSITIVE
+                .iter()
+                .map(|header| header.to_string())
+                .collect(),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            next_id: Arc::new(AtomicU64::new(1)),
+        }
+    }
+
+    /// Set the hook receiving request snapshots
+    pub fn on_request<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&CapturedRequest) + Send + Sync + 'static,
+    {
+        self.on_request = Some(Arc::new(hook));
+        self
+    }
+
+    /// Set the hook receiving response snapshots
+    pub fn on_response<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&CapturedResponse) + Send + Sync + 'static,
+    {
+        self.on_response = Some(Arc::new(hook));
+        self
+    }
+
+    /// Redact an additional header from snapshots
+    pub fn with_redacted_header(mut self, header: impl Into<String>) -> Self {
+        self.redacted_headers
+            .insert(header.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Set the maximum number of body bytes kept in snapshots
+    pub fn with_max_body_bytes(mut self, max_body_bytes: usize) -> Self {
+        self.max_body_bytes = max_body_bytes;
+        self
+    }
+
+    /// Get the wrapped transport
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    fn capture_request(&self, request: &TransportRequest, streaming: bool) -> u64 {
+        let exchange_id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        if let Some(hook) = &self.on_request {
+            let (body, body_truncated) = self.body(&request.body);
+            hook(&CapturedRequest {
+                exchange_id,
+                method: request.method.clone(),
+                endpoint: request.endpoint.clone(),
+                headers: self.headers(&request.headers),
+                body,
+                body_truncated,
+                streaming,
+            });
+        }
+        exchange_id
+    }
+
+    fn capture_response(
+        &self,
+        exchange_id: u64,
+        result: Result<&TransportResponse, &A2AError>,
+        elapsed: Duration,
+    ) {
+        let Some(hook) = &self.on_response else {
+            return;
+        };
+
+        let snapshot = match result {
+            Ok(response) => {
+                let (body, body_truncated) = self.body(&response.body);
+                CapturedResponse {
+                    exchange_id,
+                    status: Some(response.status),
+                    headers: self.headers(&response.headers),
+                    body,
+                    body_truncated,
+                    error: None,
+                    elapsed,
+                }
+            }
+            Err(err) => CapturedResponse {
+                exchange_id,
+                status: None,
+                headers: BTreeMap::new(),
+                body: String::new(),
+                body_truncated: false,
+                error: Some(err.to_string()),
+                elapsed,
+            },
+        };
+        hook(&snapshot);
+    }
+
+    /// Capture a response read chunk by chunk when its body has been read
+    fn capture_chunked(
+        &self,
+        exchange_id: u64,
+        mut response: ChunkedResponse,
+        started: Instant,
+    ) -> ChunkedResponse {
+        let Some(hook) = self.on_response.clone() else {
+            return response;
+        };
+
+        let tap = BodyTap {
+            body: std::mem::replace(&mut response.body, stream::empty().boxed()),
+            kept: Vec::new(),
+            max_body_bytes: self.max_body_bytes,
+            snapshot: CapturedResponse {
+                exchange_id,
+                status: Some(response.status),
+                headers: self.headers(&response.headers),
+                body: String::new(),
+                body_truncated: false,
+                error: None,
+                elapsed: Duration::ZERO,
+            },
+            started,
+            hook,
+        };
+        response.body = stream::unfold(Some(tap), |tap| async move {
+            let mut tap = tap?;
+            match tap.body.next().await {
+                Some(Ok(chunk)) => {
+                    let room = tap.max_body_bytes - tap.kept.len();
+                    tap.snapshot.body_truncated |= chunk.len() > room;
+                    tap.kept.extend_from_slice(&chunk[..chunk.len().min(room)]);
+                    Some((Ok(chunk), Some(tap)))
+                }
+                Some(Err(err)) => {
+                    tap.snapshot.error = Some(err.to_string());
+                    tap.finish();
+                    Some((Err(err), None))
+                }
+                None => {
+                    tap.finish();
+                    None
+                }
+            }
+        })
+        .boxed();
+        response
+    }
+
+    fn headers(&self, headers: &HashMap<String, String>) -> BTreeMap<String, String> {
+        headers
+            .iter()
+            .map(|(key, value)| {
+                let value = if self.redacted_headers.contains(&key.to_ascii_lowercase()) {
+                    REDACTED.to_string()
+                } else {
+                    value.clone()
+                };
+                (key.clone(), value)
+            })
+            .collect()
+    }
+
+    fn body(&self, body: &[u8]) -> (String, bool) {
+        let truncated = body.len() > self.max_body_bytes;
+        let body = &body[..body.len().min(self.max_body_bytes)];
+        (String::from_utf8_lossy(body).into_owned(), truncated)
+    }
+}
+
+/// Response body being read, kept for the snapshot up to the size limit
+struct BodyTap {
+    body: BodyStream,
+    kept: Vec<u8>,
+    max_body_bytes: usize,
+    snapshot: CapturedResponse,
+    started: Instant,
+    hook: ResponseHook,
+}
+
+impl BodyTap {
+    /// Hand the snapshot to the hook once the body ended
+    fn finish(mut self) {
+        self.snapshot.body = String::from_utf8_lossy(&self.kept).into_owned();
+        self.snapshot.elapsed = self.started.elapsed();
+        (self.hook)(&self.snapshot);
+    }
+}
+
+#[async_trait]
+impl<T: Transport> Transport for CaptureTransport<T> {
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), A2AError>> {
+        self.inner.poll_ready(cx)
+    }
+
+    async fn execute(&self, request: TransportRequest) -> Result<TransportResponse, A2AError> {
+        let exchange_id = self.capture_request(&request, false);
+        let started = Instant::now();
+        let result = self.inner.execute(request).await;
+        self.capture_response(exchange_id, result.as_ref(), started.elapsed());
+        result
+    }
+
+    async fn connect(&self) -> Result<(), A2AError> {
+        self.inner.connect().await
+    }
+
+    fn base_url(&self) -> &Url {
+        self.inner.base_url()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn keeps_request_body(&self) -> bool {
+        self.inner.keeps_request_body()
+    }
+
+    fn set_executor(&mut self, executor: ExecutorHandle) {
+        self.inner.set_executor(executor)
+    }
+
+    fn executor(&self) -> Option<ExecutorHandle> {
+        self.inner.executor()
+    }
+
+    fn set_events(&mut self, events: ClientEvents) {
+        self.inner.set_events(events)
+    }
+
+    fn connection_state(&self) -> Option<ConnectionState> {
+        self.inner.connection_state()
+    }
+
+    async fn execute_streaming(&self, request: TransportRequest) -> Result<EventStream, A2AError> {
+        self.capture_request(&request, true);
+        self.inner.execute_streaming(request).await
+    }
+
+    async fn execute_chunked(
+        &self,
+        request: TransportRequest,
+    ) -> Result<ChunkedResponse, A2AError> {
+        let exchange_id = self.capture_request(&request, false);
+        let started = Instant::now();
+        match self.inner.execute_chunked(request).await {
+            Ok(response) => Ok(self.capture_chunked(exchange_id, response, started)),
+            Err(err) => {
+                self.capture_response(exchange_id, Err(&err), started.elapsed());
+                Err(err)
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CaptureTransport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CaptureTransport")
+            .field("inner", &self.inner)
+            .field("redacted_headers", &self.redacted_headers)
+            .field("max_body_bytes", &self.max_body_bytes)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::transport::mock::MockTransport;
+
+    #[tokio::test]
+    async fn test_capture_redacts_and_truncates() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let responses = Arc::new(Mutex::new(Vec::new()));
+
+        let inner = MockTransport::new(|_| {
+            TransportResponse::new(200)
+                .header("Set-Cookie", "session=secret")
+                .body(Bytes::from_static(b"{\"id\":\"task-1\"}"))
+        });
+        let transport = CaptureTransport::new(inner)
+            .with_redacted_header("X-Session")
+            .with_max_body_bytes(4)
+            .on_request({
+                let requests = requests.clone();
+                move |request| requests.lock().unwrap().push(request.clone())
+            })
+            .on_response({
+                let responses = responses.clone();
+                move |response| responses.lock().unwrap().push(response.clone())
+            });
+
+        let request = TransportRequest::new("/message:send", "POST")
+            .header("Authorization", "Bearer secret")
+            .header("x-session", "secret")
+            .header("A2A-Version", "1.0")
+            .body(Bytes::from_static(b"hello world"));
+        transport.execute(request).await.unwrap();
+
+        let request = requests.lock().unwrap().pop().unwrap();
+        assert_eq!(request.headers["Authorization"], REDACTED);
+        assert_eq!(request.headers["x-session"], REDACTED);
+        assert_eq!(request.headers["A2A-Version"], "1.0");
+        assert_eq!(request.body, "hell");
+        assert!(request.body_truncated);
+
+        let response = responses.lock().unwrap().pop().unwrap();
+        assert_eq!(response.exchange_id, request.exchange_id);
+        assert_eq!(response.status, Some(200));
+        assert_eq!(response.headers["Set-Cookie"], REDACTED);
+
+        let request = TransportRequest::new("/tasks", "GET");
+        let response = transport.execute_chunked(request).await.unwrap();
+        assert!(responses.lock().unwrap().is_empty());
+        assert_eq!(response.collect().await.unwrap().body.len(), 15);
+
+        let request = requests.lock().unwrap().pop().unwrap();
+        let response = responses.lock().unwrap().pop().unwrap();
+        assert_eq!(response.exchange_id, request.exchange_id);
+        assert_eq!(response.body, "{\"id");
+        assert!(response.body_truncated);
+    }
+}
@@ -0,0 +1,213 @@
+//! Name resolution options for transports
+//!
+//! [`DnsOptions`] pins hostnames to fixed addresses, chooses between IPv4 and IPv6,
+//! and can replace the system resolver, e.g. to reach a staging agent under its
+//! production hostname:
+//!
+//! ```rust
+//! use tower_a2a::transport::{dns::{AddressFamily, DnsOptions}, HttpTransport};
+//!
+//! # fn main() -> Result<(), tower_a2a::protocol::error::A2AError> {
+//! let dns = DnsOptions::new()
+//!     .with_override("agent.example.com", "10.0.0.12".parse().unwrap())
+//!     .with_address_family(AddressFamily::PreferIpv6);
+//! let transport = HttpTransport::new("https://agent.example.com".parse().unwrap()).with_dns(dns)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+
+use crate::protocol::error::A2AError;
+
+/// Which IP versions connections use
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AddressFamily {
+    /// Use addresses in the order the resolver returns them
+    #[default]
+    Any,
+
+    /// Try IPv4 addresses before IPv6 addresses
+    PreferIpv4,
+
+    /// Try IPv6 addresses before IPv4 addresses
+    PreferIpv6,
+
+    /// Use IPv4 addresses only
+    Ipv4Only,
+
+    /// Use IPv6 addresses only
+    Ipv6Only,
+}
+
+impl AddressFamily {
+    /// Filter and order addresses according to the family
+    fn apply(self, mut addrs: Vec<IpAddr>) -> Vec<IpAddr> {
+        match self {
+            AddressFamily::Any => {}
+            AddressFamily::PreferIpv4 => addrs.sort_by_key(|addr| !addr.is_ipv4()),
+            AddressFamily::PreferIpv6 => addrs.sort_by_key(|addr| !addr.is_ipv6()),
+            AddressFamily::Ipv4Only => addrs.retain(IpAddr::is_ipv4),
+            AddressFamily::Ipv6Only => addrs.retain(IpAddr::is_ipv6),
+        }
+        addrs
+    }
+}
+
+/// Resolves hostnames to IP addresses
+#[async_trait]
+pub trait Resolver: Send + Sync {
+    /// Resolve a hostname
+    async fn resolve(&self, host: &str) -> Result<Vec<IpAddr>, A2AError>;
+}
+
+/// The operating system's resolver
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemResolver;
+
+#[async_trait]
+impl Resolver for SystemResolver {
+    async fn resolve(&self, host: &str) -> Result<Vec<IpAddr>, A2AError> {
+        let addrs = tokio::net::lookup_host((host, 0))
+            .await
+            .map_err(|e| A2AError::Transport(format!("Failed to resolve {}: {}", host, e)))?;
+        Ok(addrs.map(|addr| addr.ip()).collect())
+    }
+}
+
+/// Name resolution settings shared by the HTTP and WebSocket transports
+#[derive(Clone, Default)]
+pub struct DnsOptions {
+    overrides: HashMap<String, Vec<IpAddr>>,
+    family: AddressFamily,
+    resolver: Option<Arc<dyn Resolver>>,
+}
+
+impl DnsOptions {
+    /// Create options using the system resolver without overrides
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve a host to a fixed address, bypassing the resolver
+    ///
+    /// Can be called several times for the same host to pin it to multiple addresses.
+    pub fn with_override(mut self, host: impl Into<String>, addr: IpAddr) -> Self {
+        self.overrides
+            .entry(host.into().to_ascii_lowercase())
+            .or_default()
+            .push(addr);
+        self
+    }
+
+    /// Set which IP versions are used (default: any, in resolver order)
+    pub fn with_address_family(mut self, family: AddressFamily) -> Self {
+        self.family = family;
+        self
+    }
+
+    /// Replace the system resolver for hosts without an override
+    pub fn with_resolver(mut self, resolver: impl Resolver + 'static) -> Self {
+        self.resolver = Some(Arc::new(resolver));
+        self
+    }
+
+    /// Resolve a host, applying overrides and the address family
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Transport` if resolution fails or yields no usable address
+    pub async fn lookup(&self, host: &str) -> Result<Vec<IpAddr>, A2AError> {
+        // IP literals need no resolution (URL hosts keep IPv6 brackets)
+        let literal = host.trim_start_matches('[').trim_end_matches(']');
+        let addrs = if let Ok(addr) = literal.parse::<IpAddr>() {
+            vec![addr]
+        } else if let Some(addrs) = self.overrides.get(&host.to_ascii_lowercase()) {
+            addrs.clone()
+        } else {
+            match &self.resolver {
+                Some(resolver) => resolver.resolve(host).await?,
+                None => SystemResolver.resolve(host).await?,
+            }
+        };
+
+        let addrs = self.family.apply(addrs);
+        if addrs.is_empty() {
+            return Err(A2AError::Transport(format!(
+                "No {:?} address found for {}",
+                self.family, host
+            )));
+        }
+        Ok(addrs)
+    }
+}
+
+impl fmt::Debug for DnsOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DnsOptions")
+            .field("overrides", &self.overrides)
+            .field("family", &self.family)
+            .field("custom_resolver", &self.resolver.is_some())
+            .finish()
+    }
+}
+
+/// Lets reqwest clients resolve names through the options
+impl reqwest::dns::Resolve for DnsOptions {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let options = self.clone();
+        Box::pin(async move {
+            let addrs = options.lookup(name.as_str()).await?;
+            // Port 0 is replaced with the URL's port by reqwest
+            let addrs: reqwest::dns::Addrs =
+                Box::new(addrs.into_iter().map(|addr| SocketAddr::new(addr, 0)));
+            Ok(addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StaticResolver;
+
+    #[async_trait]
+    impl Resolver for StaticResolver {
+        async fn resolve(&self, _host: &str) -> Result<Vec<IpAddr>, A2AError> {
+            Ok(vec![
+                "192.0.2.1".parse().unwrap(),
+                "2001:db8::1".parse().unwrap(),
+            ])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lookup() {
+        let dns = DnsOptions::new()
+            .with_override("Staging.Example.com", "10.0.0.1".parse().unwrap())
+            .with_resolver(StaticResolver);
+
+        assert_eq!(
+            dns.lookup("staging.example.com").await.unwrap(),
+            ["10.0.0.1".parse::<IpAddr>().unwrap()]
+        );
+        assert_eq!(
+            dns.lookup("[::1]").await.unwrap(),
+            ["::1".parse::<IpAddr>().unwrap()]
+        );
+
+        let dns = dns.with_address_family(AddressFamily::PreferIpv6);
+        assert!(dns.lookup("agent.example.com").await.unwrap()[0].is_ipv6());
+
+        let dns = dns.with_address_family(AddressFamily::Ipv6Only);
+        assert!(dns.lookup("staging.example.com").await.is_err());
+    }
+}
@@ -0,0 +1,120 @@
+//! Dry-run transport that captures requests instead of sending them
+
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use url::Url;
+
+use crate::{
+    protocol::error::A2AError,
+    transport::{EventStream, Transport, TransportRequest, TransportResponse},
+};
+
+/// Message of the error returned for every captured request
+pub const DRY_RUN_ERROR: &str = "Dry run: request was not sent";
+
+/// Transport that records fully composed requests without sending them
+///
+/// Useful for debugging interop issues: the request passes through the whole client
+/// stack (layers, codec, endpoint mapping) and is captured exactly as it would go on
+/// the wire. Every request then fails with a [`A2AError::Transport`] error carrying
+/// [`DRY_RUN_ERROR`].
+///
+/// Clones share the captured requests, so a handle kept before building a client can
+/// inspect what the client would have sent.
+#[derive(Debug, Clone)]
+pub struct DryRunTransport {
+    base_url: Url,
+    requests: Arc<Mutex<Vec<TransportRequest>>>,
+}
+
+impl DryRunTransport {
+    /// Create a dry-run transport for the given agent URL
+    pub fn new(base_url: Url) -> Self {
+        Self {
+            base_url,
+            requests: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Get all captured requests, oldest first
+    pub fn requests(&self) -> Vec<TransportRequest> {
+        self.lock().clone()
+    }
+
+    /// Get the most recently captured request
+    pub fn last_request(&self) -> Option<TransportRequest> {
+        self.lock().last().cloned()
+    }
+
+    /// Remove and return all captured requests
+    pub fn take_requests(&self) -> Vec<TransportRequest> {
+        std::mem::take(&mut *self.lock())
+    }
+
+    fn capture(&self, request: TransportRequest) -> A2AError {
+        tracing::debug!(
+            method = %request.method,
+            endpoint = %request.endpoint,
+            "Captured dry-run request"
+        );
+        self.lock().push(request);
+        A2AError::Transport(DRY_RUN_ERROR.into())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<TransportRequest>> {
+        // The lock is never held across a panic point, so poisoning can be ignored
+        self.requests
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl Transport for DryRunTransport {
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), A2AError>> {
+        Poll::Ready(Ok(()))
+    }
+
+    async fn execute(&self, request: TransportRequest) -> Result<TransportResponse, A2AError> {
+        Err(self.capture(request))
+    }
+
+    fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    async fn execute_streaming(&self, request: TransportRequest) -> Result<EventStream, A2AError> {
+        Err(self.capture(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::protocol::consts::{headers, PROTOCOL_VERSION};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_captures_requests() {
+        let transport = DryRunTransport::new("https://agent.example.com".parse().unwrap());
+        let handle = transport.clone();
+
+        let request = TransportRequest::new("/message:send", "POST")
+            .header(headers::A2A_VERSION, PROTOCOL_VERSION);
+        let err = transport.execute(request).await.unwrap_err();
+        assert!(err.to_string().contains(DRY_RUN_ERROR));
+
+        let captured = handle.last_request().unwrap();
+        assert_eq!(captured.endpoint, "/message:send");
+        assert_eq!(handle.take_requests().len(), 1);
+        assert!(handle.requests().is_empty());
+    }
+}
@@ -1,25 +1,94 @@
 //! HTTP transport implementation for A2A protocol
 
-use std::task::{Context, Poll};
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
 
 use async_trait::async_trait;
-use futures::stream::Stream;
+use futures::{
+    future::Either,
+    stream::{Stream, StreamExt, TryStreamExt},
+};
+use reqwest::{header::LOCATION, Method, StatusCode};
 use url::Url;
 
 use crate::{
+    client::{
+        events::{ClientEvents, ConnectionState},
+        stream::EventStreamExt,
+    },
     codec::{sse::SseEvent, SseCodec},
-    protocol::error::A2AError,
+    protocol::{
+        consts::{headers, media_types},
+        error::A2AError,
+    },
 };
 
-use super::{Transport, TransportRequest, TransportResponse};
+use super::{
+    dns::DnsOptions, ChunkedResponse, ConnectionMonitor, EventStream, Transport, TransportRequest,
+    TransportResponse,
+};
+
+/// Headers removed when a redirect leaves the origin of the original request
+const DEFAULT_SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "x-api-key",
+];
+
+/// How the HTTP transport follows redirects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedirectPolicy {
+    /// Never follow redirects; the redirect response is returned as is
+    None,
+
+    /// Follow redirects to the origin of the original request only
+    ///
+    /// A redirect to another origin fails with `A2AError::Transport`.
+    SameOrigin {
+        /// Maximum number of redirects to follow
+        max_hops: usize,
+    },
+
+    /// Follow redirects to any origin, removing credentials once the request leaves
+    /// the original origin
+    Follow {
+        /// Maximum number of redirects to follow
+        max_hops: usize,
+    },
+}
+
+impl Default for RedirectPolicy {
+    /// Follow up to 5 redirects, forwarding credentials within the original origin only
+    fn default() -> Self {
+        RedirectPolicy::Follow { max_hops: 5 }
+    }
+}
 
 /// HTTP transport implementation using reqwest
 ///
 /// This transport implements the HTTP+JSON binding of the A2A protocol.
+///
+/// Redirects are followed by the transport itself according to its
+/// [`RedirectPolicy`], so that credentials can be removed on cross-origin hops.
+/// Redirects from HTTPS to plain HTTP are always refused.
+///
+/// The connection state follows the outcome of the last request: refused connections
+/// make the agent [`Disconnected`](ConnectionState::Disconnected), timeouts and `502`,
+/// `503`, or `504` answers [`Degraded`](ConnectionState::Degraded).
 #[derive(Clone, Debug)]
 pub struct HttpTransport {
     client: reqwest::Client,
     base_url: Url,
+    stream_idle_timeout: Option<Duration>,
+    redirect_policy: RedirectPolicy,
+    sensitive_headers: Vec<String>,
+    monitor: ConnectionMonitor,
+    /// Whether the reqwest client was passed to [`with_client`](Self::with_client)
+    custom_client: bool,
 }
 
 impl HttpTransport {
@@ -28,16 +97,100 @@ impl HttpTransport {
     /// # Arguments
     ///
     /// * `base_url` - The base URL of the agent (e.g., "<https://agent.example.com>")
+    ///
+    /// # Panics
+    ///
+    /// Panics if the reqwest client cannot be built, like `reqwest::Client::new`; use
+    /// [`try_new`](Self::try_new) to handle the failure instead.
     pub fn new(base_url: Url) -> Self {
+        Self::try_new(base_url).expect("Failed to build the HTTP client")
+    }
+
+    /// Create a new HTTP transport, failing if the reqwest client cannot be built
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Transport` if the TLS backend cannot be initialized or the
+    /// system configuration cannot be loaded
+    pub fn try_new(base_url: Url) -> Result<Self, A2AError> {
+        let client = build_client(Self::client_builder())?;
+        Ok(Self {
+            custom_client: false,
+            ..Self::with_client(base_url, client)
+        })
+    }
+
+    /// Get a reqwest client builder that leaves redirects to the transport
+    ///
+    /// Start custom clients for [`with_client`](Self::with_client) from it.
+    pub fn client_builder() -> reqwest::ClientBuilder {
+        reqwest::Client::builder().redirect(reqwest::redirect::Policy::none())
+    }
+
+    /// Create a new HTTP transport with a custom reqwest client
+    ///
+    /// The client should be built with `reqwest::redirect::Policy::none()`, e.g. from
+    /// [`client_builder`](Self::client_builder), otherwise it follows redirects itself
+    /// and the transport's [`RedirectPolicy`] never applies.
+    pub fn with_client(base_url: Url, client: reqwest::Client) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client,
             base_url,
+            stream_idle_timeout: None,
+            redirect_policy: RedirectPolicy::default(),
+            sensitive_headers: DEFAULT_SENSITIVE_HEADERS
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            monitor: ConnectionMonitor::default(),
+            custom_client: true,
         }
     }
 
-    /// Create a new HTTP transport with a custom reqwest client
-    pub fn with_client(base_url: Url, client: reqwest::Client) -> Self {
-        Self { client, base_url }
+    /// Resolve hostnames through the given options
+    ///
+    /// Rebuilds the transport's reqwest client. A custom client cannot be rebuilt;
+    /// pass the options to its builder's `dns_resolver` instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Validation` if the transport was created
+    /// [`with_client`](Self::with_client), and `A2AError::Transport` if the client
+    /// cannot be built
+    pub fn with_dns(mut self, dns: DnsOptions) -> Result<Self, A2AError> {
+        if self.custom_client {
+            return Err(A2AError::Validation(
+                "DNS options cannot be applied to a custom HTTP client; \
+                 pass them to its builder's dns_resolver instead"
+                    .into(),
+            ));
+        }
+        self.client = build_client(Self::client_builder().dns_resolver(Arc::new(dns)))?;
+        Ok(self)
+    }
+
+    /// Set how redirects are followed (default: up to 5 hops, credentials kept within
+    /// the original origin)
+    pub fn with_redirect_policy(mut self, policy: RedirectPolicy) -> Self {
+        self.redirect_policy = policy;
+        self
+    }
+
+    /// Treat a header as a credential that is removed on cross-origin redirects
+    ///
+    /// `Authorization`, `Proxy-Authorization`, `Cookie`, `X-API-Key`, and the header of
+    /// the request's credentials are always treated as credentials.
+    pub fn with_sensitive_header(mut self, name: impl Into<String>) -> Self {
+        self.sensitive_headers.push(name.into().to_lowercase());
+        self
+    }
+
+    /// Terminate streams with `A2AError::Timeout` after this long without an event
+    ///
+    /// SSE comments sent by the agent count as activity and reset the timer.
+    pub fn with_stream_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.stream_idle_timeout = Some(timeout);
+        self
     }
 
     /// Execute a streaming request (Server-Sent Events)
@@ -56,12 +209,10 @@ impl HttpTransport {
         &self,
         request: TransportRequest,
     ) -> Result<impl Stream<Item = Result<SseEvent, A2AError>>, A2AError> {
-        let url = format!("{}{}", self.base_url, request.endpoint);
-
-        let mut req_builder = match request.method.as_str() {
-            "POST" => self.client.post(&url),
-            "GET" => self.client.get(&url),
-            "PUT" => self.client.put(&url),
+        let method = match request.method.as_str() {
+            "POST" => Method::POST,
+            "GET" => Method::GET,
+            "PUT" => Method::PUT,
             _ => {
                 return Err(A2AError::Transport(format!(
                     "Unsupported HTTP method for streaming: {}",
@@ -71,20 +222,14 @@ impl HttpTransport {
         };
 
         // Add Accept header for SSE
-        req_builder = req_builder.header("Accept", "text/event-stream");
-
-        // Add other headers
-        for (key, value) in request.headers {
-            req_builder = req_builder.header(key, value);
-        }
-
-        // Add body if not empty
-        if !request.body.is_empty() {
-            req_builder = req_builder.body(request.body);
-        }
+        let mut request = request;
+        request
+            .headers
+            .entry(headers::ACCEPT.to_string())
+            .or_insert_with(|| media_types::EVENT_STREAM.to_string());
 
         // Execute the request
-        let response = req_builder.send().await?;
+        let response = self.send(method, request).await?;
 
         // Check status
         if !response.status().is_success() {
@@ -101,7 +246,136 @@ impl HttpTransport {
 
         // Parse SSE events
         let sse_codec = SseCodec;
-        Ok(sse_codec.parse_stream(byte_stream))
+        let events = sse_codec.parse_stream(byte_stream);
+
+        Ok(match self.stream_idle_timeout {
+            Some(timeout) => Either::Left(events.idle_timeout(timeout)),
+            None => Either::Right(events),
+        })
+    }
+
+    /// Record the connection state a request's outcome shows
+    fn observe(
+        &self,
+        result: Result<reqwest::Response, reqwest::Error>,
+    ) -> Result<reqwest::Response, A2AError> {
+        let (state, reason) = match &result {
+            Ok(response) => match response.status() {
+                status @ (StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT) => (
+                    ConnectionState::Degraded,
+                    Some(format!("Agent answered {}", status)),
+                ),
+                _ => (ConnectionState::Connected, None),
+            },
+            Err(e) if e.is_connect() => (ConnectionState::Disconnected, Some(e.to_string())),
+            Err(e) if e.is_timeout() => (ConnectionState::Degraded, Some(e.to_string())),
+            Err(_) => return Ok(result?),
+        };
+        self.monitor.report(&self.base_url, state, reason);
+        Ok(result?)
+    }
+
+    /// Map a request method to its HTTP method
+    fn method(method: &str) -> Result<Method, A2AError> {
+        match method {
+            "POST" => Ok(Method::POST),
+            "GET" => Ok(Method::GET),
+            "PUT" => Ok(Method::PUT),
+            "DELETE" => Ok(Method::DELETE),
+            _ => Err(A2AError::Transport(format!(
+                "Unsupported HTTP method: {}",
+                method
+            ))),
+        }
+    }
+
+    /// Send a request, following redirects according to the redirect policy
+    async fn send(
+        &self,
+        mut method: Method,
+        request: TransportRequest,
+    ) -> Result<reqwest::Response, A2AError> {
+        let mut url = Url::parse(&format!("{}{}", self.base_url, request.endpoint))
+            .map_err(|e| A2AError::Transport(format!("Invalid request URL: {}", e)))?;
+        let origin = url.origin();
+        let auth_header = request
+            .auth
+            .as_ref()
+            .map(|auth| auth.to_header().0.to_lowercase());
+        let mut request_headers = request.headers;
+        let mut body = request.body;
+        let mut hops = 0;
+
+        loop {
+            let mut req_builder = self.client.request(method.clone(), url.clone());
+            for (key, value) in &request_headers {
+                req_builder = req_builder.header(key, value);
+            }
+            if !body.is_empty() {
+                req_builder = req_builder.body(body.clone());
+            }
+
+            let response = self.observe(req_builder.send().await)?;
+            let status = response.status();
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|location| location.to_str().ok());
+            let (Some(location), true) = (location, status.is_redirection()) else {
+                return Ok(response);
+            };
+
+            let max_hops = match self.redirect_policy {
+                RedirectPolicy::None => return Ok(response),
+                RedirectPolicy::SameOrigin { max_hops } | RedirectPolicy::Follow { max_hops } => {
+                    max_hops
+                }
+            };
+            if hops >= max_hops {
+                return Err(A2AError::Transport(format!(
+                    "Too many redirects (more than {})",
+                    max_hops
+                )));
+            }
+
+            let next = url
+                .join(location)
+                .map_err(|e| A2AError::Transport(format!("Invalid redirect location: {}", e)))?;
+            if url.scheme() == "https" && next.scheme() != "https" {
+                return Err(A2AError::Transport(format!(
+                    "Refusing redirect from HTTPS to {}",
+                    next
+                )));
+            }
+            if next.origin() != origin {
+                if matches!(self.redirect_policy, RedirectPolicy::SameOrigin { .. }) {
+                    return Err(A2AError::Transport(format!(
+                        "Refusing cross-origin redirect to {}",
+                        next
+                    )));
+                }
+                request_headers.retain(|name, _| {
+                    let name = name.to_lowercase();
+                    !self.sensitive_headers.contains(&name) && auth_header.as_ref() != Some(&name)
+                });
+            }
+
+            // Browsers (and reqwest) turn redirected POSTs into GETs except for 307/308
+            if status == StatusCode::SEE_OTHER
+                || (matches!(status, StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND)
+                    && method == Method::POST)
+            {
+                method = Method::GET;
+                body = Default::default();
+                request_headers.retain(|name, _| !name.eq_ignore_ascii_case(headers::CONTENT_TYPE));
+            }
+
+            tracing::debug!(status = %status, location = %next, "Following redirect");
+            url = next;
+            hops += 1;
+        }
     }
 }
 
@@ -113,33 +387,10 @@ impl Transport for HttpTransport {
     }
 
     async fn execute(&self, request: TransportRequest) -> Result<TransportResponse, A2AError> {
-        let url = format!("{}{}", self.base_url, request.endpoint);
-
-        let mut req_builder = match request.method.as_str() {
-            "POST" => self.client.post(&url),
-            "GET" => self.client.get(&url),
-            "PUT" => self.client.put(&url),
-            "DELETE" => self.client.delete(&url),
-            _ => {
-                return Err(A2AError::Transport(format!(
-                    "Unsupported HTTP method: {}",
-                    request.method
-                )))
-            }
-        };
-
-        // Add headers
-        for (key, value) in request.headers {
-            req_builder = req_builder.header(key, value);
-        }
-
-        // Add body if not empty
-        if !request.body.is_empty() {
-            req_builder = req_builder.body(request.body);
-        }
+        let method = Self::method(&request.method)?;
 
         // Execute the request
-        let response = req_builder.send().await?;
+        let response = self.send(method, request).await?;
 
         // Extract status and headers
         let status = response.status().as_u16();
@@ -159,6 +410,14 @@ impl Transport for HttpTransport {
         })
     }
 
+    /// Opens a pooled connection with a `HEAD` request to the base URL
+    ///
+    /// Any response counts as success; only connection and TLS failures are errors.
+    async fn connect(&self) -> Result<(), A2AError> {
+        self.observe(self.client.head(self.base_url.clone()).send().await)?;
+        Ok(())
+    }
+
     fn base_url(&self) -> &Url {
         &self.base_url
     }
@@ -166,10 +425,62 @@ impl Transport for HttpTransport {
     fn supports_streaming(&self) -> bool {
         true
     }
+
+    fn set_events(&mut self, events: ClientEvents) {
+        self.monitor.set_events(events);
+    }
+
+    fn connection_state(&self) -> Option<ConnectionState> {
+        self.monitor.state()
+    }
+
+    async fn execute_streaming(&self, request: TransportRequest) -> Result<EventStream, A2AError> {
+        Ok(HttpTransport::execute_streaming(self, request)
+            .await?
+            .boxed())
+    }
+
+    async fn execute_chunked(
+        &self,
+        request: TransportRequest,
+    ) -> Result<ChunkedResponse, A2AError> {
+        let method = Self::method(&request.method)?;
+        let response = self.send(method, request).await?;
+
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
+            .collect();
+        let body = response.bytes_stream().map_err(A2AError::from).boxed();
+
+        Ok(ChunkedResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+/// Build a reqwest client, reporting failures as transport errors
+fn build_client(builder: reqwest::ClientBuilder) -> Result<reqwest::Client, A2AError> {
+    builder
+        .build()
+        .map_err(|e| A2AError::Transport(format!("Failed to build the HTTP client: {}", e)))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Mutex;
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use crate::layer::AuthCredentials;
+
     use super::*;
 
     #[test]
@@ -178,4 +489,127 @@ mod tests {
         assert_eq!(transport.base_url().as_str(), "https://example.com/");
         assert!(transport.supports_streaming());
     }
+
+    /// Serve canned responses by path, recording each request head
+    async fn serve(routes: Vec<(&'static str, String)>) -> (Url, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0; 8192];
+                let n = stream.read(&mut buf).await.unwrap();
+                let head = String::from_utf8_lossy(&buf[..n]).to_string();
+                let path = head
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string();
+                recorded.lock().unwrap().push(head.to_lowercase());
+
+                let response = routes
+                    .iter()
+                    .find(|(route, _)| *route == path)
+                    .map(|(_, response)| response.clone())
+                    .unwrap_or_else(|| "HTTP/1.1 404 Not Found\r\n".to_string());
+                let response =
+                    format!("{}Content-Length: 0\r\nConnection: close\r\n\r\n", response);
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    fn redirect(status: &str, location: impl std::fmt::Display) -> String {
+        format!("HTTP/1.1 {}\r\nLocation: {}\r\n", status, location)
+    }
+
+    fn request(endpoint: &str) -> TransportRequest {
+        TransportRequest::new(endpoint, "POST")
+            .header("Authorization", "Bearer secret")
+            .header("Content-Type", "application/a2a+json")
+            .body(bytes::Bytes::from_static(b"{}"))
+    }
+
+    #[tokio::test]
+    async fn test_redirects_strip_credentials_across_origins() {
+        let ok = "HTTP/1.1 200 OK\r\n".to_string();
+        let (other, other_requests) = serve(vec![("/end", ok.clone())]).await;
+        let (url, requests) = serve(vec![
+            ("/local", redirect("307 Temporary Redirect", "/end")),
+            ("/end", ok),
+            (
+                "/remote",
+                redirect("302 Found", other.join("/end").unwrap()),
+            ),
+            ("/loop", redirect("302 Found", "/loop")),
+        ])
+        .await;
+        let transport = HttpTransport::new(url.clone());
+
+        // Same origin: credentials, method, and body are kept for 307
+        let response = transport.execute(request("local")).await.unwrap();
+        assert_eq!(response.status, 200);
+        let end = requests.lock().unwrap()[1].clone();
+        assert!(end.starts_with("post /end"));
+        assert!(end.contains("authorization: bearer secret"));
+
+        // Cross origin: credentials are removed and the 302'd POST becomes a GET
+        let response = transport.execute(request("remote")).await.unwrap();
+        assert_eq!(response.status, 200);
+        let end = other_requests.lock().unwrap()[0].clone();
+        assert!(end.starts_with("get /end"));
+        assert!(!end.contains("authorization"));
+
+        // So are API keys sent in custom headers
+        let api_key = AuthCredentials::api_key("agent-key", "X-Agent-Token");
+        let (header, value) = api_key.to_header();
+        let keyed = request("remote").header(header, value).auth(api_key);
+        transport.execute(keyed).await.unwrap();
+        let end = other_requests.lock().unwrap()[1].clone();
+        assert!(!end.contains("agent-key"));
+
+        let err = transport.execute(request("loop")).await.unwrap_err();
+        assert!(err.to_string().contains("Too many redirects"));
+
+        let same_origin = HttpTransport::new(url.clone())
+            .with_redirect_policy(RedirectPolicy::SameOrigin { max_hops: 5 });
+        assert!(same_origin.execute(request("remote")).await.is_err());
+
+        let disabled = HttpTransport::new(url).with_redirect_policy(RedirectPolicy::None);
+        let response = disabled.execute(request("local")).await.unwrap();
+        assert_eq!(response.status, 307);
+    }
+
+    #[tokio::test]
+    async fn test_dns_override() {
+        let (url, requests) = serve(vec![("/card", "HTTP/1.1 200 OK\r\n".to_string())]).await;
+        let pinned = Url::parse(&format!(
+            "http://staging-agent.invalid:{}",
+            url.port().unwrap()
+        ))
+        .unwrap();
+
+        let dns =
+            DnsOptions::new().with_override("staging-agent.invalid", "127.0.0.1".parse().unwrap());
+        let transport = HttpTransport::new(pinned.clone())
+            .with_dns(dns.clone())
+            .unwrap();
+        let response = transport
+            .execute(TransportRequest::new("card", "GET"))
+            .await
+            .unwrap();
+        assert_eq!(response.status, 200);
+        assert!(requests.lock().unwrap()[0].contains("host: staging-agent.invalid"));
+
+        // Custom clients are not replaced
+        let custom = HttpTransport::client_builder().build().unwrap();
+        assert!(matches!(
+            HttpTransport::with_client(pinned, custom).with_dns(dns),
+            Err(A2AError::Validation(_))
+        ));
+    }
 }
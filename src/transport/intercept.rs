@@ -0,0 +1,209 @@
+//! Last-mile changes to transport requests
+//!
+//! Tower layers see [`A2ARequest`](crate::service::A2ARequest)s, before the codec and
+//! endpoint mapping have produced the wire request. [`InterceptTransport`] wraps any
+//! transport and hands each final [`TransportRequest`] to [`RequestInterceptor`]s just
+//! before it is sent, e.g. to add a gateway token or rewrite the `Host` header.
+
+use std::{
+    fmt,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use url::Url;
+
+use crate::{
+    client::events::{ClientEvents, ConnectionState},
+    protocol::error::A2AError,
+    service::ExecutorHandle,
+    transport::{ChunkedResponse, EventStream, Transport, TransportRequest, TransportResponse},
+};
+
+/// Hook changing a transport request just before it is sent
+pub trait RequestInterceptor: Send + Sync {
+    /// Change the request
+    ///
+    /// Returning an error fails the request without sending it.
+    fn intercept(&self, request: &mut TransportRequest) -> Result<(), A2AError>;
+}
+
+impl<F> RequestInterceptor for F
+where
+    F: Fn(&mut TransportRequest) + Send + Sync,
+{
+    fn intercept(&self, request: &mut TransportRequest) -> Result<(), A2AError> {
+        self(request);
+        Ok(())
+    }
+}
+
+/// Transport wrapper running interceptors on every request
+///
+/// Interceptors run in the order they were added, for plain, streaming, and chunked
+/// requests.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tower_a2a::transport::{HttpTransport, InterceptTransport, TransportRequest};
+///
+/// let url: url::Url = "https://agent.example.com".parse().unwrap();
+/// let transport = InterceptTransport::new(HttpTransport::new(url))
+///     .with_interceptor(|request: &mut TransportRequest| {
+///         request.headers.insert("X-Gateway-Token".into(), "token".into());
+///     });
+/// ```
+#[derive(Clone)]
+pub struct InterceptTransport<T> {
+    inner: T,
+    interceptors: Vec<Arc<dyn RequestInterceptor>>,
+}
+
+impl<T> InterceptTransport<T> {
+    /// Wrap a transport without any interceptors
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            interceptors: Vec::new(),
+        }
+    }
+
+    /// Add an interceptor, run after those already added
+    pub fn with_interceptor(mut self, interceptor: impl RequestInterceptor + 'static) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// Get the wrapped transport
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    fn intercept(&self, mut request: TransportRequest) -> Result<TransportRequest, A2AError> {
+        for interceptor in &self.interceptors {
+            interceptor.intercept(&mut request)?;
+        }
+        Ok(request)
+    }
+}
+
+#[async_trait]
+impl<T: Transport> Transport for InterceptTransport<T> {
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), A2AError>> {
+        self.inner.poll_ready(cx)
+    }
+
+    async fn execute(&self, request: TransportRequest) -> Result<TransportResponse, A2AError> {
+        let request = self.intercept(request)?;
+        self.inner.execute(request).await
+    }
+
+    async fn connect(&self) -> Result<(), A2AError> {
+        self.inner.connect().await
+    }
+
+    fn base_url(&self) -> &Url {
+        self.inner.base_url()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn keeps_request_body(&self) -> bool {
+        self.inner.keeps_request_body()
+    }
+
+    fn set_executor(&mut self, executor: ExecutorHandle) {
+        self.inner.set_executor(executor)
+    }
+
+    fn executor(&self) -> Option<ExecutorHandle> {
+        self.inner.executor()
+    }
+
+    fn set_events(&mut self, events: ClientEvents) {
+        self.inner.set_events(events)
+    }
+
+    fn connection_state(&self) -> Option<ConnectionState> {
+        self.inner.connection_state()
+    }
+
+    async fn execute_streaming(&self, request: TransportRequest) -> Result<EventStream, A2AError> {
+        let request = self.intercept(request)?;
+        self.inner.execute_streaming(request).await
+    }
+
+    async fn execute_chunked(
+        &self,
+        request: TransportRequest,
+    ) -> Result<ChunkedResponse, A2AError> {
+        let request = self.intercept(request)?;
+        self.inner.execute_chunked(request).await
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for InterceptTransport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InterceptTransport")
+            .field("inner", &self.inner)
+            .field("interceptors", &self.interceptors.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::transport::mock::MockTransport;
+
+    /// Interceptor rejecting requests without a body
+    struct RequireBody;
+
+    impl RequestInterceptor for RequireBody {
+        fn intercept(&self, request: &mut TransportRequest) -> Result<(), A2AError> {
+            if request.body.is_empty() {
+                return Err(A2AError::Validation("Empty body".into()));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interceptors() {
+        let seen = Arc::new(Mutex::new(None));
+        let recorded = seen.clone();
+        let inner = MockTransport::new(move |req| {
+            *recorded.lock().unwrap() = Some(req);
+            TransportResponse::new(200)
+        });
+        let transport = InterceptTransport::new(inner)
+            .with_interceptor(|request: &mut TransportRequest| {
+                request
+                    .headers
+                    .insert("Host".into(), "internal.example.com".into());
+            })
+            .with_interceptor(RequireBody);
+
+        let request = TransportRequest::new("/message:send", "POST").body("{}".into());
+        transport.execute(request).await.unwrap();
+        let sent = seen.lock().unwrap().take().unwrap();
+        assert_eq!(sent.headers["Host"], "internal.example.com");
+
+        let result = transport
+            .execute(TransportRequest::new("/message:send", "POST"))
+            .await;
+        assert!(matches!(result, Err(A2AError::Validation(_))));
+        assert!(seen.lock().unwrap().is_none());
+
+        let request = TransportRequest::new("/tasks", "GET").body("{}".into());
+        transport.execute_chunked(request).await.unwrap();
+        let sent = seen.lock().unwrap().take().unwrap();
+        assert_eq!(sent.headers["Host"], "internal.example.com");
+    }
+}
@@ -4,9 +4,19 @@ use std::{
 };
 
 use async_trait::async_trait;
+use futures::StreamExt;
 use url::Url;
 
-use crate::transport::{Transport, TransportRequest, TransportResponse};
+use crate::{
+    codec::SseEvent,
+    protocol::error::A2AError,
+    transport::{EventStream, Transport, TransportRequest, TransportResponse},
+};
+
+/// Handler producing the events of a streaming request
+type StreamHandler = Arc<
+    dyn Fn(TransportRequest) -> Result<Vec<Result<SseEvent, A2AError>>, A2AError> + Send + Sync,
+>;
 
 /// Mock transport for internal testing
 ///
@@ -15,6 +25,7 @@ use crate::transport::{Transport, TransportRequest, TransportResponse};
 #[derive(Clone)]
 pub(crate) struct MockTransport {
     handler: Arc<dyn Fn(TransportRequest) -> TransportResponse + Send + Sync>,
+    stream_handler: Option<StreamHandler>,
     base_url: Url,
 }
 
@@ -26,10 +37,23 @@ impl MockTransport {
     {
         Self {
             handler: Arc::new(handler),
+            stream_handler: None,
             base_url: Url::parse("mock://").unwrap(),
         }
     }
 
+    /// Handle streaming requests with a custom handler, enabling streaming support
+    pub fn with_stream_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(TransportRequest) -> Result<Vec<Result<SseEvent, A2AError>>, A2AError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.stream_handler = Some(Arc::new(handler));
+        self
+    }
+
     /// Create a mock transport that always returns 200 OK
     #[cfg(test)]
     pub fn ok() -> Self {
@@ -58,7 +82,16 @@ impl Transport for MockTransport {
     }
 
     fn supports_streaming(&self) -> bool {
-        false
+        self.stream_handler.is_some()
+    }
+
+    async fn execute_streaming(&self, request: TransportRequest) -> Result<EventStream, A2AError> {
+        match &self.stream_handler {
+            Some(handler) => Ok(futures::stream::iter(handler(request)?).boxed()),
+            None => Err(A2AError::Transport(
+                "Streaming is not supported by this transport".into(),
+            )),
+        }
     }
 }
 
@@ -1,21 +1,50 @@
 //! Transport abstraction layer for A2A protocol
 
+#[cfg(feature = "wire-capture")]
+pub mod capture;
+pub mod dns;
+pub mod dry_run;
 pub mod http;
+pub mod intercept;
 #[cfg(test)]
 pub mod mock;
+pub mod monitor;
+pub mod negotiated;
 pub mod websocket;
 
 use std::{
     collections::HashMap,
+    fmt,
     task::{Context, Poll},
 };
 
-pub use http::HttpTransport;
+#[cfg(feature = "wire-capture")]
+pub use capture::CaptureTransport;
+pub use dry_run::DryRunTransport;
+pub use http::{HttpTransport, RedirectPolicy};
+pub use intercept::{InterceptTransport, RequestInterceptor};
+pub use monitor::ConnectionMonitor;
+pub use negotiated::NegotiatedTransport;
 use reqwest::Url;
-pub use websocket::WebSocketTransport;
+pub use websocket::{OverflowPolicy, WebSocketTransport};
 
 use async_trait::async_trait;
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+
+use crate::{
+    client::events::{ClientEvents, ConnectionState},
+    codec::SseEvent,
+    layer::AuthCredentials,
+    protocol::error::A2AError,
+    service::ExecutorHandle,
+};
+
+/// Stream of events produced by a streaming request
+pub type EventStream = BoxStream<'static, Result<SseEvent, A2AError>>;
+
+/// Stream of the chunks of a response body
+pub type BodyStream = BoxStream<'static, Result<Bytes, A2AError>>;
 
 /// Protocol-agnostic transport request
 #[derive(Debug, Clone)]
@@ -31,6 +60,12 @@ pub struct TransportRequest {
 
     /// Request body as bytes
     pub body: Bytes,
+
+    /// Credentials the authentication header was rendered from, if any
+    ///
+    /// Connection-oriented transports use them to authenticate the connection itself,
+    /// e.g. the WebSocket handshake.
+    pub auth: Option<AuthCredentials>,
 }
 
 impl TransportRequest {
@@ -41,6 +76,7 @@ impl TransportRequest {
             method: method.into(),
             headers: HashMap::new(),
             body: Bytes::new(),
+            auth: None,
         }
     }
 
@@ -55,6 +91,34 @@ impl TransportRequest {
         self.body = body;
         self
     }
+
+    /// Attach the credentials of the request
+    ///
+    /// Does not add a header; see [`AuthCredentials::to_header`].
+    pub fn auth(mut self, credentials: AuthCredentials) -> Self {
+        self.auth = Some(credentials);
+        self
+    }
+}
+
+/// Renders the request in an HTTP-like format for debugging
+///
+/// Headers are sorted by name and the body is shown as (lossy) UTF-8.
+impl fmt::Display for TransportRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", self.method, self.endpoint)?;
+
+        let mut headers: Vec<_> = self.headers.iter().collect();
+        headers.sort();
+        for (key, value) in headers {
+            writeln!(f, "{}: {}", key, value)?;
+        }
+
+        if !self.body.is_empty() {
+            write!(f, "\n{}", String::from_utf8_lossy(&self.body))?;
+        }
+        Ok(())
+    }
 }
 
 /// Protocol-agnostic transport response
@@ -108,6 +172,55 @@ impl TransportResponse {
     }
 }
 
+/// Transport response whose body is read as it arrives
+pub struct ChunkedResponse {
+    /// Status code (e.g., HTTP status code)
+    pub status: u16,
+
+    /// Response headers or metadata
+    pub headers: HashMap<String, String>,
+
+    /// Response body, chunk by chunk
+    pub body: BodyStream,
+}
+
+impl ChunkedResponse {
+    /// Read the rest of the body into a buffered response
+    pub async fn collect(self) -> Result<TransportResponse, A2AError> {
+        let body = self
+            .body
+            .try_fold(BytesMut::new(), |mut body, chunk| async move {
+                body.extend_from_slice(&chunk);
+                Ok(body)
+            })
+            .await?;
+        Ok(TransportResponse {
+            status: self.status,
+            headers: self.headers,
+            body: body.freeze(),
+        })
+    }
+}
+
+impl From<TransportResponse> for ChunkedResponse {
+    fn from(response: TransportResponse) -> Self {
+        Self {
+            status: response.status,
+            headers: response.headers,
+            body: stream::iter([Ok(response.body)]).boxed(),
+        }
+    }
+}
+
+impl fmt::Debug for ChunkedResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkedResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Core transport trait for executing protocol-agnostic requests
 ///
 /// This trait abstracts over different network protocols (HTTP, gRPC, WebSocket, etc.)
@@ -136,6 +249,13 @@ pub trait Transport: Clone + Send + Sync + 'static {
         request: TransportRequest,
     ) -> Result<TransportResponse, crate::protocol::error::A2AError>;
 
+    /// Establish the underlying connection ahead of the first request
+    ///
+    /// Transports without connection setup keep the default, which does nothing.
+    async fn connect(&self) -> Result<(), crate::protocol::error::A2AError> {
+        Ok(())
+    }
+
     /// Get the base URL or identifier for this transport
     ///
     /// For HTTP transports, this would be the base URL (e.g., "<https://agent.example.com>")
@@ -146,6 +266,60 @@ pub trait Transport: Clone + Send + Sync + 'static {
     fn supports_streaming(&self) -> bool {
         false
     }
+
+    /// Check if requests must carry their encoded body whatever their HTTP method
+    ///
+    /// Message-based transports such as WebSocket have no URL to carry the parameters
+    /// of `GET` operations, so the body is kept. HTTP transports keep the default.
+    fn keeps_request_body(&self) -> bool {
+        false
+    }
+
+    /// Spawn the transport's background tasks on an executor
+    ///
+    /// Transports given an executor of their own keep it. Transports without
+    /// background tasks keep the default, which does nothing.
+    fn set_executor(&mut self, _executor: ExecutorHandle) {}
+
+    /// Get the executor the transport was given explicitly, if any
+    fn executor(&self) -> Option<ExecutorHandle> {
+        None
+    }
+
+    /// Publish changes of the connection state to a hub
+    ///
+    /// Transports without connection state keep the default, which does nothing.
+    fn set_events(&mut self, _events: ClientEvents) {}
+
+    /// Get the state of the connection to the agent, or `None` if the transport does
+    /// not track it or has not sent anything yet
+    fn connection_state(&self) -> Option<ConnectionState> {
+        None
+    }
+
+    /// Execute a transport request whose response is a stream of events
+    ///
+    /// Transports that support streaming must override this along with
+    /// [`supports_streaming`](Transport::supports_streaming).
+    async fn execute_streaming(
+        &self,
+        _request: TransportRequest,
+    ) -> Result<EventStream, crate::protocol::error::A2AError> {
+        Err(A2AError::Transport(
+            "Streaming is not supported by this transport".into(),
+        ))
+    }
+
+    /// Execute a transport request, reading the response body as it arrives
+    ///
+    /// Used for large responses such as task lists. The default executes the request
+    /// with [`execute`](Transport::execute) and yields the whole body at once.
+    async fn execute_chunked(
+        &self,
+        request: TransportRequest,
+    ) -> Result<ChunkedResponse, crate::protocol::error::A2AError> {
+        self.execute(request).await.map(ChunkedResponse::from)
+    }
 }
 
 /// Implement Transport for `Box<dyn Transport>`
@@ -165,6 +339,10 @@ impl<T: Transport> Transport for Box<T> {
         (**self).execute(request).await
     }
 
+    async fn connect(&self) -> Result<(), crate::protocol::error::A2AError> {
+        (**self).connect().await
+    }
+
     fn base_url(&self) -> &Url {
         (**self).base_url()
     }
@@ -172,4 +350,38 @@ impl<T: Transport> Transport for Box<T> {
     fn supports_streaming(&self) -> bool {
         (**self).supports_streaming()
     }
+
+    fn keeps_request_body(&self) -> bool {
+        (**self).keeps_request_body()
+    }
+
+    fn set_executor(&mut self, executor: ExecutorHandle) {
+        (**self).set_executor(executor)
+    }
+
+    fn executor(&self) -> Option<ExecutorHandle> {
+        (**self).executor()
+    }
+
+    fn set_events(&mut self, events: ClientEvents) {
+        (**self).set_events(events)
+    }
+
+    fn connection_state(&self) -> Option<ConnectionState> {
+        (**self).connection_state()
+    }
+
+    async fn execute_streaming(
+        &self,
+        request: TransportRequest,
+    ) -> Result<EventStream, crate::protocol::error::A2AError> {
+        (**self).execute_streaming(request).await
+    }
+
+    async fn execute_chunked(
+        &self,
+        request: TransportRequest,
+    ) -> Result<ChunkedResponse, crate::protocol::error::A2AError> {
+        (**self).execute_chunked(request).await
+    }
 }
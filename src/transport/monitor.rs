@@ -0,0 +1,101 @@
+//! Tracking of connection state
+//!
+//! Transports report what they observe about the agent, e.g. a refused connection or a
+//! `503`, to a [`ConnectionMonitor`]. The monitor keeps the current
+//! [`ConnectionState`] and publishes each change as a
+//! [`ClientEvent::ConnectionChanged`] to the hub the transport was given with
+//! [`Transport::set_events`](crate::transport::Transport::set_events).
+
+use std::sync::{Arc, Mutex};
+
+use url::Url;
+
+use crate::client::events::{ClientEvent, ClientEvents, ConnectionState};
+
+/// Current connection state of a transport, publishing its changes
+///
+/// Clones share the state, so all clones of a transport report to the same monitor.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionMonitor {
+    events: Option<ClientEvents>,
+    state: Arc<Mutex<Option<ConnectionState>>>,
+}
+
+impl ConnectionMonitor {
+    /// Create a monitor publishing changes to a hub
+    pub fn new(events: ClientEvents) -> Self {
+        Self {
+            events: Some(events),
+            state: Arc::default(),
+        }
+    }
+
+    /// Publish changes to a hub from now on
+    pub fn set_events(&mut self, events: ClientEvents) {
+        self.events = Some(events);
+    }
+
+    /// Get the current state, or `None` before anything was observed
+    pub fn state(&self) -> Option<ConnectionState> {
+        *self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record the state observed for an agent, publishing it if it changed
+    ///
+    /// Returns whether the state changed.
+    pub fn report(&self, agent: &Url, state: ConnectionState, reason: Option<String>) -> bool {
+        let previous = self
+            .state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .replace(state);
+        if previous == Some(state) {
+            return false;
+        }
+
+        if let Some(events) = &self.events {
+            events.emit(ClientEvent::ConnectionChanged {
+                agent: agent.to_string(),
+                state,
+                reason,
+            });
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_monitor() {
+        let events = ClientEvents::default();
+        let mut subscriber = events.subscribe();
+        let monitor = ConnectionMonitor::new(events);
+        let agent: Url = "https://agent.example.com".parse().unwrap();
+        assert!(monitor.state().is_none());
+
+        assert!(monitor.report(&agent, ConnectionState::Connected, None));
+        assert!(!monitor
+            .clone()
+            .report(&agent, ConnectionState::Connected, None));
+        assert!(monitor.report(
+            &agent,
+            ConnectionState::Disconnected,
+            Some("Connection refused".into())
+        ));
+        assert_eq!(monitor.state(), Some(ConnectionState::Disconnected));
+
+        let states: Vec<_> = std::iter::from_fn(|| subscriber.try_recv().ok())
+            .map(|event| match event {
+                ClientEvent::ConnectionChanged { state, .. } => state,
+                other => panic!("unexpected event {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            states,
+            [ConnectionState::Connected, ConnectionState::Disconnected]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Transport chosen at runtime from an agent card
+//!
+//! Clients are generic over their transport, so the transport must be known when the
+//! client is built. [`NegotiatedTransport`] holds whichever transport
+//! [`A2AClientBuilder::discover_and_build`](crate::client::A2AClientBuilder::discover_and_build)
+//! selected for the bindings an agent advertises.
+
+use std::task::{Context, Poll};
+
+use async_trait::async_trait;
+use url::Url;
+
+use crate::{
+    client::events::{ClientEvents, ConnectionState},
+    protocol::error::A2AError,
+    service::ExecutorHandle,
+    transport::{
+        ChunkedResponse, EventStream, HttpTransport, Transport, TransportRequest,
+        TransportResponse, WebSocketTransport,
+    },
+};
+
+/// One of the transports an agent card can call for
+#[derive(Debug, Clone)]
+pub enum NegotiatedTransport {
+    /// HTTP, for the HTTP+JSON and REST bindings
+    Http(HttpTransport),
+
+    /// WebSocket, for the JSON-RPC binding at `ws://` and `wss://` endpoints
+    WebSocket(WebSocketTransport),
+}
+
+impl NegotiatedTransport {
+    /// Get the WebSocket transport, if it was selected
+    pub fn as_websocket(&self) -> Option<&WebSocketTransport> {
+        match self {
+            Self::WebSocket(transport) => Some(transport),
+            Self::Http(_) => None,
+        }
+    }
+}
+
+impl From<HttpTransport> for NegotiatedTransport {
+    fn from(transport: HttpTransport) -> Self {
+        Self::Http(transport)
+    }
+}
+
+impl From<WebSocketTransport> for NegotiatedTransport {
+    fn from(transport: WebSocketTransport) -> Self {
+        Self::WebSocket(transport)
+    }
+}
+
+#[async_trait]
+impl Transport for NegotiatedTransport {
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), A2AError>> {
+        match self {
+            Self::Http(transport) => transport.poll_ready(cx),
+            Self::WebSocket(transport) => transport.poll_ready(cx),
+        }
+    }
+
+    async fn execute(&self, request: TransportRequest) -> Result<TransportResponse, A2AError> {
+        match self {
+            Self::Http(transport) => transport.execute(request).await,
+            Self::WebSocket(transport) => transport.execute(request).await,
+        }
+    }
+
+    async fn connect(&self) -> Result<(), A2AError> {
+        match self {
+            Self::Http(transport) => transport.connect().await,
+            Self::WebSocket(transport) => transport.connect().await,
+        }
+    }
+
+    fn base_url(&self) -> &Url {
+        match self {
+            Self::Http(transport) => transport.base_url(),
+            Self::WebSocket(transport) => transport.base_url(),
+        }
+    }
+
+    fn supports_streaming(&self) -> bool {
+        match self {
+            Self::Http(transport) => transport.supports_streaming(),
+            Self::WebSocket(transport) => transport.supports_streaming(),
+        }
+    }
+
+    fn keeps_request_body(&self) -> bool {
+        match self {
+            Self::Http(transport) => transport.keeps_request_body(),
+            Self::WebSocket(transport) => transport.keeps_request_body(),
+        }
+    }
+
+    fn set_executor(&mut self, executor: ExecutorHandle) {
+        match self {
+            Self::Http(transport) => transport.set_executor(executor),
+            Self::WebSocket(transport) => transport.set_executor(executor),
+        }
+    }
+
+    fn executor(&self) -> Option<ExecutorHandle> {
+        match self {
+            Self::Http(transport) => transport.executor(),
+            Self::WebSocket(transport) => transport.executor(),
+        }
+    }
+
+    fn set_events(&mut self, events: ClientEvents) {
+        match self {
+            Self::Http(transport) => transport.set_events(events),
+            Self::WebSocket(transport) => transport.set_events(events),
+        }
+    }
+
+    fn connection_state(&self) -> Option<ConnectionState> {
+        match self {
+            Self::Http(transport) => transport.connection_state(),
+            Self::WebSocket(transport) => transport.connection_state(),
+        }
+    }
+
+    async fn execute_streaming(&self, request: TransportRequest) -> Result<EventStream, A2AError> {
+        match self {
+            Self::Http(transport) => Transport::execute_streaming(transport, request).await,
+            Self::WebSocket(transport) => Transport::execute_streaming(transport, request).await,
+        }
+    }
+
+    async fn execute_chunked(
+        &self,
+        request: TransportRequest,
+    ) -> Result<ChunkedResponse, A2AError> {
+        match self {
+            Self::Http(transport) => transport.execute_chunked(request).await,
+            Self::WebSocket(transport) => transport.execute_chunked(request).await,
+        }
+    }
+}
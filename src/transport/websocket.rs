@@ -2,85 +2,608 @@
 //!
 //! This transport provides bidirectional, stateful communication with A2A agents
 //! over WebSockets, supporting both request/response and streaming patterns.
+//!
+//! The handshake negotiates the [`WEBSOCKET_SUBPROTOCOL`] (`a2a.v1.json`). Connections
+//! to servers that do not accept it fail immediately rather than exchanging frames the
+//! server may not understand.
+//!
+//! With a binary [`WireFormat`] (see [`WebSocketTransport::with_format`]), requests and
+//! responses travel in binary frames and the subprotocol announces the format.
 
 use std::{
-    collections::HashMap,
-    sync::Arc,
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex as StdMutex, MutexGuard, Weak},
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
 use async_trait::async_trait;
+use bytes::Bytes;
 use futures::{
+    future::{AbortHandle, BoxFuture},
     stream::{SplitSink, SplitStream, Stream, StreamExt},
     SinkExt,
 };
 use serde_json::Value;
-use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio::{
+    net::TcpStream,
+    sync::{broadcast, Mutex, Notify},
+    time::{Instant as TokioInstant, MissedTickBehavior},
+};
 use tokio_tungstenite::{
-    connect_async, tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream,
+    client_async_tls, connect_async,
+    tungstenite::{
+        client::IntoClientRequest,
+        http::{header::SEC_WEBSOCKET_PROTOCOL, HeaderName, HeaderValue},
+        protocol::Message,
+    },
+    MaybeTlsStream, WebSocketStream,
 };
 use url::Url;
-use uuid::Uuid;
 
 use crate::{
-    codec::sse::SseEvent,
-    protocol::error::A2AError,
-    transport::{Transport, TransportRequest, TransportResponse},
+    client::events::{ClientEvents, ConnectionState},
+    codec::{sse::SseEvent, JsonRpcCodec, JsonRpcNotification, WireFormat},
+    layer::AuthCredentials,
+    protocol::{
+        consts::{headers, WEBSOCKET_SUBPROTOCOL},
+        error::A2AError,
+    },
+    service::ExecutorHandle,
+    transport::{
+        dns::DnsOptions, ConnectionMonitor, EventStream, Transport, TransportRequest,
+        TransportResponse,
+    },
 };
 
-type WsStream = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
 type WsSink = SplitSink<WsStream, Message>;
 type WsSource = SplitStream<WsStream>;
 
+/// Default capacity of the notification broadcast channel
+const DEFAULT_NOTIFICATION_CAPACITY: usize = 256;
+
+/// Default number of responses buffered per request
+const DEFAULT_RESPONSE_CAPACITY: usize = 64;
+
+/// Default time to wait for the response to a request
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often expired pending requests are swept
+const SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Response queues of the requests waiting for responses
+type PendingRequests = Arc<StdMutex<Routes>>;
+
+fn lock_pending(pending: &PendingRequests) -> MutexGuard<'_, Routes> {
+    pending.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Where incoming frames are routed
+///
+/// Responses carry the JSON-RPC id of their request. Agents may also push the events
+/// of a stream as notifications, which carry no id but name the task or subscription;
+/// streams are indexed by the task or subscription their request names, and by those
+/// their events reveal. Several streams may follow the same task.
+#[derive(Default)]
+struct Routes {
+    /// Pending requests by JSON-RPC id
+    requests: HashMap<String, Arc<ResponseQueue>>,
+
+    /// Open streams by task or subscription id
+    subscriptions: HashMap<String, Vec<Arc<ResponseQueue>>>,
+}
+
+impl Routes {
+    /// Remove a queue, from wherever it is registered
+    fn remove(&mut self, queue: &Arc<ResponseQueue>) {
+        self.requests.retain(|_, known| !Arc::ptr_eq(known, queue));
+        self.subscriptions.retain(|_, queues| {
+            queues.retain(|known| !Arc::ptr_eq(known, queue));
+            !queues.is_empty()
+        });
+    }
+
+    /// Index a stream by a task or subscription id
+    fn subscribe(&mut self, key: &str, queue: &Arc<ResponseQueue>) {
+        let queues = self.subscriptions.entry(key.to_string()).or_default();
+        if !queues.iter().any(|known| Arc::ptr_eq(known, queue)) {
+            queues.push(queue.clone());
+        }
+    }
+
+    /// Index a stream by the task or subscription its event names
+    fn index(&mut self, queue: &Arc<ResponseQueue>, event: &Value) {
+        if let Some(key) = subscription_key(event) {
+            self.subscribe(key, queue);
+        }
+    }
+}
+
+/// Get the subscription or task id a streaming request names in its parameters
+fn request_key(request: &Value) -> Option<&str> {
+    let params = request.get("params")?;
+    let field = |name: &str| params.get(name).and_then(Value::as_str);
+    field("subscriptionId")
+        .or_else(|| field("taskId"))
+        .or_else(|| field("id"))
+        .or_else(|| params.pointer("/message/taskId").and_then(Value::as_str))
+}
+
+/// Get the subscription or task id an event or notification belongs to
+fn subscription_key(value: &Value) -> Option<&str> {
+    let field = |name: &str| value.get(name).and_then(Value::as_str);
+    field("subscriptionId")
+        .or_else(|| field("taskId"))
+        .or_else(|| {
+            (value.get("kind").and_then(Value::as_str) == Some("task"))
+                .then(|| field("id"))
+                .flatten()
+        })
+}
+
+/// What to do when a request's response buffer is full
+///
+/// Streaming subscriptions receive events as fast as the agent sends them; if the
+/// consumer stalls, the buffer of [`WebSocketTransport::with_response_capacity`]
+/// events fills up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Stop reading from the connection until the consumer catches up
+    ///
+    /// Nothing is lost, but every request on the connection waits.
+    #[default]
+    Block,
+
+    /// Drop the oldest buffered status update to make room, blocking if there is none
+    ///
+    /// Later status updates supersede earlier ones, so consumers interested in the
+    /// current state lose little.
+    DropOldestStatus,
+
+    /// End the stream with an error and discard its buffered events
+    Error,
+}
+
+/// Bounded queue of the responses to one request
+struct ResponseQueue {
+    state: StdMutex<QueueState>,
+    capacity: usize,
+    policy: OverflowPolicy,
+    /// Whether the request streams, keeping the queue open until a final event
+    streaming: bool,
+    /// How long the request may go without a response before it expires
+    timeout: Option<Duration>,
+    /// Connection the request was sent on, closing the queue when it drops
+    connection: Weak<Mutex<WebSocketConnection>>,
+    /// Signalled when a response is pushed or the queue closes
+    readable: Notify,
+    /// Signalled when a response is popped or the receiver goes away
+    writable: Notify,
+}
+
+#[derive(Default)]
+struct QueueState {
+    responses: VecDeque<Value>,
+    overflowed: bool,
+    timed_out: bool,
+    failed: Option<A2AError>,
+    closed: bool,
+    abandoned: bool,
+    expires_at: Option<Instant>,
+}
+
+impl ResponseQueue {
+    fn new(capacity: usize, policy: OverflowPolicy, streaming: bool) -> Self {
+        Self {
+            state: StdMutex::default(),
+            capacity: capacity.max(1),
+            policy,
+            streaming,
+            timeout: None,
+            connection: Weak::new(),
+            readable: Notify::new(),
+            writable: Notify::new(),
+        }
+    }
+
+    /// Expire the request if it goes without a response for `timeout`
+    fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self.state
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+            .expires_at = timeout.map(|timeout| Instant::now() + timeout);
+        self
+    }
+
+    /// Tie the request to the connection it is sent on
+    fn on_connection(mut self, connection: &Arc<Mutex<WebSocketConnection>>) -> Self {
+        self.connection = Arc::downgrade(connection);
+        self
+    }
+
+    /// Check whether the request was sent on a connection
+    fn is_on(&self, connection: &Weak<Mutex<WebSocketConnection>>) -> bool {
+        Weak::ptr_eq(&self.connection, connection)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Push a response, applying the overflow policy if the queue is full
+    ///
+    /// Returns false if nobody will read the queue anymore.
+    async fn push(&self, mut response: Value) -> bool {
+        loop {
+            let writable = self.writable.notified();
+            match self.try_push(response) {
+                Ok(delivered) => return delivered,
+                Err(rejected) => response = rejected,
+            }
+            writable.await;
+        }
+    }
+
+    /// Push a response without waiting, handing it back if the queue is full
+    fn try_push(&self, response: Value) -> Result<bool, Value> {
+        let mut state = self.lock();
+        if state.abandoned || state.closed {
+            return Ok(false);
+        }
+        if state.responses.len() >= self.capacity {
+            match self.policy {
+                OverflowPolicy::Block => return Err(response),
+                OverflowPolicy::DropOldestStatus => {
+                    let status = state.responses.iter().position(|r| {
+                        r.get("kind").and_then(Value::as_str) == Some("status-update")
+                    });
+                    match status {
+                        Some(index) => state.responses.remove(index),
+                        None => return Err(response),
+                    };
+                }
+                OverflowPolicy::Error => {
+                    state.overflowed = true;
+                    state.closed = true;
+                    state.responses.clear();
+                    drop(state);
+                    self.readable.notify_one();
+                    return Ok(false);
+                }
+            }
+        }
+        state.responses.push_back(response);
+        state.expires_at = self.timeout.map(|timeout| Instant::now() + timeout);
+        drop(state);
+        self.readable.notify_one();
+        Ok(true)
+    }
+
+    /// Pop the next response, waiting for one; `None` once the queue is closed and
+    /// drained
+    async fn pop(&self) -> Option<Result<Value, A2AError>> {
+        loop {
+            let readable = self.readable.notified();
+            {
+                let mut state = self.lock();
+                if let Some(response) = state.responses.pop_front() {
+                    drop(state);
+                    self.writable.notify_one();
+                    return Some(Ok(response));
+                }
+                if std::mem::take(&mut state.overflowed) {
+                    return Some(Err(A2AError::Transport(format!(
+                        "WebSocket stream consumer fell more than {} events behind",
+                        self.capacity
+                    ))));
+                }
+                if std::mem::take(&mut state.timed_out) {
+                    return Some(Err(A2AError::Timeout));
+                }
+                if let Some(error) = state.failed.take() {
+                    return Some(Err(error));
+                }
+                if state.closed {
+                    return None;
+                }
+            }
+            readable.await;
+        }
+    }
+
+    /// Close the queue once it is drained, e.g. after a final event
+    fn close(&self) {
+        self.lock().closed = true;
+        self.readable.notify_one();
+    }
+
+    /// Close the queue with an error, delivered once the queue is drained
+    fn fail(&self, error: A2AError) {
+        let mut state = self.lock();
+        if state.closed || state.abandoned {
+            return;
+        }
+        state.failed = Some(error);
+        state.closed = true;
+        drop(state);
+        self.readable.notify_one();
+    }
+
+    /// Mark the queue as no longer read, unblocking a pending push
+    fn abandon(&self) {
+        self.lock().abandoned = true;
+        self.writable.notify_one();
+    }
+
+    /// Close the queue with a timeout error if it is past its expiry, returning whether
+    /// it expired
+    fn expire(&self, now: Instant) -> bool {
+        let mut state = self.lock();
+        if state.closed || state.abandoned {
+            return true;
+        }
+        if state.expires_at.is_none_or(|expires_at| expires_at > now) {
+            return false;
+        }
+        state.timed_out = true;
+        state.closed = true;
+        drop(state);
+        self.readable.notify_one();
+        true
+    }
+}
+
+/// Receiving end of a pending request's [`ResponseQueue`]
+///
+/// Dropping it, e.g. when the caller times out or stops reading a stream, abandons the
+/// queue and removes the request from the pending requests.
+struct ResponseReceiver {
+    queue: Arc<ResponseQueue>,
+    pending: PendingRequests,
+}
+
+impl ResponseReceiver {
+    /// Register a pending request, returning the receiver of its responses
+    fn register(pending: &PendingRequests, id: String, queue: ResponseQueue) -> Self {
+        let queue = Arc::new(queue);
+        lock_pending(pending).requests.insert(id, queue.clone());
+        Self {
+            queue,
+            pending: pending.clone(),
+        }
+    }
+}
+
+impl Drop for ResponseReceiver {
+    fn drop(&mut self) {
+        self.queue.abandon();
+        lock_pending(&self.pending).remove(&self.queue);
+    }
+}
+
+/// Remove the pending requests past their expiry, failing them with a timeout
+fn sweep_pending(pending: &PendingRequests) {
+    let now = Instant::now();
+    let mut routes = lock_pending(pending);
+    routes.requests.retain(|id, queue| {
+        let expired = queue.expire(now);
+        if expired {
+            tracing::debug!("Expired pending WebSocket request {}", id);
+        }
+        !expired
+    });
+    routes.subscriptions.retain(|_, queues| {
+        queues.retain(|queue| !queue.expire(now));
+        !queues.is_empty()
+    });
+}
+
+/// Check whether a streamed response is the last of its stream
+fn is_final_response(response: &Value) -> bool {
+    let terminal = |state: Option<&Value>| {
+        matches!(
+            state.and_then(Value::as_str),
+            Some("completed" | "failed" | "canceled" | "cancelled" | "rejected")
+        )
+    };
+    response.get("final").and_then(Value::as_bool) == Some(true)
+        || terminal(response.get("state"))
+        || terminal(response.pointer("/status/state"))
+}
+
 /// WebSocket connection state
 struct WebSocketConnection {
     /// Outgoing message sink
     sink: WsSink,
 
-    /// Response channels for pending requests
-    pending_requests: Arc<RwLock<HashMap<String, mpsc::UnboundedSender<Value>>>>,
+    /// Credentials the handshake was authenticated with
+    auth: Option<AuthCredentials>,
 }
 
 impl WebSocketConnection {
     /// Create a new WebSocket connection
-    async fn new(url: &Url) -> Result<(Self, WsSource), A2AError> {
+    ///
+    /// Requests `subprotocol`, if any, and fails unless the server accepts it. The
+    /// `headers` are sent with the handshake, along with the header of `auth`.
+    async fn new(
+        url: &Url,
+        dns: Option<&DnsOptions>,
+        subprotocol: Option<&str>,
+        headers: &HashMap<String, String>,
+        auth: Option<AuthCredentials>,
+    ) -> Result<(Self, WsSource), A2AError> {
+        let mut request = url
+            .as_str()
+            .into_client_request()
+            .map_err(|e| A2AError::Transport(format!("Invalid WebSocket URL: {}", e)))?;
+        let auth_header = auth.as_ref().map(AuthCredentials::to_header);
+        for (key, value) in headers
+            .iter()
+            .chain(auth_header.as_ref().map(|(k, v)| (k, v)))
+        {
+            let name = HeaderName::from_bytes(key.as_bytes()).map_err(|_| {
+                A2AError::Validation(format!("Invalid WebSocket handshake header: {}", key))
+            })?;
+            let value = HeaderValue::from_str(value).map_err(|_| {
+                A2AError::Validation(format!(
+                    "Invalid value of WebSocket handshake header {}",
+                    key
+                ))
+            })?;
+            request.headers_mut().insert(name, value);
+        }
+        if let Some(subprotocol) = subprotocol {
+            let value = HeaderValue::from_str(subprotocol).map_err(|_| {
+                A2AError::Validation(format!("Invalid WebSocket subprotocol: {}", subprotocol))
+            })?;
+            request.headers_mut().insert(SEC_WEBSOCKET_PROTOCOL, value);
+        }
+
         // Connect to WebSocket
-        let (ws_stream, _) = connect_async(url)
-            .await
-            .map_err(|e| A2AError::Transport(format!("WebSocket connection failed: {}", e)))?;
+        let (ws_stream, response) = match dns {
+            Some(dns) => client_async_tls(request, Self::connect_tcp(url, dns).await?).await,
+            None => connect_async(request).await,
+        }
+        .map_err(|e| A2AError::Transport(format!("WebSocket connection failed: {}", e)))?;
+
+        let accepted = response
+            .headers()
+            .get(SEC_WEBSOCKET_PROTOCOL)
+            .and_then(|value| value.to_str().ok());
+        if accepted != subprotocol {
+            return Err(A2AError::Protocol(match (subprotocol, accepted) {
+                (Some(requested), None) => format!(
+                    "Agent at {} did not accept the {} WebSocket subprotocol",
+                    url, requested
+                ),
+                (_, accepted) => format!(
+                    "Agent at {} selected unexpected WebSocket subprotocol {:?}",
+                    url, accepted
+                ),
+            }));
+        }
 
         let (sink, source) = ws_stream.split();
 
-        let connection = Self {
-            sink,
-            pending_requests: Arc::new(RwLock::new(HashMap::new())),
-        };
+        Ok((Self { sink, auth }, source))
+    }
+
+    /// Open a TCP connection to the URL's host, trying each resolved address in turn
+    async fn connect_tcp(url: &Url, dns: &DnsOptions) -> Result<TcpStream, A2AError> {
+        let host = url
+            .host_str()
+            .ok_or_else(|| A2AError::Transport(format!("WebSocket URL has no host: {}", url)))?;
+        let port = url.port_or_known_default().unwrap_or(80);
 
-        Ok((connection, source))
+        let mut last_error = None;
+        for addr in dns.lookup(host).await? {
+            match TcpStream::connect((addr, port)).await {
+                Ok(stream) => return Ok(stream),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(A2AError::Transport(format!(
+            "WebSocket connection to {} failed: {}",
+            host,
+            last_error.map(|e| e.to_string()).unwrap_or_default()
+        )))
     }
 
-    /// Send a JSON-RPC message
-    async fn send_message(&mut self, message: Value) -> Result<(), A2AError> {
-        let text = serde_json::to_string(&message)?;
+    /// Send an encoded JSON-RPC message, in a binary frame for binary formats
+    async fn send_message(&mut self, body: &Bytes, format: WireFormat) -> Result<(), A2AError> {
+        let message = if format.is_binary() {
+            Message::Binary(body.to_vec())
+        } else {
+            let text = std::str::from_utf8(body)
+                .map_err(|e| A2AError::Protocol(format!("Request body is not UTF-8: {}", e)))?;
+            Message::Text(text.to_owned())
+        };
         self.sink
-            .send(Message::Text(text))
+            .send(message)
             .await
             .map_err(|e| A2AError::Transport(format!("WebSocket send failed: {}", e)))?;
         Ok(())
     }
+}
 
-    /// Register a pending request
-    async fn register_request(&self, id: String, tx: mpsc::UnboundedSender<Value>) {
-        let mut pending = self.pending_requests.write().await;
-        pending.insert(id, tx);
+/// Route a response to the queue of its request
+///
+/// Request-response exchanges get the whole JSON-RPC envelope, for the codec to
+/// decode; streams get the events carried as results, and end with the error of an
+/// error response.
+async fn handle_response(pending: &PendingRequests, id: String, mut response: Value) {
+    let Some(queue) = lock_pending(pending).requests.get(&id).cloned() else {
+        return;
+    };
+    if queue.streaming {
+        if let Some(error) = response.get_mut("error").map(Value::take) {
+            fail(pending, &queue, error);
+            return;
+        }
+        let Some(result) = response.get_mut("result").map(Value::take) else {
+            return;
+        };
+        response = result;
     }
+    deliver(pending, queue, response).await;
+}
 
-    /// Handle incoming message
-    async fn handle_response(&self, id: String, result: Value) {
-        let mut pending = self.pending_requests.write().await;
-        if let Some(tx) = pending.remove(&id) {
-            let _ = tx.send(result);
+/// Route a notification carrying a stream event to its stream
+///
+/// The event is the notification's `result` parameter if it has one, and its
+/// parameters otherwise; an `error` parameter ends the streams instead. Hands the
+/// notification back if no stream is waiting for it.
+async fn handle_stream_notification(
+    pending: &PendingRequests,
+    mut notification: Value,
+) -> Option<Value> {
+    let params = notification.get_mut("params")?;
+    let queues = subscription_key(params)
+        .and_then(|key| lock_pending(pending).subscriptions.get(key).cloned())
+        .unwrap_or_default();
+    if queues.is_empty() {
+        return Some(notification);
+    }
+
+    if let Some(error) = params.get("error") {
+        for queue in &queues {
+            fail(pending, queue, error.clone());
         }
+        return None;
+    }
+
+    let event = match params.get_mut("result") {
+        Some(result) => result.take(),
+        None => params.take(),
+    };
+    for queue in queues {
+        deliver(pending, queue, event.clone()).await;
+    }
+    None
+}
+
+/// End a stream with the error an agent answered, removing it from the routes
+fn fail(pending: &PendingRequests, queue: &Arc<ResponseQueue>, error: Value) {
+    queue.fail(JsonRpcCodec::decode_error(error));
+    lock_pending(pending).remove(queue);
+}
+
+/// Push a response to a queue
+///
+/// The queue of a request-response exchange is closed after the first response, and
+/// that of a stream after its final event or once its consumer is gone. Streams stay
+/// registered until then, indexed by the task or subscription of their events.
+async fn deliver(pending: &PendingRequests, queue: Arc<ResponseQueue>, response: Value) {
+    let done = !queue.streaming || is_final_response(&response);
+    if queue.streaming && !done {
+        lock_pending(pending).index(&queue, &response);
+    }
+    let delivered = queue.push(response).await;
+    if done || !delivered {
+        queue.close();
+        lock_pending(pending).remove(&queue);
     }
 }
 
@@ -88,11 +611,45 @@ impl WebSocketConnection {
 ///
 /// This transport maintains a persistent WebSocket connection and supports
 /// concurrent requests, streaming responses, and task subscriptions.
+///
+/// Streams stay open until their final event or an error response. Agents may push the events of a stream
+/// as JSON-RPC notifications naming its task (`taskId`) or subscription
+/// (`subscriptionId`) instead of responses carrying the request's id; both reach the
+/// stream. Other notifications are broadcast to every subscriber registered through
+/// [`subscribe_notifications`](Self::subscribe_notifications).
+///
+/// When the agent closes the connection, the transport reports it
+/// [`Disconnected`](ConnectionState::Disconnected) and reconnects on the next request,
+/// [`Reconnecting`](ConnectionState::Reconnecting) until the connection is open again.
+/// Half-open connections, which never close, are detected with
+/// [`with_keepalive`](Self::with_keepalive) and [`with_idle_timeout`](Self::with_idle_timeout).
+///
+/// Requests share one connection, authenticated at the handshake. Clients whose
+/// requests carry different credentials, e.g. one per tenant, should use a transport
+/// per identity: a request with other credentials than the connection's reconnects
+/// when the connection is unused and fails with `A2AError::Auth` otherwise.
 #[derive(Clone)]
 pub struct WebSocketTransport {
     url: Url,
     connection: Arc<Mutex<Option<Arc<Mutex<WebSocketConnection>>>>>,
-    message_handler: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    pending: PendingRequests,
+    message_handler: Arc<Mutex<Option<AbortHandle>>>,
+    executor: ExecutorHandle,
+    /// Whether the executor was set with `with_executor`, overriding the client's
+    own_executor: bool,
+    notifications: broadcast::Sender<JsonRpcNotification>,
+    dns: Option<DnsOptions>,
+    subprotocol: Option<String>,
+    format: WireFormat,
+    response_capacity: usize,
+    overflow: OverflowPolicy,
+    request_timeout: Duration,
+    stream_idle_timeout: Option<Duration>,
+    keepalive: Option<Duration>,
+    idle_timeout: Option<Duration>,
+    handshake_headers: Arc<HashMap<String, String>>,
+    auth: Option<Box<AuthCredentials>>,
+    monitor: ConnectionMonitor,
 }
 
 impl WebSocketTransport {
@@ -102,73 +659,456 @@ impl WebSocketTransport {
     ///
     /// * `url` - The WebSocket URL (e.g., "ws://agent.example.com" or "wss://agent.example.com")
     pub fn new(url: impl Into<Url>) -> Self {
+        let (notifications, _) = broadcast::channel(DEFAULT_NOTIFICATION_CAPACITY);
+
         Self {
             url: url.into(),
             connection: Arc::new(Mutex::new(None)),
+            pending: PendingRequests::default(),
             message_handler: Arc::new(Mutex::new(None)),
+            executor: ExecutorHandle::default(),
+            own_executor: false,
+            notifications,
+            dns: None,
+            subprotocol: Some(WEBSOCKET_SUBPROTOCOL.to_string()),
+            format: WireFormat::Json,
+            response_capacity: DEFAULT_RESPONSE_CAPACITY,
+            overflow: OverflowPolicy::default(),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            stream_idle_timeout: None,
+            keepalive: None,
+            idle_timeout: None,
+            handshake_headers: Arc::default(),
+            auth: None,
+            monitor: ConnectionMonitor::default(),
         }
     }
 
-    /// Get or establish a WebSocket connection
-    async fn get_connection(&self) -> Result<Arc<Mutex<WebSocketConnection>>, A2AError> {
+    /// Exchange messages in the given format (default: JSON)
+    ///
+    /// Binary formats are sent in binary frames. The subprotocol is set to the one
+    /// announcing the format, so use a codec producing the same format, e.g.
+    /// [`JsonRpcCodec::with_format`].
+    pub fn with_format(mut self, format: WireFormat) -> Self {
+        self.format = format;
+        self.subprotocol = Some(format.subprotocol().to_string());
+        self
+    }
+
+    /// Negotiate a different subprotocol (default: [`WEBSOCKET_SUBPROTOCOL`])
+    pub fn with_subprotocol(mut self, subprotocol: impl Into<String>) -> Self {
+        self.subprotocol = Some(subprotocol.into());
+        self
+    }
+
+    /// Connect without negotiating a subprotocol, for servers predating it
+    pub fn without_subprotocol(mut self) -> Self {
+        self.subprotocol = None;
+        self
+    }
+
+    /// Run the message reader on an executor (default: the ambient Tokio runtime)
+    ///
+    /// The executor is kept when the transport is passed to
+    /// [`A2AClientBuilder`](crate::client::A2AClientBuilder), whose clients report its
+    /// tasks unless the builder was given an executor too.
+    pub fn with_executor(mut self, executor: ExecutorHandle) -> Self {
+        self.executor = executor;
+        self.own_executor = true;
+        self
+    }
+
+    /// Resolve the agent's hostname through the given options
+    pub fn with_dns(mut self, dns: DnsOptions) -> Self {
+        self.dns = Some(dns);
+        self
+    }
+
+    /// Set the capacity of the notification channel (default: 256)
+    ///
+    /// Subscribers that fall more than `capacity` notifications behind skip the
+    /// oldest ones. A capacity of zero is raised to one.
+    pub fn with_notification_capacity(mut self, capacity: usize) -> Self {
+        let (notifications, _) = broadcast::channel(capacity.max(1));
+        self.notifications = notifications;
+        self
+    }
+
+    /// Set how many events are buffered per stream (default: 64)
+    pub fn with_response_capacity(mut self, capacity: usize) -> Self {
+        self.response_capacity = capacity;
+        self
+    }
+
+    /// Set what to do when a stream's buffer is full (default: [`OverflowPolicy::Block`])
+    pub fn with_overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow = policy;
+        self
+    }
+
+    /// Set how long to wait for the response to a request (default: 30 seconds)
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// End streams with a timeout error once the agent sends nothing for `timeout`
+    ///
+    /// By default streams wait for events as long as the connection is open.
+    pub fn with_stream_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.stream_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Send a ping every `interval` while connected
+    ///
+    /// Agents answer pings with pongs, so a connection stays busy even while no
+    /// requests are made. Combine with [`with_idle_timeout`](Self::with_idle_timeout)
+    /// to detect agents that stopped answering.
+    pub fn with_keepalive(mut self, interval: Duration) -> Self {
+        self.keepalive = Some(interval);
+        self
+    }
+
+    /// Drop the connection and reconnect once no frame arrived for `timeout`
+    ///
+    /// Any frame counts, including pongs. Without [`with_keepalive`](Self::with_keepalive),
+    /// a ping is sent every half `timeout`, so quiet but healthy connections are kept.
+    /// Requests waiting on the dropped connection fail right away instead of timing
+    /// out. By default connections are kept until the agent closes them.
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Send a header with the handshake of every connection
+    pub fn with_handshake_header(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        Arc::make_mut(&mut self.handshake_headers).insert(key.into(), value.into());
+        self
+    }
+
+    /// Authenticate the handshake with credentials
+    ///
+    /// Requests carrying their own credentials, e.g. those of an
+    /// [`AuthLayer`](crate::layer::AuthLayer), authenticate with those instead.
+    pub fn with_auth(mut self, credentials: AuthCredentials) -> Self {
+        self.auth = Some(Box::new(credentials));
+        self
+    }
+
+    /// Get the number of requests waiting for responses, including open streams
+    pub fn pending_requests(&self) -> usize {
+        lock_pending(&self.pending).requests.len()
+    }
+
+    /// Subscribe to JSON-RPC notifications pushed by the agent
+    ///
+    /// Only notifications received after subscribing are delivered.
+    pub fn subscribe_notifications(&self) -> broadcast::Receiver<JsonRpcNotification> {
+        self.notifications.subscribe()
+    }
+
+    /// Stream of JSON-RPC notifications pushed by the agent
+    ///
+    /// Notifications missed because the consumer lagged behind are skipped.
+    pub fn notifications(&self) -> impl Stream<Item = JsonRpcNotification> {
+        futures::stream::unfold(self.subscribe_notifications(), |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(notification) => return Some((notification, rx)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Skipped {} WebSocket notifications", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+
+    /// Get or establish a WebSocket connection authenticated with `auth`
+    ///
+    /// The connection is authenticated with `auth`, or the transport's credentials if
+    /// the request carries none. A connection authenticated with other credentials is
+    /// replaced once no request waits on it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `A2AError::Auth` if requests authenticated with other credentials still
+    /// wait on the connection
+    async fn get_connection(
+        &self,
+        auth: Option<&AuthCredentials>,
+    ) -> Result<Arc<Mutex<WebSocketConnection>>, A2AError> {
+        let auth = auth.or(self.auth.as_deref());
         let mut conn_guard = self.connection.lock().await;
 
-        if conn_guard.is_none() {
-            // Establish new connection
-            let (connection, source) = WebSocketConnection::new(&self.url).await?;
-            let conn_arc = Arc::new(Mutex::new(connection));
-            *conn_guard = Some(conn_arc.clone());
+        if let Some(current) = conn_guard.clone() {
+            let mut connection = current.lock().await;
+            if connection.auth.as_ref() == auth {
+                return Ok(current.clone());
+            }
 
-            // Start message handler task
-            self.start_message_handler(source, conn_arc.clone()).await;
+            let weak = Arc::downgrade(&current);
+            let in_use = lock_pending(&self.pending)
+                .requests
+                .values()
+                .any(|queue| queue.is_on(&weak));
+            if in_use {
+                return Err(A2AError::Auth(
+                    "WebSocket connection is in use by requests with other credentials".into(),
+                ));
+            }
 
-            Ok(conn_arc)
-        } else {
-            Ok(conn_guard.as_ref().unwrap().clone())
+            // Nobody else uses the connection, so reconnect with the new credentials
+            tracing::debug!(url = %self.url, "Reconnecting WebSocket with other credentials");
+            if let Some(handler) = self.message_handler.lock().await.take() {
+                handler.abort();
+            }
+            let _ = connection.sink.close().await;
+            *conn_guard = None;
         }
+
+        if self.monitor.state() == Some(ConnectionState::Disconnected) {
+            self.monitor
+                .report(&self.url, ConnectionState::Reconnecting, None);
+        }
+
+        // Establish new connection
+        let (connection, source) = WebSocketConnection::new(
+            &self.url,
+            self.dns.as_ref(),
+            self.subprotocol.as_deref(),
+            &self.handshake_headers,
+            auth.cloned(),
+        )
+        .await
+        .inspect_err(|e| {
+            self.monitor.report(
+                &self.url,
+                ConnectionState::Disconnected,
+                Some(e.to_string()),
+            );
+        })?;
+        let conn_arc = Arc::new(Mutex::new(connection));
+        *conn_guard = Some(conn_arc.clone());
+        self.monitor
+            .report(&self.url, ConnectionState::Connected, None);
+
+        // Start message handler task
+        self.start_message_handler(conn_arc.clone(), source, auth.cloned())
+            .await;
+
+        Ok(conn_arc)
+    }
+
+    /// Connect with the credentials of a dropped connection
+    ///
+    /// Boxed, since it runs in the task [`get_connection`](Self::get_connection) starts.
+    fn reconnect(&self, auth: Option<AuthCredentials>) -> BoxFuture<'_, Result<(), A2AError>> {
+        Box::pin(async move { self.get_connection(auth.as_ref()).await.map(|_| ()) })
     }
 
     /// Start the background task that handles incoming WebSocket messages
+    ///
+    /// Reconnects after an idle timeout authenticate with `auth`, the credentials of
+    /// the connection.
     async fn start_message_handler(
         &self,
-        mut source: WsSource,
         connection: Arc<Mutex<WebSocketConnection>>,
+        mut source: WsSource,
+        auth: Option<AuthCredentials>,
     ) {
         let mut handler_guard = self.message_handler.lock().await;
+        let notifications = self.notifications.clone();
+        let pending = self.pending.clone();
+        let monitor = self.monitor.clone();
+        let url = self.url.clone();
+
+        // Hold the transport weakly, so the reader stops once the transport is gone
+        let slot = Arc::downgrade(&self.connection);
+        let connection = Arc::downgrade(&connection);
+        let reconnect = self.idle_timeout.is_some().then(|| {
+            let mut transport = self.clone();
+            transport.connection = Arc::default();
+            transport
+        });
 
-        let handle = tokio::spawn(async move {
-            while let Some(result) = source.next().await {
-                match result {
-                    Ok(Message::Text(text)) => {
-                        // Parse JSON-RPC response
-                        if let Ok(jsonrpc) = serde_json::from_str::<Value>(&text) {
-                            // Extract id and result
-                            if let (Some(id), Some(result)) = (
-                                jsonrpc
-                                    .get("id")
-                                    .and_then(|i| i.as_str())
-                                    .map(|s| s.to_string()),
-                                jsonrpc.get("result"),
-                            ) {
-                                let conn = connection.lock().await;
-                                conn.handle_response(id, result.clone()).await;
-                            }
+        let format = self.format;
+        let idle_timeout = self.idle_timeout;
+        let period = self.keepalive.or(idle_timeout.map(|timeout| timeout / 2));
+        let mut keepalive = period.map(|period| {
+            let mut keepalive = tokio::time::interval_at(TokioInstant::now() + period, period);
+            keepalive.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            keepalive
+        });
+
+        let handler = async move {
+            let mut sweep = tokio::time::interval(SWEEP_INTERVAL);
+            sweep.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            let mut reason = "Connection closed by agent".to_string();
+            let mut last_seen = TokioInstant::now();
+            let mut idle = false;
+            loop {
+                let result = tokio::select! {
+                    result = source.next() => result,
+                    _ = sweep.tick() => {
+                        sweep_pending(&pending);
+                        let waiting = || {
+                            let routes = lock_pending(&pending);
+                            routes.requests.values().any(|queue| queue.is_on(&connection))
+                        };
+                        if slot.strong_count() == 0 && !waiting() {
+                            reason = "Transport dropped".to_string();
+                            break;
+                        }
+                        continue;
+                    }
+                    _ = async {
+                        match keepalive.as_mut() {
+                            Some(keepalive) => keepalive.tick().await,
+                            None => std::future::pending().await,
+                        }
+                    } => {
+                        let Some(connection) = connection.upgrade() else {
+                            break;
+                        };
+                        let sent = connection.lock().await.sink.send(Message::Ping(Vec::new())).await;
+                        if let Err(e) = sent {
+                            reason = format!("WebSocket ping failed: {}", e);
+                            break;
+                        }
+                        continue;
+                    }
+                    _ = async {
+                        match idle_timeout {
+                            Some(timeout) => tokio::time::sleep_until(last_seen + timeout).await,
+                            None => std::future::pending().await,
                         }
+                    } => {
+                        tracing::warn!(%url, "WebSocket connection idle, reconnecting");
+                        reason = format!("No frames received for {:?}", idle_timeout.unwrap_or_default());
+                        idle = true;
+                        break;
                     }
+                };
+                let Some(result) = result else {
+                    break;
+                };
+                last_seen = TokioInstant::now();
+                let message = match result {
+                    Ok(Message::Text(text)) => serde_json::from_str::<Value>(&text).ok(),
+                    Ok(Message::Binary(data)) => format
+                        .decode::<Value>(&data)
+                        .inspect_err(|e| tracing::warn!("Ignoring malformed binary frame: {}", e))
+                        .ok(),
                     Ok(Message::Close(_)) => {
                         // Connection closed
                         break;
                     }
                     Err(e) => {
                         tracing::error!("WebSocket receive error: {}", e);
+                        reason = e.to_string();
                         break;
                     }
-                    _ => {}
+                    _ => None,
+                };
+
+                // Parse JSON-RPC response or notification
+                let Some(jsonrpc) = message else {
+                    continue;
+                };
+                if matches!(jsonrpc.get("id"), None | Some(Value::Null)) {
+                    if let Some(jsonrpc) = handle_stream_notification(&pending, jsonrpc).await {
+                        Self::handle_notification(&notifications, jsonrpc);
+                    }
+                    continue;
+                }
+
+                if let Some(id) = jsonrpc.get("id").and_then(Value::as_str) {
+                    let id = id.to_string();
+                    handle_response(&pending, id, jsonrpc).await;
                 }
             }
-        });
 
-        *handler_guard = Some(handle);
+            // Let the next request reconnect, unless a new connection replaced this one
+            let slot = slot.upgrade();
+            if let Some(slot) = &slot {
+                let mut slot = slot.lock().await;
+                if slot
+                    .as_ref()
+                    .is_some_and(|current| Arc::as_ptr(current) == connection.as_ptr())
+                {
+                    *slot = None;
+                }
+            }
+            monitor.report(&url, ConnectionState::Disconnected, Some(reason));
+
+            // Wake up the requests waiting on this connection; requests sent on a
+            // connection that replaced it keep waiting
+            {
+                let mut routes = lock_pending(&pending);
+                let orphaned: Vec<_> = routes
+                    .requests
+                    .values()
+                    .chain(routes.subscriptions.values().flatten())
+                    .filter(|queue| queue.is_on(&connection))
+                    .cloned()
+                    .collect();
+                for queue in &orphaned {
+                    queue.close();
+                    routes.remove(queue);
+                }
+            }
+
+            // Reconnect right away, rather than on the next request
+            if let (true, Some(mut transport), Some(slot)) = (idle, reconnect, slot) {
+                transport.connection = slot;
+                if let Err(e) = transport.reconnect(auth).await {
+                    tracing::warn!(%url, error = %e, "WebSocket reconnect failed");
+                }
+            }
+        };
+
+        *handler_guard = Some(self.executor.spawn("a2a-ws-reader", handler));
+    }
+
+    /// Broadcast a JSON-RPC notification to subscribers
+    fn handle_notification(notifications: &broadcast::Sender<JsonRpcNotification>, value: Value) {
+        match serde_json::from_value::<JsonRpcNotification>(value) {
+            // Sending only fails when nobody is subscribed, which is fine
+            Ok(notification) => {
+                let _ = notifications.send(notification);
+            }
+            Err(e) => tracing::warn!("Ignoring malformed WebSocket notification: {}", e),
+        }
+    }
+
+    /// Get the JSON-RPC id of an encoded request, used to route its responses
+    ///
+    /// Fails if the request was encoded in a different format than the transport's.
+    fn request_id(&self, request: &TransportRequest) -> Result<String, A2AError> {
+        let content_type = request
+            .headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(headers::CONTENT_TYPE))
+            .map(|(_, value)| value.as_str());
+        if let Some(content_type) = content_type {
+            if WireFormat::from_media_type(content_type) != Some(self.format) {
+                return Err(A2AError::Validation(format!(
+                    "Request encoded as {} but the WebSocket transport exchanges {}",
+                    content_type,
+                    self.format.media_type()
+                )));
+            }
+        }
+
+        JsonRpcCodec::request_id_in(self.format, &request.body)?.ok_or_else(|| {
+            A2AError::Protocol("WebSocket requests must be JSON-RPC requests with an id".into())
+        })
     }
 
     /// Execute a streaming request (for task subscription)
@@ -178,41 +1118,31 @@ impl WebSocketTransport {
         &self,
         request: TransportRequest,
     ) -> Result<impl Stream<Item = Result<SseEvent, A2AError>>, A2AError> {
-        // Parse request body as JSON-RPC
-        let jsonrpc: Value = serde_json::from_slice(&request.body)?;
+        let request_id = self.request_id(&request)?;
 
         // Get connection
-        let connection = self.get_connection().await?;
+        let connection = self.get_connection(request.auth.as_ref()).await?;
 
-        // Send message
-        {
-            let mut conn = connection.lock().await;
-            conn.send_message(jsonrpc.clone()).await?;
+        // Register the streaming response queue before sending, so no event is missed
+        let queue = ResponseQueue::new(self.response_capacity, self.overflow, true)
+            .with_timeout(self.stream_idle_timeout)
+            .on_connection(&connection);
+        let rx = ResponseReceiver::register(&self.pending, request_id, queue);
+        let body = self.format.decode::<Value>(&request.body).ok();
+        if let Some(key) = body.as_ref().and_then(request_key) {
+            lock_pending(&self.pending).subscribe(key, &rx.queue);
         }
 
-        // Create a channel for streaming events
-        let (tx, rx) = mpsc::unbounded_channel();
-
-        // Extract request ID
-        let request_id = jsonrpc
-            .get("id")
-            .and_then(|i| i.as_str())
-            .unwrap_or("")
-            .to_string();
-
-        // Register the streaming response handler
+        // Send message
         {
-            let conn = connection.lock().await;
-            conn.register_request(request_id, tx).await;
+            let mut conn = connection.lock().await;
+            conn.send_message(&request.body, self.format).await?;
         }
 
-        // Convert receiver into a stream
-        let stream = futures::stream::unfold(rx, |mut rx| async move {
-            rx.recv().await.map(|value| {
-                // Convert Value to SseEvent
-                let event = Self::value_to_sse_event(value);
-                (event, rx)
-            })
+        // Convert the queue into a stream
+        let stream = futures::stream::unfold(rx, |rx| async move {
+            let event = rx.queue.pop().await?.and_then(Self::value_to_sse_event);
+            Some((event, rx))
         });
 
         Ok(stream)
@@ -235,6 +1165,7 @@ impl WebSocketTransport {
             kind,
             payload: value,
             final_event,
+            id: None,
         })
     }
 }
@@ -243,6 +1174,15 @@ impl std::fmt::Debug for WebSocketTransport {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("WebSocketTransport")
             .field("url", &self.url)
+            .field("subprotocol", &self.subprotocol)
+            .field("format", &self.format)
+            .field("response_capacity", &self.response_capacity)
+            .field("overflow", &self.overflow)
+            .field("request_timeout", &self.request_timeout)
+            .field("stream_idle_timeout", &self.stream_idle_timeout)
+            .field("keepalive", &self.keepalive)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("pending_requests", &self.pending_requests())
             .finish()
     }
 }
@@ -255,50 +1195,43 @@ impl Transport for WebSocketTransport {
     }
 
     async fn execute(&self, request: TransportRequest) -> Result<TransportResponse, A2AError> {
-        // Parse request body as JSON-RPC
-        let jsonrpc: Value = serde_json::from_slice(&request.body)?;
-
-        // Generate request ID if not present
-        let request_id = jsonrpc
-            .get("id")
-            .and_then(|i| i.as_str())
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| Uuid::now_v7().to_string());
+        let request_id = self.request_id(&request)?;
 
         // Get connection
-        let connection = self.get_connection().await?;
+        let connection = self.get_connection(request.auth.as_ref()).await?;
 
-        // Create channel for response
-        let (tx, mut rx) = mpsc::unbounded_channel();
-
-        // Register request
-        {
-            let conn = connection.lock().await;
-            conn.register_request(request_id.clone(), tx).await;
-        }
+        // Register the request; it is removed once answered, timed out, or cancelled
+        let queue = ResponseQueue::new(1, OverflowPolicy::Block, false)
+            .with_timeout(Some(self.request_timeout))
+            .on_connection(&connection);
+        let rx = ResponseReceiver::register(&self.pending, request_id, queue);
 
         // Send message
         {
             let mut conn = connection.lock().await;
-            conn.send_message(jsonrpc).await?;
+            conn.send_message(&request.body, self.format).await?;
         }
 
-        // Wait for response (with timeout)
-        let response_value = tokio::time::timeout(std::time::Duration::from_secs(30), rx.recv())
+        // Wait for the response envelope (with timeout)
+        let response_value = tokio::time::timeout(self.request_timeout, rx.queue.pop())
             .await
             .map_err(|_| A2AError::Timeout)?
-            .ok_or_else(|| A2AError::Transport("Response channel closed".to_string()))?;
+            .ok_or_else(|| A2AError::Transport("Response channel closed".to_string()))??;
 
         // Convert response to TransportResponse
-        let body = serde_json::to_vec(&response_value)?;
+        let body = self.format.encode(&response_value)?;
 
         Ok(TransportResponse {
             status: 200,
             headers: HashMap::new(),
-            body: body.into(),
+            body,
         })
     }
 
+    async fn connect(&self) -> Result<(), A2AError> {
+        self.get_connection(None).await.map(|_| ())
+    }
+
     fn base_url(&self) -> &Url {
         &self.url
     }
@@ -306,12 +1239,66 @@ impl Transport for WebSocketTransport {
     fn supports_streaming(&self) -> bool {
         true
     }
+
+    fn keeps_request_body(&self) -> bool {
+        true
+    }
+
+    fn set_executor(&mut self, executor: ExecutorHandle) {
+        if !self.own_executor {
+            self.executor = executor;
+        }
+    }
+
+    fn executor(&self) -> Option<ExecutorHandle> {
+        self.own_executor.then(|| self.executor.clone())
+    }
+
+    fn set_events(&mut self, events: ClientEvents) {
+        self.monitor.set_events(events);
+    }
+
+    fn connection_state(&self) -> Option<ConnectionState> {
+        self.monitor.state()
+    }
+
+    async fn execute_streaming(&self, request: TransportRequest) -> Result<EventStream, A2AError> {
+        Ok(WebSocketTransport::execute_streaming(self, request)
+            .await?
+            .boxed())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::{
+        accept_async, accept_hdr_async,
+        tungstenite::handshake::server::{Callback, ErrorResponse, Request, Response},
+    };
+
+    use crate::{codec::Codec, protocol::operation::A2AOperation};
+
     use super::*;
 
+    /// Handshake callback agreeing to the requested A2A subprotocol
+    struct AcceptA2A;
+
+    impl Callback for AcceptA2A {
+        fn on_request(
+            self,
+            request: &Request,
+            mut response: Response,
+        ) -> Result<Response, ErrorResponse> {
+            if let Some(subprotocol) = request.headers().get(SEC_WEBSOCKET_PROTOCOL) {
+                response
+                    .headers_mut()
+                    .insert(SEC_WEBSOCKET_PROTOCOL, subprotocol.clone());
+            }
+            Ok(response)
+        }
+    }
+
     #[test]
     fn test_websocket_transport_creation() {
         let transport = WebSocketTransport::new(Url::parse("ws://example.com").unwrap());
@@ -319,6 +1306,758 @@ mod tests {
         assert!(transport.supports_streaming());
     }
 
+    #[test]
+    fn test_zero_notification_capacity() {
+        let transport = WebSocketTransport::new(Url::parse("ws://example.com").unwrap())
+            .with_notification_capacity(0);
+        let mut notifications = transport.subscribe_notifications();
+        assert!(notifications.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_notifications_are_broadcast() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_hdr_async(stream, AcceptA2A).await.unwrap();
+
+            // Wait for the request, push a notification, then answer the request
+            let Some(Ok(Message::Text(text))) = ws.next().await else {
+                panic!("Expected a text frame");
+            };
+            let request: Value = serde_json::from_str(&text).unwrap();
+
+            let notification = serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "tasks/statusUpdate",
+                "params": {"taskId": "task-123", "state": "working"}
+            });
+            ws.send(Message::Text(notification.to_string()))
+                .await
+                .unwrap();
+
+            let response = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": {}
+            });
+            ws.send(Message::Text(response.to_string())).await.unwrap();
+        });
+
+        let transport = WebSocketTransport::new(Url::parse(&format!("ws://{}", addr)).unwrap());
+        let mut notifications = transport.subscribe_notifications();
+
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "req-1",
+            "method": "tasks/get",
+            "params": {}
+        });
+        let request = TransportRequest::new("", "POST").body(body.to_string().into());
+        transport.execute(request).await.unwrap();
+
+        let notification = notifications.recv().await.unwrap();
+        assert_eq!(notification.method, "tasks/statusUpdate");
+        assert_eq!(notification.params["taskId"], "task-123");
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_closes_only_its_requests() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_hdr_async(stream, AcceptA2A).await.unwrap();
+            ws.next().await;
+            ws.close(None).await.unwrap();
+        });
+
+        let transport = WebSocketTransport::new(url);
+        let connection = transport.get_connection(None).await.unwrap();
+        let queue = |connection: Option<&Arc<Mutex<WebSocketConnection>>>| {
+            let queue = ResponseQueue::new(1, OverflowPolicy::Block, false);
+            match connection {
+                Some(connection) => queue.on_connection(connection),
+                None => queue,
+            }
+        };
+        let sent = ResponseReceiver::register(
+            &transport.pending,
+            "req-1".into(),
+            queue(Some(&connection)),
+        );
+        let other = ResponseReceiver::register(&transport.pending, "req-2".into(), queue(None));
+
+        connection
+            .lock()
+            .await
+            .send_message(&Bytes::from_static(b"{}"), WireFormat::Json)
+            .await
+            .unwrap();
+        assert!(sent.queue.pop().await.is_none());
+        assert!(!other.queue.lock().closed);
+        assert_eq!(transport.pending_requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_connection_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = accept_hdr_async(stream, AcceptA2A).await.unwrap();
+                ws.close(None).await.unwrap();
+            }
+        });
+
+        let events = ClientEvents::default();
+        let mut subscriber = events.subscribe();
+        let mut transport = WebSocketTransport::new(url);
+        transport.set_events(events);
+
+        async fn next_state(
+            subscriber: &mut broadcast::Receiver<crate::client::ClientEvent>,
+        ) -> ConnectionState {
+            match subscriber.recv().await.unwrap() {
+                crate::client::ClientEvent::ConnectionChanged { state, .. } => state,
+                other => panic!("unexpected event {:?}", other),
+            }
+        }
+
+        transport.connect().await.unwrap();
+        assert_eq!(
+            next_state(&mut subscriber).await,
+            ConnectionState::Connected
+        );
+        assert_eq!(
+            next_state(&mut subscriber).await,
+            ConnectionState::Disconnected
+        );
+        assert_eq!(
+            transport.connection_state(),
+            Some(ConnectionState::Disconnected)
+        );
+
+        transport.connect().await.unwrap();
+        assert_eq!(
+            next_state(&mut subscriber).await,
+            ConnectionState::Reconnecting
+        );
+        assert_eq!(
+            next_state(&mut subscriber).await,
+            ConnectionState::Connected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handshake_headers() {
+        /// Handshake callback recording headers, rejecting handshakes without credentials
+        struct RequireAuth(tokio::sync::mpsc::UnboundedSender<(String, Option<String>)>);
+
+        impl Callback for RequireAuth {
+            fn on_request(
+                self,
+                request: &Request,
+                response: Response,
+            ) -> Result<Response, ErrorResponse> {
+                let header = |name| {
+                    request
+                        .headers()
+                        .get(name)
+                        .and_then(|value| value.to_str().ok())
+                        .map(str::to_string)
+                };
+                let Some(authorization) = header("authorization") else {
+                    let mut rejection = ErrorResponse::new(None);
+                    *rejection.status_mut() =
+                        tokio_tungstenite::tungstenite::http::StatusCode::UNAUTHORIZED;
+                    return Err(rejection);
+                };
+                self.0.send((authorization, header("x-tenant"))).unwrap();
+                AcceptA2A.on_request(request, response)
+            }
+        }
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+        let (handshakes, mut received) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let Ok(mut ws) = accept_hdr_async(stream, RequireAuth(handshakes.clone())).await
+                else {
+                    continue;
+                };
+                tokio::spawn(async move {
+                    while let Some(Ok(Message::Text(text))) = ws.next().await {
+                        let request: Value = serde_json::from_str(&text).unwrap();
+                        let response = serde_json::json!({"jsonrpc": "2.0", "id": request["id"], "result": {}});
+                        ws.send(Message::Text(response.to_string())).await.unwrap();
+                    }
+                });
+            }
+        });
+
+        let transport =
+            WebSocketTransport::new(url.clone()).with_handshake_header("X-Tenant", "acme");
+        assert!(transport.connect().await.is_err());
+
+        // Connections opened by a request authenticate with its credentials
+        let body = serde_json::json!({"jsonrpc": "2.0", "id": "req-1", "method": "tasks/get"});
+        let request = TransportRequest::new("", "POST")
+            .body(body.to_string().into())
+            .auth(AuthCredentials::bearer("token-1"));
+        transport.execute(request).await.unwrap();
+        assert_eq!(
+            received.recv().await.unwrap(),
+            ("Bearer token-1".to_string(), Some("acme".to_string()))
+        );
+
+        // Requests with other credentials reconnect while the connection is unused
+        let request = |id: &str, token: &str| {
+            let body =
+                serde_json::json!({"jsonrpc": "2.0", "id": id, "method": "tasks/resubscribe"});
+            TransportRequest::new("", "POST")
+                .body(body.to_string().into())
+                .auth(AuthCredentials::bearer(token))
+        };
+        let _stream = Transport::execute_streaming(&transport, request("req-2", "token-2"))
+            .await
+            .unwrap();
+        assert_eq!(received.recv().await.unwrap().0, "Bearer token-2");
+
+        // ...and are refused while requests of other credentials wait on it
+        assert!(matches!(
+            transport.execute(request("req-3", "token-3")).await,
+            Err(A2AError::Auth(_))
+        ));
+
+        let transport = WebSocketTransport::new(url).with_auth(AuthCredentials::bearer("token-4"));
+        transport.connect().await.unwrap();
+        assert_eq!(
+            received.recv().await.unwrap(),
+            ("Bearer token-4".to_string(), None)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reader_stops_with_transport() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_hdr_async(stream, AcceptA2A).await.unwrap();
+            while let Some(Ok(message)) = ws.next().await {
+                if message.is_close() {
+                    break;
+                }
+            }
+        });
+
+        let transport = WebSocketTransport::new(url).with_keepalive(Duration::from_millis(20));
+        transport.connect().await.unwrap();
+        drop(transport);
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_idle_timeout_keeps_quiet_connections() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+        tokio::spawn(async move {
+            // Reading answers pings with pongs
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_hdr_async(stream, AcceptA2A).await.unwrap();
+            while ws.next().await.is_some() {}
+        });
+
+        let events = ClientEvents::default();
+        let mut subscriber = events.subscribe();
+        let mut transport =
+            WebSocketTransport::new(url).with_idle_timeout(Duration::from_millis(100));
+        transport.set_events(events);
+        transport.connect().await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        let states: Vec<_> = std::iter::from_fn(|| subscriber.try_recv().ok()).collect();
+        assert_eq!(states.len(), 1);
+        assert_eq!(
+            transport.connection_state(),
+            Some(ConnectionState::Connected)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_idle_connection_reconnects() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+        tokio::spawn(async move {
+            // Accept connections but never read from them, so pings go unanswered
+            let mut connections = Vec::new();
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                connections.push(accept_hdr_async(stream, AcceptA2A).await.unwrap());
+            }
+        });
+
+        let events = ClientEvents::default();
+        let mut subscriber = events.subscribe();
+        let mut transport = WebSocketTransport::new(url)
+            .with_keepalive(Duration::from_millis(20))
+            .with_idle_timeout(Duration::from_millis(100));
+        transport.set_events(events);
+        transport.connect().await.unwrap();
+
+        let mut states = Vec::new();
+        while states.len() < 4 {
+            let event = tokio::time::timeout(Duration::from_secs(5), subscriber.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            if let crate::client::ClientEvent::ConnectionChanged { state, .. } = event {
+                states.push(state);
+            }
+        }
+        assert_eq!(
+            states,
+            [
+                ConnectionState::Connected,
+                ConnectionState::Disconnected,
+                ConnectionState::Reconnecting,
+                ConnectionState::Connected
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subprotocol_must_be_accepted() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let _ = accept_async(stream).await;
+            }
+        });
+
+        let error = WebSocketTransport::new(url.clone())
+            .connect()
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&error, A2AError::Protocol(message) if message.contains(WEBSOCKET_SUBPROTOCOL)),
+            "{:?}",
+            error
+        );
+
+        // Servers predating the subprotocol are reachable when negotiation is disabled
+        WebSocketTransport::new(url)
+            .without_subprotocol()
+            .connect()
+            .await
+            .unwrap();
+    }
+
+    #[cfg(feature = "cbor")]
+    #[tokio::test]
+    async fn test_binary_frames() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_hdr_async(stream, AcceptA2A).await.unwrap();
+
+            let Some(Ok(Message::Binary(data))) = ws.next().await else {
+                panic!("Expected a binary frame");
+            };
+            let request: Value = WireFormat::Cbor.decode(&data).unwrap();
+            let response = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": {"echo": request["params"]}
+            });
+            let body = WireFormat::Cbor.encode(&response).unwrap();
+            ws.send(Message::Binary(body.to_vec())).await.unwrap();
+        });
+
+        let transport = WebSocketTransport::new(Url::parse(&format!("ws://{}", addr)).unwrap())
+            .with_format(WireFormat::Cbor);
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "req-1",
+            "method": "tasks/get",
+            "params": {"id": "task-123"}
+        });
+
+        // Requests encoded by a JSON codec are rejected before anything is sent
+        let json = TransportRequest::new("", "POST")
+            .header(headers::CONTENT_TYPE, WireFormat::Json.media_type())
+            .body(body.to_string().into());
+        assert!(matches!(
+            transport.execute(json).await,
+            Err(A2AError::Validation(_))
+        ));
+
+        let request = TransportRequest::new("", "POST")
+            .header(headers::CONTENT_TYPE, WireFormat::Cbor.media_type())
+            .body(WireFormat::Cbor.encode(&body).unwrap());
+        let response = transport.execute(request).await.unwrap();
+        let envelope: Value = WireFormat::Cbor.decode(&response.body).unwrap();
+        assert_eq!(envelope["result"]["echo"]["id"], "task-123");
+    }
+
+    #[tokio::test]
+    async fn test_streamed_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_hdr_async(stream, AcceptA2A).await.unwrap();
+
+            let Some(Ok(Message::Text(text))) = ws.next().await else {
+                panic!("Expected a text frame");
+            };
+            let request: Value = serde_json::from_str(&text).unwrap();
+            for (state, last) in [
+                ("submitted", false),
+                ("working", false),
+                ("completed", true),
+            ] {
+                let event = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "result": {"kind": "status-update", "status": {"state": state}, "final": last}
+                });
+                ws.send(Message::Text(event.to_string())).await.unwrap();
+            }
+            // Keep the connection open until the client is done
+            let _ = ws.next().await;
+        });
+
+        let transport = WebSocketTransport::new(Url::parse(&format!("ws://{}", addr)).unwrap());
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "req-1",
+            "method": "message/stream",
+            "params": {}
+        });
+        let request = TransportRequest::new("", "POST").body(body.to_string().into());
+        let events: Vec<_> = transport
+            .execute_streaming(request)
+            .await
+            .unwrap()
+            .map(|event| event.unwrap().payload["status"]["state"].clone())
+            .collect()
+            .await;
+        assert_eq!(events, ["submitted", "working", "completed"]);
+    }
+
+    #[tokio::test]
+    async fn test_error_responses() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_hdr_async(stream, AcceptA2A).await.unwrap();
+
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let request: Value = serde_json::from_str(&text).unwrap();
+                let error = serde_json::json!({"code": -32001, "message": "Task not found"});
+                // Answer subscriptions with a notification, other requests by id
+                let response = if request["method"] == "tasks/subscribe" {
+                    serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "method": "tasks/event",
+                        "params": {"taskId": request["params"]["id"], "error": error}
+                    })
+                } else {
+                    serde_json::json!({"jsonrpc": "2.0", "id": request["id"], "error": error})
+                };
+                ws.send(Message::Text(response.to_string())).await.unwrap();
+            }
+        });
+
+        let transport = WebSocketTransport::new(Url::parse(&format!("ws://{}", addr)).unwrap());
+        let codec = JsonRpcCodec::new();
+        let not_found = |result: Option<Result<SseEvent, A2AError>>| {
+            matches!(result, Some(Err(A2AError::JsonRpc { code: -32001, .. })))
+        };
+
+        // Request-response exchanges hand the error to the codec
+        let operation = A2AOperation::CancelTask {
+            task_id: "task-404".into(),
+            reason: None,
+        };
+        let request =
+            TransportRequest::new("", "POST").body(codec.encode_request(&operation).unwrap());
+        let response = transport.execute(request).await.unwrap();
+        assert!(matches!(
+            codec.decode_response(&response.body, &operation),
+            Err(A2AError::JsonRpc { code: -32001, .. })
+        ));
+
+        // Streams end with the error, answered by id or in a notification
+        for operation in [
+            A2AOperation::ResubscribeTask {
+                task_id: "task-404".into(),
+            },
+            A2AOperation::SubscribeTask {
+                task_id: "task-404".into(),
+            },
+        ] {
+            let request =
+                TransportRequest::new("", "POST").body(codec.encode_request(&operation).unwrap());
+            let mut events = Box::pin(transport.execute_streaming(request).await.unwrap());
+            assert!(not_found(events.next().await));
+            assert!(events.next().await.is_none());
+        }
+        assert_eq!(transport.pending_requests(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_events_in_notifications() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_hdr_async(stream, AcceptA2A).await.unwrap();
+
+            let Some(Ok(Message::Text(text))) = ws.next().await else {
+                panic!("Expected a text frame");
+            };
+            let request: Value = serde_json::from_str(&text).unwrap();
+            let frames = [
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "result": {"kind": "task", "id": "task-1", "status": {"state": "working"}}
+                }),
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "tasks/event",
+                    "params": {"taskId": "task-2", "kind": "status-update", "status": {"state": "working"}}
+                }),
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "tasks/event",
+                    "params": {"taskId": "task-1", "kind": "artifact-update", "artifact": {}}
+                }),
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "tasks/event",
+                    "params": {
+                        "taskId": "task-1",
+                        "result": {"kind": "status-update", "status": {"state": "completed"}, "final": true}
+                    }
+                }),
+            ];
+            for frame in frames {
+                ws.send(Message::Text(frame.to_string())).await.unwrap();
+            }
+            let _ = ws.next().await;
+        });
+
+        let transport = WebSocketTransport::new(Url::parse(&format!("ws://{}", addr)).unwrap());
+        let mut notifications = transport.subscribe_notifications();
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "req-1",
+            "method": "tasks/resubscribe",
+            "params": {"id": "task-1"}
+        });
+        let request = TransportRequest::new("", "POST").body(body.to_string().into());
+        let kinds: Vec<_> = transport
+            .execute_streaming(request)
+            .await
+            .unwrap()
+            .map(|event| event.unwrap().kind)
+            .collect()
+            .await;
+        assert_eq!(kinds, ["task", "artifact-update", "status-update"]);
+        assert_eq!(transport.pending_requests(), 0);
+
+        // Notifications of other tasks are still broadcast
+        let notification = notifications.recv().await.unwrap();
+        assert_eq!(notification.params["taskId"], "task-2");
+        assert!(notifications.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resubscriptions_share_notifications() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_hdr_async(stream, AcceptA2A).await.unwrap();
+
+            // Answer neither request; the events only arrive as notifications
+            for _ in 0..2 {
+                ws.next().await.unwrap().unwrap();
+            }
+            let frame = serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "tasks/event",
+                "params": {"taskId": "task-1", "kind": "status-update", "status": {"state": "completed"}, "final": true}
+            });
+            ws.send(Message::Text(frame.to_string())).await.unwrap();
+            let _ = ws.next().await;
+        });
+
+        let transport = WebSocketTransport::new(Url::parse(&format!("ws://{}", addr)).unwrap());
+        let resubscribe = |id: &str| {
+            let body = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "tasks/resubscribe",
+                "params": {"id": "task-1"}
+            });
+            TransportRequest::new("", "POST").body(body.to_string().into())
+        };
+        let first = transport
+            .execute_streaming(resubscribe("req-1"))
+            .await
+            .unwrap();
+        let second = transport
+            .execute_streaming(resubscribe("req-2"))
+            .await
+            .unwrap();
+
+        for stream in [first.boxed(), second.boxed()] {
+            let kinds: Vec<_> = stream.map(|event| event.unwrap().kind).collect().await;
+            assert_eq!(kinds, ["status-update"]);
+        }
+        assert_eq!(transport.pending_requests(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_response_queue_overflow() {
+        let status = |n: u64| serde_json::json!({"kind": "status-update", "n": n});
+        let artifact = serde_json::json!({"kind": "artifact-update"});
+
+        let queue = ResponseQueue::new(2, OverflowPolicy::DropOldestStatus, true);
+        assert!(queue.push(status(1)).await);
+        assert!(queue.push(artifact.clone()).await);
+        assert!(queue.push(status(2)).await);
+        assert_eq!(queue.pop().await.unwrap().unwrap(), artifact);
+        assert_eq!(queue.pop().await.unwrap().unwrap(), status(2));
+
+        let queue = ResponseQueue::new(1, OverflowPolicy::Error, true);
+        assert!(queue.push(status(1)).await);
+        assert!(!queue.push(status(2)).await);
+        assert!(matches!(
+            queue.pop().await,
+            Some(Err(A2AError::Transport(_)))
+        ));
+        assert!(queue.pop().await.is_none());
+
+        let queue = Arc::new(ResponseQueue::new(1, OverflowPolicy::Block, true));
+        assert!(queue.push(status(1)).await);
+        let blocked = tokio::spawn({
+            let queue = queue.clone();
+            async move { queue.push(status(2)).await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!blocked.is_finished());
+        assert_eq!(queue.pop().await.unwrap().unwrap(), status(1));
+        assert!(blocked.await.unwrap());
+
+        // Dropping the receiver unblocks the sender and unregisters the request
+        let pending = PendingRequests::default();
+        let receiver = ResponseReceiver::register(
+            &pending,
+            "req-1".into(),
+            ResponseQueue::new(1, OverflowPolicy::Block, true),
+        );
+        let queue = receiver.queue.clone();
+        assert!(queue.push(status(1)).await);
+        let blocked = tokio::spawn(async move { queue.push(status(2)).await });
+        drop(receiver);
+        assert!(!blocked.await.unwrap());
+        assert!(lock_pending(&pending).requests.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pending_requests_are_cleaned_up() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // An agent that never answers
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_hdr_async(stream, AcceptA2A).await.unwrap();
+            while ws.next().await.is_some() {}
+        });
+
+        let transport = WebSocketTransport::new(Url::parse(&format!("ws://{}", addr)).unwrap())
+            .with_request_timeout(Duration::from_millis(50));
+        let request = |id: &str, method: &str| {
+            let body = serde_json::json!({"jsonrpc": "2.0", "id": id, "method": method});
+            TransportRequest::new("", "POST").body(body.to_string().into())
+        };
+
+        assert!(matches!(
+            transport.execute(request("req-1", "tasks/get")).await,
+            Err(A2AError::Timeout)
+        ));
+        assert_eq!(transport.pending_requests(), 0);
+
+        let stream = transport
+            .execute_streaming(request("req-2", "tasks/resubscribe"))
+            .await
+            .unwrap();
+        assert_eq!(transport.pending_requests(), 1);
+        drop(stream);
+        assert_eq!(transport.pending_requests(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_expired_requests_are_swept() {
+        let pending = PendingRequests::default();
+        let idle = ResponseReceiver::register(
+            &pending,
+            "req-1".into(),
+            ResponseQueue::new(4, OverflowPolicy::Block, true)
+                .with_timeout(Some(Duration::from_millis(20))),
+        );
+        let open = ResponseReceiver::register(
+            &pending,
+            "req-2".into(),
+            ResponseQueue::new(4, OverflowPolicy::Block, true),
+        );
+
+        handle_response(
+            &pending,
+            "req-1".into(),
+            serde_json::json!({"id": "req-1", "result": {"kind": "task"}}),
+        )
+        .await;
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        sweep_pending(&pending);
+        let pending_ids: Vec<_> = lock_pending(&pending).requests.keys().cloned().collect();
+        assert_eq!(pending_ids, ["req-2"]);
+
+        // Events received before the expiry are still delivered
+        assert!(idle.queue.pop().await.unwrap().is_ok());
+        assert!(matches!(
+            idle.queue.pop().await,
+            Some(Err(A2AError::Timeout))
+        ));
+        assert!(idle.queue.pop().await.is_none());
+        drop(open);
+        assert!(lock_pending(&pending).requests.is_empty());
+    }
+
     #[test]
     fn test_value_to_sse_event() {
         let value = serde_json::json!({
@@ -147,6 +147,7 @@ fn test_task_artifacts_field() {
         parts: vec![MessagePart::text("Output")],
         metadata: None,
         extensions: vec![],
+        extra: Default::default(),
     };
 
     let task = Task::new("task-123", msg).with_artifact(artifact);